@@ -2014,6 +2014,26 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Accepts an unaggregated `VerifiedAttestation` and buffers it for insertion into
+    /// `self.op_pool`.
+    ///
+    /// The buffered attestations are inserted as a batch at the start of each slot and before
+    /// packing attestations into a block, so that the op pool lock is not taken for each
+    /// attestation received.
+    pub fn add_unaggregated_to_block_inclusion_pool<A>(&self, verified_attestation: A)
+    where
+        A: VerifiedAttestation<T>,
+    {
+        // If there's no eth1 chain then it's impossible to produce blocks and therefore
+        // useless to put things in the op pool.
+        if self.eth1_chain.is_some() {
+            let (attestation, attesting_indices) =
+                verified_attestation.into_attestation_and_indices();
+            self.op_pool
+                .buffer_unaggregated_attestation(attestation, attesting_indices);
+        }
+    }
+
     /// Accepts a `VerifiedSyncContribution` and attempts to apply it to `self.op_pool`.
     ///
     /// The op pool is used by local block producers to pack blocks with operations.
@@ -2913,6 +2933,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             current_epoch,
         );
         self.import_block_update_validator_monitor(
+            block_root,
             block,
             &state,
             &mut consensus_context,
@@ -3114,6 +3135,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// Process a block for the validator monitor, including all its constituent messages.
     fn import_block_update_validator_monitor(
         &self,
+        block_root: Hash256,
         block: BeaconBlockRef<T::EthSpec>,
         state: &BeaconState<T::EthSpec>,
         ctxt: &mut ConsensusContext<T::EthSpec>,
@@ -3136,6 +3158,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let validator_monitor = self.validator_monitor.read();
 
+        // Skipped slots. The alerts are only published if this block becomes canonical.
+        let mut alerts = vec![];
+        validator_monitor.register_skipped_slots(state, parent_block_slot, &self.spec, &mut alerts);
+        validator_monitor.defer(block_root, block.slot(), vec![], alerts);
+
         // Sync aggregate.
         if let Ok(sync_aggregate) = block.body().sync_aggregate() {
             // `SyncCommittee` for the sync_aggregate should correspond to the duty slot
@@ -4281,10 +4308,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .op_pool
             .get_bls_to_execution_changes(&state, &self.spec);
 
-        // Iterate through the naive aggregation pool and ensure all the attestations from there
-        // are included in the operation pool.
+        // Insert the buffered unaggregated attestations, then iterate through the naive
+        // aggregation pool and ensure all the attestations from there are included in the
+        // operation pool.
         let unagg_import_timer =
             metrics::start_timer(&metrics::BLOCK_PRODUCTION_UNAGGREGATED_TIMES);
+        self.op_pool.insert_buffered_unaggregated_attestations();
         for attestation in self.naive_aggregation_pool.read().iter() {
             let import = |attestation: &Attestation<T::EthSpec>| {
                 let attesting_indices = get_attesting_indices_from_state(&state, attestation)?;
@@ -4322,10 +4351,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let mut attestations = self
             .op_pool
-            .get_attestations(
+            .get_attestations_with_packing(
                 &state,
                 prev_attestation_filter,
                 curr_attestation_filter,
+                self.config.attestation_packing_algorithm,
+                self.config.attestation_packing_time_budget,
                 &self.spec,
             )
             .map_err(BlockProductionError::OpPoolError)?;
@@ -4612,6 +4643,79 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok((block, state))
     }
 
+    /// Re-submits the execution payload of an optimistically imported block to the execution
+    /// layer and updates fork choice with the response.
+    ///
+    /// This allows the optimistic status of blocks to be resolved without waiting for the next
+    /// fork choice update, e.g. after the execution layer has recovered from an outage.
+    pub async fn revalidate_optimistic_block(
+        self: &Arc<Self>,
+        block_root: Hash256,
+    ) -> Result<PayloadStatus, Error> {
+        let execution_layer = self
+            .execution_layer
+            .as_ref()
+            .ok_or(Error::ExecutionLayerMissing)?;
+        let block = self
+            .get_block(&block_root)
+            .await?
+            .ok_or(Error::MissingBeaconBlock(block_root))?;
+        let execution_payload = block
+            .message()
+            .execution_payload()
+            .map_err(Error::BeaconStateError)?;
+
+        let status = execution_layer
+            .notify_new_payload(&execution_payload.into())
+            .await
+            .map_err(Error::ExecutionNewPayloadFailed)?;
+
+        match status {
+            PayloadStatus::Valid => {
+                let chain = self.clone();
+                self.spawn_blocking_handle(
+                    move || {
+                        chain
+                            .canonical_head
+                            .fork_choice_write_lock()
+                            .on_valid_execution_payload(block_root)
+                    },
+                    "revalidate_optimistic_block",
+                )
+                .await?
+                .map_err(Error::ForkChoiceError)?;
+                // Update the cached execution status of the head.
+                self.recompute_head_at_current_slot().await;
+            }
+            PayloadStatus::Invalid {
+                latest_valid_hash, ..
+            } => {
+                let op = match latest_valid_hash {
+                    Some(latest_valid_ancestor)
+                        if latest_valid_ancestor != ExecutionBlockHash::zero() =>
+                    {
+                        InvalidationOperation::InvalidateMany {
+                            head_block_root: block_root,
+                            always_invalidate_head: true,
+                            latest_valid_ancestor,
+                        }
+                    }
+                    _ => InvalidationOperation::InvalidateOne { block_root },
+                };
+                self.process_invalid_execution_payload(&op).await?;
+            }
+            PayloadStatus::InvalidBlockHash { .. } => {
+                self.process_invalid_execution_payload(&InvalidationOperation::InvalidateOne {
+                    block_root,
+                })
+                .await?;
+            }
+            PayloadStatus::Syncing | PayloadStatus::Accepted => (),
+        }
+
+        Ok(status)
+    }
+
     /// This method must be called whenever an execution engine indicates that a payload is
     /// invalid.
     ///
@@ -5340,6 +5444,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             // sync anyway).
             self.naive_aggregation_pool.write().prune(slot);
             self.block_times_cache.write().prune(slot);
+            self.op_pool.insert_buffered_unaggregated_attestations();
 
             // Don't run heavy-weight tasks during sync.
             if self.best_slot() + MAX_PER_SLOT_FORK_CHOICE_DISTANCE < slot {
@@ -5,7 +5,10 @@ use state_processing::{
     common::get_attesting_indices_from_state,
     per_block_processing::altair::sync_committee::compute_sync_aggregate_rewards,
 };
-use types::{AbstractExecPayload, BeaconBlockRef, BeaconState, EthSpec, Hash256};
+use std::collections::HashMap;
+use types::{
+    AbstractExecPayload, Attestation, BeaconBlockRef, BeaconState, EthSpec, ExecPayload, Hash256,
+};
 
 impl<T: BeaconChainTypes> BeaconChain<T> {
     pub fn compute_block_reward<Payload: AbstractExecPayload<T::EthSpec>>(
@@ -22,50 +25,20 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         reward_cache.update(state)?;
 
-        let total_active_balance = state.get_total_active_balance()?;
-
-        let split_attestations = block
-            .body()
-            .attestations()
-            .iter()
-            .map(|att| {
-                let attesting_indices = get_attesting_indices_from_state(state, att)?;
-                Ok(SplitAttestation::new(att.clone(), attesting_indices))
-            })
-            .collect::<Result<Vec<_>, BeaconChainError>>()?;
-
-        let mut per_attestation_rewards = split_attestations
-            .iter()
-            .map(|att| {
-                AttMaxCover::new(
-                    att.as_ref(),
-                    state,
-                    reward_cache,
-                    total_active_balance,
-                    &self.spec,
-                )
-                .ok_or(BeaconChainError::BlockRewardAttestationError)
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        // Update the attestation rewards for each previous attestation included.
-        // This is O(n^2) in the number of attestations n.
-        for i in 0..per_attestation_rewards.len() {
-            let (updated, to_update) = per_attestation_rewards.split_at_mut(i + 1);
-            let latest_att = &updated[i];
-
-            for att in to_update {
-                att.update_covering_set(latest_att.intermediate(), latest_att.covering_set());
-            }
-        }
+        let per_attestation_rewards =
+            self.compute_attestation_rewards(block.body().attestations(), state, reward_cache)?;
 
         let mut prev_epoch_total = 0;
         let mut curr_epoch_total = 0;
 
-        for cover in &per_attestation_rewards {
-            for &reward in cover.fresh_validators_rewards.values() {
-                if cover.att.data.slot.epoch(T::EthSpec::slots_per_epoch()) == state.current_epoch()
-                {
+        for (att, rewards) in block
+            .body()
+            .attestations()
+            .iter()
+            .zip(&per_attestation_rewards)
+        {
+            for &reward in rewards.values() {
+                if att.data.slot.epoch(T::EthSpec::slots_per_epoch()) == state.current_epoch() {
                     curr_epoch_total += reward;
                 } else {
                     prev_epoch_total += reward;
@@ -75,12 +48,6 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let attestation_total = prev_epoch_total + curr_epoch_total;
 
-        // Drop the covers.
-        let per_attestation_rewards = per_attestation_rewards
-            .into_iter()
-            .map(|cover| cover.fresh_validators_rewards)
-            .collect();
-
         // Add the attestation data if desired.
         let attestations = if include_attestations {
             block
@@ -120,12 +87,76 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             graffiti: block.body().graffiti().as_utf8_lossy(),
         };
 
+        // Payload values are only known for blocks produced by this node.
+        let payload_values = block
+            .body()
+            .execution_payload()
+            .ok()
+            .zip(self.execution_layer.as_ref())
+            .and_then(|(payload, execution_layer)| {
+                execution_layer.payload_values(payload.block_hash())
+            });
+
         Ok(BlockReward {
             total,
             block_root,
             meta,
             attestation_rewards,
             sync_committee_rewards,
+            payload_values,
         })
     }
+
+    /// Compute the proposer reward for each of `attestations` when they are included in a block
+    /// in order, on top of `state`.
+    ///
+    /// Each element of the result is a map from validator index to reward, for the validators
+    /// rewarded for the first time by that attestation. The `reward_cache` must have been updated
+    /// for `state`.
+    pub fn compute_attestation_rewards(
+        &self,
+        attestations: &[Attestation<T::EthSpec>],
+        state: &BeaconState<T::EthSpec>,
+        reward_cache: &RewardCache,
+    ) -> Result<Vec<HashMap<u64, u64>>, BeaconChainError> {
+        let total_active_balance = state.get_total_active_balance()?;
+
+        let split_attestations = attestations
+            .iter()
+            .map(|att| {
+                let attesting_indices = get_attesting_indices_from_state(state, att)?;
+                Ok(SplitAttestation::new(att.clone(), attesting_indices))
+            })
+            .collect::<Result<Vec<_>, BeaconChainError>>()?;
+
+        let mut per_attestation_rewards = split_attestations
+            .iter()
+            .map(|att| {
+                AttMaxCover::new(
+                    att.as_ref(),
+                    state,
+                    reward_cache,
+                    total_active_balance,
+                    &self.spec,
+                )
+                .ok_or(BeaconChainError::BlockRewardAttestationError)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Update the attestation rewards for each previous attestation included.
+        // This is O(n^2) in the number of attestations n.
+        for i in 0..per_attestation_rewards.len() {
+            let (updated, to_update) = per_attestation_rewards.split_at_mut(i + 1);
+            let latest_att = &updated[i];
+
+            for att in to_update {
+                att.update_covering_set(latest_att.intermediate(), latest_att.covering_set());
+            }
+        }
+
+        Ok(per_attestation_rewards
+            .into_iter()
+            .map(|cover| cover.fresh_validators_rewards)
+            .collect())
+    }
 }
@@ -343,6 +343,14 @@ pub enum ExecutionPayloadError {
         terminal_block_hash: ExecutionBlockHash,
         payload_parent_hash: ExecutionBlockHash,
     },
+    /// The block hash of the execution payload does not match the payload, which was verified
+    /// locally without an execution node.
+    ///
+    /// ## Peer scoring
+    ///
+    /// The peer is not penalized. The block hash is not a gossip propagation condition, so an
+    /// honest peer may have forwarded the block without computing it.
+    InvalidBlockHash(execution_layer::Error),
     /// The execution node is syncing but we fail the conditions for optimistic sync
     ///
     /// ## Peer scoring
@@ -377,6 +385,9 @@ impl ExecutionPayloadError {
             ExecutionPayloadError::InvalidActivationEpoch { .. } => false,
             // As per `Self::InvalidActivationEpoch`.
             ExecutionPayloadError::InvalidTerminalBlockHash { .. } => false,
+            // The block hash is only verified locally when there is no execution node, which an
+            // honest peer need not do before propagating the block.
+            ExecutionPayloadError::InvalidBlockHash(_) => false,
             // Do not penalize the peer since it's not their fault that *we're* optimistic.
             ExecutionPayloadError::UnverifiedNonOptimisticCandidate => false,
         }
@@ -1334,21 +1345,30 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
                 >= epoch
             {
                 let validator_monitor = chain.validator_monitor.read();
+                let mut records = vec![];
+                let mut alerts = vec![];
                 // Update the summaries in a separate loop to `per_slot_processing`. This protects
                 // the `validator_monitor` lock from being bounced or held for a long time whilst
                 // performing `per_slot_processing`.
                 for (i, summary) in summaries.iter().enumerate() {
                     let epoch = state_current_epoch - Epoch::from(summaries.len() - i);
-                    if let Err(e) =
-                        validator_monitor.process_validator_statuses(epoch, summary, &chain.spec)
-                    {
-                        error!(
+                    match validator_monitor.process_validator_statuses(
+                        epoch,
+                        summary,
+                        &chain.spec,
+                        &mut alerts,
+                    ) {
+                        Ok(epoch_records) => records.extend(epoch_records),
+                        Err(e) => error!(
                             chain.log,
                             "Failed to process validator statuses";
                             "error" => ?e
-                        );
+                        ),
                     }
                 }
+                // The records are only persisted and the alerts only published if this block
+                // becomes canonical.
+                validator_monitor.defer(block_root, block_slot, records, alerts);
             }
         }
 
@@ -730,6 +730,9 @@ where
                 &head_snapshot.beacon_state,
             );
         }
+        // Learn which validators are unslashed, so that later slashings are alerted. Slashings
+        // which happened whilst the node was offline are not alerted.
+        validator_monitor.process_head_state(&head_snapshot.beacon_state);
 
         // If enabled, set up the fork choice signaller.
         let (fork_choice_signal_tx, fork_choice_signal_rx) =
@@ -0,0 +1,388 @@
+//! Audits of payloads revealed by builders, which check that the proposer was paid the value of
+//! the bid that was chosen for the proposal.
+
+use crate::{metrics, BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{BuilderAudit, BuilderAuditOutcome, BuilderBidRecord};
+use itertools::process_results;
+use slog::{debug, error, info, warn};
+use ssz::{Decode, DecodeError, Encode};
+use ssz_derive::{Decode, Encode};
+use std::sync::Arc;
+use store::{DBColumn, Error as StoreError, KeyValueStore, StoreItem};
+use tokio::time::sleep;
+use types::{Address, ExecutionBlockHash, ExecutionPayload, Hash256, Slot, Uint256};
+
+/// A `BuilderBidRecord` as it is stored in the database.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct PersistedBuilderBidRecord {
+    relay: Vec<u8>,
+    value: Uint256,
+    block_hash: ExecutionBlockHash,
+    parent_hash: ExecutionBlockHash,
+    fee_recipient: Address,
+    block_number: u64,
+    gas_limit: u64,
+    gas_used: u64,
+    timestamp: u64,
+}
+
+impl From<&BuilderBidRecord> for PersistedBuilderBidRecord {
+    fn from(bid: &BuilderBidRecord) -> Self {
+        Self {
+            relay: bid.relay.as_bytes().to_vec(),
+            value: bid.value,
+            block_hash: bid.block_hash,
+            parent_hash: bid.parent_hash,
+            fee_recipient: bid.fee_recipient,
+            block_number: bid.block_number,
+            gas_limit: bid.gas_limit,
+            gas_used: bid.gas_used,
+            timestamp: bid.timestamp,
+        }
+    }
+}
+
+impl TryFrom<PersistedBuilderBidRecord> for BuilderBidRecord {
+    type Error = DecodeError;
+
+    fn try_from(bid: PersistedBuilderBidRecord) -> Result<Self, DecodeError> {
+        Ok(Self {
+            relay: utf8_string(bid.relay)?,
+            value: bid.value,
+            block_hash: bid.block_hash,
+            parent_hash: bid.parent_hash,
+            fee_recipient: bid.fee_recipient,
+            block_number: bid.block_number,
+            gas_limit: bid.gas_limit,
+            gas_used: bid.gas_used,
+            timestamp: bid.timestamp,
+        })
+    }
+}
+
+/// A `BuilderAudit` as it is stored in the database.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct PersistedBuilderAudit {
+    block_root: Hash256,
+    slot: Slot,
+    proposer_fee_recipient: Address,
+    bid: Option<PersistedBuilderBidRecord>,
+    bids: Vec<PersistedBuilderBidRecord>,
+    revealed_transactions: u64,
+    execution_transactions: u64,
+    delivered_value: Uint256,
+    outcome: u8,
+    error: Option<Vec<u8>>,
+}
+
+impl From<&BuilderAudit> for PersistedBuilderAudit {
+    fn from(audit: &BuilderAudit) -> Self {
+        Self {
+            block_root: audit.block_root,
+            slot: audit.slot,
+            proposer_fee_recipient: audit.proposer_fee_recipient,
+            bid: audit.bid.as_ref().map(Into::into),
+            bids: audit.bids.iter().map(Into::into).collect(),
+            revealed_transactions: audit.revealed_transactions,
+            execution_transactions: audit.execution_transactions,
+            delivered_value: audit.delivered_value,
+            outcome: match audit.outcome {
+                BuilderAuditOutcome::Delivered => 0,
+                BuilderAuditOutcome::UnderDelivered => 1,
+                BuilderAuditOutcome::TransactionCountMismatch => 2,
+                BuilderAuditOutcome::Unavailable => 3,
+                BuilderAuditOutcome::LocalPayload => 4,
+            },
+            error: audit.error.as_ref().map(|e| e.as_bytes().to_vec()),
+        }
+    }
+}
+
+impl TryFrom<PersistedBuilderAudit> for BuilderAudit {
+    type Error = DecodeError;
+
+    fn try_from(audit: PersistedBuilderAudit) -> Result<Self, DecodeError> {
+        Ok(Self {
+            block_root: audit.block_root,
+            slot: audit.slot,
+            proposer_fee_recipient: audit.proposer_fee_recipient,
+            bid: audit.bid.map(TryInto::try_into).transpose()?,
+            bids: audit
+                .bids
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            revealed_transactions: audit.revealed_transactions,
+            execution_transactions: audit.execution_transactions,
+            delivered_value: audit.delivered_value,
+            outcome: match audit.outcome {
+                0 => BuilderAuditOutcome::Delivered,
+                1 => BuilderAuditOutcome::UnderDelivered,
+                2 => BuilderAuditOutcome::TransactionCountMismatch,
+                3 => BuilderAuditOutcome::Unavailable,
+                4 => BuilderAuditOutcome::LocalPayload,
+                outcome => {
+                    return Err(DecodeError::BytesInvalid(format!(
+                        "invalid builder audit outcome: {}",
+                        outcome
+                    )))
+                }
+            },
+            error: audit.error.map(utf8_string).transpose()?,
+        })
+    }
+}
+
+fn utf8_string(bytes: Vec<u8>) -> Result<String, DecodeError> {
+    String::from_utf8(bytes).map_err(|e| DecodeError::BytesInvalid(format!("{:?}", e)))
+}
+
+impl StoreItem for PersistedBuilderAudit {
+    fn db_column() -> DBColumn {
+        DBColumn::BuilderAudit
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Audit the payload of a published block, storing the result in the database if any builder
+    /// bids were received for its proposal.
+    ///
+    /// The audit of a payload revealed by a builder is delayed by a slot so that the block has
+    /// been published and its payload imported by the execution layer.
+    pub fn spawn_builder_audit(
+        self: &Arc<Self>,
+        block_root: Hash256,
+        payload: ExecutionPayload<T::EthSpec>,
+    ) {
+        let chain = self.clone();
+        self.task_executor.spawn(
+            async move {
+                let is_builder_payload = match chain.execution_layer.as_ref() {
+                    Some(execution_layer) => {
+                        execution_layer
+                            .is_builder_payload(payload.block_hash())
+                            .await
+                    }
+                    None => false,
+                };
+                if is_builder_payload {
+                    sleep(chain.slot_clock.slot_duration()).await;
+                }
+                chain.audit_builder_payload(block_root, &payload).await
+            },
+            "builder_audit",
+        );
+    }
+
+    async fn audit_builder_payload(
+        &self,
+        block_root: Hash256,
+        payload: &ExecutionPayload<T::EthSpec>,
+    ) {
+        let execution_layer = match self.execution_layer.as_ref() {
+            Some(execution_layer) => execution_layer,
+            None => return,
+        };
+
+        let audit = match execution_layer
+            .audit_builder_payload(block_root, payload)
+            .await
+        {
+            Some(audit) => audit,
+            None => {
+                debug!(
+                    self.log,
+                    "No builder bid to audit";
+                    "block_root" => ?block_root,
+                    "block_hash" => ?payload.block_hash(),
+                );
+                return;
+            }
+        };
+
+        let relay = match &audit.bid {
+            Some(bid) => bid.relay.as_str(),
+            None => "local",
+        };
+        let bid_value = audit.bid.as_ref().map_or(Uint256::zero(), |bid| bid.value);
+        metrics::inc_counter_vec(
+            &metrics::BUILDER_AUDITS_TOTAL,
+            &[relay, audit.outcome.as_str()],
+        );
+
+        match audit.outcome {
+            BuilderAuditOutcome::Delivered => info!(
+                self.log,
+                "Builder payload audit passed";
+                "relay" => relay,
+                "bid_value" => %bid_value,
+                "delivered_value" => %audit.delivered_value,
+                "block_root" => ?block_root,
+                "slot" => audit.slot,
+            ),
+            BuilderAuditOutcome::UnderDelivered => {
+                let shortfall_gwei =
+                    bid_value.saturating_sub(audit.delivered_value) / 1_000_000_000;
+                metrics::inc_counter_vec_by(
+                    &metrics::BUILDER_AUDIT_SHORTFALL_GWEI_TOTAL,
+                    &[relay],
+                    std::cmp::min(shortfall_gwei, Uint256::from(u64::MAX)).low_u64(),
+                );
+                warn!(
+                    self.log,
+                    "Relay under-delivered on bid";
+                    "relay" => relay,
+                    "bid_value" => %bid_value,
+                    "delivered_value" => %audit.delivered_value,
+                    "fee_recipient" => ?audit.proposer_fee_recipient,
+                    "block_root" => ?block_root,
+                    "slot" => audit.slot,
+                )
+            }
+            BuilderAuditOutcome::TransactionCountMismatch => warn!(
+                self.log,
+                "Relay payload does not match execution block";
+                "relay" => relay,
+                "revealed_transactions" => audit.revealed_transactions,
+                "execution_transactions" => audit.execution_transactions,
+                "block_root" => ?block_root,
+                "slot" => audit.slot,
+            ),
+            BuilderAuditOutcome::Unavailable => warn!(
+                self.log,
+                "Unable to audit builder payload";
+                "info" => "the execution layer could not provide the block or balances",
+                "relay" => relay,
+                "error" => ?audit.error,
+                "block_root" => ?block_root,
+                "slot" => audit.slot,
+            ),
+            BuilderAuditOutcome::LocalPayload => debug!(
+                self.log,
+                "Local payload chosen over builder bids";
+                "bids" => audit.bids.len(),
+                "block_root" => ?block_root,
+                "slot" => audit.slot,
+            ),
+        }
+
+        if let Err(e) = self
+            .store
+            .put_item(&block_root, &PersistedBuilderAudit::from(&audit))
+        {
+            error!(
+                self.log,
+                "Failed to store builder audit";
+                "error" => ?e,
+                "block_root" => ?block_root,
+            );
+        }
+    }
+
+    /// Returns all stored builder audits, oldest first.
+    pub fn builder_audits(&self) -> Result<Vec<BuilderAudit>, BeaconChainError> {
+        let mut audits = process_results(
+            self.store.hot_db.iter_column(DBColumn::BuilderAudit),
+            |iter| {
+                iter.map(|(_, bytes)| {
+                    let audit = PersistedBuilderAudit::from_store_bytes(&bytes)?;
+                    BuilderAudit::try_from(audit).map_err(StoreError::from)
+                })
+                .collect::<Result<Vec<_>, _>>()
+            },
+        )??;
+        audits.sort_by_key(|audit| audit.slot);
+        Ok(audits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bid_record(relay: &str, value: u64) -> BuilderBidRecord {
+        BuilderBidRecord {
+            relay: relay.to_string(),
+            value: Uint256::from(value),
+            block_hash: ExecutionBlockHash::repeat_byte(value as u8),
+            parent_hash: ExecutionBlockHash::repeat_byte(1),
+            fee_recipient: Address::repeat_byte(2),
+            block_number: 100,
+            gas_limit: 30_000_000,
+            gas_used: 12_000_000,
+            timestamp: 1_676_406_203,
+        }
+    }
+
+    fn round_trip(audit: &BuilderAudit) -> BuilderAudit {
+        let bytes = PersistedBuilderAudit::from(audit).as_store_bytes();
+        let persisted = PersistedBuilderAudit::from_store_bytes(&bytes).unwrap();
+        BuilderAudit::try_from(persisted).unwrap()
+    }
+
+    #[test]
+    fn builder_audit_round_trip() {
+        let bids = vec![
+            bid_record("https://relay-a.test/", 3),
+            bid_record("https://relay-b.test/", 5),
+        ];
+        let audit = BuilderAudit {
+            block_root: Hash256::repeat_byte(9),
+            slot: Slot::new(42),
+            proposer_fee_recipient: Address::repeat_byte(3),
+            bid: Some(bids[1].clone()),
+            bids,
+            revealed_transactions: 10,
+            execution_transactions: 0,
+            delivered_value: Uint256::zero(),
+            outcome: BuilderAuditOutcome::Unavailable,
+            error: Some("missing execution block".to_string()),
+        };
+        assert_eq!(round_trip(&audit), audit);
+    }
+
+    #[test]
+    fn local_payload_audit_round_trip() {
+        let audit = BuilderAudit {
+            block_root: Hash256::repeat_byte(9),
+            slot: Slot::new(42),
+            proposer_fee_recipient: Address::repeat_byte(3),
+            bid: None,
+            bids: vec![bid_record("https://relay-a.test/", 3)],
+            revealed_transactions: 10,
+            execution_transactions: 0,
+            delivered_value: Uint256::zero(),
+            outcome: BuilderAuditOutcome::LocalPayload,
+            error: None,
+        };
+        assert_eq!(round_trip(&audit), audit);
+    }
+
+    #[test]
+    fn invalid_outcome_is_rejected() {
+        let mut persisted = PersistedBuilderAudit {
+            block_root: Hash256::repeat_byte(9),
+            slot: Slot::new(42),
+            proposer_fee_recipient: Address::repeat_byte(3),
+            bid: None,
+            bids: vec![],
+            revealed_transactions: 0,
+            execution_transactions: 0,
+            delivered_value: Uint256::zero(),
+            outcome: 4,
+            error: None,
+        };
+        assert!(BuilderAudit::try_from(persisted.clone()).is_ok());
+
+        persisted.outcome = 5;
+        assert!(BuilderAudit::try_from(persisted).is_err());
+    }
+}
@@ -853,6 +853,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 );
             });
 
+        // Store the validator monitor records and publish the alerts produced whilst processing
+        // the blocks which are now canonical.
+        self.process_canonical_validator_monitor_outputs(
+            new_snapshot.beacon_block_root,
+            &new_snapshot.beacon_state,
+            new_cached_head
+                .finalized_checkpoint()
+                .epoch
+                .start_slot(T::EthSpec::slots_per_epoch()),
+        );
+
         observe_head_block_delays(
             &mut self.block_times_cache.write(),
             &new_head_proto_block,
@@ -1,4 +1,6 @@
+pub use operation_pool::AttestationPackingAlgorithm;
 pub use proto_array::{CountUnrealizedFull, ReOrgThreshold};
+use sensitive_url::SensitiveUrl;
 use serde_derive::{Deserialize, Serialize};
 use std::time::Duration;
 use types::{Checkpoint, Epoch};
@@ -13,6 +15,12 @@ pub const DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR: u32 = 3;
 /// Fraction of a slot lookahead for fork choice in the state advance timer (500ms on mainnet).
 pub const FORK_CHOICE_LOOKAHEAD_FACTOR: u32 = 24;
 
+/// Default time budget for packing each epoch's attestations with an exact solver.
+pub const DEFAULT_ATTESTATION_PACKING_TIME_BUDGET: Duration = Duration::from_millis(50);
+
+/// Default number of epochs of validator monitor history to retain (~5 weeks on mainnet).
+pub const DEFAULT_VALIDATOR_MONITOR_HISTORY_EPOCHS: u64 = 8192;
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct ChainConfig {
     /// Maximum number of slots to skip when importing a consensus message (e.g., block,
@@ -71,6 +79,21 @@ pub struct ChainConfig {
     ///
     /// This is useful for block builders and testing.
     pub always_prepare_payload: bool,
+    /// UNSAFE: import blocks optimistically without an execution layer, verifying only the block
+    /// hashes of their execution payloads.
+    ///
+    /// This is only intended for building archive nodes that replay the chain history, it must
+    /// never be used to follow the head or perform validator duties.
+    pub historical_archive_sync: bool,
+    /// The number of epochs of per-validator records kept for validators monitored by the
+    /// validator monitor. If set to 0 then no records are stored.
+    pub validator_monitor_history_epochs: u64,
+    /// An optional URL to which alerts raised by the validator monitor are sent.
+    pub validator_monitor_alert_webhook: Option<SensitiveUrl>,
+    /// The algorithm used to select attestations for inclusion in produced blocks.
+    pub attestation_packing_algorithm: AttestationPackingAlgorithm,
+    /// The time allowed for packing each epoch's attestations, if the algorithm is not greedy.
+    pub attestation_packing_time_budget: Duration,
 }
 
 impl Default for ChainConfig {
@@ -98,6 +121,11 @@ impl Default for ChainConfig {
             // This value isn't actually read except in tests.
             optimistic_finalized_sync: true,
             always_prepare_payload: false,
+            historical_archive_sync: false,
+            validator_monitor_history_epochs: DEFAULT_VALIDATOR_MONITOR_HISTORY_EPOCHS,
+            validator_monitor_alert_webhook: None,
+            attestation_packing_algorithm: AttestationPackingAlgorithm::default(),
+            attestation_packing_time_budget: DEFAULT_ATTESTATION_PACKING_TIME_BUDGET,
         }
     }
 }
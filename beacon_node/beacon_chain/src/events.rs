@@ -17,6 +17,12 @@ pub struct ServerSentEventHandler<T: EthSpec> {
     payload_attributes_tx: Sender<EventKind<T>>,
     late_head: Sender<EventKind<T>>,
     block_reward_tx: Sender<EventKind<T>>,
+    gossip_validation_tx: Sender<EventKind<T>>,
+    backfill_progress_tx: Sender<EventKind<T>>,
+    validator_missed_attestation_tx: Sender<EventKind<T>>,
+    validator_missed_proposal_tx: Sender<EventKind<T>>,
+    validator_slashed_tx: Sender<EventKind<T>>,
+    validator_balance_decrease_tx: Sender<EventKind<T>>,
     log: Logger,
 }
 
@@ -36,6 +42,12 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         let (payload_attributes_tx, _) = broadcast::channel(capacity);
         let (late_head, _) = broadcast::channel(capacity);
         let (block_reward_tx, _) = broadcast::channel(capacity);
+        let (gossip_validation_tx, _) = broadcast::channel(capacity);
+        let (backfill_progress_tx, _) = broadcast::channel(capacity);
+        let (validator_missed_attestation_tx, _) = broadcast::channel(capacity);
+        let (validator_missed_proposal_tx, _) = broadcast::channel(capacity);
+        let (validator_slashed_tx, _) = broadcast::channel(capacity);
+        let (validator_balance_decrease_tx, _) = broadcast::channel(capacity);
 
         Self {
             attestation_tx,
@@ -48,6 +60,12 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
             payload_attributes_tx,
             late_head,
             block_reward_tx,
+            gossip_validation_tx,
+            backfill_progress_tx,
+            validator_missed_attestation_tx,
+            validator_missed_proposal_tx,
+            validator_slashed_tx,
+            validator_balance_decrease_tx,
             log,
         }
     }
@@ -102,6 +120,30 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
                 .block_reward_tx
                 .send(kind)
                 .map(|count| log_count("block reward", count)),
+            EventKind::GossipValidation(_) => self
+                .gossip_validation_tx
+                .send(kind)
+                .map(|count| log_count("gossip validation", count)),
+            EventKind::BackFillProgress(_) => self
+                .backfill_progress_tx
+                .send(kind)
+                .map(|count| log_count("backfill progress", count)),
+            EventKind::ValidatorMissedAttestation(_) => self
+                .validator_missed_attestation_tx
+                .send(kind)
+                .map(|count| log_count("validator missed attestation", count)),
+            EventKind::ValidatorMissedProposal(_) => self
+                .validator_missed_proposal_tx
+                .send(kind)
+                .map(|count| log_count("validator missed proposal", count)),
+            EventKind::ValidatorSlashed(_) => self
+                .validator_slashed_tx
+                .send(kind)
+                .map(|count| log_count("validator slashed", count)),
+            EventKind::ValidatorBalanceDecrease(_) => self
+                .validator_balance_decrease_tx
+                .send(kind)
+                .map(|count| log_count("validator balance decrease", count)),
         };
         if let Err(SendError(event)) = result {
             trace!(self.log, "No receivers registered to listen for event"; "event" => ?event);
@@ -148,6 +190,30 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         self.block_reward_tx.subscribe()
     }
 
+    pub fn subscribe_gossip_validation(&self) -> Receiver<EventKind<T>> {
+        self.gossip_validation_tx.subscribe()
+    }
+
+    pub fn subscribe_backfill_progress(&self) -> Receiver<EventKind<T>> {
+        self.backfill_progress_tx.subscribe()
+    }
+
+    pub fn subscribe_validator_missed_attestation(&self) -> Receiver<EventKind<T>> {
+        self.validator_missed_attestation_tx.subscribe()
+    }
+
+    pub fn subscribe_validator_missed_proposal(&self) -> Receiver<EventKind<T>> {
+        self.validator_missed_proposal_tx.subscribe()
+    }
+
+    pub fn subscribe_validator_slashed(&self) -> Receiver<EventKind<T>> {
+        self.validator_slashed_tx.subscribe()
+    }
+
+    pub fn subscribe_validator_balance_decrease(&self) -> Receiver<EventKind<T>> {
+        self.validator_balance_decrease_tx.subscribe()
+    }
+
     pub fn has_attestation_subscribers(&self) -> bool {
         self.attestation_tx.receiver_count() > 0
     }
@@ -187,4 +253,28 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
     pub fn has_block_reward_subscribers(&self) -> bool {
         self.block_reward_tx.receiver_count() > 0
     }
+
+    pub fn has_gossip_validation_subscribers(&self) -> bool {
+        self.gossip_validation_tx.receiver_count() > 0
+    }
+
+    pub fn has_backfill_progress_subscribers(&self) -> bool {
+        self.backfill_progress_tx.receiver_count() > 0
+    }
+
+    pub fn has_validator_missed_attestation_subscribers(&self) -> bool {
+        self.validator_missed_attestation_tx.receiver_count() > 0
+    }
+
+    pub fn has_validator_missed_proposal_subscribers(&self) -> bool {
+        self.validator_missed_proposal_tx.receiver_count() > 0
+    }
+
+    pub fn has_validator_slashed_subscribers(&self) -> bool {
+        self.validator_slashed_tx.receiver_count() > 0
+    }
+
+    pub fn has_validator_balance_decrease_subscribers(&self) -> bool {
+        self.validator_balance_decrease_tx.receiver_count() > 0
+    }
 }
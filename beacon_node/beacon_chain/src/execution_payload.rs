@@ -12,7 +12,10 @@ use crate::{
     BeaconChain, BeaconChainError, BeaconChainTypes, BlockError, BlockProductionError,
     ExecutionPayloadError,
 };
-use execution_layer::{BlockProposalContents, BuilderParams, PayloadAttributes, PayloadStatus};
+use execution_layer::{
+    verify_payload_block_hash, BlockProposalContents, BuilderParams, PayloadAttributes,
+    PayloadStatus,
+};
 use fork_choice::{InvalidationOperation, PayloadVerificationStatus};
 use proto_array::{Block as ProtoBlock, ExecutionStatus};
 use slog::{debug, warn};
@@ -78,6 +81,14 @@ impl<T: BeaconChainTypes> PayloadNotifier<T> {
             .map_err(BlockError::PerBlockProcessingError)?;
 
             match notify_execution_layer {
+                // There is no execution layer to verify payloads with. Their block hash is still
+                // checked, but the blocks are imported optimistically since the rest of the
+                // payload can't be verified.
+                _ if chain.config.historical_archive_sync => {
+                    verify_payload_block_hash(payload.execution_payload_ref())
+                        .map_err(ExecutionPayloadError::InvalidBlockHash)?;
+                    Some(PayloadVerificationStatus::Optimistic)
+                }
                 NotifyExecutionLayer::No if chain.config.optimistic_finalized_sync => {
                     // Verify the block hash here in Lighthouse and immediately mark the block as
                     // optimistically imported. This saves a lot of roundtrips to the EL.
@@ -269,6 +280,16 @@ pub async fn validate_merge_block<'a, T: BeaconChainTypes>(
         return Ok(());
     }
 
+    if chain.config.historical_archive_sync {
+        debug!(
+            chain.log,
+            "Skipping terminal PoW block verification";
+            "block_hash" => ?execution_payload.parent_hash(),
+            "msg" => "historical archive sync is enabled"
+        );
+        return Ok(());
+    }
+
     let execution_layer = chain
         .execution_layer
         .as_ref()
@@ -1,5 +1,5 @@
 use crate::{errors::BeaconChainError as Error, metrics, BeaconChain, BeaconChainTypes};
-use itertools::Itertools;
+use rayon::prelude::*;
 use slog::debug;
 use state_processing::{
     per_block_processing::ParallelSignatureSets,
@@ -138,6 +138,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // Verify signatures in one batch, holding the pubkey cache lock for the shortest duration
         // possible. For each block fetch the parent root from its successor. Slicing from index 1
         // is safe because we've already checked that `blocks_to_import` is non-empty.
+        //
+        // Sync may provide several batches at once, so the signature sets are built in parallel as
+        // well as verified in parallel.
         let sig_timer = metrics::start_timer(&metrics::BACKFILL_SIGNATURE_TOTAL_TIMES);
         let setup_timer = metrics::start_timer(&metrics::BACKFILL_SIGNATURE_SETUP_TIMES);
         let pubkey_cache = self
@@ -149,10 +152,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .ok_or(HistoricalBlockError::IndexOutOfBounds)?
             .iter()
             .map(|block| block.parent_root())
-            .chain(iter::once(anchor_info.oldest_block_parent));
+            .chain(iter::once(anchor_info.oldest_block_parent))
+            .collect::<Vec<_>>();
         let signature_set = blocks_to_import
-            .iter()
-            .zip_eq(block_roots)
+            .par_iter()
+            .zip(block_roots.into_par_iter())
             .map(|(block, block_root)| {
                 block_proposal_signature_set_from_parts(
                     block,
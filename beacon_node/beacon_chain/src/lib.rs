@@ -10,6 +10,7 @@ pub mod block_reward;
 mod block_times_cache;
 mod block_verification;
 pub mod builder;
+mod builder_audits;
 pub mod canonical_head;
 pub mod capella_readiness;
 pub mod chain_config;
@@ -33,6 +34,7 @@ mod observed_aggregates;
 mod observed_attesters;
 mod observed_block_producers;
 pub mod observed_operations;
+mod op_pool_summary;
 pub mod otb_verification_service;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
@@ -47,6 +49,8 @@ pub mod sync_committee_verification;
 pub mod test_utils;
 mod timeout_rw_lock;
 pub mod validator_monitor;
+mod validator_monitor_alerts;
+mod validator_monitor_history;
 pub mod validator_pubkey_cache;
 
 pub use self::beacon_chain::{
@@ -444,6 +444,8 @@ lazy_static! {
         try_create_int_gauge("beacon_op_pool_attestation_data_total", "Count of attestation data in the op pool");
     pub static ref OP_POOL_MAX_AGGREGATES_PER_DATA: Result<IntGauge> =
         try_create_int_gauge("beacon_op_pool_max_aggregates_per_data", "Max aggregates per AttestationData");
+    pub static ref OP_POOL_NUM_UNAGGREGATED_VOTES: Result<IntGauge> =
+        try_create_int_gauge("beacon_op_pool_unaggregated_votes_total", "Count of unaggregated votes retained in the op pool");
     pub static ref OP_POOL_NUM_ATTESTER_SLASHINGS: Result<IntGauge> =
         try_create_int_gauge("beacon_op_pool_attester_slashings_total", "Count of attester slashings in the op pool");
     pub static ref OP_POOL_NUM_PROPOSER_SLASHINGS: Result<IntGauge> =
@@ -813,6 +815,15 @@ lazy_static! {
         "Number of attester slashings seen",
         &["src", "validator"]
     );
+    pub static ref VALIDATOR_MONITOR_MISSED_BLOCKS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "validator_monitor_missed_blocks_total",
+        "Number of proposals missed, as observed by skipped slots in imported blocks",
+        &["validator"]
+    );
+    pub static ref VALIDATOR_MONITOR_ALERTS_DROPPED_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "validator_monitor_alerts_dropped_total",
+        "Number of validator monitor alerts dropped because too many were awaiting a canonical block"
+    );
 
     /*
      * Block Delay Metrics
@@ -990,6 +1001,20 @@ lazy_static! {
         "light_client_optimistic_update_verification_success_total",
         "Number of light client optimistic updates verified for gossip"
     );
+
+    /*
+     * Builder payload audits
+     */
+    pub static ref BUILDER_AUDITS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_builder_audits_total",
+        "Count of audits of payloads revealed by relays, by relay and outcome",
+        &["relay", "outcome"]
+    );
+    pub static ref BUILDER_AUDIT_SHORTFALL_GWEI_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_builder_audit_shortfall_gwei_total",
+        "Total amount by which relays under-delivered on their bids, in gwei",
+        &["relay"]
+    );
 }
 
 /// Scrape the `beacon_chain` for metrics that are not constantly updated (e.g., the present slot,
@@ -1034,6 +1059,10 @@ pub fn scrape_for_metrics<T: BeaconChainTypes>(beacon_chain: &BeaconChain<T>) {
         &OP_POOL_MAX_AGGREGATES_PER_DATA,
         attestation_stats.max_aggregates_per_data,
     );
+    set_gauge_by_usize(
+        &OP_POOL_NUM_UNAGGREGATED_VOTES,
+        attestation_stats.num_unaggregated_votes,
+    );
     set_gauge_by_usize(
         &OP_POOL_NUM_ATTESTER_SLASHINGS,
         beacon_chain.op_pool.num_attester_slashings(),
@@ -0,0 +1,231 @@
+//! Inspection of the operation pool, for debugging proposals which earned lower rewards than
+//! expected.
+//!
+//! Both the summary and the dry run are computed against the head state advanced to the slot of
+//! a hypothetical block, without touching any of the state used for block production.
+
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{
+    AttestationPoolSummary, OpPoolDryRun, OpPoolSummary, OperationPoolSummary, PackedOperation,
+};
+use operation_pool::{AttestationRef, RewardCache};
+use state_processing::{
+    per_block_processing::get_slashable_indices, state_advance::complete_state_advance,
+};
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use types::{BeaconState, ChainSpec, CloneConfig, EthSpec, Slot};
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Summarise the contents of the operation pool.
+    ///
+    /// Rewards are computed for a block at the current slot, or the slot after the head if the
+    /// head is not behind the current slot.
+    pub fn op_pool_summary(&self) -> Result<OpPoolSummary, BeaconChainError> {
+        let head_slot = self.canonical_head.cached_head().head_slot();
+        let slot = cmp::max(self.slot()?, head_slot + 1);
+        let state = self.op_pool_state_at_slot(slot)?;
+
+        let stats = self.op_pool.attestation_stats();
+        let attestation_rewards = self.op_pool.attestation_rewards(&state, &self.spec)?;
+        let attestations = AttestationPoolSummary {
+            count: stats.num_attestations,
+            num_attestation_data: stats.num_attestation_data,
+            num_unaggregated_votes: stats.num_unaggregated_votes,
+            age_distribution: self.op_pool.attestation_age_distribution(slot),
+            num_includable: attestation_rewards.len(),
+            total_reward: attestation_rewards.iter().sum(),
+            max_reward: attestation_rewards.iter().copied().max().unwrap_or(0),
+        };
+
+        let proposer_slashings = summarize(self.op_pool.get_all_proposer_slashings().iter().map(
+            |slashing| {
+                slashing_reward(
+                    &state,
+                    [slashing.proposer_index()],
+                    &mut HashSet::new(),
+                    &self.spec,
+                )
+            },
+        ));
+        let attester_slashings = summarize(self.op_pool.get_all_attester_slashings().iter().map(
+            |slashing| {
+                slashing_reward(
+                    &state,
+                    get_slashable_indices(&state, slashing).unwrap_or_default(),
+                    &mut HashSet::new(),
+                    &self.spec,
+                )
+            },
+        ));
+        let voluntary_exits = summarize(self.op_pool.get_all_voluntary_exits().iter().map(|_| 0));
+        let bls_to_execution_changes = summarize(
+            self.op_pool
+                .get_all_bls_to_execution_changes()
+                .iter()
+                .map(|_| 0),
+        );
+
+        Ok(OpPoolSummary {
+            slot,
+            attestations,
+            proposer_slashings,
+            attester_slashings,
+            voluntary_exits,
+            bls_to_execution_changes,
+        })
+    }
+
+    /// Select the operations that would be packed into a block at `slot` on top of the head, in
+    /// the same way as block production, along with the reward contributed by each of them.
+    ///
+    /// The buffered unaggregated attestations are inserted into the op pool first, but unlike
+    /// block production, the naive aggregation pool is not copied into it.
+    pub fn op_pool_dry_run(
+        &self,
+        slot: Slot,
+    ) -> Result<OpPoolDryRun<T::EthSpec>, BeaconChainError> {
+        self.op_pool.insert_buffered_unaggregated_attestations();
+        let state = self.op_pool_state_at_slot(slot)?;
+        let proposer_index = state.get_beacon_proposer_index(slot, &self.spec)? as u64;
+
+        let mut prev_filter_cache = HashMap::new();
+        let prev_attestation_filter = |att: &AttestationRef<T::EthSpec>| {
+            self.filter_op_pool_attestation(&mut prev_filter_cache, att, &state)
+        };
+        let mut curr_filter_cache = HashMap::new();
+        let curr_attestation_filter = |att: &AttestationRef<T::EthSpec>| {
+            self.filter_op_pool_attestation(&mut curr_filter_cache, att, &state)
+        };
+        let attestations = self.op_pool.get_attestations_with_packing(
+            &state,
+            prev_attestation_filter,
+            curr_attestation_filter,
+            self.config.attestation_packing_algorithm,
+            self.config.attestation_packing_time_budget,
+            &self.spec,
+        )?;
+        let (proposer_slashings, attester_slashings, voluntary_exits) =
+            self.op_pool.get_slashings_and_exits(&state, &self.spec);
+        let bls_to_execution_changes = self
+            .op_pool
+            .get_bls_to_execution_changes(&state, &self.spec);
+
+        let mut reward_cache = RewardCache::default();
+        reward_cache.update(&state)?;
+        let attestation_rewards =
+            self.compute_attestation_rewards(&attestations, &state, &reward_cache)?;
+        let attestations = attestations
+            .into_iter()
+            .zip(attestation_rewards)
+            .map(|(operation, rewards)| PackedOperation {
+                operation,
+                reward: rewards.values().sum(),
+            })
+            .collect::<Vec<_>>();
+
+        // Slashings are processed in this order within a block, and a validator is only rewarded
+        // for the first time it is slashed.
+        let mut slashed = HashSet::new();
+        let proposer_slashings = proposer_slashings
+            .into_iter()
+            .map(|operation| PackedOperation {
+                reward: slashing_reward(
+                    &state,
+                    [operation.proposer_index()],
+                    &mut slashed,
+                    &self.spec,
+                ),
+                operation,
+            })
+            .collect::<Vec<_>>();
+        let attester_slashings = attester_slashings
+            .into_iter()
+            .map(|operation| PackedOperation {
+                reward: slashing_reward(
+                    &state,
+                    get_slashable_indices(&state, &operation).unwrap_or_default(),
+                    &mut slashed,
+                    &self.spec,
+                ),
+                operation,
+            })
+            .collect::<Vec<_>>();
+
+        let total_reward = attestations
+            .iter()
+            .map(|packed| packed.reward)
+            .chain(proposer_slashings.iter().map(|packed| packed.reward))
+            .chain(attester_slashings.iter().map(|packed| packed.reward))
+            .sum();
+
+        Ok(OpPoolDryRun {
+            slot,
+            proposer_index,
+            total_reward,
+            attestations,
+            proposer_slashings,
+            attester_slashings,
+            voluntary_exits,
+            bls_to_execution_changes,
+        })
+    }
+
+    /// Return a clone of the head state advanced to `slot`, with all caches built.
+    fn op_pool_state_at_slot(
+        &self,
+        slot: Slot,
+    ) -> Result<BeaconState<T::EthSpec>, BeaconChainError> {
+        let head = self.head_snapshot();
+        let state_root = head.beacon_state_root();
+        let mut state = head
+            .beacon_state
+            .clone_with(CloneConfig::committee_caches_only());
+        drop(head);
+
+        complete_state_advance(&mut state, Some(state_root), slot, &self.spec)?;
+        state.build_all_caches(&self.spec)?;
+        Ok(state)
+    }
+}
+
+/// Summarise operations with the given `rewards`.
+fn summarize(rewards: impl Iterator<Item = u64>) -> OperationPoolSummary {
+    let mut summary = OperationPoolSummary {
+        count: 0,
+        total_reward: 0,
+        max_reward: 0,
+    };
+    for reward in rewards {
+        summary.count += 1;
+        summary.total_reward += reward;
+        summary.max_reward = cmp::max(summary.max_reward, reward);
+    }
+    summary
+}
+
+/// The proposer reward for slashing each of the validators in `indices` on top of `state`.
+///
+/// Validators in `slashed` are skipped, and the others are added to it.
+fn slashing_reward<E: EthSpec>(
+    state: &BeaconState<E>,
+    indices: impl IntoIterator<Item = u64>,
+    slashed: &mut HashSet<u64>,
+    spec: &ChainSpec,
+) -> u64 {
+    let mut reward = 0;
+    for index in indices {
+        if !slashed.insert(index) {
+            continue;
+        }
+        // With no separate whistleblower, the proposer receives the entire whistleblower reward.
+        if let Some(validator) = state
+            .validators()
+            .get(index as usize)
+            .filter(|validator| validator.is_slashable_at(state.current_epoch()))
+        {
+            reward += validator.effective_balance / spec.whistleblower_reward_quotient;
+        }
+    }
+    reward
+}
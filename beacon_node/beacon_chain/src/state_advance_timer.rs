@@ -346,17 +346,28 @@ fn advance_head<T: BeaconChainTypes>(
             >= current_slot.epoch(T::EthSpec::slots_per_epoch())
         {
             // Potentially create logs/metrics for locally monitored validators.
-            if let Err(e) = beacon_chain
+            //
+            // The state descends from the canonical head, so its records and alerts need not wait
+            // for a block to become canonical.
+            let mut alerts = vec![];
+            let result = beacon_chain
                 .validator_monitor
                 .read()
-                .process_validator_statuses(state.current_epoch(), &summary, &beacon_chain.spec)
-            {
-                error!(
+                .process_validator_statuses(
+                    state.current_epoch(),
+                    &summary,
+                    &beacon_chain.spec,
+                    &mut alerts,
+                );
+            match result {
+                Ok(records) => beacon_chain.persist_validator_monitor_history(records),
+                Err(e) => error!(
                     log,
                     "Unable to process validator statuses";
                     "error" => ?e
-                );
+                ),
             }
+            beacon_chain.publish_validator_monitor_alerts(alerts);
         }
     }
 
@@ -442,6 +442,7 @@ where
             DEFAULT_TERMINAL_BLOCK,
             shanghai_time,
             None,
+            None,
             Some(JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap()),
             spec,
             None,
@@ -455,6 +456,7 @@ where
         mut self,
         beacon_url: SensitiveUrl,
         builder_threshold: Option<u128>,
+        builder_boost_factor: Option<u64>,
     ) -> Self {
         // Get a random unused port
         let port = unused_port::unused_tcp4_port().unwrap();
@@ -469,6 +471,7 @@ where
             DEFAULT_TERMINAL_BLOCK,
             shanghai_time,
             builder_threshold,
+            builder_boost_factor,
             Some(JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap()),
             spec.clone(),
             Some(builder_url.clone()),
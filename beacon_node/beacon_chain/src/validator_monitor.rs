@@ -3,8 +3,13 @@
 //! This component should not affect consensus.
 
 use crate::metrics;
-use parking_lot::RwLock;
-use slog::{crit, debug, info, Logger};
+use eth2::lighthouse::ValidatorMonitorEpochRecord;
+use eth2::types::{
+    EventKind, SseValidatorBalanceDecrease, SseValidatorMissedAttestation,
+    SseValidatorMissedProposal, SseValidatorSlashed,
+};
+use parking_lot::{Mutex, RwLock};
+use slog::{crit, debug, info, warn, Logger};
 use slot_clock::SlotClock;
 use state_processing::per_epoch_processing::{
     errors::EpochProcessingError, EpochProcessingSummary,
@@ -36,6 +41,14 @@ pub const HISTORIC_EPOCHS: usize = 10;
 /// Prometheus cardinality and log volume.
 pub const DEFAULT_INDIVIDUAL_TRACKING_THRESHOLD: usize = 64;
 
+/// The maximum number of alerts held by the validator monitor until their blocks become canonical.
+/// Further alerts are dropped.
+const MAX_PENDING_ALERTS: usize = 1_024;
+
+/// The maximum number of blocks for which epoch records and alerts are held until the block
+/// becomes canonical. The outputs of the oldest block are dropped first.
+const MAX_DEFERRED_BLOCKS: usize = 64;
+
 #[derive(Debug)]
 pub enum Error {
     InvalidPubkey(String),
@@ -43,6 +56,19 @@ pub enum Error {
     InvalidUtf8(Utf8Error),
 }
 
+/// Identifies an alert by its kind, validator index and epoch, so that an alert raised by both a
+/// block and the state advance timer is only published once. Missed proposals are also identified
+/// by their slot, since a validator may miss several proposals in an epoch.
+type AlertKey = (&'static str, u64, Epoch, Option<Slot>);
+
+/// The epoch records and alerts produced whilst verifying and importing a block, which are held
+/// until the block becomes canonical.
+pub struct DeferredBlockOutputs<T: EthSpec> {
+    pub slot: Slot,
+    pub records: Vec<ValidatorMonitorEpochRecord>,
+    pub alerts: Vec<EventKind<T>>,
+}
+
 /// Contains data pertaining to one validator for one epoch.
 #[derive(Default)]
 pub struct EpochSummary {
@@ -315,6 +341,13 @@ impl MonitoredValidator {
             summary_opt.and_then(|summary| summary.total_balance)
         })
     }
+
+    /// Returns the change in total balance from the epoch prior to `epoch`.
+    fn get_balance_delta(&self, epoch: Epoch) -> Option<i64> {
+        let balance = self.get_total_balance(epoch)?;
+        let prev_balance = self.get_total_balance(epoch.saturating_sub(1u64))?;
+        Some(balance as i64 - prev_balance as i64)
+    }
 }
 
 /// Holds a collection of `MonitoredValidator` and is notified about a variety of events on the P2P
@@ -325,7 +358,7 @@ impl MonitoredValidator {
 ///
 /// The intention of this struct is to provide users with more logging and Prometheus metrics around
 /// validators that they are interested in.
-pub struct ValidatorMonitor<T> {
+pub struct ValidatorMonitor<T: EthSpec> {
     /// The validators that require additional monitoring.
     validators: HashMap<PublicKeyBytes, MonitoredValidator>,
     /// A map of validator index (state.validators) to a validator public key.
@@ -337,6 +370,14 @@ pub struct ValidatorMonitor<T> {
     /// large validator counts causing infeasibly high cardinailty for
     /// Prometheus and high log volumes.
     individual_tracking_threshold: usize,
+    /// The indices of monitored validators which were unslashed in the last head state, used to
+    /// detect slashings.
+    unslashed: HashSet<u64>,
+    /// Epoch records and alerts produced whilst processing blocks which may not become canonical,
+    /// keyed by block root.
+    deferred: Mutex<HashMap<Hash256, DeferredBlockOutputs<T>>>,
+    /// The alerts which have already been published.
+    published_alerts: Mutex<HashSet<AlertKey>>,
     log: Logger,
     _phantom: PhantomData<T>,
 }
@@ -353,6 +394,9 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             indices: <_>::default(),
             auto_register,
             individual_tracking_threshold,
+            unslashed: <_>::default(),
+            deferred: <_>::default(),
+            published_alerts: <_>::default(),
             log,
             _phantom: PhantomData,
         };
@@ -485,6 +529,39 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         }
     }
 
+    /// Returns an alert for each monitored validator which is slashed in `state` but was unslashed
+    /// in the previous head state. The `state` *must* be the canonical head state, so that
+    /// slashings on abandoned forks are not reported.
+    pub fn process_head_state(&mut self, state: &BeaconState<T>) -> Vec<EventKind<T>> {
+        let epoch = state.current_epoch();
+        let mut alerts = vec![];
+        for monitored_validator in self.validators.values() {
+            let validator_index = match monitored_validator.index {
+                Some(i) => i,
+                None => continue,
+            };
+            if let Some(validator) = state.validators().get(validator_index as usize) {
+                if !validator.slashed {
+                    self.unslashed.insert(validator_index);
+                } else if self.unslashed.remove(&validator_index) {
+                    // Not gated behind `self.individual_tracking()` since it's an
+                    // infrequent and interesting message.
+                    crit!(
+                        self.log,
+                        "Validator slashed";
+                        "epoch" => epoch,
+                        "validator" => &monitored_validator.id,
+                    );
+                    alerts.push(EventKind::ValidatorSlashed(SseValidatorSlashed {
+                        validator_index,
+                        epoch,
+                    }));
+                }
+            }
+        }
+        alerts
+    }
+
     /// Run `func` with the `TOTAL_LABEL` and optionally the
     /// `individual_id`.
     ///
@@ -503,12 +580,19 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         }
     }
 
+    /// Logs and records the on-chain performance of monitored validators in the epoch before the
+    /// previous epoch of the state which produced `summary`, returning a record for each active
+    /// validator.
+    ///
+    /// Alerts for missed attestations and balance decreases are added to `alerts`.
     pub fn process_validator_statuses(
         &self,
         epoch: Epoch,
         summary: &EpochProcessingSummary<T>,
         spec: &ChainSpec,
-    ) -> Result<(), EpochProcessingError> {
+        alerts: &mut Vec<EventKind<T>>,
+    ) -> Result<Vec<ValidatorMonitorEpochRecord>, EpochProcessingError> {
+        let mut records = Vec::new();
         let mut attestation_success = Vec::new();
         let mut attestation_miss = Vec::new();
         let mut head_miss = Vec::new();
@@ -600,6 +684,12 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                         );
                     });
                     attestation_miss.push(id);
+                    alerts.push(EventKind::ValidatorMissedAttestation(
+                        SseValidatorMissedAttestation {
+                            validator_index: i as u64,
+                            epoch: prev_epoch,
+                        },
+                    ));
                     if self.individual_tracking() {
                         debug!(
                             self.log,
@@ -671,6 +761,43 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                         .previous_epoch_inclusion_info(i)
                         .map(|info| info.delay),
                 );
+                let (sync_signature_inclusions, blocks_proposed) = monitored_validator
+                    .get_from_epoch_summary(prev_epoch, |summary| {
+                        summary.map(|summary| {
+                            (summary.sync_signature_block_inclusions, summary.blocks)
+                        })
+                    })
+                    .unwrap_or_default();
+                let balance = monitored_validator.get_total_balance(prev_epoch);
+                let balance_delta = monitored_validator.get_balance_delta(prev_epoch);
+                records.push(ValidatorMonitorEpochRecord {
+                    epoch: prev_epoch,
+                    validator_index: i as u64,
+                    attestation_hit: previous_epoch_matched_any,
+                    attestation_head_hit: previous_epoch_matched_head,
+                    attestation_target_hit: previous_epoch_matched_target,
+                    attestation_inclusion_distance: min_inclusion_distance,
+                    sync_signature_inclusions: sync_signature_inclusions as u64,
+                    blocks_proposed: blocks_proposed as u64,
+                    balance,
+                    balance_delta,
+                });
+
+                // Decreases which leave the balance at or above the maximum effective balance are
+                // withdrawals of excess balance, rather than penalties.
+                if let (Some(balance), Some(balance_delta)) = (balance, balance_delta) {
+                    if balance_delta < 0 && balance < spec.max_effective_balance {
+                        alerts.push(EventKind::ValidatorBalanceDecrease(
+                            SseValidatorBalanceDecrease {
+                                validator_index: i as u64,
+                                epoch: prev_epoch,
+                                balance,
+                                balance_delta,
+                            },
+                        ));
+                    }
+                }
+
                 if let Some(inclusion_delay) = min_inclusion_distance {
                     if inclusion_delay > spec.min_attestation_inclusion_delay {
                         suboptimal_inclusion.push(id);
@@ -788,7 +915,92 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             );
         }
 
-        Ok(())
+        Ok(records)
+    }
+
+    /// Hold the `records` and `alerts` produced whilst processing the block with `block_root` at
+    /// `slot` until that block becomes canonical.
+    ///
+    /// Outputs deferred for the same block are combined.
+    pub fn defer(
+        &self,
+        block_root: Hash256,
+        slot: Slot,
+        records: Vec<ValidatorMonitorEpochRecord>,
+        mut alerts: Vec<EventKind<T>>,
+    ) {
+        if records.is_empty() && alerts.is_empty() {
+            return;
+        }
+
+        let mut deferred = self.deferred.lock();
+
+        let pending_alerts = deferred
+            .values()
+            .map(|outputs| outputs.alerts.len())
+            .sum::<usize>();
+        let available = MAX_PENDING_ALERTS.saturating_sub(pending_alerts);
+        if alerts.len() > available {
+            let dropped = alerts.len() - available;
+            warn!(
+                self.log,
+                "Dropping validator monitor alerts";
+                "info" => "too many alerts are awaiting a canonical block",
+                "dropped" => dropped,
+                "block_root" => ?block_root,
+            );
+            metrics::inc_counter_by(
+                &metrics::VALIDATOR_MONITOR_ALERTS_DROPPED_TOTAL,
+                dropped as u64,
+            );
+            alerts.truncate(available);
+        }
+
+        let outputs = deferred
+            .entry(block_root)
+            .or_insert_with(|| DeferredBlockOutputs {
+                slot,
+                records: vec![],
+                alerts: vec![],
+            });
+        outputs.records.extend(records);
+        outputs.alerts.extend(alerts);
+
+        while deferred.len() > MAX_DEFERRED_BLOCKS {
+            let oldest = deferred
+                .iter()
+                .min_by_key(|(_, outputs)| outputs.slot)
+                .map(|(block_root, _)| *block_root);
+            match oldest {
+                Some(block_root) => deferred.remove(&block_root),
+                None => break,
+            };
+        }
+    }
+
+    /// Removes and returns the outputs of every deferred block, alongside the block's root.
+    pub fn take_deferred(&self) -> Vec<(Hash256, DeferredBlockOutputs<T>)> {
+        self.deferred.lock().drain().collect()
+    }
+
+    /// Returns the `alerts` which have not already been published, in their original order, and
+    /// marks them as published.
+    pub fn retain_unpublished_alerts(&self, alerts: Vec<EventKind<T>>) -> Vec<EventKind<T>> {
+        let mut published = self.published_alerts.lock();
+        let alerts = alerts
+            .into_iter()
+            .filter(|alert| match alert_key::<T>(alert) {
+                Some(key) => published.insert(key),
+                None => true,
+            })
+            .collect::<Vec<_>>();
+
+        // Forget alerts which are too old to be raised again.
+        if let Some(latest_epoch) = published.iter().map(|(_, _, epoch, _)| *epoch).max() {
+            published.retain(|(_, _, epoch, _)| *epoch + HISTORIC_EPOCHS as u64 >= latest_epoch);
+        }
+
+        alerts
     }
 
     fn get_validator(&self, validator_index: u64) -> Option<&MonitoredValidator> {
@@ -1438,6 +1650,62 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         }
     }
 
+    /// Add an alert to `alerts` for each monitored validator which was due to propose a block in
+    /// the slots skipped between `parent_slot` and the slot of the block which produced `state`.
+    ///
+    /// Only skipped slots in the current epoch of `state` are checked, since the proposers of
+    /// earlier slots cannot be computed from `state`.
+    pub fn register_skipped_slots(
+        &self,
+        state: &BeaconState<T>,
+        parent_slot: Slot,
+        spec: &ChainSpec,
+        alerts: &mut Vec<EventKind<T>>,
+    ) {
+        let epoch_start_slot = state.current_epoch().start_slot(T::slots_per_epoch());
+        let start_slot = std::cmp::max(parent_slot + 1, epoch_start_slot);
+        for slot in (start_slot.as_u64()..state.slot().as_u64()).map(Slot::new) {
+            let proposer = match state.get_beacon_proposer_index(slot, spec) {
+                Ok(proposer) => proposer as u64,
+                Err(e) => {
+                    debug!(
+                        self.log,
+                        "Unable to compute proposer";
+                        "purpose" => "validator monitor",
+                        "slot" => slot,
+                        "error" => ?e,
+                    );
+                    continue;
+                }
+            };
+
+            if let Some(validator) = self.get_validator(proposer) {
+                let id = &validator.id;
+
+                self.aggregatable_metric(id, |label| {
+                    metrics::inc_counter_vec(
+                        &metrics::VALIDATOR_MONITOR_MISSED_BLOCKS_TOTAL,
+                        &[label],
+                    );
+                });
+
+                warn!(
+                    self.log,
+                    "Missed block proposal";
+                    "slot" => slot,
+                    "validator" => id,
+                );
+
+                alerts.push(EventKind::ValidatorMissedProposal(
+                    SseValidatorMissedProposal {
+                        validator_index: proposer,
+                        slot,
+                    },
+                ));
+            }
+        }
+    }
+
     /// Register a proposer slashing from the gossip network.
     pub fn register_gossip_proposer_slashing(&self, slashing: &ProposerSlashing) {
         self.register_proposer_slashing("gossip", slashing)
@@ -1731,6 +1999,37 @@ pub fn timestamp_now() -> Duration {
         .unwrap_or_else(|_| Duration::from_secs(0))
 }
 
+/// Returns the key which identifies `alert`, or `None` if it is not a validator monitor alert.
+fn alert_key<T: EthSpec>(alert: &EventKind<T>) -> Option<AlertKey> {
+    match alert {
+        EventKind::ValidatorMissedAttestation(alert) => Some((
+            "validator_missed_attestation",
+            alert.validator_index,
+            alert.epoch,
+            None,
+        )),
+        EventKind::ValidatorMissedProposal(alert) => Some((
+            "validator_missed_proposal",
+            alert.validator_index,
+            alert.slot.epoch(T::slots_per_epoch()),
+            Some(alert.slot),
+        )),
+        EventKind::ValidatorSlashed(alert) => Some((
+            "validator_slashed",
+            alert.validator_index,
+            alert.epoch,
+            None,
+        )),
+        EventKind::ValidatorBalanceDecrease(alert) => Some((
+            "validator_balance_decrease",
+            alert.validator_index,
+            alert.epoch,
+            None,
+        )),
+        _ => None,
+    }
+}
+
 fn u64_to_i64(n: impl Into<u64>) -> i64 {
     i64::try_from(n.into()).unwrap_or(i64::max_value())
 }
@@ -1786,3 +2085,120 @@ fn min_opt<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    fn validator_monitor() -> ValidatorMonitor<E> {
+        ValidatorMonitor::new(
+            vec![],
+            false,
+            DEFAULT_INDIVIDUAL_TRACKING_THRESHOLD,
+            logging::test_logger(),
+        )
+    }
+
+    fn missed_attestation(validator_index: u64, epoch: u64) -> EventKind<E> {
+        EventKind::ValidatorMissedAttestation(SseValidatorMissedAttestation {
+            validator_index,
+            epoch: Epoch::new(epoch),
+        })
+    }
+
+    fn missed_proposal(validator_index: u64, slot: u64) -> EventKind<E> {
+        EventKind::ValidatorMissedProposal(SseValidatorMissedProposal {
+            validator_index,
+            slot: Slot::new(slot),
+        })
+    }
+
+    #[test]
+    fn alerts_are_published_once() {
+        let monitor = validator_monitor();
+
+        let alerts = vec![
+            missed_attestation(1, 2),
+            missed_attestation(1, 2),
+            missed_attestation(2, 2),
+            missed_attestation(1, 3),
+            missed_proposal(1, 64),
+            missed_proposal(1, 65),
+        ];
+        assert_eq!(
+            monitor.retain_unpublished_alerts(alerts),
+            vec![
+                missed_attestation(1, 2),
+                missed_attestation(2, 2),
+                missed_attestation(1, 3),
+                missed_proposal(1, 64),
+                missed_proposal(1, 65),
+            ]
+        );
+
+        assert_eq!(
+            monitor
+                .retain_unpublished_alerts(vec![missed_attestation(2, 2), missed_proposal(1, 66)]),
+            vec![missed_proposal(1, 66)]
+        );
+    }
+
+    #[test]
+    fn deferred_alerts_are_capped() {
+        let monitor = validator_monitor();
+        let block_a = Hash256::repeat_byte(1);
+        let block_b = Hash256::repeat_byte(2);
+
+        monitor.defer(
+            block_a,
+            Slot::new(1),
+            vec![],
+            (0..MAX_PENDING_ALERTS as u64 - 1)
+                .map(|i| missed_attestation(i, 0))
+                .collect(),
+        );
+        monitor.defer(
+            block_b,
+            Slot::new(2),
+            vec![],
+            vec![missed_attestation(0, 1), missed_attestation(1, 1)],
+        );
+
+        let deferred = monitor
+            .take_deferred()
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        assert_eq!(deferred[&block_a].alerts.len(), MAX_PENDING_ALERTS - 1);
+        assert_eq!(deferred[&block_b].alerts, vec![missed_attestation(0, 1)]);
+    }
+
+    #[test]
+    fn deferred_outputs_of_a_block_are_combined() {
+        let monitor = validator_monitor();
+        let block_root = Hash256::repeat_byte(1);
+
+        monitor.defer(
+            block_root,
+            Slot::new(1),
+            vec![],
+            vec![missed_proposal(1, 0)],
+        );
+        monitor.defer(
+            block_root,
+            Slot::new(1),
+            vec![],
+            vec![missed_attestation(1, 0)],
+        );
+
+        let deferred = monitor.take_deferred();
+        assert_eq!(deferred.len(), 1);
+        assert_eq!(
+            deferred[0].1.alerts,
+            vec![missed_proposal(1, 0), missed_attestation(1, 0)]
+        );
+        assert!(monitor.take_deferred().is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+//! Publishes the alerts raised by the validator monitor, so that operators can be notified of
+//! missed duties, slashings and balance decreases without scraping metrics.
+//!
+//! Alerts are only published once the block which raised them is canonical, and each alert is
+//! published at most once.
+//!
+//! Each alert is sent to the server-sent events stream under its own topic and, if
+//! `ChainConfig::validator_monitor_alert_webhook` is set, alerts are POSTed to the webhook in
+//! batches as a JSON array of `{"event": <topic>, "data": <alert>}` objects.
+
+use crate::{BeaconChain, BeaconChainTypes};
+use eth2::types::EventKind;
+use serde_json::{json, Value};
+use slog::{debug, warn};
+use std::time::Duration;
+use types::EthSpec;
+
+/// The timeout applied to requests to the alert webhook.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Publish those `alerts` which have not already been published. The `alerts` *must* have
+    /// been raised by the canonical chain.
+    ///
+    /// ## Warning
+    ///
+    /// This function obtains a read lock on `self.validator_monitor`, so it must not be called
+    /// whilst a lock on it is held.
+    pub fn publish_validator_monitor_alerts(&self, alerts: Vec<EventKind<T::EthSpec>>) {
+        if alerts.is_empty() {
+            return;
+        }
+        let alerts = self
+            .validator_monitor
+            .read()
+            .retain_unpublished_alerts(alerts);
+        if alerts.is_empty() {
+            return;
+        }
+
+        if let Some(url) = self.config.validator_monitor_alert_webhook.clone() {
+            let body = webhook_body(&alerts);
+            let log = self.log.clone();
+            self.task_executor.spawn(
+                async move {
+                    let result = reqwest::Client::new()
+                        .post(url.full.clone())
+                        .timeout(WEBHOOK_TIMEOUT)
+                        .json(&body)
+                        .send()
+                        .await
+                        .and_then(|response| response.error_for_status());
+                    match result {
+                        Ok(_) => debug!(
+                            log,
+                            "Sent validator monitor alerts";
+                            "count" => body.len(),
+                        ),
+                        Err(e) => warn!(
+                            log,
+                            "Failed to send validator monitor alerts";
+                            "error" => %e.without_url(),
+                            "url" => %url,
+                        ),
+                    }
+                },
+                "validator_monitor_alert_webhook",
+            );
+        }
+
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            for alert in alerts {
+                event_handler.register(alert);
+            }
+        }
+    }
+}
+
+/// Returns the body of the webhook request which reports `alerts`.
+fn webhook_body<E: EthSpec>(alerts: &[EventKind<E>]) -> Vec<Value> {
+    alerts
+        .iter()
+        .map(|alert| json!({ "event": alert.topic_name(), "data": alert }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth2::types::{
+        SseValidatorBalanceDecrease, SseValidatorMissedAttestation, SseValidatorMissedProposal,
+        SseValidatorSlashed,
+    };
+    use types::{Epoch, MainnetEthSpec, Slot};
+
+    #[test]
+    fn webhook_body_lists_each_alert_with_its_topic() {
+        let alerts = vec![
+            EventKind::<MainnetEthSpec>::ValidatorMissedAttestation(
+                SseValidatorMissedAttestation {
+                    validator_index: 1,
+                    epoch: Epoch::new(2),
+                },
+            ),
+            EventKind::ValidatorMissedProposal(SseValidatorMissedProposal {
+                validator_index: 3,
+                slot: Slot::new(70),
+            }),
+            EventKind::ValidatorSlashed(SseValidatorSlashed {
+                validator_index: 4,
+                epoch: Epoch::new(5),
+            }),
+            EventKind::ValidatorBalanceDecrease(SseValidatorBalanceDecrease {
+                validator_index: 6,
+                epoch: Epoch::new(7),
+                balance: 31_999_990_000,
+                balance_delta: -10_000,
+            }),
+        ];
+
+        assert_eq!(
+            Value::Array(webhook_body(&alerts)),
+            json!([
+                {
+                    "event": "validator_missed_attestation",
+                    "data": { "validator_index": "1", "epoch": "2" }
+                },
+                {
+                    "event": "validator_missed_proposal",
+                    "data": { "validator_index": "3", "slot": "70" }
+                },
+                {
+                    "event": "validator_slashed",
+                    "data": { "validator_index": "4", "epoch": "5" }
+                },
+                {
+                    "event": "validator_balance_decrease",
+                    "data": {
+                        "validator_index": "6",
+                        "epoch": "7",
+                        "balance": "31999990000",
+                        "balance_delta": "-10000"
+                    }
+                }
+            ])
+        );
+    }
+}
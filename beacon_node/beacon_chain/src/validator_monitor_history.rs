@@ -0,0 +1,243 @@
+//! Persists the per-epoch records produced by the validator monitor, so that the performance of
+//! monitored validators can be audited long after the in-memory summaries have been pruned.
+//!
+//! Records produced whilst processing a block are only persisted once that block is canonical.
+//!
+//! Records are keyed by epoch and then validator index, so that records for a range of epochs can
+//! be read with point lookups and expired records are found at the start of the column.
+
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::ValidatorMonitorEpochRecord;
+use slog::{debug, error};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error as StoreError, ItemStore, KeyValueStore, KeyValueStoreOp, StoreItem};
+use types::{BeaconState, Epoch, Hash256, Slot};
+
+/// A `ValidatorMonitorEpochRecord` as it is stored in the database.
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct PersistedValidatorMonitorRecord {
+    epoch: Epoch,
+    validator_index: u64,
+    attestation_hit: bool,
+    attestation_head_hit: bool,
+    attestation_target_hit: bool,
+    attestation_inclusion_distance: Option<u64>,
+    sync_signature_inclusions: u64,
+    blocks_proposed: u64,
+    balance: Option<u64>,
+    /// SSZ has no signed integers, so the balance delta is stored as its two's complement.
+    balance_delta: Option<u64>,
+}
+
+impl From<&ValidatorMonitorEpochRecord> for PersistedValidatorMonitorRecord {
+    fn from(record: &ValidatorMonitorEpochRecord) -> Self {
+        Self {
+            epoch: record.epoch,
+            validator_index: record.validator_index,
+            attestation_hit: record.attestation_hit,
+            attestation_head_hit: record.attestation_head_hit,
+            attestation_target_hit: record.attestation_target_hit,
+            attestation_inclusion_distance: record.attestation_inclusion_distance,
+            sync_signature_inclusions: record.sync_signature_inclusions,
+            blocks_proposed: record.blocks_proposed,
+            balance: record.balance,
+            balance_delta: record.balance_delta.map(|delta| delta as u64),
+        }
+    }
+}
+
+impl From<PersistedValidatorMonitorRecord> for ValidatorMonitorEpochRecord {
+    fn from(record: PersistedValidatorMonitorRecord) -> Self {
+        Self {
+            epoch: record.epoch,
+            validator_index: record.validator_index,
+            attestation_hit: record.attestation_hit,
+            attestation_head_hit: record.attestation_head_hit,
+            attestation_target_hit: record.attestation_target_hit,
+            attestation_inclusion_distance: record.attestation_inclusion_distance,
+            sync_signature_inclusions: record.sync_signature_inclusions,
+            blocks_proposed: record.blocks_proposed,
+            balance: record.balance,
+            balance_delta: record.balance_delta.map(|delta| delta as i64),
+        }
+    }
+}
+
+impl StoreItem for PersistedValidatorMonitorRecord {
+    fn db_column() -> DBColumn {
+        DBColumn::ValidatorMonitorHistory
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
+
+/// Returns the database key for the record of `validator_index` at `epoch`.
+///
+/// Both values are big-endian so that keys are ordered by epoch.
+fn record_key(epoch: Epoch, validator_index: u64) -> Hash256 {
+    let mut key = Hash256::zero();
+    key[0..8].copy_from_slice(&epoch.as_u64().to_be_bytes());
+    key[8..16].copy_from_slice(&validator_index.to_be_bytes());
+    key
+}
+
+fn record_key_epoch(key: &Hash256) -> Epoch {
+    let mut epoch_bytes = [0; 8];
+    epoch_bytes.copy_from_slice(&key[0..8]);
+    Epoch::new(u64::from_be_bytes(epoch_bytes))
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Store the records produced by the validator monitor for the canonical chain, and delete any
+    /// records which have passed the retention period.
+    ///
+    /// Records are overwritten if the same epoch is processed more than once.
+    pub fn persist_validator_monitor_history(&self, records: Vec<ValidatorMonitorEpochRecord>) {
+        let retention_epochs = self.config.validator_monitor_history_epochs;
+        let latest_epoch = match records.iter().map(|record| record.epoch).max() {
+            Some(epoch) if retention_epochs > 0 => epoch,
+            _ => return,
+        };
+
+        let result = self
+            .prune_validator_monitor_history(latest_epoch.saturating_sub(retention_epochs))
+            .and_then(|mut ops| {
+                ops.extend(records.iter().map(|record| {
+                    let key = record_key(record.epoch, record.validator_index);
+                    PersistedValidatorMonitorRecord::from(record).as_kv_store_op(key)
+                }));
+                self.store.hot_db.do_atomically(ops)
+            });
+
+        if let Err(e) = result {
+            error!(
+                self.log,
+                "Failed to store validator monitor history";
+                "error" => ?e,
+                "epoch" => latest_epoch,
+            );
+        }
+    }
+
+    /// Store the records and publish the alerts which were deferred whilst processing blocks that
+    /// are now the canonical head or its ancestors, alongside any slashing alerts for the new
+    /// `head_state`.
+    ///
+    /// The outputs of other blocks are held in case a re-org makes them canonical, unless they
+    /// are no later than `finalized_slot`.
+    pub fn process_canonical_validator_monitor_outputs(
+        &self,
+        head_block_root: Hash256,
+        head_state: &BeaconState<T::EthSpec>,
+        finalized_slot: Slot,
+    ) {
+        let slashing_alerts = self
+            .validator_monitor
+            .write()
+            .process_head_state(head_state);
+
+        let deferred = self.validator_monitor.read().take_deferred();
+
+        let fork_choice = self.canonical_head.fork_choice_read_lock();
+        let (mut canonical, other): (Vec<_>, Vec<_>) =
+            deferred.into_iter().partition(|(block_root, _)| {
+                fork_choice
+                    .proto_array()
+                    .is_descendant(*block_root, head_block_root)
+            });
+        drop(fork_choice);
+
+        let validator_monitor = self.validator_monitor.read();
+        for (block_root, outputs) in other {
+            if outputs.slot > finalized_slot {
+                validator_monitor.defer(block_root, outputs.slot, outputs.records, outputs.alerts);
+            }
+        }
+        drop(validator_monitor);
+
+        canonical.sort_by_key(|(_, outputs)| outputs.slot);
+        let mut records = vec![];
+        let mut alerts = vec![];
+        for (_, outputs) in canonical {
+            records.extend(outputs.records);
+            alerts.extend(outputs.alerts);
+        }
+        alerts.extend(slashing_alerts);
+
+        self.persist_validator_monitor_history(records);
+        self.publish_validator_monitor_alerts(alerts);
+    }
+
+    /// Returns the operations which delete records from before `earliest_epoch`.
+    fn prune_validator_monitor_history(
+        &self,
+        earliest_epoch: Epoch,
+    ) -> Result<Vec<KeyValueStoreOp>, StoreError> {
+        let column = DBColumn::ValidatorMonitorHistory;
+        let mut ops = vec![];
+        for key in self.store.hot_db.iter_column_keys(column) {
+            let key = key?;
+            if record_key_epoch(&key) >= earliest_epoch {
+                break;
+            }
+            ops.push(KeyValueStoreOp::DeleteKey(store::get_key_for_col(
+                column.into(),
+                key.as_bytes(),
+            )));
+        }
+
+        if !ops.is_empty() {
+            debug!(
+                self.log,
+                "Pruning validator monitor history";
+                "records" => ops.len(),
+                "earliest_epoch" => earliest_epoch,
+            );
+        }
+        Ok(ops)
+    }
+
+    /// Returns the stored records of `validator_index` from `from_epoch` to `to_epoch`
+    /// (inclusive), oldest first.
+    ///
+    /// Epochs in which the validator was not monitored or was inactive have no record.
+    pub fn validator_monitor_history(
+        &self,
+        validator_index: u64,
+        from_epoch: Epoch,
+        to_epoch: Epoch,
+    ) -> Result<Vec<ValidatorMonitorEpochRecord>, BeaconChainError> {
+        let mut records = vec![];
+        for epoch in (from_epoch.as_u64()..=to_epoch.as_u64()).map(Epoch::new) {
+            if let Some(record) = self
+                .store
+                .hot_db
+                .get::<PersistedValidatorMonitorRecord>(&record_key(epoch, validator_index))?
+            {
+                records.push(record.into());
+            }
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_keys_are_ordered_by_epoch() {
+        let early = record_key(Epoch::new(1), u64::MAX);
+        let late = record_key(Epoch::new(256), 0);
+        assert!(early < late);
+        assert_eq!(record_key_epoch(&early), Epoch::new(1));
+        assert_eq!(record_key_epoch(&late), Epoch::new(256));
+    }
+}
@@ -7,7 +7,7 @@ use beacon_chain::otb_verification_service::{
 use beacon_chain::{
     canonical_head::{CachedHead, CanonicalHead},
     test_utils::{BeaconChainHarness, EphemeralHarnessType},
-    BeaconChainError, BlockError, ExecutionPayloadError, NotifyExecutionLayer,
+    BeaconChainError, BlockError, ChainConfig, ExecutionPayloadError, NotifyExecutionLayer,
     OverrideForkchoiceUpdate, StateSkipConfig, WhenSlotSkipped,
     INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON,
     INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON,
@@ -2029,3 +2029,52 @@ async fn weights_after_resetting_optimistic_status() {
         rig.import_block(Payload::Valid).await;
     }
 }
+
+/// With historical archive sync enabled, post-merge blocks are imported optimistically without an
+/// execution layer once their block hashes have been verified locally.
+#[tokio::test]
+async fn historical_archive_sync_without_execution_layer() {
+    let mut rig = InvalidPayloadRig::new();
+    rig.move_to_terminal_block();
+    let roots = rig.build_blocks(E::slots_per_epoch(), Payload::Valid).await;
+
+    let spec = rig.harness.spec.clone();
+    let archive = BeaconChainHarness::builder(MainnetEthSpec)
+        .spec(spec.clone())
+        .chain_config(ChainConfig {
+            historical_archive_sync: true,
+            ..ChainConfig::default()
+        })
+        .logger(test_logger())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .fresh_ephemeral_store()
+        .build();
+    assert!(archive.chain.execution_layer.is_none());
+
+    // The blocks are old enough to be imported optimistically, as they would be when syncing the
+    // chain history.
+    archive.set_current_slot(
+        rig.harness.chain.slot().unwrap() + spec.safe_slots_to_import_optimistically,
+    );
+
+    for root in &roots {
+        let block = rig.harness.chain.get_block(root).await.unwrap().unwrap();
+        archive.process_block_result(block).await.unwrap();
+    }
+
+    assert_eq!(archive.head_block_root(), *roots.last().unwrap());
+    for root in &roots {
+        let execution_status = archive
+            .chain
+            .canonical_head
+            .fork_choice_read_lock()
+            .get_block(root)
+            .unwrap()
+            .execution_status;
+        assert_eq!(
+            execution_status,
+            ExecutionStatus::Optimistic(rig.block_hash(*root)),
+            "blocks without an execution layer are not verified"
+        );
+    }
+}
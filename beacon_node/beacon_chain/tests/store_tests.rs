@@ -12,6 +12,7 @@ use beacon_chain::{
     BeaconChainError, BeaconChainTypes, BeaconSnapshot, ChainConfig, NotifyExecutionLayer,
     ServerSentEventHandler, WhenSlotSkipped,
 };
+use eth2::lighthouse::ValidatorMonitorEpochRecord;
 use fork_choice::CountUnrealized;
 use lazy_static::lazy_static;
 use logging::test_logger;
@@ -99,6 +100,99 @@ async fn full_participation_no_skips() {
     check_iterators(&harness);
 }
 
+#[tokio::test]
+async fn validator_monitor_history_is_persisted() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store, LOW_VALIDATOR_COUNT);
+
+    // Extend the chain so that the validator monitor learns the validator indices.
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    for validator_index in 0..LOW_VALIDATOR_COUNT as u64 {
+        harness
+            .chain
+            .validator_monitor
+            .write()
+            .auto_register_local_validator(validator_index);
+    }
+
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize * 3,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let current_epoch = harness.chain.epoch().unwrap();
+    let history = harness
+        .chain
+        .validator_monitor_history(0, Epoch::new(0), current_epoch)
+        .unwrap();
+    assert!(!history.is_empty());
+    assert!(history.windows(2).all(|w| w[0].epoch < w[1].epoch));
+    for record in history {
+        assert_eq!(record.validator_index, 0);
+        assert!(record.attestation_hit);
+        assert!(record.attestation_target_hit);
+    }
+}
+
+#[tokio::test]
+async fn prune_validator_monitor_history() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .default_spec()
+        .keypairs(KEYPAIRS[0..LOW_VALIDATOR_COUNT].to_vec())
+        .logger(store.logger().clone())
+        .fresh_disk_store(store)
+        .mock_execution_layer()
+        .chain_config(ChainConfig {
+            validator_monitor_history_epochs: 2,
+            ..ChainConfig::default()
+        })
+        .build();
+
+    let record = |epoch: u64, validator_index: u64| ValidatorMonitorEpochRecord {
+        epoch: Epoch::new(epoch),
+        validator_index,
+        attestation_hit: true,
+        attestation_head_hit: false,
+        attestation_target_hit: true,
+        attestation_inclusion_distance: Some(1),
+        sync_signature_inclusions: 0,
+        blocks_proposed: 0,
+        balance: Some(31_999_999_990),
+        balance_delta: Some(-10),
+    };
+    for epoch in 0..6 {
+        harness
+            .chain
+            .persist_validator_monitor_history(vec![record(epoch, 0), record(epoch, 1)]);
+    }
+
+    // Records from before the retention period are deleted for every validator.
+    for validator_index in 0..2 {
+        let history = harness
+            .chain
+            .validator_monitor_history(validator_index, Epoch::new(0), Epoch::new(5))
+            .unwrap();
+        assert_eq!(
+            history,
+            (3..6)
+                .map(|epoch| record(epoch, validator_index))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
 #[tokio::test]
 async fn randomised_skips() {
     let num_slots = E::slots_per_epoch() * 5;
@@ -2,6 +2,8 @@
 
 use beacon_chain::{
     attestation_verification::Error as AttnError,
+    events::EventKind,
+    observed_operations::ObservationOutcome,
     test_utils::{
         AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
         OP_POOL_DB_KEY,
@@ -14,6 +16,7 @@ use operation_pool::PersistedOperationPool;
 use state_processing::{
     per_slot_processing, per_slot_processing::Error as SlotProcessingError, EpochProcessingError,
 };
+use std::collections::HashSet;
 use types::{
     BeaconState, BeaconStateError, EthSpec, Hash256, Keypair, MinimalEthSpec, RelativeEpoch, Slot,
 };
@@ -869,3 +872,200 @@ async fn block_roots_skip_slot_behaviour() {
         "WhenSlotSkipped::Prev should return None on a future slot"
     );
 }
+
+/// Import a block so that the validator monitor learns the validator indices, then monitor the
+/// validators with `validator_indices`.
+async fn monitor_validators(
+    harness: &BeaconChainHarness<EphemeralHarnessType<MinimalEthSpec>>,
+    validator_indices: impl IntoIterator<Item = u64>,
+) {
+    harness
+        .extend_chain(
+            1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    for validator_index in validator_indices {
+        harness
+            .chain
+            .validator_monitor
+            .write()
+            .auto_register_local_validator(validator_index);
+    }
+}
+
+fn received_events(
+    receiver: &mut tokio::sync::broadcast::Receiver<EventKind<MinimalEthSpec>>,
+) -> Vec<EventKind<MinimalEthSpec>> {
+    let mut events = vec![];
+    while let Ok(event) = receiver.try_recv() {
+        events.push(event);
+    }
+    events
+}
+
+#[tokio::test]
+async fn validator_monitor_alerts_on_missed_proposals() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let slots_per_epoch = MinimalEthSpec::slots_per_epoch();
+
+    monitor_validators(&harness, 0..VALIDATOR_COUNT as u64).await;
+
+    let mut alerts = harness
+        .chain
+        .event_handler
+        .as_ref()
+        .unwrap()
+        .subscribe_validator_missed_proposal();
+
+    // Skip the first slots of the next epoch. Skipped slots in the previous epoch are not
+    // reported.
+    let block_slot = Slot::new(slots_per_epoch + 3);
+    let (state, state_root) = harness.get_current_state_and_root();
+    harness
+        .add_attested_blocks_at_slots(
+            state,
+            state_root,
+            &[block_slot],
+            &harness.get_all_validators(),
+        )
+        .await;
+
+    let mut missed_slots = vec![];
+    while let Ok(alert) = alerts.try_recv() {
+        match alert {
+            EventKind::ValidatorMissedProposal(alert) => missed_slots.push(alert.slot),
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+    assert_eq!(
+        missed_slots,
+        (slots_per_epoch..block_slot.as_u64())
+            .map(Slot::new)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn validator_monitor_alerts_on_missed_attestations_and_balance_decreases() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let event_handler = harness.chain.event_handler.as_ref().unwrap();
+    let mut missed_attestations = event_handler.subscribe_validator_missed_attestation();
+    let mut balance_decreases = event_handler.subscribe_validator_balance_decrease();
+
+    // Only the first half of the validators attest. Only a few of the others are monitored, so
+    // that the alerts of a block fit in the event channels.
+    let attesters = (0..VALIDATOR_COUNT / 2).collect::<Vec<_>>();
+    let absentees = (VALIDATOR_COUNT as u64 - 3..VALIDATOR_COUNT as u64).collect::<Vec<_>>();
+    monitor_validators(&harness, absentees.clone()).await;
+
+    let mut missed = HashSet::new();
+    let mut decreases = HashSet::new();
+    for _ in 0..6 * MinimalEthSpec::slots_per_epoch() {
+        harness.advance_slot();
+        harness
+            .extend_chain(
+                1,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::SomeValidators(attesters.clone()),
+            )
+            .await;
+
+        for alert in received_events(&mut missed_attestations) {
+            match alert {
+                EventKind::ValidatorMissedAttestation(alert) => {
+                    assert!(
+                        absentees.contains(&alert.validator_index),
+                        "validator {} attested",
+                        alert.validator_index
+                    );
+                    assert!(
+                        missed.insert((alert.validator_index, alert.epoch)),
+                        "duplicate alert for validator {} in epoch {}",
+                        alert.validator_index,
+                        alert.epoch
+                    );
+                }
+                other => panic!("unexpected event {:?}", other),
+            }
+        }
+
+        for alert in received_events(&mut balance_decreases) {
+            match alert {
+                EventKind::ValidatorBalanceDecrease(alert) => {
+                    assert!(absentees.contains(&alert.validator_index));
+                    assert!(alert.balance_delta < 0);
+                    assert!(decreases.insert((alert.validator_index, alert.epoch)));
+                }
+                other => panic!("unexpected event {:?}", other),
+            }
+        }
+    }
+
+    for validator_index in &absentees {
+        assert!(
+            missed.iter().any(|(index, _)| index == validator_index),
+            "no missed attestation alert for validator {}",
+            validator_index
+        );
+        assert!(
+            decreases.iter().any(|(index, _)| index == validator_index),
+            "no balance decrease alert for validator {}",
+            validator_index
+        );
+    }
+}
+
+#[tokio::test]
+async fn validator_monitor_alerts_on_slashing() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let mut alerts = harness
+        .chain
+        .event_handler
+        .as_ref()
+        .unwrap()
+        .subscribe_validator_slashed();
+
+    monitor_validators(&harness, 0..VALIDATOR_COUNT as u64).await;
+
+    // Import another block, so that the validator monitor sees the validators unslashed.
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let slashed_index = 3;
+    let slashing = match harness
+        .chain
+        .verify_proposer_slashing_for_gossip(harness.make_proposer_slashing(slashed_index))
+        .unwrap()
+    {
+        ObservationOutcome::New(slashing) => slashing,
+        ObservationOutcome::AlreadyKnown => panic!("slashing should be new"),
+    };
+    harness.chain.import_proposer_slashing(slashing);
+
+    // The slashing is included in the next block and alerted once, when it is the head.
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            2,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let slashed = received_events(&mut alerts)
+        .into_iter()
+        .map(|alert| match alert {
+            EventKind::ValidatorSlashed(alert) => alert.validator_index,
+            other => panic!("unexpected event {:?}", other),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(slashed, vec![slashed_index]);
+}
@@ -60,6 +60,10 @@ impl BuilderHttpClient {
         })
     }
 
+    pub fn server(&self) -> &SensitiveUrl {
+        &self.server
+    }
+
     async fn get_with_timeout<T: DeserializeOwned, U: IntoUrl>(
         &self,
         url: U,
@@ -1,6 +1,11 @@
 use crate::address_change_broadcast::broadcast_address_changes_at_capella;
+use crate::checkpoint_sync::{
+    agree_on_deposit_snapshot, agree_on_finalized_checkpoint, default_quorum,
+    verify_block_root_quorum,
+};
 use crate::config::{ClientGenesis, Config as ClientConfig};
 use crate::notifier::spawn_notifier;
+use crate::peer_checkpoint_sync::download_checkpoint_from_peers;
 use crate::Client;
 use beacon_chain::otb_verification_service::start_otb_verification_service;
 use beacon_chain::proposer_prep_service::start_proposer_prep_service;
@@ -203,6 +208,7 @@ where
         } else if chain_exists {
             if matches!(client_genesis, ClientGenesis::WeakSubjSszBytes { .. })
                 || matches!(client_genesis, ClientGenesis::CheckpointSyncUrl { .. })
+                || matches!(client_genesis, ClientGenesis::CheckpointSyncPeers { .. })
             {
                 info!(
                     context.log(),
@@ -265,19 +271,25 @@ where
             ClientGenesis::CheckpointSyncUrl {
                 genesis_state_bytes,
                 url,
+                additional_urls,
+                quorum,
             } => {
                 info!(
                     context.log(),
                     "Starting checkpoint sync";
                     "remote_url" => %url,
+                    "additional_providers" => additional_urls.len(),
                 );
 
-                let remote = BeaconNodeHttpClient::new(
-                    url,
-                    Timeouts::set_all(Duration::from_secs(
-                        config.chain.checkpoint_sync_url_timeout,
-                    )),
-                );
+                let timeouts = Timeouts::set_all(Duration::from_secs(
+                    config.chain.checkpoint_sync_url_timeout,
+                ));
+                let providers = std::iter::once(url)
+                    .chain(additional_urls)
+                    .map(|url| BeaconNodeHttpClient::new(url, timeouts.clone()))
+                    .collect::<Vec<_>>();
+                let quorum = quorum.unwrap_or_else(|| default_quorum(providers.len()));
+                let remote = &providers[0];
                 let slots_per_epoch = TEthSpec::slots_per_epoch();
 
                 let deposit_snapshot = if config.sync_eth1_chain {
@@ -285,50 +297,33 @@ where
                     // ensure that the snapshot is not newer than the beacon state that satisfies the
                     // deposit finalization conditions
                     debug!(context.log(), "Downloading deposit snapshot");
-                    let deposit_snapshot_result = remote
-                        .get_deposit_snapshot()
-                        .await
-                        .map_err(|e| match e {
-                            ApiError::InvalidSsz(e) => format!(
-                                "Unable to parse SSZ: {:?}. Ensure the checkpoint-sync-url refers to a \
-                                node for the correct network",
-                                e
-                            ),
-                            e => format!("Error fetching deposit snapshot from remote: {:?}", e),
-                        });
-                    match deposit_snapshot_result {
-                        Ok(Some(deposit_snapshot)) => {
-                            if deposit_snapshot.is_valid() {
-                                Some(deposit_snapshot)
-                            } else {
-                                warn!(context.log(), "Remote BN sent invalid deposit snapshot!");
-                                None
-                            }
-                        }
-                        Ok(None) => {
-                            warn!(
-                                context.log(),
-                                "Remote BN does not support EIP-4881 fast deposit sync"
-                            );
-                            None
-                        }
-                        Err(e) => {
-                            warn!(
-                                context.log(),
-                                "Remote BN does not support EIP-4881 fast deposit sync";
-                                "error" => e
-                            );
-                            None
-                        }
-                    }
+                    agree_on_deposit_snapshot(&providers, quorum, context.log()).await
                 } else {
                     None
                 };
 
-                debug!(context.log(), "Downloading finalized block");
+                let agreed = agree_on_finalized_checkpoint(
+                    &providers,
+                    quorum,
+                    config.chain.weak_subjectivity_checkpoint,
+                    context.log(),
+                )
+                .await?;
+                // Download from the primary provider unless it disagrees with the quorum.
+                let remote = if agreed.agreeing.contains(&0) {
+                    remote
+                } else {
+                    &providers[agreed.agreeing[0]]
+                };
+
+                debug!(
+                    context.log(),
+                    "Downloading finalized block";
+                    "remote_url" => %remote,
+                );
                 // Find a suitable finalized block on an epoch boundary.
                 let mut block = remote
-                    .get_beacon_blocks_ssz::<TEthSpec>(BlockId::Finalized, &spec)
+                    .get_beacon_blocks_ssz::<TEthSpec>(BlockId::Root(agreed.checkpoint.root), &spec)
                     .await
                     .map_err(|e| match e {
                         ApiError::InvalidSsz(e) => format!(
@@ -377,6 +372,19 @@ where
                     "block_slot" => block.slot(),
                 );
 
+                // The aligned block was chosen by a single provider, have the others confirm it.
+                let block_root = block.canonical_root();
+                if block_root != agreed.checkpoint.root {
+                    verify_block_root_quorum(
+                        &providers,
+                        quorum,
+                        block.slot(),
+                        block_root,
+                        context.log(),
+                    )
+                    .await?;
+                }
+
                 let state_root = block.state_root();
                 debug!(
                     context.log(),
@@ -437,6 +445,46 @@ where
                     .weak_subjectivity_state(state, block, genesis_state)
                     .map(|v| (v, service))?
             }
+            ClientGenesis::CheckpointSyncPeers {
+                genesis_state_bytes,
+            } => {
+                let wss_checkpoint = config
+                    .chain
+                    .weak_subjectivity_checkpoint
+                    .ok_or("Checkpoint sync from peers requires a weak subjectivity checkpoint")?;
+
+                info!(
+                    context.log(),
+                    "Starting checkpoint sync from peers";
+                    "block_root" => ?wss_checkpoint.root,
+                    "epoch" => wss_checkpoint.epoch,
+                );
+
+                let genesis_state = BeaconState::from_ssz_bytes(&genesis_state_bytes, &spec)
+                    .map_err(|e| format!("Unable to parse genesis state SSZ: {:?}", e))?;
+
+                let (state, block) = download_checkpoint_from_peers(
+                    &config.network,
+                    context.executor.clone(),
+                    &genesis_state,
+                    wss_checkpoint,
+                    &spec,
+                    context.log(),
+                )
+                .await?;
+
+                info!(
+                    context.log(),
+                    "Loaded checkpoint block and state";
+                    "slot" => block.slot(),
+                    "block_root" => ?block.canonical_root(),
+                    "state_root" => ?block.state_root(),
+                );
+
+                builder
+                    .weak_subjectivity_state(state, block, genesis_state)
+                    .map(|v| (v, None))?
+            }
             ClientGenesis::DepositContract => {
                 info!(
                     context.log(),
@@ -0,0 +1,471 @@
+//! Cross-checks the finalized checkpoint served by several checkpoint sync providers so that a
+//! single compromised or stale provider cannot bootstrap the node onto the wrong chain.
+use eth2::{
+    types::{BlockId, StateId},
+    BeaconNodeHttpClient,
+};
+use futures::future::join_all;
+use slog::{crit, debug, info, warn, Logger};
+use std::collections::HashMap;
+use std::fmt::Display;
+use types::{Checkpoint, DepositTreeSnapshot, Hash256, Slot};
+
+/// Returns the number of providers that must agree when no quorum is configured: a strict
+/// majority.
+pub fn default_quorum(provider_count: usize) -> usize {
+    provider_count / 2 + 1
+}
+
+/// A finalized checkpoint which a quorum of providers agree upon.
+pub struct AgreedCheckpoint {
+    pub checkpoint: Checkpoint,
+    /// Indices into the provider list of the providers which served `checkpoint`.
+    pub agreeing: Vec<usize>,
+}
+
+/// Requests the finalized checkpoint from each of the `providers` and returns the checkpoint
+/// reported by the most providers, so long as at least `quorum` of them agree.
+///
+/// If a `wss_checkpoint` is provided for the same epoch as the agreed checkpoint, their roots must
+/// match. Older weak subjectivity checkpoints are verified against the downloaded state when the
+/// beacon chain is built.
+pub async fn agree_on_finalized_checkpoint(
+    providers: &[BeaconNodeHttpClient],
+    quorum: usize,
+    wss_checkpoint: Option<Checkpoint>,
+    log: &Logger,
+) -> Result<AgreedCheckpoint, String> {
+    let responses = join_all(providers.iter().map(|provider| async move {
+        provider
+            .get_beacon_states_finality_checkpoints(StateId::Head)
+            .await
+            .map_err(|e| format!("{:?}", e))?
+            .map(|response| response.data.finalized)
+            .ok_or_else(|| "head state missing".to_string())
+    }))
+    .await;
+
+    select_finalized_checkpoint(providers, &responses, quorum, wss_checkpoint, log)
+}
+
+/// Returns the checkpoint in `responses` served by the most providers, so long as at least
+/// `quorum` of them agree and it does not conflict with `wss_checkpoint`.
+///
+/// `responses[i]` is the response of `providers[i]`.
+fn select_finalized_checkpoint<P: Display>(
+    providers: &[P],
+    responses: &[Result<Checkpoint, String>],
+    quorum: usize,
+    wss_checkpoint: Option<Checkpoint>,
+    log: &Logger,
+) -> Result<AgreedCheckpoint, String> {
+    let mut votes: HashMap<Checkpoint, Vec<usize>> = HashMap::new();
+    for (index, response) in responses.iter().enumerate() {
+        match response {
+            Ok(checkpoint) => {
+                debug!(
+                    log,
+                    "Checkpoint sync provider finalized checkpoint";
+                    "provider" => %providers[index],
+                    "epoch" => checkpoint.epoch,
+                    "root" => ?checkpoint.root,
+                );
+                votes.entry(*checkpoint).or_default().push(index);
+            }
+            Err(e) => warn!(
+                log,
+                "Unable to query checkpoint sync provider";
+                "provider" => %providers[index],
+                "error" => e,
+            ),
+        }
+    }
+
+    // Prefer the checkpoint with the most votes, breaking ties with the later epoch.
+    let (checkpoint, agreeing) = votes
+        .into_iter()
+        .max_by_key(|(checkpoint, agreeing)| (agreeing.len(), checkpoint.epoch))
+        .ok_or("Unable to fetch a finalized checkpoint from any checkpoint sync provider")?;
+
+    for (index, response) in responses.iter().enumerate() {
+        if let Ok(other) = response {
+            if *other != checkpoint {
+                warn!(
+                    log,
+                    "Checkpoint sync provider disagrees";
+                    "provider" => %providers[index],
+                    "provider_epoch" => other.epoch,
+                    "provider_root" => ?other.root,
+                    "majority_epoch" => checkpoint.epoch,
+                    "majority_root" => ?checkpoint.root,
+                );
+            }
+        }
+    }
+
+    if agreeing.len() < quorum {
+        crit!(
+            log,
+            "Checkpoint sync providers failed to reach quorum";
+            "agreeing" => agreeing.len(),
+            "quorum" => quorum,
+            "providers" => providers.len(),
+        );
+        return Err(format!(
+            "Only {} of {} checkpoint sync providers agree on the finalized checkpoint, {} required",
+            agreeing.len(),
+            providers.len(),
+            quorum
+        ));
+    }
+
+    if let Some(wss_checkpoint) = wss_checkpoint {
+        if wss_checkpoint.epoch == checkpoint.epoch && wss_checkpoint.root != checkpoint.root {
+            crit!(
+                log,
+                "Checkpoint sync providers conflict with weak subjectivity checkpoint";
+                "weak_subjectivity_root" => ?wss_checkpoint.root,
+                "finalized_checkpoint_root" => ?checkpoint.root,
+                "epoch" => checkpoint.epoch,
+            );
+            return Err(
+                "Finalized checkpoint from checkpoint sync providers conflicts with the \
+                 weak subjectivity checkpoint"
+                    .to_string(),
+            );
+        }
+    }
+
+    info!(
+        log,
+        "Checkpoint sync providers reached quorum";
+        "agreeing" => agreeing.len(),
+        "providers" => providers.len(),
+        "epoch" => checkpoint.epoch,
+        "root" => ?checkpoint.root,
+    );
+
+    Ok(AgreedCheckpoint {
+        checkpoint,
+        agreeing,
+    })
+}
+
+/// Checks that at least `quorum` of the `providers` report `block_root` as the canonical block
+/// at `slot`.
+pub async fn verify_block_root_quorum(
+    providers: &[BeaconNodeHttpClient],
+    quorum: usize,
+    slot: Slot,
+    block_root: Hash256,
+    log: &Logger,
+) -> Result<(), String> {
+    let responses = join_all(providers.iter().map(|provider| async move {
+        provider
+            .get_beacon_blocks_root(BlockId::Slot(slot))
+            .await
+            .map(|response| response.map(|response| response.data.root))
+    }))
+    .await
+    .into_iter()
+    .map(|response| response.map_err(|e| format!("{:?}", e)))
+    .collect::<Vec<_>>();
+
+    check_block_root_quorum(providers, responses, quorum, slot, block_root, log)
+}
+
+/// Checks that at least `quorum` of `responses` are `block_root`.
+///
+/// `responses[i]` is the response of `providers[i]`.
+fn check_block_root_quorum<P: Display>(
+    providers: &[P],
+    responses: Vec<Result<Option<Hash256>, String>>,
+    quorum: usize,
+    slot: Slot,
+    block_root: Hash256,
+    log: &Logger,
+) -> Result<(), String> {
+    let mut agreeing = 0;
+    for (index, response) in responses.into_iter().enumerate() {
+        match response {
+            Ok(Some(root)) if root == block_root => agreeing += 1,
+            Ok(root) => warn!(
+                log,
+                "Checkpoint sync provider disagrees on block root";
+                "provider" => %providers[index],
+                "slot" => slot,
+                "provider_root" => ?root,
+                "expected_root" => ?block_root,
+            ),
+            Err(e) => warn!(
+                log,
+                "Unable to query checkpoint sync provider";
+                "provider" => %providers[index],
+                "error" => e,
+            ),
+        }
+    }
+
+    if agreeing < quorum {
+        return Err(format!(
+            "Only {} of {} checkpoint sync providers agree on block root {:?} at slot {}, {} \
+             required",
+            agreeing,
+            providers.len(),
+            block_root,
+            slot,
+            quorum
+        ));
+    }
+
+    Ok(())
+}
+
+/// Requests the deposit snapshot from each of the `providers` and returns the snapshot served by
+/// the most providers, so long as at least `quorum` of them agree.
+///
+/// Returns `None` if quorum is not reached, in which case deposits are synced from the execution
+/// node instead.
+pub async fn agree_on_deposit_snapshot(
+    providers: &[BeaconNodeHttpClient],
+    quorum: usize,
+    log: &Logger,
+) -> Option<DepositTreeSnapshot> {
+    let responses = join_all(providers.iter().map(|provider| async move {
+        provider
+            .get_deposit_snapshot()
+            .await
+            .map_err(|e| format!("{:?}", e))
+    }))
+    .await;
+
+    select_deposit_snapshot(providers, responses, quorum, log)
+}
+
+/// Returns the valid snapshot in `responses` served by the most providers, so long as at least
+/// `quorum` of them agree.
+///
+/// `responses[i]` is the response of `providers[i]`.
+fn select_deposit_snapshot<P: Display>(
+    providers: &[P],
+    responses: Vec<Result<Option<DepositTreeSnapshot>, String>>,
+    quorum: usize,
+    log: &Logger,
+) -> Option<DepositTreeSnapshot> {
+    // `DepositTreeSnapshot` is not hashable, so votes are tallied in a list.
+    let mut votes: Vec<(DepositTreeSnapshot, usize)> = vec![];
+    for (index, response) in responses.into_iter().enumerate() {
+        match response {
+            Ok(Some(snapshot)) if snapshot.is_valid() => {
+                match votes.iter_mut().find(|(other, _)| *other == snapshot) {
+                    Some((_, count)) => *count += 1,
+                    None => votes.push((snapshot, 1)),
+                }
+            }
+            Ok(Some(_)) => warn!(
+                log,
+                "Checkpoint sync provider sent invalid deposit snapshot";
+                "provider" => %providers[index],
+            ),
+            Ok(None) => warn!(
+                log,
+                "Checkpoint sync provider does not support EIP-4881 fast deposit sync";
+                "provider" => %providers[index],
+            ),
+            Err(e) => warn!(
+                log,
+                "Unable to fetch deposit snapshot from checkpoint sync provider";
+                "provider" => %providers[index],
+                "error" => e,
+            ),
+        }
+    }
+
+    let (snapshot, agreeing) = votes.into_iter().max_by_key(|(_, count)| *count)?;
+    if agreeing < quorum {
+        warn!(
+            log,
+            "Checkpoint sync providers disagree on deposit snapshot";
+            "info" => "deposits will be synced from the execution node",
+            "agreeing" => agreeing,
+            "quorum" => quorum,
+            "providers" => providers.len(),
+        );
+        return None;
+    }
+
+    Some(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logging::test_logger;
+    use types::Epoch;
+
+    const PROVIDERS: [&str; 3] = ["provider_0", "provider_1", "provider_2"];
+
+    fn checkpoint(epoch: u64, root: u64) -> Checkpoint {
+        Checkpoint {
+            epoch: Epoch::new(epoch),
+            root: Hash256::from_low_u64_be(root),
+        }
+    }
+
+    /// A valid, empty deposit snapshot taken at `execution_block_height`.
+    fn snapshot(execution_block_height: u64) -> DepositTreeSnapshot {
+        DepositTreeSnapshot {
+            execution_block_height,
+            ..DepositTreeSnapshot::default()
+        }
+    }
+
+    #[test]
+    fn default_quorum_is_strict_majority() {
+        assert_eq!(default_quorum(1), 1);
+        assert_eq!(default_quorum(2), 2);
+        assert_eq!(default_quorum(3), 2);
+        assert_eq!(default_quorum(4), 3);
+        assert_eq!(default_quorum(5), 3);
+    }
+
+    #[test]
+    fn finalized_checkpoint_majority_wins() {
+        let responses = vec![
+            Ok(checkpoint(10, 1)),
+            Ok(checkpoint(11, 2)),
+            Ok(checkpoint(10, 1)),
+        ];
+        let agreed =
+            select_finalized_checkpoint(&PROVIDERS, &responses, 2, None, &test_logger()).unwrap();
+        assert_eq!(agreed.checkpoint, checkpoint(10, 1));
+        assert_eq!(agreed.agreeing, vec![0, 2]);
+    }
+
+    #[test]
+    fn finalized_checkpoint_tie_prefers_later_epoch() {
+        let responses = vec![
+            Ok(checkpoint(10, 1)),
+            Ok(checkpoint(11, 2)),
+            Err("timeout".to_string()),
+        ];
+        let agreed =
+            select_finalized_checkpoint(&PROVIDERS, &responses, 1, None, &test_logger()).unwrap();
+        assert_eq!(agreed.checkpoint, checkpoint(11, 2));
+        assert_eq!(agreed.agreeing, vec![1]);
+    }
+
+    #[test]
+    fn finalized_checkpoint_without_quorum() {
+        let responses = vec![
+            Ok(checkpoint(10, 1)),
+            Ok(checkpoint(10, 2)),
+            Err("timeout".to_string()),
+        ];
+        assert!(
+            select_finalized_checkpoint(&PROVIDERS, &responses, 2, None, &test_logger()).is_err()
+        );
+    }
+
+    #[test]
+    fn finalized_checkpoint_all_providers_failed() {
+        let responses = vec![Err("timeout".to_string()), Err("timeout".to_string())];
+        assert!(
+            select_finalized_checkpoint(&PROVIDERS[..2], &responses, 1, None, &test_logger())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn finalized_checkpoint_conflicts_with_weak_subjectivity_checkpoint() {
+        let responses = vec![Ok(checkpoint(10, 1)), Ok(checkpoint(10, 1))];
+        let log = test_logger();
+
+        // Same epoch, different root.
+        assert!(select_finalized_checkpoint(
+            &PROVIDERS[..2],
+            &responses,
+            2,
+            Some(checkpoint(10, 2)),
+            &log
+        )
+        .is_err());
+        // Same epoch and root.
+        assert!(select_finalized_checkpoint(
+            &PROVIDERS[..2],
+            &responses,
+            2,
+            Some(checkpoint(10, 1)),
+            &log
+        )
+        .is_ok());
+        // Older checkpoints are verified against the downloaded state instead.
+        assert!(select_finalized_checkpoint(
+            &PROVIDERS[..2],
+            &responses,
+            2,
+            Some(checkpoint(5, 2)),
+            &log
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn block_root_quorum() {
+        let root = Hash256::from_low_u64_be(1);
+        let other = Hash256::from_low_u64_be(2);
+        let slot = Slot::new(64);
+        let log = test_logger();
+
+        let responses = || vec![Ok(Some(root)), Ok(Some(other)), Err("timeout".to_string())];
+        assert!(check_block_root_quorum(&PROVIDERS, responses(), 1, slot, root, &log).is_ok());
+        assert!(check_block_root_quorum(&PROVIDERS, responses(), 2, slot, root, &log).is_err());
+
+        let responses = vec![Ok(Some(root)), Ok(None), Ok(Some(root))];
+        assert!(check_block_root_quorum(&PROVIDERS, responses, 2, slot, root, &log).is_ok());
+    }
+
+    #[test]
+    fn deposit_snapshot_quorum() {
+        let log = test_logger();
+
+        let responses = vec![
+            Ok(Some(snapshot(0))),
+            Ok(Some(snapshot(0))),
+            Err("timeout".to_string()),
+        ];
+        assert_eq!(
+            select_deposit_snapshot(&PROVIDERS, responses, 2, &log),
+            Some(snapshot(0))
+        );
+
+        let responses = vec![Ok(Some(snapshot(0))), Ok(None), Err("timeout".to_string())];
+        assert_eq!(
+            select_deposit_snapshot(&PROVIDERS, responses, 2, &log),
+            None
+        );
+
+        let responses = vec![
+            Ok(Some(snapshot(0))),
+            Ok(Some(snapshot(1))),
+            Ok(Some(snapshot(1))),
+        ];
+        assert_eq!(
+            select_deposit_snapshot(&PROVIDERS, responses, 2, &log),
+            Some(snapshot(1))
+        );
+    }
+
+    #[test]
+    fn deposit_snapshot_ignores_invalid_snapshots() {
+        let mut invalid = snapshot(0);
+        invalid.deposit_count = 1;
+        assert!(!invalid.is_valid());
+
+        let responses = vec![Ok(Some(invalid.clone())), Ok(Some(invalid))];
+        assert_eq!(
+            select_deposit_snapshot(&PROVIDERS[..2], responses, 1, &test_logger()),
+            None
+        );
+    }
+}
@@ -37,7 +37,13 @@ pub enum ClientGenesis {
     CheckpointSyncUrl {
         genesis_state_bytes: Vec<u8>,
         url: SensitiveUrl,
+        /// Further providers which must agree with `url` on the finalized checkpoint.
+        additional_urls: Vec<SensitiveUrl>,
+        /// The number of providers which must agree. Defaults to a majority.
+        quorum: Option<usize>,
     },
+    /// Downloads the state of the weak subjectivity checkpoint from libp2p peers.
+    CheckpointSyncPeers { genesis_state_bytes: Vec<u8> },
 }
 
 /// The core configuration of a Lighthouse beacon node.
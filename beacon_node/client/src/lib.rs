@@ -1,9 +1,11 @@
 extern crate slog;
 
 mod address_change_broadcast;
+mod checkpoint_sync;
 pub mod config;
 mod metrics;
 mod notifier;
+mod peer_checkpoint_sync;
 
 pub mod builder;
 pub mod error;
@@ -0,0 +1,687 @@
+//! Downloads a finalized block and state directly from libp2p peers, so that a node can checkpoint
+//! sync without relying upon a trusted HTTP provider.
+//!
+//! The block is requested by the root of the user-supplied weak subjectivity checkpoint and its
+//! post-state is then downloaded in chunks via the `FinalizedStateChunk` protocol. Peers which
+//! fail or disconnect are skipped and the download resumes from another peer at the same offset.
+//!
+//! The chain can only be started from a block at the start of an epoch. If the first slot of the
+//! checkpoint epoch was skipped, the checkpoint block's ancestors are downloaded until a block at
+//! the start of an epoch is found, and the state of that block is used instead.
+//!
+//! Peers serve the state of any finalized block at the start of an epoch which they still hold,
+//! so peers which have finalized the weak subjectivity checkpoint or a later one are used.
+use beacon_chain::slot_clock::{SlotClock, SystemTimeSlotClock};
+use lighthouse_network::rpc::{
+    BlocksByRootRequest, FinalizedStateChunk, FinalizedStateChunkRequest, RPCResponseErrorCode,
+    StatusMessage,
+};
+use lighthouse_network::service::Network;
+use lighthouse_network::{Context, NetworkConfig, NetworkEvent, PeerId, Request, Response};
+use slog::{debug, info, warn, Logger};
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use task_executor::TaskExecutor;
+use types::{
+    BeaconBlock, BeaconState, ChainSpec, Checkpoint, Epoch, EthSpec, ForkContext, Hash256,
+    SignedBeaconBlock, VariableList,
+};
+
+/// The maximum time to spend finding peers and downloading the checkpoint block and state.
+const PEER_CHECKPOINT_SYNC_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// The largest SSZ-encoded state we are willing to download (1 GiB).
+///
+/// Each validator adds 139 bytes to a post-Altair state (121 for the `Validator`, 8 for its balance
+/// and inactivity score each, and 1 for each participation flag list). The whole ETH supply staked
+/// at 32 ETH per validator is under 4M validators, or about 560 MB, so this leaves room for all
+/// other fields twice over while bounding the memory a malicious peer can make us allocate.
+const MAX_STATE_LEN: u64 = 1 << 30;
+
+#[derive(Debug, Clone, Copy)]
+enum RequestId {
+    Status,
+    Block,
+    StateChunk,
+}
+
+/// Tracks the progress of the download across peers.
+struct Download<E: EthSpec> {
+    /// The root of the block being downloaded.
+    ///
+    /// This starts at the checkpoint root and moves to the parent of each downloaded block which is
+    /// not at the start of an epoch.
+    root: Hash256,
+    /// The peer which is currently serving the download, if any.
+    active: Option<PeerId>,
+    /// Peers which have finalized the requested checkpoint and are waiting to be used.
+    candidates: VecDeque<PeerId>,
+    /// Peers which have failed to serve the download and must not be used again.
+    failed: HashSet<PeerId>,
+    /// Peers which have sent chunks of the state downloaded so far.
+    state_peers: HashSet<PeerId>,
+    /// Whether the active peer has sent the block requested from it.
+    block_received: bool,
+    /// The epoch-aligned block whose post-state is downloaded.
+    block: Option<Arc<SignedBeaconBlock<E>>>,
+    state_bytes: Vec<u8>,
+    total_len: Option<u64>,
+}
+
+impl<E: EthSpec> Download<E> {
+    fn new(root: Hash256) -> Self {
+        Self {
+            root,
+            active: None,
+            candidates: VecDeque::new(),
+            failed: HashSet::new(),
+            state_peers: HashSet::new(),
+            block_received: false,
+            block: None,
+            state_bytes: vec![],
+            total_len: None,
+        }
+    }
+
+    /// Adds the peer which sent the `remote` status as a candidate for the download, returning
+    /// whether it was added.
+    ///
+    /// Peers which have finalized an earlier checkpoint, or a different block at the requested
+    /// epoch, do not serve the requested state and are not used.
+    fn add_candidate(
+        &mut self,
+        peer_id: PeerId,
+        remote: &StatusMessage,
+        local: &StatusMessage,
+        wss_checkpoint: Checkpoint,
+    ) -> bool {
+        if remote.fork_digest != local.fork_digest
+            || remote.finalized_epoch < wss_checkpoint.epoch
+            || (remote.finalized_epoch == wss_checkpoint.epoch
+                && remote.finalized_root != wss_checkpoint.root)
+            || self.failed.contains(&peer_id)
+            || self.active == Some(peer_id)
+            || self.candidates.contains(&peer_id)
+        {
+            return false;
+        }
+        self.candidates.push_back(peer_id);
+        true
+    }
+
+    /// Verifies the downloaded block and stores it if it is at the start of an epoch, otherwise
+    /// continues the download with its parent.
+    fn on_block(&mut self, block: Arc<SignedBeaconBlock<E>>) -> Result<(), &'static str> {
+        if block.canonical_root() != self.root {
+            return Err("sent the wrong block");
+        }
+        self.block_received = true;
+        if block.slot() % E::slots_per_epoch() == 0 {
+            self.block = Some(block);
+        } else {
+            self.root = block.parent_root();
+        }
+        Ok(())
+    }
+
+    /// Verifies that `chunk` sent by `peer_id` continues the downloaded state and appends it.
+    fn on_state_chunk(
+        &mut self,
+        peer_id: PeerId,
+        chunk: FinalizedStateChunk,
+    ) -> Result<(), &'static str> {
+        let expected_len = self.total_len.unwrap_or(chunk.total_len);
+        if chunk.block_root != self.root
+            || chunk.offset != self.state_bytes.len() as u64
+            || chunk.total_len != expected_len
+            || chunk.total_len > MAX_STATE_LEN
+            || chunk.data.is_empty()
+            || chunk.offset + chunk.data.len() as u64 > chunk.total_len
+        {
+            return Err("sent an invalid state chunk");
+        }
+
+        self.total_len = Some(chunk.total_len);
+        self.state_bytes.extend_from_slice(&chunk.data);
+        self.state_peers.insert(peer_id);
+        Ok(())
+    }
+
+    /// Decodes the downloaded state and verifies it against the `state_root` of the block.
+    ///
+    /// If the state is invalid, the peers which sent it are marked as failed and the download of
+    /// the state is restarted.
+    fn verify_state(&mut self, spec: &ChainSpec) -> Result<BeaconState<E>, &'static str> {
+        let state_root = self
+            .block
+            .as_ref()
+            .ok_or("checkpoint block missing")?
+            .state_root();
+        let state = BeaconState::from_ssz_bytes(&self.state_bytes, spec)
+            .ok()
+            .filter(|state| state.canonical_root() == state_root);
+
+        if let Some(state) = state {
+            return Ok(state);
+        }
+
+        self.failed.extend(self.state_peers.drain());
+        let failed = &self.failed;
+        self.candidates.retain(|peer_id| !failed.contains(peer_id));
+        if self
+            .active
+            .map_or(false, |peer_id| failed.contains(&peer_id))
+        {
+            self.active = None;
+        }
+        self.state_bytes.clear();
+        self.total_len = None;
+        Err("sent a state which does not match the checkpoint block")
+    }
+
+    fn is_complete(&self) -> bool {
+        self.block.is_some() && self.total_len == Some(self.state_bytes.len() as u64)
+    }
+
+    /// Returns the next request to send to the active peer.
+    fn next_request(&self) -> (RequestId, Request) {
+        if self.block.is_none() {
+            (
+                RequestId::Block,
+                Request::BlocksByRoot(BlocksByRootRequest {
+                    block_roots: VariableList::from(vec![self.root]),
+                }),
+            )
+        } else {
+            (
+                RequestId::StateChunk,
+                Request::FinalizedStateChunk(FinalizedStateChunkRequest {
+                    block_root: self.root,
+                    offset: self.state_bytes.len() as u64,
+                }),
+            )
+        }
+    }
+}
+
+/// Connects to the network described by `network_config` and downloads the block at
+/// `wss_checkpoint.root`, or its latest ancestor at the start of an epoch, along with its
+/// post-state.
+///
+/// The blocks are verified against the checkpoint root and their parent roots, and the state
+/// against the block's `state_root`. If the state is invalid, the peers which sent it are skipped
+/// and the state is downloaded again.
+pub async fn download_checkpoint_from_peers<E: EthSpec>(
+    network_config: &NetworkConfig,
+    executor: TaskExecutor,
+    genesis_state: &BeaconState<E>,
+    wss_checkpoint: Checkpoint,
+    spec: &ChainSpec,
+    log: &Logger,
+) -> Result<(BeaconState<E>, SignedBeaconBlock<E>), String> {
+    let slot_clock = SystemTimeSlotClock::new(
+        spec.genesis_slot,
+        Duration::from_secs(genesis_state.genesis_time()),
+        Duration::from_secs(spec.seconds_per_slot),
+    );
+    let current_slot = slot_clock.now().unwrap_or(spec.genesis_slot);
+    let genesis_validators_root = genesis_state.genesis_validators_root();
+    let fork_context = Arc::new(ForkContext::new::<E>(
+        current_slot,
+        genesis_validators_root,
+        spec,
+    ));
+    let enr_fork_id = spec.enr_fork_id::<E>(current_slot, genesis_validators_root);
+
+    let mut genesis_block = BeaconBlock::<E>::empty(spec);
+    *genesis_block.state_root_mut() = genesis_state.canonical_root();
+    let status = StatusMessage {
+        fork_digest: enr_fork_id.fork_digest,
+        finalized_root: Hash256::zero(),
+        finalized_epoch: Epoch::new(0),
+        head_root: genesis_block.canonical_root(),
+        head_slot: spec.genesis_slot,
+    };
+
+    let context = Context {
+        config: network_config,
+        enr_fork_id,
+        fork_context,
+        chain_spec: spec,
+        gossipsub_registry: None,
+    };
+    let (mut network, _network_globals) = Network::<RequestId, E>::new(executor, context, log)
+        .await
+        .map_err(|e| format!("Unable to start network for checkpoint sync: {:?}", e))?;
+
+    info!(
+        log,
+        "Searching for peers to checkpoint sync from";
+        "block_root" => ?wss_checkpoint.root,
+        "epoch" => wss_checkpoint.epoch,
+    );
+
+    let mut download = Download::<E>::new(wss_checkpoint.root);
+
+    let result = tokio::time::timeout(
+        PEER_CHECKPOINT_SYNC_TIMEOUT,
+        drive_download(
+            &mut network,
+            &mut download,
+            &status,
+            wss_checkpoint,
+            spec,
+            log,
+        ),
+    )
+    .await;
+
+    // Dropping the network closes all connections and releases the listening ports before the
+    // network service is started for the beacon chain.
+    drop(network);
+
+    let state = result.map_err(|_| {
+        format!(
+            "Timed out downloading checkpoint from peers after {} of {:?} state bytes",
+            download.state_bytes.len(),
+            download.total_len,
+        )
+    })?;
+    let block = download
+        .block
+        .ok_or("Checkpoint block missing after download")?;
+
+    Ok((state, (*block).clone()))
+}
+
+/// Processes network events until `download` is complete and returns the verified state.
+async fn drive_download<E: EthSpec>(
+    network: &mut Network<RequestId, E>,
+    download: &mut Download<E>,
+    status: &StatusMessage,
+    wss_checkpoint: Checkpoint,
+    spec: &ChainSpec,
+    log: &Logger,
+) -> BeaconState<E> {
+    loop {
+        match network.next_event().await {
+            NetworkEvent::PeerConnectedOutgoing(peer_id)
+            | NetworkEvent::PeerConnectedIncoming(peer_id)
+            | NetworkEvent::StatusPeer(peer_id) => {
+                if !download.failed.contains(&peer_id) {
+                    network.send_request(
+                        peer_id,
+                        RequestId::Status,
+                        Request::Status(status.clone()),
+                    );
+                }
+            }
+            NetworkEvent::PeerDisconnected(peer_id) | NetworkEvent::PeerBanned(peer_id) => {
+                download
+                    .candidates
+                    .retain(|candidate| *candidate != peer_id);
+                if download.active == Some(peer_id) {
+                    fail_active_peer(network, download, "disconnected", log);
+                }
+            }
+            NetworkEvent::RPCFailed { id, peer_id } => {
+                if download.active == Some(peer_id) && !matches!(id, RequestId::Status) {
+                    fail_active_peer(network, download, "request failed", log);
+                }
+            }
+            NetworkEvent::RequestReceived {
+                peer_id,
+                id,
+                request,
+            } => match request {
+                Request::Status(_) => {
+                    network.send_response(peer_id, id, Response::Status(status.clone()))
+                }
+                _ => network.send_error_reponse(
+                    peer_id,
+                    id,
+                    RPCResponseErrorCode::ResourceUnavailable,
+                    "Checkpoint syncing".into(),
+                ),
+            },
+            NetworkEvent::ResponseReceived {
+                peer_id, response, ..
+            } => match response {
+                Response::Status(remote) => {
+                    if download.add_candidate(peer_id, &remote, status, wss_checkpoint) {
+                        debug!(
+                            log,
+                            "Found checkpoint sync peer";
+                            "peer" => %peer_id,
+                            "finalized_epoch" => remote.finalized_epoch,
+                        );
+                        if download.active.is_none() {
+                            activate_next_peer(network, download, log);
+                        }
+                    }
+                }
+                Response::BlocksByRoot(Some(block)) if download.active == Some(peer_id) => {
+                    let slot = block.slot();
+                    if let Err(reason) = download.on_block(block) {
+                        fail_active_peer(network, download, reason, log);
+                    } else if download.block.is_some() {
+                        info!(
+                            log,
+                            "Downloaded checkpoint block";
+                            "peer" => %peer_id,
+                            "slot" => slot,
+                        );
+                    } else {
+                        debug!(
+                            log,
+                            "Searching for aligned checkpoint block";
+                            "block_slot" => slot,
+                            "parent_root" => ?download.root,
+                        );
+                    }
+                }
+                Response::BlocksByRoot(None) if download.active == Some(peer_id) => {
+                    if std::mem::take(&mut download.block_received) {
+                        let (id, request) = download.next_request();
+                        network.send_request(peer_id, id, request);
+                    } else {
+                        fail_active_peer(network, download, "does not have the block", log);
+                    }
+                }
+                Response::FinalizedStateChunk(chunk) if download.active == Some(peer_id) => {
+                    let total_len = chunk.total_len;
+                    if let Err(reason) = download.on_state_chunk(peer_id, chunk) {
+                        fail_active_peer(network, download, reason, log);
+                        continue;
+                    }
+                    debug!(
+                        log,
+                        "Downloaded checkpoint state chunk";
+                        "peer" => %peer_id,
+                        "downloaded" => download.state_bytes.len(),
+                        "total" => total_len,
+                    );
+
+                    if !download.is_complete() {
+                        let (id, request) = download.next_request();
+                        network.send_request(peer_id, id, request);
+                        continue;
+                    }
+
+                    match download.verify_state(spec) {
+                        Ok(state) => {
+                            info!(
+                                log,
+                                "Downloaded checkpoint state";
+                                "bytes" => download.state_bytes.len(),
+                            );
+                            return state;
+                        }
+                        Err(reason) => {
+                            warn!(
+                                log,
+                                "Checkpoint sync peers failed";
+                                "reason" => reason,
+                                "msg" => "restarting the state download",
+                            );
+                            if download.active.is_none() {
+                                activate_next_peer(network, download, log);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Stops downloading from the active peer and continues with the next candidate, if any.
+fn fail_active_peer<E: EthSpec>(
+    network: &mut Network<RequestId, E>,
+    download: &mut Download<E>,
+    reason: &str,
+    log: &Logger,
+) {
+    if let Some(peer_id) = download.active.take() {
+        warn!(
+            log,
+            "Checkpoint sync peer failed";
+            "peer" => %peer_id,
+            "reason" => reason,
+        );
+        download.failed.insert(peer_id);
+    }
+    activate_next_peer(network, download, log);
+}
+
+/// Sends the next request of the download to the first candidate peer.
+fn activate_next_peer<E: EthSpec>(
+    network: &mut Network<RequestId, E>,
+    download: &mut Download<E>,
+    log: &Logger,
+) {
+    if let Some(peer_id) = download.candidates.pop_front() {
+        debug!(
+            log,
+            "Downloading checkpoint from peer";
+            "peer" => %peer_id,
+            "offset" => download.state_bytes.len(),
+        );
+        download.active = Some(peer_id);
+        download.block_received = false;
+        let (id, request) = download.next_request();
+        network.send_request(peer_id, id, request);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{MinimalEthSpec, Signature, Slot};
+
+    type E = MinimalEthSpec;
+
+    fn checkpoint_block() -> Arc<SignedBeaconBlock<E>> {
+        let block = BeaconBlock::empty(&E::default_spec());
+        Arc::new(SignedBeaconBlock::from_block(block, Signature::empty()))
+    }
+
+    fn unaligned_block(parent_root: Hash256) -> Arc<SignedBeaconBlock<E>> {
+        let mut block = BeaconBlock::empty(&E::default_spec());
+        *block.slot_mut() = Slot::new(E::slots_per_epoch() + 1);
+        *block.parent_root_mut() = parent_root;
+        Arc::new(SignedBeaconBlock::from_block(block, Signature::empty()))
+    }
+
+    fn status(finalized_epoch: u64, finalized_root: Hash256) -> StatusMessage {
+        StatusMessage {
+            fork_digest: [1; 4],
+            finalized_root,
+            finalized_epoch: Epoch::new(finalized_epoch),
+            head_root: Hash256::zero(),
+            head_slot: Epoch::new(finalized_epoch).start_slot(E::slots_per_epoch()),
+        }
+    }
+
+    fn chunk(root: Hash256, offset: u64, total_len: u64, data: Vec<u8>) -> FinalizedStateChunk {
+        FinalizedStateChunk {
+            block_root: root,
+            offset,
+            total_len,
+            data: VariableList::from(data),
+        }
+    }
+
+    #[test]
+    fn candidates_must_be_finalized_at_or_after_checkpoint() {
+        let root = Hash256::repeat_byte(1);
+        let checkpoint = Checkpoint {
+            epoch: Epoch::new(2),
+            root,
+        };
+        let local = status(0, Hash256::zero());
+        let mut download = Download::<E>::new(root);
+
+        let (later, exact, conflicting, behind, other_fork, failed) = (
+            PeerId::random(),
+            PeerId::random(),
+            PeerId::random(),
+            PeerId::random(),
+            PeerId::random(),
+            PeerId::random(),
+        );
+        download.failed.insert(failed);
+        let mut other_fork_status = status(2, root);
+        other_fork_status.fork_digest = [2; 4];
+
+        assert!(download.add_candidate(later, &status(3, Hash256::zero()), &local, checkpoint));
+        assert!(download.add_candidate(exact, &status(2, root), &local, checkpoint));
+        assert!(!download.add_candidate(exact, &status(2, root), &local, checkpoint));
+        let conflicting_status = status(2, Hash256::zero());
+        assert!(!download.add_candidate(conflicting, &conflicting_status, &local, checkpoint));
+        assert!(!download.add_candidate(behind, &status(1, Hash256::zero()), &local, checkpoint));
+        assert!(!download.add_candidate(other_fork, &other_fork_status, &local, checkpoint));
+        assert!(!download.add_candidate(failed, &status(2, root), &local, checkpoint));
+
+        assert_eq!(download.candidates, vec![later, exact]);
+    }
+
+    #[test]
+    fn wrong_block_rejected() {
+        let block = checkpoint_block();
+        let mut download = Download::<E>::new(Hash256::repeat_byte(1));
+        assert!(download.on_block(block.clone()).is_err());
+        assert!(download.block.is_none());
+
+        let mut download = Download::<E>::new(block.canonical_root());
+        assert!(download.on_block(block).is_ok());
+        assert!(download.block.is_some());
+    }
+
+    #[test]
+    fn unaligned_block_walks_back_to_parent() {
+        let aligned = checkpoint_block();
+        let unaligned = unaligned_block(aligned.canonical_root());
+        let mut download = Download::<E>::new(unaligned.canonical_root());
+
+        download.on_block(unaligned).unwrap();
+        assert!(download.block.is_none());
+        match download.next_request() {
+            (RequestId::Block, Request::BlocksByRoot(request)) => {
+                assert_eq!(request.block_roots.to_vec(), vec![aligned.canonical_root()]);
+            }
+            other => panic!("unexpected request {:?}", other.0),
+        }
+
+        download.on_block(aligned.clone()).unwrap();
+        assert_eq!(download.block, Some(aligned.clone()));
+        match download.next_request() {
+            (RequestId::StateChunk, Request::FinalizedStateChunk(request)) => {
+                assert_eq!(request.block_root, aligned.canonical_root());
+            }
+            other => panic!("unexpected request {:?}", other.0),
+        }
+    }
+
+    #[test]
+    fn state_downloaded_in_chunks() {
+        let block = checkpoint_block();
+        let root = block.canonical_root();
+        let mut download = Download::<E>::new(root);
+        download.on_block(block).unwrap();
+        let peer_id = PeerId::random();
+
+        download
+            .on_state_chunk(peer_id, chunk(root, 0, 5, vec![1, 2]))
+            .unwrap();
+        assert!(!download.is_complete());
+        match download.next_request() {
+            (RequestId::StateChunk, Request::FinalizedStateChunk(request)) => {
+                assert_eq!(request.block_root, root);
+                assert_eq!(request.offset, 2);
+            }
+            other => panic!("unexpected request {:?}", other.0),
+        }
+
+        download
+            .on_state_chunk(peer_id, chunk(root, 2, 5, vec![3, 4, 5]))
+            .unwrap();
+        assert!(download.is_complete());
+        assert_eq!(download.state_bytes, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn invalid_state_chunks_rejected() {
+        let root = Hash256::repeat_byte(1);
+        let mut download = Download::<E>::new(root);
+        let peer_id = PeerId::random();
+        download
+            .on_state_chunk(peer_id, chunk(root, 0, 5, vec![1, 2]))
+            .unwrap();
+
+        let invalid_chunks = [
+            // wrong block
+            chunk(Hash256::repeat_byte(2), 2, 5, vec![3]),
+            // not contiguous with the downloaded bytes
+            chunk(root, 3, 5, vec![4]),
+            chunk(root, 0, 5, vec![1]),
+            // total length changed
+            chunk(root, 2, 6, vec![3]),
+            // empty
+            chunk(root, 2, 5, vec![]),
+            // past the end of the state
+            chunk(root, 2, 5, vec![3, 4, 5, 6]),
+        ];
+        for invalid in invalid_chunks {
+            assert!(download.on_state_chunk(peer_id, invalid).is_err());
+            assert_eq!(download.state_bytes, vec![1, 2]);
+            assert_eq!(download.total_len, Some(5));
+        }
+    }
+
+    #[test]
+    fn oversized_state_rejected() {
+        let root = Hash256::repeat_byte(1);
+        let mut download = Download::<E>::new(root);
+        let peer_id = PeerId::random();
+        assert!(download
+            .on_state_chunk(peer_id, chunk(root, 0, MAX_STATE_LEN + 1, vec![1]))
+            .is_err());
+        assert_eq!(download.total_len, None);
+    }
+
+    #[test]
+    fn invalid_state_restarts_download() {
+        let block = checkpoint_block();
+        let root = block.canonical_root();
+        let mut download = Download::<E>::new(root);
+        download.on_block(block).unwrap();
+
+        let (first, second, candidate) = (PeerId::random(), PeerId::random(), PeerId::random());
+        download.active = Some(second);
+        download.candidates.push_back(candidate);
+        download
+            .on_state_chunk(first, chunk(root, 0, 5, vec![1, 2]))
+            .unwrap();
+        download
+            .on_state_chunk(second, chunk(root, 2, 5, vec![3, 4, 5]))
+            .unwrap();
+        assert!(download.is_complete());
+
+        assert!(download.verify_state(&E::default_spec()).is_err());
+        assert!(download.failed.contains(&first));
+        assert!(download.failed.contains(&second));
+        assert_eq!(download.active, None);
+        assert_eq!(download.candidates, vec![candidate]);
+        assert!(!download.is_complete());
+        match download.next_request() {
+            (RequestId::StateChunk, Request::FinalizedStateChunk(request)) => {
+                assert_eq!(request.offset, 0);
+            }
+            other => panic!("unexpected request {:?}", other.0),
+        }
+    }
+}
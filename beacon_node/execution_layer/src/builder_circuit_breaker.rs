@@ -0,0 +1,365 @@
+//! A circuit breaker which stops bids being requested from relays which are unlikely to deliver a
+//! payload in time, so that proposals fall back to the local execution engine rather than being
+//! missed.
+//!
+//! This complements the chain health checks of the `ChainConfig::builder_fallback_*` options,
+//! which detect network-wide problems, by tracking the behaviour of each relay:
+//!
+//! - The proportion of recent header requests which failed.
+//! - The proportion of recent bids which arrived close to the request deadline.
+//! - Consecutive failures to reveal the payload of a signed blinded block, which result in the
+//!   block never being published.
+//!
+//! A relay which trips the breaker is not asked for bids until its cool-down has elapsed. The
+//! breaker can also be tripped and reset manually, for a single relay or for all of them.
+use eth2::lighthouse::{BuilderCircuitBreakerStatus, CircuitBreakerTrigger, RelayCircuitStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+use types::Slot;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuilderCircuitBreakerConfig {
+    /// The number of most recent header requests to each relay used to compute its error and late
+    /// bid rates.
+    pub window: usize,
+    /// The minimum number of header requests in the window before the error and late bid rates
+    /// are considered.
+    pub min_requests: usize,
+    /// The percentage of failed header requests at which a relay is tripped.
+    pub max_error_rate: u64,
+    /// The percentage of late bids at which a relay is tripped.
+    pub max_late_bid_rate: u64,
+    /// Bids received after this many milliseconds are considered late.
+    pub late_bid_threshold_ms: u64,
+    /// The number of consecutive failures to reveal a payload at which a relay is tripped.
+    pub max_failed_reveals: usize,
+    /// The number of slots a relay remains tripped for.
+    pub cooldown_slots: u64,
+}
+
+impl Default for BuilderCircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            window: 20,
+            min_requests: 5,
+            max_error_rate: 50,
+            max_late_bid_rate: 50,
+            late_bid_threshold_ms: 800,
+            max_failed_reveals: 2,
+            cooldown_slots: 64,
+        }
+    }
+}
+
+/// The outcome of a single header request to a relay.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HeaderOutcome {
+    /// The relay responded in time, with or without a bid.
+    Timely,
+    /// The relay responded after the late bid threshold.
+    Late,
+    /// The request failed, including by timing out.
+    Error,
+}
+
+/// Returns a label for `trigger` suitable for use in metrics.
+pub fn trigger_label(trigger: CircuitBreakerTrigger) -> &'static str {
+    match trigger {
+        CircuitBreakerTrigger::ErrorRate => "error_rate",
+        CircuitBreakerTrigger::LateBids => "late_bids",
+        CircuitBreakerTrigger::FailedReveals => "failed_reveals",
+        CircuitBreakerTrigger::Manual => "manual",
+    }
+}
+
+struct Trip {
+    trigger: CircuitBreakerTrigger,
+    /// `None` if the relay remains tripped until it is reset.
+    until: Option<Slot>,
+}
+
+#[derive(Default)]
+struct RelayState {
+    outcomes: VecDeque<HeaderOutcome>,
+    consecutive_failed_reveals: usize,
+    trip: Option<Trip>,
+}
+
+impl RelayState {
+    fn count(&self, outcome: HeaderOutcome) -> usize {
+        self.outcomes.iter().filter(|o| **o == outcome).count()
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+pub struct BuilderCircuitBreaker {
+    config: BuilderCircuitBreakerConfig,
+    /// `true` if all relays have been tripped manually.
+    tripped: bool,
+    relays: Vec<RelayState>,
+}
+
+impl BuilderCircuitBreaker {
+    pub fn new(config: BuilderCircuitBreakerConfig, num_relays: usize) -> Self {
+        Self {
+            config,
+            tripped: false,
+            relays: (0..num_relays).map(|_| RelayState::default()).collect(),
+        }
+    }
+
+    /// Classify the response to a header request which took `duration`.
+    pub fn header_outcome<T, E>(&self, result: &Result<T, E>, duration: Duration) -> HeaderOutcome {
+        if result.is_err() {
+            HeaderOutcome::Error
+        } else if duration > Duration::from_millis(self.config.late_bid_threshold_ms) {
+            HeaderOutcome::Late
+        } else {
+            HeaderOutcome::Timely
+        }
+    }
+
+    /// Returns the indices of the relays which may be asked for bids at `slot`.
+    ///
+    /// Relays whose cool-down has elapsed are restored with a clean history.
+    pub fn available_relays(&mut self, slot: Slot) -> Vec<usize> {
+        if self.tripped {
+            return vec![];
+        }
+
+        self.relays
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(relay_index, relay)| {
+                if let Some(trip) = &relay.trip {
+                    match trip.until {
+                        Some(until) if until <= slot => relay.reset(),
+                        _ => return None,
+                    }
+                }
+                Some(relay_index)
+            })
+            .collect()
+    }
+
+    /// Record the outcome of a header request, returning the trigger if it tripped the relay.
+    pub fn record_header(
+        &mut self,
+        relay_index: usize,
+        slot: Slot,
+        outcome: HeaderOutcome,
+    ) -> Option<CircuitBreakerTrigger> {
+        let config = &self.config;
+        let relay = self.relays.get_mut(relay_index)?;
+        if relay.trip.is_some() {
+            return None;
+        }
+
+        if relay.outcomes.len() >= config.window {
+            relay.outcomes.pop_front();
+        }
+        relay.outcomes.push_back(outcome);
+
+        let requests = relay.outcomes.len();
+        if requests < config.min_requests {
+            return None;
+        }
+
+        let exceeds =
+            |count: usize, max_rate: u64| count as u64 * 100 >= max_rate * requests as u64;
+        let trigger = if exceeds(relay.count(HeaderOutcome::Error), config.max_error_rate) {
+            CircuitBreakerTrigger::ErrorRate
+        } else if exceeds(relay.count(HeaderOutcome::Late), config.max_late_bid_rate) {
+            CircuitBreakerTrigger::LateBids
+        } else {
+            return None;
+        };
+        self.trip_relay(relay_index, trigger, slot)
+    }
+
+    /// Record whether a relay revealed the payload of a blinded block built from its bid,
+    /// returning the trigger if it tripped the relay.
+    pub fn record_reveal(
+        &mut self,
+        relay_index: usize,
+        slot: Slot,
+        success: bool,
+    ) -> Option<CircuitBreakerTrigger> {
+        let max_failed_reveals = self.config.max_failed_reveals;
+        let relay = self.relays.get_mut(relay_index)?;
+        if success {
+            relay.consecutive_failed_reveals = 0;
+            return None;
+        }
+
+        relay.consecutive_failed_reveals += 1;
+        if relay.trip.is_none() && relay.consecutive_failed_reveals >= max_failed_reveals {
+            self.trip_relay(relay_index, CircuitBreakerTrigger::FailedReveals, slot)
+        } else {
+            None
+        }
+    }
+
+    fn trip_relay(
+        &mut self,
+        relay_index: usize,
+        trigger: CircuitBreakerTrigger,
+        slot: Slot,
+    ) -> Option<CircuitBreakerTrigger> {
+        let until = slot + self.config.cooldown_slots;
+        self.relays.get_mut(relay_index)?.trip = Some(Trip {
+            trigger,
+            until: Some(until),
+        });
+        Some(trigger)
+    }
+
+    /// Trip the breaker until it is reset, for a single relay or for all relays.
+    pub fn trip(&mut self, relay_index: Option<usize>) {
+        match relay_index.and_then(|i| self.relays.get_mut(i)) {
+            Some(relay) => {
+                relay.trip = Some(Trip {
+                    trigger: CircuitBreakerTrigger::Manual,
+                    until: None,
+                })
+            }
+            None => self.tripped = true,
+        }
+    }
+
+    /// Reset the breaker and forget the history of a single relay or of all relays.
+    pub fn reset(&mut self, relay_index: Option<usize>) {
+        match relay_index.and_then(|i| self.relays.get_mut(i)) {
+            Some(relay) => relay.reset(),
+            None => {
+                self.tripped = false;
+                self.relays.iter_mut().for_each(RelayState::reset);
+            }
+        }
+    }
+
+    /// Returns the state of the breaker at `current_slot`, labelling each relay with the
+    /// corresponding entry of `relay_names`.
+    pub fn status(
+        &self,
+        current_slot: Slot,
+        relay_names: &[String],
+    ) -> BuilderCircuitBreakerStatus {
+        BuilderCircuitBreakerStatus {
+            tripped: self.tripped,
+            relays: self
+                .relays
+                .iter()
+                .zip(relay_names)
+                .map(|(relay, name)| RelayCircuitStatus {
+                    relay: name.clone(),
+                    tripped: self.tripped
+                        || relay
+                            .trip
+                            .as_ref()
+                            .map_or(false, |trip| trip.until.map_or(true, |s| s > current_slot)),
+                    trigger: relay.trip.as_ref().map(|trip| trip.trigger),
+                    cooldown_until: relay.trip.as_ref().and_then(|trip| trip.until),
+                    requests: relay.outcomes.len() as u64,
+                    errors: relay.count(HeaderOutcome::Error) as u64,
+                    late_bids: relay.count(HeaderOutcome::Late) as u64,
+                    consecutive_failed_reveals: relay.consecutive_failed_reveals as u64,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> BuilderCircuitBreaker {
+        BuilderCircuitBreaker::new(BuilderCircuitBreakerConfig::default(), 2)
+    }
+
+    #[test]
+    fn trips_on_error_rate_until_cooldown() {
+        let mut breaker = breaker();
+        let config = BuilderCircuitBreakerConfig::default();
+        let slot = Slot::new(100);
+
+        for _ in 0..config.min_requests - 1 {
+            assert_eq!(breaker.record_header(0, slot, HeaderOutcome::Error), None);
+        }
+        assert_eq!(
+            breaker.record_header(0, slot, HeaderOutcome::Error),
+            Some(CircuitBreakerTrigger::ErrorRate)
+        );
+        assert_eq!(breaker.available_relays(slot + 1), vec![1]);
+
+        let until = slot + config.cooldown_slots;
+        assert_eq!(breaker.available_relays(until - 1), vec![1]);
+        assert_eq!(breaker.available_relays(until), vec![0, 1]);
+        assert_eq!(
+            breaker.status(until, &["a".into(), "b".into()]).relays[0].requests,
+            0
+        );
+    }
+
+    #[test]
+    fn trips_on_late_bids() {
+        let mut breaker = breaker();
+        let slot = Slot::new(1);
+        let late = breaker.header_outcome::<(), ()>(&Ok(()), Duration::from_secs(1));
+        assert_eq!(late, HeaderOutcome::Late);
+
+        let mut triggers = vec![];
+        for i in 0..10 {
+            let outcome = if i % 2 == 0 {
+                HeaderOutcome::Timely
+            } else {
+                late
+            };
+            triggers.extend(breaker.record_header(1, slot, outcome));
+        }
+        assert_eq!(triggers, vec![CircuitBreakerTrigger::LateBids]);
+        assert_eq!(breaker.available_relays(slot), vec![0]);
+    }
+
+    #[test]
+    fn trips_on_consecutive_failed_reveals() {
+        let mut breaker = breaker();
+        let slot = Slot::new(1);
+
+        assert_eq!(breaker.record_reveal(0, slot, false), None);
+        assert_eq!(breaker.record_reveal(0, slot, true), None);
+        assert_eq!(breaker.record_reveal(0, slot, false), None);
+        assert_eq!(
+            breaker.record_reveal(0, slot, false),
+            Some(CircuitBreakerTrigger::FailedReveals)
+        );
+        assert_eq!(breaker.available_relays(slot), vec![1]);
+    }
+
+    #[test]
+    fn manual_trip_and_reset() {
+        let mut breaker = breaker();
+        let slot = Slot::new(1);
+        let names = ["a".to_string(), "b".to_string()];
+
+        breaker.trip(Some(1));
+        assert_eq!(breaker.available_relays(slot + 1_000_000), vec![0]);
+        assert_eq!(
+            breaker.status(slot, &names).relays[1].trigger,
+            Some(CircuitBreakerTrigger::Manual)
+        );
+
+        breaker.trip(None);
+        assert!(breaker.available_relays(slot).is_empty());
+        assert!(breaker.status(slot, &names).relays[0].tripped);
+
+        breaker.reset(None);
+        assert_eq!(breaker.available_relays(slot), vec![0, 1]);
+        assert!(!breaker.status(slot, &names).tripped);
+    }
+}
@@ -23,6 +23,8 @@ use types::{ExecutionPayloadCapella, ExecutionPayloadMerge};
 pub mod auth;
 pub mod http;
 pub mod json_structures;
+pub mod latency;
+pub mod recording;
 
 pub const LATEST_TAG: &str = "latest";
 
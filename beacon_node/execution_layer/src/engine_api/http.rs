@@ -3,11 +3,14 @@
 use super::*;
 use crate::auth::Auth;
 use crate::json_structures::*;
+use crate::latency::LatencyTracker;
+use crate::recording::{EngineRecorder, RecordedExchange, RecordedOutcome};
 use reqwest::header::CONTENT_TYPE;
 use sensitive_url::SensitiveUrl;
 use serde::de::DeserializeOwned;
 use serde_json::json;
 use std::collections::HashSet;
+use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use std::time::{Duration, Instant};
@@ -27,6 +30,9 @@ pub const ETH_GET_BLOCK_BY_NUMBER_TIMEOUT: Duration = Duration::from_secs(1);
 pub const ETH_GET_BLOCK_BY_HASH: &str = "eth_getBlockByHash";
 pub const ETH_GET_BLOCK_BY_HASH_TIMEOUT: Duration = Duration::from_secs(1);
 
+pub const ETH_GET_BALANCE: &str = "eth_getBalance";
+pub const ETH_GET_BALANCE_TIMEOUT: Duration = Duration::from_secs(1);
+
 pub const ETH_SYNCING: &str = "eth_syncing";
 pub const ETH_SYNCING_TIMEOUT: Duration = Duration::from_secs(1);
 
@@ -586,7 +592,11 @@ pub struct HttpJsonRpc {
     pub url: SensitiveUrl,
     pub execution_timeout_multiplier: u32,
     pub engine_capabilities_cache: Mutex<Option<CapabilitiesCacheEntry>>,
+    /// The latency of recent responses from the engine, per method.
+    pub latencies: LatencyTracker,
     auth: Option<Auth>,
+    /// The recorder for exchanges with this engine, and the index of this engine.
+    recorder: Option<(Arc<EngineRecorder>, usize)>,
 }
 
 impl HttpJsonRpc {
@@ -599,7 +609,9 @@ impl HttpJsonRpc {
             url,
             execution_timeout_multiplier: execution_timeout_multiplier.unwrap_or(1),
             engine_capabilities_cache: Mutex::new(None),
+            latencies: LatencyTracker::default(),
             auth: None,
+            recorder: None,
         })
     }
 
@@ -613,17 +625,26 @@ impl HttpJsonRpc {
             url,
             execution_timeout_multiplier: execution_timeout_multiplier.unwrap_or(1),
             engine_capabilities_cache: Mutex::new(None),
+            latencies: LatencyTracker::default(),
             auth: Some(auth),
+            recorder: None,
         })
     }
 
+    /// Record every JSON-RPC exchange with the engine using `recorder`, identifying this engine
+    /// by `engine`.
+    pub fn with_recorder(mut self, recorder: Arc<EngineRecorder>, engine: usize) -> Self {
+        self.recorder = Some((recorder, engine));
+        self
+    }
+
     pub async fn rpc_request<D: DeserializeOwned>(
         &self,
         method: &str,
         params: serde_json::Value,
         timeout: Duration,
     ) -> Result<D, Error> {
-        let body = JsonRequestBody {
+        let request_body = JsonRequestBody {
             jsonrpc: JSONRPC_VERSION,
             method,
             params,
@@ -635,28 +656,73 @@ impl HttpJsonRpc {
             .post(self.url.full.clone())
             .timeout(timeout)
             .header(CONTENT_TYPE, "application/json")
-            .json(&body);
+            .json(&request_body);
 
         // Generate and add a jwt token to the header if auth is defined.
         if let Some(auth) = &self.auth {
             request = request.bearer_auth(auth.generate_token()?);
         };
 
-        let body: JsonResponseBody = request.send().await?.error_for_status()?.json().await?;
-
-        match (body.result, body.error) {
-            (result, None) => serde_json::from_value(result).map_err(Into::into),
-            (_, Some(error)) => {
-                if error.message.contains(EIP155_ERROR_STR) {
-                    Err(Error::Eip155Failure)
-                } else {
-                    Err(Error::ServerMessage {
-                        code: error.code,
-                        message: error.message,
-                    })
+        let start = Instant::now();
+        let result: Result<D, Error> = async {
+            let body: JsonResponseBody = if let Some((recorder, engine)) = &self.recorder {
+                let sequence = recorder.next_sequence();
+                let result = async {
+                    let response = request.send().await?;
+                    let status = response.status();
+                    let status_error = response.error_for_status_ref().err();
+                    let bytes = response.bytes().await?;
+                    Ok::<_, reqwest::Error>((status, status_error, bytes))
+                }
+                .await;
+
+                let outcome = match &result {
+                    Ok((status, _, bytes)) => RecordedOutcome::Response {
+                        status: status.as_u16(),
+                        body: String::from_utf8_lossy(bytes).into_owned(),
+                    },
+                    Err(e) => RecordedOutcome::Error {
+                        error: e.to_string(),
+                    },
+                };
+                recorder.record(RecordedExchange {
+                    sequence,
+                    engine: *engine,
+                    method: method.to_string(),
+                    params: request_body.params,
+                    outcome,
+                    elapsed_ms: start.elapsed().as_millis() as u64,
+                });
+
+                let (_, status_error, bytes) = result?;
+                if let Some(e) = status_error {
+                    return Err(e.into());
+                }
+                serde_json::from_slice(&bytes)?
+            } else {
+                request.send().await?.error_for_status()?.json().await?
+            };
+
+            match (body.result, body.error) {
+                (result, None) => serde_json::from_value(result).map_err(Into::into),
+                (_, Some(error)) => {
+                    if error.message.contains(EIP155_ERROR_STR) {
+                        Err(Error::Eip155Failure)
+                    } else {
+                        Err(Error::ServerMessage {
+                            code: error.code,
+                            message: error.message,
+                        })
+                    }
                 }
             }
         }
+        .await;
+        // Failed requests are timed too, so that timeouts show up in the percentiles.
+        self.latencies
+            .observe(method, start.elapsed(), result.is_err());
+
+        result
     }
 }
 
@@ -716,6 +782,23 @@ impl HttpJsonRpc {
         .await
     }
 
+    /// Returns the balance of `address` in the state after the block with `block_hash`.
+    pub async fn get_balance(
+        &self,
+        address: Address,
+        block_hash: ExecutionBlockHash,
+    ) -> Result<Uint256, Error> {
+        // Use an EIP-1898 block parameter so the balance is read from the exact block.
+        let params = json!([address, { "blockHash": block_hash }]);
+
+        self.rpc_request(
+            ETH_GET_BALANCE,
+            params,
+            ETH_GET_BALANCE_TIMEOUT * self.execution_timeout_multiplier,
+        )
+        .await
+    }
+
     pub async fn get_block_by_hash_with_txns<T: EthSpec>(
         &self,
         block_hash: ExecutionBlockHash,
@@ -1017,7 +1100,7 @@ impl HttpJsonRpc {
 mod test {
     use super::auth::JwtKey;
     use super::*;
-    use crate::test_utils::{MockServer, DEFAULT_JWT_SECRET};
+    use crate::test_utils::{MockServer, ReplaySession, DEFAULT_JWT_SECRET};
     use std::future::Future;
     use std::str::FromStr;
     use std::sync::Arc;
@@ -1800,4 +1883,108 @@ mod test {
             )
             .await;
     }
+
+    /// Waits for the recording at `path` to contain `lines` exchanges.
+    async fn wait_for_recording(path: &std::path::Path, lines: usize) -> String {
+        for _ in 0..500 {
+            let recording = std::fs::read_to_string(path).unwrap_or_default();
+            if recording.lines().count() >= lines {
+                return recording;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("recording was not written");
+    }
+
+    #[tokio::test]
+    async fn recorded_session_replays_responses() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(crate::recording::ENGINE_RECORDING_FILE);
+        let auth = || Auth::new(JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap(), None, None);
+        let runtime = task_executor::test_utils::TestRuntime::default();
+
+        // Record a session against a mock server, and a request to an engine which is offline.
+        let server = MockServer::<MainnetEthSpec>::unit_testing();
+        let recorder = Arc::new(
+            EngineRecorder::spawn(&path, u64::MAX, &runtime.task_executor, runtime.log.clone())
+                .unwrap(),
+        );
+        let client =
+            HttpJsonRpc::new_with_auth(SensitiveUrl::parse(&server.url()).unwrap(), auth(), None)
+                .unwrap()
+                .with_recorder(recorder.clone(), 0);
+        let offline_client = HttpJsonRpc::new_with_auth(
+            SensitiveUrl::parse("http://127.0.0.1:1").unwrap(),
+            auth(),
+            None,
+        )
+        .unwrap()
+        .with_recorder(recorder, 1);
+        let recorded_block = client
+            .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+            .await
+            .unwrap();
+        assert!(recorded_block.is_some());
+        client.upcheck().await.unwrap();
+        assert!(offline_client.upcheck().await.is_err());
+        drop((client, offline_client));
+
+        // The JWT is never written to the recording.
+        let recording = wait_for_recording(&path, 3).await;
+        assert!(!recording.contains("Bearer"));
+
+        // Requests are numbered in the order they were sent and failures are recorded.
+        let exchanges = crate::recording::read_recording(&path).unwrap();
+        assert_eq!(
+            exchanges
+                .iter()
+                .map(|exchange| (exchange.sequence, exchange.engine))
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (2, 1)]
+        );
+        assert!(matches!(
+            exchanges[1].outcome,
+            RecordedOutcome::Response { status: 200, .. }
+        ));
+        assert!(matches!(
+            exchanges[2].outcome,
+            RecordedOutcome::Error { .. }
+        ));
+
+        // Replay the session of the first engine against a server which knows of no blocks.
+        let server = MockServer::<MainnetEthSpec>::unit_testing();
+        server.drop_all_blocks();
+        server.start_replay(ReplaySession::from_file(&path, 0).unwrap());
+        let client =
+            HttpJsonRpc::new_with_auth(SensitiveUrl::parse(&server.url()).unwrap(), auth(), None)
+                .unwrap();
+        // A request made out of the recorded order is answered, but is a divergence.
+        client.upcheck().await.unwrap();
+        assert_eq!(
+            client
+                .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+                .await
+                .unwrap(),
+            recorded_block
+        );
+        // The last response for a method is repeated.
+        client.upcheck().await.unwrap();
+        // Methods which were not recorded return an error.
+        assert!(matches!(
+            client.get_block_by_hash(ExecutionBlockHash::zero()).await,
+            Err(Error::ServerMessage {
+                code: METHOD_NOT_FOUND_CODE,
+                ..
+            })
+        ));
+
+        let session = server.stop_replay().unwrap();
+        assert_eq!(session.divergences(), &[ETH_SYNCING.to_string()]);
+        assert_eq!(session.remaining(), 0);
+
+        // Replaying the second engine reproduces its failure.
+        server.start_replay(ReplaySession::from_file(&path, 1).unwrap());
+        assert!(matches!(client.upcheck().await, Err(Error::Reqwest(_))));
+        assert_eq!(server.stop_replay().unwrap().remaining(), 0);
+    }
 }
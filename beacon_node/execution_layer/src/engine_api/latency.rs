@@ -0,0 +1,105 @@
+//! Tracks the response latency of an execution engine per JSON-RPC method, so that percentiles
+//! and error counts can be reported by the HTTP API.
+
+use eth2::lighthouse::MethodLatency;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// The number of most recent requests retained for each method.
+pub const LATENCY_SAMPLES_PER_METHOD: usize = 256;
+
+#[derive(Default)]
+pub struct LatencyTracker {
+    samples: Mutex<HashMap<String, VecDeque<Sample>>>,
+}
+
+#[derive(Clone, Copy)]
+struct Sample {
+    elapsed: Duration,
+    is_error: bool,
+}
+
+impl LatencyTracker {
+    /// Record that a request to `method` completed after `elapsed`, either with a response or
+    /// with an error (e.g. a timeout).
+    pub fn observe(&self, method: &str, elapsed: Duration, is_error: bool) {
+        let mut samples = self.samples.lock();
+        let method_samples = samples.entry(method.to_string()).or_default();
+        if method_samples.len() >= LATENCY_SAMPLES_PER_METHOD {
+            method_samples.pop_front();
+        }
+        method_samples.push_back(Sample { elapsed, is_error });
+    }
+
+    /// Returns the latency percentiles and error count of each method with at least one request,
+    /// sorted by method name.
+    pub fn percentiles(&self) -> Vec<MethodLatency> {
+        let samples = self.samples.lock();
+        let mut latencies = samples
+            .iter()
+            .filter(|(_, method_samples)| !method_samples.is_empty())
+            .map(|(method, method_samples)| {
+                let mut sorted = method_samples
+                    .iter()
+                    .map(|sample| sample.elapsed)
+                    .collect::<Vec<_>>();
+                sorted.sort_unstable();
+                let percentile = |p: usize| {
+                    let index = (sorted.len() * p / 100).min(sorted.len() - 1);
+                    sorted[index].as_millis() as u64
+                };
+                MethodLatency {
+                    method: method.clone(),
+                    samples: sorted.len() as u64,
+                    errors: method_samples
+                        .iter()
+                        .filter(|sample| sample.is_error)
+                        .count() as u64,
+                    p50_ms: percentile(50),
+                    p90_ms: percentile(90),
+                    p99_ms: percentile(99),
+                    max_ms: percentile(100),
+                }
+            })
+            .collect::<Vec<_>>();
+        latencies.sort_by(|a, b| a.method.cmp(&b.method));
+        latencies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_of_recent_samples() {
+        let tracker = LatencyTracker::default();
+        for ms in 1..=LATENCY_SAMPLES_PER_METHOD as u64 + 100 {
+            tracker.observe(
+                "engine_newPayloadV1",
+                Duration::from_millis(ms),
+                ms % 2 == 0,
+            );
+        }
+        tracker.observe("eth_syncing", Duration::from_millis(3), false);
+
+        let latencies = tracker.percentiles();
+        assert_eq!(latencies.len(), 2);
+
+        let new_payload = &latencies[0];
+        assert_eq!(new_payload.method, "engine_newPayloadV1");
+        assert_eq!(new_payload.samples, LATENCY_SAMPLES_PER_METHOD as u64);
+        // The oldest 100 samples have been evicted.
+        assert_eq!(new_payload.p50_ms, 101 + 128);
+        assert_eq!(new_payload.max_ms, 100 + LATENCY_SAMPLES_PER_METHOD as u64);
+        // Only errors among the retained samples are counted.
+        assert_eq!(new_payload.errors, LATENCY_SAMPLES_PER_METHOD as u64 / 2);
+
+        let syncing = &latencies[1];
+        assert_eq!(syncing.samples, 1);
+        assert_eq!(syncing.p50_ms, 3);
+        assert_eq!(syncing.p99_ms, 3);
+        assert_eq!(syncing.errors, 0);
+    }
+}
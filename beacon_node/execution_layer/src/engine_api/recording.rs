@@ -0,0 +1,151 @@
+//! Records the JSON-RPC exchanges between Lighthouse and its execution engines to disk, so that a
+//! session can later be replayed against the mock execution engine.
+//!
+//! Each exchange is written as a single line of JSON. Only the request method and params and the
+//! raw response body (or the error, if no response was received) are recorded; HTTP headers
+//! (including the JWT bearer token) are never written.
+//!
+//! Requests to all engines share a single recording and are numbered in the order they were sent,
+//! so a session can be replayed in the order it happened. Exchanges are written by a blocking
+//! task, so that disk I/O never delays a request to the engine.
+
+use crate::metrics;
+use serde::{Deserialize, Serialize};
+use slog::{debug, warn, Logger};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use task_executor::TaskExecutor;
+
+/// The name of the recording written to the recording directory.
+pub const ENGINE_RECORDING_FILE: &str = "engine_api.jsonl";
+
+/// The maximum size of a recording. Recording stops once it is reached.
+pub const MAX_ENGINE_RECORDING_SIZE: u64 = 4 * 1_024 * 1_048_576;
+
+/// The number of exchanges that may be queued for the writer before new exchanges are dropped.
+const RECORDER_QUEUE_LEN: usize = 1_024;
+
+/// A single JSON-RPC request and its outcome.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    /// The position of this request amongst the requests sent to all engines.
+    pub sequence: u64,
+    /// The index of the engine the request was sent to.
+    pub engine: usize,
+    pub method: String,
+    pub params: serde_json::Value,
+    pub outcome: RecordedOutcome,
+    /// The time taken for the engine to respond.
+    pub elapsed_ms: u64,
+}
+
+/// The outcome of a recorded request.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordedOutcome {
+    /// The engine responded. The body is recorded as received, even if it is not valid JSON or
+    /// the status indicates an error.
+    Response { status: u16, body: String },
+    /// No response was received, e.g. because the connection failed or the request timed out.
+    Error { error: String },
+}
+
+/// Queues `RecordedExchange`s to be appended to a recording by a blocking writer task.
+///
+/// Exchanges are dropped, rather than delaying the request, if the writer falls behind.
+pub struct EngineRecorder {
+    sender: SyncSender<RecordedExchange>,
+    next_sequence: AtomicU64,
+}
+
+impl EngineRecorder {
+    /// Open `path` for recording, appending to it if it already exists, and spawn a task to write
+    /// to it. The recording is not extended beyond `max_size` bytes.
+    pub fn spawn(
+        path: &Path,
+        max_size: u64,
+        executor: &TaskExecutor,
+        log: Logger,
+    ) -> Result<Self, String> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Unable to open engine recording {:?}: {:?}", path, e))?;
+        let mut size = file
+            .metadata()
+            .map_err(|e| format!("Unable to read engine recording {:?}: {:?}", path, e))?
+            .len();
+        let (sender, receiver) = mpsc::sync_channel::<RecordedExchange>(RECORDER_QUEUE_LEN);
+
+        executor.spawn_blocking(
+            move || {
+                while let Ok(exchange) = receiver.recv() {
+                    let mut line = match serde_json::to_vec(&exchange) {
+                        Ok(line) => line,
+                        Err(e) => {
+                            warn!(log, "Unable to serialize engine exchange"; "error" => ?e);
+                            continue;
+                        }
+                    };
+                    line.push(b'\n');
+
+                    if size.saturating_add(line.len() as u64) > max_size {
+                        warn!(log, "Engine recording stopped"; "reason" => "maximum size reached", "max_size" => max_size);
+                        break;
+                    }
+                    if let Err(e) = file.write_all(&line) {
+                        warn!(log, "Engine recording stopped"; "error" => ?e);
+                        break;
+                    }
+                    size += line.len() as u64;
+                }
+                debug!(log, "Engine recorder shutting down"; "size" => size);
+            },
+            "engine_recorder",
+        );
+
+        Ok(Self {
+            sender,
+            next_sequence: AtomicU64::new(0),
+        })
+    }
+
+    /// Returns the sequence number to record with the next request.
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Queue `exchange` to be written, without blocking.
+    pub fn record(&self, exchange: RecordedExchange) {
+        match self.sender.try_send(exchange) {
+            Ok(()) | Err(TrySendError::Disconnected(_)) => {}
+            Err(TrySendError::Full(_)) => {
+                metrics::inc_counter(&metrics::EXECUTION_LAYER_RECORDING_DROPPED_EXCHANGES)
+            }
+        }
+    }
+}
+
+/// Read all of the exchanges from a recording, in the order their requests were sent.
+pub fn read_recording(path: &Path) -> Result<Vec<RecordedExchange>, String> {
+    let file = File::open(path)
+        .map_err(|e| format!("Unable to open engine recording {:?}: {:?}", path, e))?;
+    let mut exchanges = BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+        .map(|(i, line)| {
+            let line = line.map_err(|e| format!("Unable to read engine recording: {:?}", e))?;
+            serde_json::from_str::<RecordedExchange>(&line)
+                .map_err(|e| format!("Invalid exchange on line {}: {:?}", i + 1, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // Exchanges are written once their response arrives, which may differ from the order in
+    // which the requests were sent.
+    exchanges.sort_by_key(|exchange| exchange.sequence);
+    Ok(exchanges)
+}
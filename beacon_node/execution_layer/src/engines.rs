@@ -1,10 +1,11 @@
 //! Provides generic behaviour for multiple execution engines, specifically fallback behaviour.
 
 use crate::engine_api::{
-    EngineCapabilities, Error as EngineApiError, ForkchoiceUpdatedResponse, PayloadAttributes,
-    PayloadId,
+    BlockByNumberQuery, EngineCapabilities, Error as EngineApiError, ExecutionBlock,
+    ForkchoiceUpdatedResponse, PayloadAttributes, PayloadId, LATEST_TAG,
 };
 use crate::HttpJsonRpc;
+use eth2::lighthouse::ExecutionEngineState;
 use lru::LruCache;
 use slog::{debug, error, info, warn, Logger};
 use std::future::Future;
@@ -46,6 +47,17 @@ pub enum EngineState {
     Offline,
 }
 
+impl From<EngineStateInternal> for ExecutionEngineState {
+    fn from(state: EngineStateInternal) -> Self {
+        match state {
+            EngineStateInternal::Synced => ExecutionEngineState::Synced,
+            EngineStateInternal::Syncing => ExecutionEngineState::Syncing,
+            EngineStateInternal::Offline => ExecutionEngineState::Offline,
+            EngineStateInternal::AuthFailed => ExecutionEngineState::AuthFailed,
+        }
+    }
+}
+
 impl From<EngineStateInternal> for EngineState {
     fn from(state: EngineStateInternal) -> Self {
         match state {
@@ -125,6 +137,8 @@ pub struct Engine {
     payload_id_cache: Mutex<LruCache<PayloadIdCacheKey, PayloadId>>,
     state: RwLock<State>,
     latest_forkchoice_state: RwLock<Option<ForkchoiceState>>,
+    /// The engine's `latest` block, as of the most recent upcheck.
+    latest_block: RwLock<Option<ExecutionBlock>>,
     executor: TaskExecutor,
     log: Logger,
 }
@@ -137,6 +151,7 @@ impl Engine {
             payload_id_cache: Mutex::new(LruCache::new(PAYLOAD_ID_LRU_CACHE_SIZE)),
             state: Default::default(),
             latest_forkchoice_state: Default::default(),
+            latest_block: Default::default(),
             executor,
             log: log.clone(),
         }
@@ -238,6 +253,21 @@ impl Engine {
         **self.state.read().await == EngineStateInternal::Synced
     }
 
+    /// Returns `true` if the engine can't be reached or rejected our JWT.
+    pub async fn is_offline(&self) -> bool {
+        EngineState::from(**self.state.read().await) == EngineState::Offline
+    }
+
+    /// Returns the state of the engine as of the most recent upcheck.
+    pub async fn health_state(&self) -> ExecutionEngineState {
+        (**self.state.read().await).into()
+    }
+
+    /// Returns the engine's `latest` block as of the most recent upcheck, if it was reachable.
+    pub async fn latest_block(&self) -> Option<ExecutionBlock> {
+        *self.latest_block.read().await
+    }
+
     /// Run the `EngineApi::upcheck` function if the node's last known state is not synced. This
     /// might be used to recover the node if offline.
     pub async fn upcheck(&self) {
@@ -310,6 +340,26 @@ impl Engine {
             CapabilitiesCacheAction::Clear => self.api.clear_exchange_capabilties_cache().await,
         }
 
+        // Keep track of the engine's head so that an engine which has stopped following our
+        // forkchoice updates can be detected.
+        let latest_block = if EngineState::from(state) == EngineState::Online {
+            self.api
+                .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+                .await
+                .map_err(|e| {
+                    debug!(
+                        self.log,
+                        "Unable to get latest execution block";
+                        "error" => ?e,
+                    )
+                })
+                .ok()
+                .flatten()
+        } else {
+            None
+        };
+        *self.latest_block.write().await = latest_block;
+
         debug!(
             self.log,
             "Execution engine upcheck complete";
@@ -6,18 +6,28 @@
 
 use crate::payload_cache::PayloadCache;
 use auth::{strip_prefix, Auth, JwtKey};
+pub use block_hash::{calculate_execution_block_hash, verify_payload_block_hash};
+use builder_circuit_breaker::{BuilderCircuitBreaker, BuilderCircuitBreakerConfig};
 use builder_client::BuilderHttpClient;
+use engine_api::recording::{EngineRecorder, ENGINE_RECORDING_FILE, MAX_ENGINE_RECORDING_SIZE};
 pub use engine_api::EngineCapabilities;
 use engine_api::Error as ApiError;
 pub use engine_api::*;
 pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
 use engines::{Engine, EngineError};
 pub use engines::{EngineState, ForkchoiceState};
+use eth2::lighthouse::{
+    BuilderAudit, BuilderAuditOutcome, BuilderBidRecord, BuilderCircuitBreakerStatus,
+    CircuitBreakerTrigger, ExecutionEngineHealth, ExecutionEngineState, ExecutionLayerHealth,
+    PayloadSource, PayloadValues,
+};
 use eth2::types::builder_bid::SignedBuilderBid;
 use fork_choice::ForkchoiceUpdateParameters;
+use futures::future::{join_all, ready, select_ok};
+use futures::stream::{select_all, Stream, StreamExt};
 use lru::LruCache;
-use payload_status::process_payload_status;
 pub use payload_status::PayloadStatus;
+use payload_status::{await_payload_statuses, process_payload_status};
 use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, trace, warn, Logger};
@@ -27,7 +37,7 @@ use std::fmt;
 use std::future::Future;
 use std::io::Write;
 use std::marker::PhantomData;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use strum::AsRefStr;
@@ -36,16 +46,17 @@ use tokio::{
     sync::{Mutex, MutexGuard, RwLock},
     time::sleep,
 };
-use tokio_stream::wrappers::WatchStream;
 use tree_hash::TreeHash;
 use types::{AbstractExecPayload, BeaconStateError, ExecPayload, Withdrawals};
 use types::{
     BlindedPayload, BlockType, ChainSpec, Epoch, ExecutionBlockHash, ExecutionPayload,
     ExecutionPayloadCapella, ExecutionPayloadMerge, ForkName, ForkVersionedResponse,
-    ProposerPreparationData, PublicKeyBytes, Signature, SignedBeaconBlock, Slot, Uint256,
+    ProposerPreparationData, PublicKeyBytes, Signature, SignedBeaconBlock,
+    SignedValidatorRegistrationData, Slot, Uint256,
 };
 
 mod block_hash;
+pub mod builder_circuit_breaker;
 mod engine_api;
 pub mod engines;
 mod keccak;
@@ -64,6 +75,18 @@ pub const DEFAULT_JWT_FILE: &str = "jwt.hex";
 /// in an LRU cache to avoid redundant lookups. This is the size of that cache.
 const EXECUTION_BLOCKS_LRU_CACHE_SIZE: usize = 128;
 
+/// The number of recently chosen builder bids for which we remember the relay that supplied them,
+/// so that the signed blinded block is only revealed to that relay and its payload can be audited.
+const CHOSEN_BIDS_LRU_CACHE_SIZE: usize = 32;
+
+/// The number of recently produced payloads for which we remember the values of the local and
+/// builder payloads that competed for the proposal.
+const PAYLOAD_VALUES_LRU_CACHE_SIZE: usize = 256;
+
+/// The percentage that builder bids are multiplied by before comparison with the value of the
+/// local payload, unless configured otherwise.
+pub const DEFAULT_BUILDER_BOOST_FACTOR: u64 = 100;
+
 /// A fee recipient address for use during block production. Only used as a very last resort if
 /// there is no address provided by the user.
 ///
@@ -76,6 +99,22 @@ const DEFAULT_SUGGESTED_FEE_RECIPIENT: [u8; 20] =
 
 const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(60);
 
+/// The number of blocks an engine's `latest` block may trail the execution block of the beacon
+/// chain head before the engine is considered degraded.
+///
+/// Engine heads are only polled once per slot, so a small lag is expected.
+pub const ENGINE_HEAD_LAG_TOLERANCE: u64 = 4;
+
+/// Every builder bid received for a proposal, alongside the relay whose bid was used for it.
+struct ChosenBuilderBid {
+    /// The index of the relay in `Inner::builders` which supplied the bid, or `None` if the local
+    /// payload was chosen over every bid.
+    relay_index: Option<usize>,
+    slot: Slot,
+    proposer_fee_recipient: Address,
+    bids: Vec<BuilderBidRecord>,
+}
+
 /// A payload alongside some information about where it came from.
 enum ProvenancedPayload<P> {
     /// A good ol' fashioned farm-to-table payload from your local EE.
@@ -88,6 +127,10 @@ enum ProvenancedPayload<P> {
 pub enum Error {
     NoEngine,
     NoPayloadBuilder,
+    UnknownBuilder(String),
+    /// The relay which supplied the bid for a blinded block is unknown, so it can't safely be
+    /// revealed to any one relay.
+    UnknownBuilderRelay(Option<ExecutionBlockHash>),
     ApiError(ApiError),
     Builder(builder_client::Error),
     NoHeaderFromBuilder,
@@ -97,12 +140,18 @@ pub enum Error {
     ShuttingDown,
     FeeRecipientUnspecified,
     MissingLatestValidHash,
+    MissingExecutionBlock(ExecutionBlockHash),
     BlockHashMismatch {
         computed: ExecutionBlockHash,
         payload: ExecutionBlockHash,
         transactions_root: Hash256,
     },
     InvalidJWTSecret(String),
+    EngineRecording(String),
+    InvalidExecutionQuorum {
+        quorum: usize,
+        engines: usize,
+    },
     BeaconStateError(BeaconStateError),
 }
 
@@ -208,8 +257,16 @@ pub enum FailedCondition {
 }
 
 struct Inner<E: EthSpec> {
-    engine: Arc<Engine>,
-    builder: Option<BuilderHttpClient>,
+    engines: Vec<Arc<Engine>>,
+    /// The number of engines which must consider a payload valid for it to be valid, if set.
+    /// Otherwise a majority of the engines which are not offline is required.
+    execution_quorum: Option<usize>,
+    builders: Vec<BuilderHttpClient>,
+    builder_circuit_breaker: parking_lot::Mutex<BuilderCircuitBreaker>,
+    /// Maps the block hash of each recently chosen builder bid to the relay which supplied it and
+    /// the other bids received for the same proposal.
+    chosen_bids: Mutex<LruCache<ExecutionBlockHash, ChosenBuilderBid>>,
+    payload_values: parking_lot::Mutex<LruCache<ExecutionBlockHash, PayloadValues>>,
     execution_engine_forkchoice_lock: Mutex<()>,
     suggested_fee_recipient: Option<Address>,
     proposer_preparation_data: Mutex<HashMap<u64, ProposerPreparationDataEntry>>,
@@ -218,6 +275,7 @@ struct Inner<E: EthSpec> {
     executor: TaskExecutor,
     payload_cache: PayloadCache<E>,
     builder_profit_threshold: Uint256,
+    builder_boost_factor: u64,
     log: Logger,
     always_prefer_builder_payload: bool,
 }
@@ -227,9 +285,20 @@ pub struct Config {
     /// Endpoint urls for EL nodes that are running the engine api.
     pub execution_endpoints: Vec<SensitiveUrl>,
     /// Endpoint urls for services providing the builder api.
-    pub builder_url: Option<SensitiveUrl>,
+    ///
+    /// Bids are requested from all builders and the most valuable valid bid is used.
+    pub builder_urls: Vec<SensitiveUrl>,
     /// JWT secrets for the above endpoints running the engine api.
+    ///
+    /// If there are fewer secrets than endpoints, the first secret is used for the remaining
+    /// endpoints.
     pub secret_files: Vec<PathBuf>,
+    /// The number of engines which must consider a payload valid for it to be valid.
+    ///
+    /// Defaults to a majority of the `execution_endpoints` which are not offline, so that an
+    /// engine going offline does not prevent payloads from being verified. An invalid status from
+    /// any engine still makes a payload invalid.
+    pub execution_quorum: Option<usize>,
     /// The default fee recipient to use on the beacon node if none if provided from
     /// the validator client during block preparation.
     pub suggested_fee_recipient: Option<Address>,
@@ -241,82 +310,119 @@ pub struct Config {
     pub default_datadir: PathBuf,
     /// The minimum value of an external payload for it to be considered in a proposal.
     pub builder_profit_threshold: u128,
+    /// The percentage that builder bids are multiplied by before comparison with the value of the
+    /// local payload. A value below 100 favours local payloads.
+    ///
+    /// Defaults to `DEFAULT_BUILDER_BOOST_FACTOR`.
+    pub builder_boost_factor: Option<u64>,
+    /// Conditions under which builder relays stop being asked for bids.
+    pub builder_circuit_breaker: BuilderCircuitBreakerConfig,
     pub execution_timeout_multiplier: Option<u32>,
     pub always_prefer_builder_payload: bool,
+    /// If set, every JSON-RPC exchange with each engine is recorded to a file in this directory.
+    pub engine_recording_dir: Option<PathBuf>,
 }
 
-/// Provides access to one execution engine and provides a neat interface for consumption by the
-/// `BeaconChain`.
+/// Provides access to one or more execution engines and provides a neat interface for consumption
+/// by the `BeaconChain`.
+///
+/// Payload verification is sent to all engines and their results are combined conservatively,
+/// whilst payloads are produced by whichever engine responds first.
 #[derive(Clone)]
 pub struct ExecutionLayer<T: EthSpec> {
     inner: Arc<Inner<T>>,
 }
 
 impl<T: EthSpec> ExecutionLayer<T> {
-    /// Instantiate `Self` with an Execution engine specified in `Config`, using JSON-RPC via HTTP.
+    /// Instantiate `Self` with the execution engines specified in `Config`, using JSON-RPC via
+    /// HTTP.
     pub fn from_config(config: Config, executor: TaskExecutor, log: Logger) -> Result<Self, Error> {
         let Config {
             execution_endpoints: urls,
-            builder_url,
+            builder_urls,
             secret_files,
+            execution_quorum,
             suggested_fee_recipient,
             jwt_id,
             jwt_version,
             default_datadir,
             builder_profit_threshold,
+            builder_boost_factor,
+            builder_circuit_breaker,
             execution_timeout_multiplier,
             always_prefer_builder_payload,
+            engine_recording_dir,
         } = config;
 
-        if urls.len() > 1 {
-            warn!(log, "Only the first execution engine url will be used");
+        if urls.is_empty() {
+            return Err(Error::NoEngine);
         }
-        let execution_url = urls.into_iter().next().ok_or(Error::NoEngine)?;
 
-        // Use the default jwt secret path if not provided via cli.
-        let secret_file = secret_files
+        if let Some(quorum) = execution_quorum {
+            if quorum == 0 || quorum > urls.len() {
+                return Err(Error::InvalidExecutionQuorum {
+                    quorum,
+                    engines: urls.len(),
+                });
+            }
+        }
+
+        let recorder = engine_recording_dir
+            .map(|dir| {
+                std::fs::create_dir_all(&dir).map_err(|e| {
+                    Error::EngineRecording(format!("Unable to create {:?}: {:?}", dir, e))
+                })?;
+                let path = dir.join(ENGINE_RECORDING_FILE);
+                let recorder =
+                    EngineRecorder::spawn(&path, MAX_ENGINE_RECORDING_SIZE, &executor, log.clone())
+                        .map_err(Error::EngineRecording)?;
+                warn!(
+                    log,
+                    "Recording engine API traffic";
+                    "info" => "recordings contain full payloads and may grow large",
+                    "max_size" => MAX_ENGINE_RECORDING_SIZE,
+                    "path" => %path.display(),
+                );
+                Ok::<_, Error>(Arc::new(recorder))
+            })
+            .transpose()?;
+
+        let engines = urls
             .into_iter()
-            .next()
-            .unwrap_or_else(|| default_datadir.join(DEFAULT_JWT_FILE));
-
-        let jwt_key = if secret_file.exists() {
-            // Read secret from file if it already exists
-            std::fs::read_to_string(&secret_file)
-                .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
-                .and_then(|ref s| {
-                    let secret = JwtKey::from_slice(
-                        &hex::decode(strip_prefix(s.trim_end()))
-                            .map_err(|e| format!("Invalid hex string: {:?}", e))?,
-                    )?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        } else {
-            // Create a new file and write a randomly generated secret to it if file does not exist
-            warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
-            std::fs::File::options()
-                .write(true)
-                .create_new(true)
-                .open(&secret_file)
-                .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
-                .and_then(|mut f| {
-                    let secret = auth::JwtKey::random();
-                    f.write_all(secret.hex_string().as_bytes())
-                        .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        }?;
-
-        let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
-            debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
-            let api = HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
-                .map_err(Error::ApiError)?;
-            Engine::new(api, executor.clone(), &log)
-        };
+            .enumerate()
+            .map(|(i, execution_url)| {
+                // Use the default jwt secret path if not provided via cli.
+                let secret_file = secret_files
+                    .get(i)
+                    .or_else(|| secret_files.first())
+                    .cloned()
+                    .unwrap_or_else(|| default_datadir.join(DEFAULT_JWT_FILE));
+                let jwt_key = load_jwt_key(&secret_file, &log)?;
+
+                let auth = Auth::new(jwt_key, jwt_id.clone(), jwt_version.clone());
+                debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
+                let mut api =
+                    HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
+                        .map_err(Error::ApiError)?;
+                if let Some(recorder) = &recorder {
+                    api = api.with_recorder(recorder.clone(), i);
+                }
+                Ok(Arc::new(Engine::new(api, executor.clone(), &log)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        if engines.len() > 1 {
+            info!(
+                log,
+                "Using multiple execution engines";
+                "engines" => engines.len(),
+                "quorum" => execution_quorum
+                    .map_or_else(|| "majority online".to_string(), |quorum| quorum.to_string()),
+            );
+        }
 
-        let builder = builder_url
+        let builders = builder_urls
+            .into_iter()
             .map(|url| {
                 let builder_client = BuilderHttpClient::new(url.clone()).map_err(Error::Builder);
                 info!(log,
@@ -325,11 +431,20 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     "builder_profit_threshold" => builder_profit_threshold);
                 builder_client
             })
-            .transpose()?;
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let builder_circuit_breaker = parking_lot::Mutex::new(BuilderCircuitBreaker::new(
+            builder_circuit_breaker,
+            builders.len(),
+        ));
 
         let inner = Inner {
-            engine: Arc::new(engine),
-            builder,
+            engines,
+            execution_quorum,
+            builders,
+            builder_circuit_breaker,
+            chosen_bids: Mutex::new(LruCache::new(CHOSEN_BIDS_LRU_CACHE_SIZE)),
+            payload_values: parking_lot::Mutex::new(LruCache::new(PAYLOAD_VALUES_LRU_CACHE_SIZE)),
             execution_engine_forkchoice_lock: <_>::default(),
             suggested_fee_recipient,
             proposer_preparation_data: Mutex::new(HashMap::new()),
@@ -338,6 +453,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
             executor,
             payload_cache: PayloadCache::default(),
             builder_profit_threshold: Uint256::from(builder_profit_threshold),
+            builder_boost_factor: builder_boost_factor.unwrap_or(DEFAULT_BUILDER_BOOST_FACTOR),
             log,
             always_prefer_builder_payload,
         };
@@ -348,13 +464,128 @@ impl<T: EthSpec> ExecutionLayer<T> {
     }
 }
 
+/// Reads the JWT secret from `secret_file`, generating and persisting a random secret if the file
+/// does not exist.
+fn load_jwt_key(secret_file: &Path, log: &Logger) -> Result<JwtKey, Error> {
+    if secret_file.exists() {
+        // Read secret from file if it already exists
+        std::fs::read_to_string(secret_file)
+            .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
+            .and_then(|ref s| {
+                let secret = JwtKey::from_slice(
+                    &hex::decode(strip_prefix(s.trim_end()))
+                        .map_err(|e| format!("Invalid hex string: {:?}", e))?,
+                )?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    } else {
+        // Create a new file and write a randomly generated secret to it if file does not exist
+        warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
+        std::fs::File::options()
+            .write(true)
+            .create_new(true)
+            .open(secret_file)
+            .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
+            .and_then(|mut f| {
+                let secret = auth::JwtKey::random();
+                f.write_all(secret.hex_string().as_bytes())
+                    .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    }
+}
+
 impl<T: EthSpec> ExecutionLayer<T> {
+    /// The primary engine, used for requests which are not sent to every engine.
     fn engine(&self) -> &Arc<Engine> {
-        &self.inner.engine
+        &self.inner.engines[0]
+    }
+
+    fn engines(&self) -> &[Arc<Engine>] {
+        &self.inner.engines
     }
 
-    pub fn builder(&self) -> &Option<BuilderHttpClient> {
-        &self.inner.builder
+    /// The number of engines which must consider a payload valid for it to be valid.
+    ///
+    /// Unless configured, this is a majority of the engines which are not offline. Offline engines
+    /// are not counted, since they can't respond in time anyway.
+    async fn execution_quorum(&self) -> usize {
+        if let Some(quorum) = self.inner.execution_quorum {
+            return quorum;
+        }
+        let mut online = 0;
+        for engine in self.engines() {
+            if !engine.is_offline().await {
+                online += 1;
+            }
+        }
+        online / 2 + 1
+    }
+
+    /// Runs `func` on each engine in turn until one succeeds, returning the last error if none
+    /// do.
+    async fn request_with_fallback<'a, F, G, H>(&'a self, func: F) -> Result<H, EngineError>
+    where
+        F: Fn(&'a Engine) -> G,
+        G: Future<Output = Result<H, ApiError>>,
+    {
+        let mut last_error = EngineError::Offline;
+        for engine in self.engines() {
+            match engine.request(&func).await {
+                Ok(result) => return Ok(result),
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+
+    pub fn builders(&self) -> &[BuilderHttpClient] {
+        &self.inner.builders
+    }
+
+    pub fn has_builder(&self) -> bool {
+        !self.inner.builders.is_empty()
+    }
+
+    /// Register validators with all connected builders.
+    ///
+    /// Succeeds if at least one builder accepts the registrations, otherwise returns the error from
+    /// the first builder.
+    pub async fn post_builder_validators(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+    ) -> Result<(), builder_client::Error> {
+        let results = join_all(
+            self.builders()
+                .iter()
+                .map(|builder| builder.post_builder_validators(registrations)),
+        )
+        .await;
+
+        let mut first_error = None;
+        let mut successes = 0;
+        for (builder, result) in self.builders().iter().zip(results) {
+            match result {
+                Ok(()) => successes += 1,
+                Err(e) => {
+                    warn!(
+                        self.log(),
+                        "Relay error when registering validator(s)";
+                        "relay" => %builder.server(),
+                        "num_registrations" => registrations.len(),
+                        "error" => ?e
+                    );
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if successes == 0 => Err(e),
+            _ => Ok(()),
+        }
     }
 
     /// Cache a full payload, keyed on the `tree_hash_root` of the payload
@@ -388,11 +619,40 @@ impl<T: EthSpec> ExecutionLayer<T> {
         self.inner.execution_blocks.lock().await
     }
 
-    /// Gives access to a channel containing if the last engine state is online or not.
+    /// Gives access to a stream of whether the execution layer is online, yielding its current
+    /// state and then every change. The execution layer is online whilst any of its engines are.
     ///
     /// This can be called several times.
-    pub async fn get_responsiveness_watch(&self) -> WatchStream<EngineState> {
-        self.engine().watch_state().await
+    pub async fn get_responsiveness_watch(&self) -> impl Stream<Item = EngineState> + Unpin {
+        let mut states = join_all(self.engines().iter().map(|engine| async move {
+            if engine.is_offline().await {
+                EngineState::Offline
+            } else {
+                EngineState::Online
+            }
+        }))
+        .await;
+        let watches = join_all(self.engines().iter().map(|engine| engine.watch_state())).await;
+
+        // Each engine's watch yields its current state first, so the combined state is yielded
+        // once before any change.
+        let mut last_state = None;
+        select_all(
+            watches
+                .into_iter()
+                .enumerate()
+                .map(|(i, watch)| watch.map(move |state| (i, state))),
+        )
+        .filter_map(move |(i, state)| {
+            states[i] = state;
+            let state = if states.contains(&EngineState::Online) {
+                EngineState::Online
+            } else {
+                EngineState::Offline
+            };
+            let changed = last_state.replace(state) != Some(state);
+            ready(changed.then_some(state))
+        })
     }
 
     /// Note: this function returns a mutex guard, be careful to avoid deadlocks.
@@ -444,7 +704,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
 
     /// Performs a single execution of the watchdog routine.
     pub async fn watchdog_task(&self) {
-        self.engine().upcheck().await;
+        join_all(self.engines().iter().map(|engine| engine.upcheck())).await;
     }
 
     /// Spawns a routine which cleans the cached proposer data periodically.
@@ -504,9 +764,57 @@ impl<T: EthSpec> ExecutionLayer<T> {
         self.spawn(routine, "exec_config_poll");
     }
 
-    /// Returns `true` if the execution engine is synced and reachable.
+    /// Returns `true` if any execution engine is synced and reachable.
     pub async fn is_synced(&self) -> bool {
-        self.engine().is_synced().await
+        for engine in self.engines() {
+            if engine.is_synced().await {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns `true` if every execution engine is unreachable or rejected our credentials.
+    pub async fn is_offline(&self) -> bool {
+        for engine in self.engines() {
+            if !engine.is_offline().await {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns the health of each execution engine, as of its most recent upcheck.
+    ///
+    /// `head_block_number` is the execution block number of the beacon chain head, which is used
+    /// to detect engines that are stuck on an old head. The execution layer is degraded if fewer
+    /// than the execution quorum of engines are healthy.
+    pub async fn health(&self, head_block_number: Option<u64>) -> ExecutionLayerHealth {
+        let engines = join_all(self.engines().iter().map(|engine| async move {
+            let state = engine.health_state().await;
+            let latest_block = engine.latest_block().await;
+            let head_lag = head_block_number
+                .zip(latest_block)
+                .map(|(head, latest)| head.saturating_sub(latest.block_number));
+            ExecutionEngineHealth {
+                endpoint: engine.api.url.to_string(),
+                state,
+                latest_block_hash: latest_block.map(|block| block.block_hash),
+                latest_block_number: latest_block.map(|block| block.block_number),
+                head_lag,
+                degraded: state != ExecutionEngineState::Synced
+                    || head_lag.map_or(false, |lag| lag > ENGINE_HEAD_LAG_TOLERANCE),
+                latencies: engine.api.latencies.percentiles(),
+            }
+        }))
+        .await;
+
+        let healthy = engines.iter().filter(|engine| !engine.degraded).count();
+        ExecutionLayerHealth {
+            head_block_number,
+            degraded: healthy < self.execution_quorum().await,
+            engines,
+        }
     }
 
     /// Execution nodes return a "SYNCED" response when they do not have any peers.
@@ -670,7 +978,15 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     current_fork,
                 )
                 .await
-                .map(ProvenancedPayload::Local)
+                .map(|local| {
+                    self.record_payload_values(
+                        local.payload().block_hash(),
+                        PayloadSource::Local,
+                        Some(*local.block_value()),
+                        None,
+                    );
+                    ProvenancedPayload::Local(local)
+                })
             }
         };
 
@@ -717,27 +1033,52 @@ impl<T: EthSpec> ExecutionLayer<T> {
         current_fork: ForkName,
         spec: &ChainSpec,
     ) -> Result<ProvenancedPayload<BlockProposalContents<T, Payload>>, Error> {
-        if let Some(builder) = self.builder() {
+        if self.has_builder() {
             let slot = builder_params.slot;
             let pubkey = builder_params.pubkey;
+            let available_relays = self
+                .inner
+                .builder_circuit_breaker
+                .lock()
+                .available_relays(slot);
 
             match builder_params.chain_health {
+                ChainHealth::Healthy if available_relays.is_empty() => info!(
+                    self.log(),
+                    "Builder circuit breaker is tripped, using local payload";
+                    "info" => "the breaker resets after a cool-down or via the \
+                        /lighthouse/builder/circuit_breaker/reset endpoint",
+                    "slot" => ?slot,
+                ),
                 ChainHealth::Healthy => {
                     info!(
                         self.log(),
-                        "Requesting blinded header from connected builder";
+                        "Requesting blinded header from connected builders";
+                        "relays" => available_relays.len(),
                         "slot" => ?slot,
                         "pubkey" => ?pubkey,
                         "parent_hash" => ?parent_hash,
                     );
 
-                    // Wait for the builder *and* local EL to produce a payload (or return an error).
-                    let ((relay_result, relay_duration), (local_result, local_duration)) = tokio::join!(
-                        timed_future(metrics::GET_BLINDED_PAYLOAD_BUILDER, async {
-                            builder
-                                .get_builder_header::<T, Payload>(slot, parent_hash, &pubkey)
-                                .await
-                        }),
+                    // Wait for all builders *and* the local EL to produce a payload (or return an
+                    // error). Each builder request is bounded by the client's `get_header` timeout,
+                    // so a slow relay cannot delay the proposal beyond that deadline.
+                    let (relay_results, (local_result, local_duration)) = tokio::join!(
+                        join_all(available_relays.iter().map(|&relay_index| async move {
+                            let builder = &self.builders()[relay_index];
+                            let result =
+                                timed_future(metrics::GET_BLINDED_PAYLOAD_BUILDER, async {
+                                    builder
+                                        .get_builder_header::<T, Payload>(
+                                            slot,
+                                            parent_hash,
+                                            &pubkey,
+                                        )
+                                        .await
+                                })
+                                .await;
+                            (relay_index, result)
+                        })),
                         timed_future(metrics::GET_BLINDED_PAYLOAD_LOCAL, async {
                             self.get_full_payload_caching::<Payload>(
                                 parent_hash,
@@ -752,12 +1093,10 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     info!(
                         self.log(),
                         "Requested blinded execution payload";
-                        "relay_fee_recipient" => match &relay_result {
-                            Ok(Some(r)) => format!("{:?}", r.data.message.header.fee_recipient()),
-                            Ok(None) => "empty response".to_string(),
-                            Err(_) => "request failed".to_string(),
-                        },
-                        "relay_response_ms" => relay_duration.as_millis(),
+                        "relay_bids" => relay_results
+                            .iter()
+                            .filter(|(_, (result, _))| matches!(result, Ok(Some(_))))
+                            .count(),
                         "local_fee_recipient" => match &local_result {
                             Ok(proposal_contents) => format!("{:?}", proposal_contents.payload().fee_recipient()),
                             Err(_) => "request failed".to_string()
@@ -766,34 +1105,56 @@ impl<T: EthSpec> ExecutionLayer<T> {
                         "parent_hash" => ?parent_hash,
                     );
 
-                    return match (relay_result, local_result) {
-                        (Err(e), Ok(local)) => {
-                            warn!(
-                                self.log(),
-                                "Builder error when requesting payload";
-                                "info" => "falling back to local execution client",
-                                "relay_error" => ?e,
-                                "local_block_hash" => ?local.payload().block_hash(),
-                                "parent_hash" => ?parent_hash,
-                            );
-                            Ok(ProvenancedPayload::Local(local))
-                        }
-                        (Ok(None), Ok(local)) => {
+                    for (relay_index, (result, duration)) in &relay_results {
+                        self.record_builder_header(*relay_index, slot, result, *duration);
+                    }
+
+                    let local_block_number = local_result
+                        .as_ref()
+                        .ok()
+                        .map(|local| local.payload().block_number());
+                    let (best_bid, bids) = self.select_builder_bid(
+                        relay_results,
+                        parent_hash,
+                        payload_attributes,
+                        local_block_number,
+                        current_fork,
+                        spec,
+                    );
+
+                    return match (best_bid, local_result) {
+                        (None, Ok(local)) => {
                             info!(
                                 self.log(),
-                                "Builder did not return a payload";
+                                "No usable builder payload";
                                 "info" => "falling back to local execution client",
                                 "local_block_hash" => ?local.payload().block_hash(),
                                 "parent_hash" => ?parent_hash,
                             );
+                            self.record_payload_values(
+                                local.payload().block_hash(),
+                                PayloadSource::Local,
+                                Some(*local.block_value()),
+                                None,
+                            );
+                            self.remember_builder_bids(
+                                local.payload().block_hash(),
+                                None,
+                                bids,
+                                slot,
+                                payload_attributes.suggested_fee_recipient(),
+                            )
+                            .await;
                             Ok(ProvenancedPayload::Local(local))
                         }
-                        (Ok(Some(relay)), Ok(local)) => {
+                        (Some((relay_index, relay, verification)), Ok(local)) => {
                             let header = &relay.data.message.header;
+                            let relay_block_hash = header.block_hash();
 
                             info!(
                                 self.log(),
                                 "Received local and builder payloads";
+                                "relay" => %self.builders()[relay_index].server(),
                                 "relay_block_hash" => ?header.block_hash(),
                                 "local_block_hash" => ?local.payload().block_hash(),
                                 "parent_hash" => ?parent_hash,
@@ -801,133 +1162,119 @@ impl<T: EthSpec> ExecutionLayer<T> {
 
                             let relay_value = relay.data.message.value;
                             let local_value = *local.block_value();
+                            let boosted_relay_value = relay_value
+                                .saturating_mul(Uint256::from(self.inner.builder_boost_factor))
+                                / 100;
                             if !self.inner.always_prefer_builder_payload
-                                && local_value >= relay_value
+                                && local_value >= boosted_relay_value
                             {
                                 info!(
                                     self.log(),
                                     "Local block is more profitable than relay block";
                                     "local_block_value" => %local_value,
-                                    "relay_value" => %relay_value
+                                    "relay_value" => %relay_value,
+                                    "boosted_relay_value" => %boosted_relay_value,
+                                    "builder_boost_factor" => self.inner.builder_boost_factor,
+                                );
+                                self.record_payload_values(
+                                    local.payload().block_hash(),
+                                    PayloadSource::Local,
+                                    Some(local_value),
+                                    Some(relay_value),
                                 );
+                                self.remember_builder_bids(
+                                    local.payload().block_hash(),
+                                    None,
+                                    bids,
+                                    slot,
+                                    payload_attributes.suggested_fee_recipient(),
+                                )
+                                .await;
                                 return Ok(ProvenancedPayload::Local(local));
                             }
 
-                            match verify_builder_bid(
-                                &relay,
-                                parent_hash,
-                                payload_attributes,
-                                Some(local.payload().block_number()),
-                                self.inner.builder_profit_threshold,
-                                current_fork,
-                                spec,
-                            ) {
-                                Ok(()) => Ok(ProvenancedPayload::Builder(
-                                    BlockProposalContents::Payload {
-                                        payload: relay.data.message.header,
-                                        block_value: relay.data.message.value,
-                                        _phantom: PhantomData::default(),
-                                    },
-                                )),
-                                Err(reason) if !reason.payload_invalid() => {
-                                    info!(
-                                        self.log(),
-                                        "Builder payload ignored";
-                                        "info" => "using local payload",
-                                        "reason" => %reason,
-                                        "relay_block_hash" => ?header.block_hash(),
-                                        "parent_hash" => ?parent_hash,
+                            match verification {
+                                Ok(()) => {
+                                    self.record_payload_values(
+                                        relay_block_hash,
+                                        PayloadSource::Builder,
+                                        Some(local_value),
+                                        Some(relay_value),
                                     );
-                                    Ok(ProvenancedPayload::Local(local))
+                                    Ok(ProvenancedPayload::Builder(
+                                        self.choose_builder_bid(
+                                            relay_index,
+                                            relay,
+                                            bids,
+                                            slot,
+                                            payload_attributes.suggested_fee_recipient(),
+                                        )
+                                        .await,
+                                    ))
                                 }
                                 Err(reason) => {
-                                    metrics::inc_counter_vec(
-                                        &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                                        &[reason.as_ref().as_ref()],
-                                    );
-                                    warn!(
+                                    info!(
                                         self.log(),
-                                        "Builder returned invalid payload";
+                                        "Builder payload ignored";
                                         "info" => "using local payload",
                                         "reason" => %reason,
-                                        "relay_block_hash" => ?header.block_hash(),
+                                        "relay_block_hash" => ?relay_block_hash,
                                         "parent_hash" => ?parent_hash,
                                     );
+                                    self.record_payload_values(
+                                        local.payload().block_hash(),
+                                        PayloadSource::Local,
+                                        Some(local_value),
+                                        Some(relay_value),
+                                    );
+                                    self.remember_builder_bids(
+                                        local.payload().block_hash(),
+                                        None,
+                                        bids,
+                                        slot,
+                                        payload_attributes.suggested_fee_recipient(),
+                                    )
+                                    .await;
                                     Ok(ProvenancedPayload::Local(local))
                                 }
                             }
                         }
-                        (Ok(Some(relay)), Err(local_error)) => {
-                            let header = &relay.data.message.header;
-
+                        (Some((relay_index, relay, _)), Err(local_error)) => {
                             info!(
                                 self.log(),
                                 "Received builder payload with local error";
-                                "relay_block_hash" => ?header.block_hash(),
+                                "relay" => %self.builders()[relay_index].server(),
+                                "relay_block_hash" => ?relay.data.message.header.block_hash(),
                                 "local_error" => ?local_error,
                                 "parent_hash" => ?parent_hash,
                             );
 
-                            match verify_builder_bid(
-                                &relay,
-                                parent_hash,
-                                payload_attributes,
+                            // Invalid bids have already been discarded. The local EE failed to
+                            // produce a payload so we have no alternative to the best remaining
+                            // bid, even if it fell short of the profit threshold.
+                            self.record_payload_values(
+                                relay.data.message.header.block_hash(),
+                                PayloadSource::Builder,
                                 None,
-                                self.inner.builder_profit_threshold,
-                                current_fork,
-                                spec,
-                            ) {
-                                Ok(()) => Ok(ProvenancedPayload::Builder(
-                                    BlockProposalContents::Payload {
-                                        payload: relay.data.message.header,
-                                        block_value: relay.data.message.value,
-                                        _phantom: PhantomData::default(),
-                                    },
-                                )),
-                                // If the payload is valid then use it. The local EE failed
-                                // to produce a payload so we have no alternative.
-                                Err(e) if !e.payload_invalid() => Ok(ProvenancedPayload::Builder(
-                                    BlockProposalContents::Payload {
-                                        payload: relay.data.message.header,
-                                        block_value: relay.data.message.value,
-                                        _phantom: PhantomData::default(),
-                                    },
-                                )),
-                                Err(reason) => {
-                                    metrics::inc_counter_vec(
-                                        &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                                        &[reason.as_ref().as_ref()],
-                                    );
-                                    crit!(
-                                        self.log(),
-                                        "Builder returned invalid payload";
-                                        "info" => "no local payload either - unable to propose block",
-                                        "reason" => %reason,
-                                        "relay_block_hash" => ?header.block_hash(),
-                                        "parent_hash" => ?parent_hash,
-                                    );
-                                    Err(Error::CannotProduceHeader)
-                                }
-                            }
-                        }
-                        (Err(relay_error), Err(local_error)) => {
-                            crit!(
-                                self.log(),
-                                "Unable to produce execution payload";
-                                "info" => "the local EL and builder both failed - unable to propose block",
-                                "relay_error" => ?relay_error,
-                                "local_error" => ?local_error,
-                                "parent_hash" => ?parent_hash,
+                                Some(relay.data.message.value),
                             );
-
-                            Err(Error::CannotProduceHeader)
+                            Ok(ProvenancedPayload::Builder(
+                                self.choose_builder_bid(
+                                    relay_index,
+                                    relay,
+                                    bids,
+                                    slot,
+                                    payload_attributes.suggested_fee_recipient(),
+                                )
+                                .await,
+                            ))
                         }
-                        (Ok(None), Err(local_error)) => {
+                        (None, Err(local_error)) => {
                             crit!(
                                 self.log(),
                                 "Unable to produce execution payload";
-                                "info" => "the local EL failed and the builder returned nothing - \
-                                    the block proposal will be missed",
+                                "info" => "the local EL failed and no builder returned a usable \
+                                    payload - the block proposal will be missed",
                                 "local_error" => ?local_error,
                                 "parent_hash" => ?parent_hash,
                             );
@@ -960,7 +1307,347 @@ impl<T: EthSpec> ExecutionLayer<T> {
             current_fork,
         )
         .await
-        .map(ProvenancedPayload::Local)
+        .map(|local| {
+            self.record_payload_values(
+                local.payload().block_hash(),
+                PayloadSource::Local,
+                Some(*local.block_value()),
+                None,
+            );
+            ProvenancedPayload::Local(local)
+        })
+    }
+
+    /// Log the values of the payloads which competed for a proposal and remember them, so that
+    /// they can be reported alongside the block's rewards.
+    fn record_payload_values(
+        &self,
+        block_hash: ExecutionBlockHash,
+        source: PayloadSource,
+        local_value: Option<Uint256>,
+        builder_value: Option<Uint256>,
+    ) {
+        let to_gwei = |value: Uint256| {
+            std::cmp::min(value / 1_000_000_000, Uint256::from(u64::MAX)).low_u64()
+        };
+        let values = PayloadValues {
+            source,
+            local_value: local_value.map(to_gwei),
+            builder_value: builder_value.map(to_gwei),
+            builder_boost_factor: self.inner.builder_boost_factor,
+        };
+
+        // Avoid recording values that we can't represent with our Prometheus library.
+        for (source_label, value) in [
+            (metrics::LOCAL, values.local_value),
+            (metrics::BUILDER, values.builder_value),
+        ] {
+            if let Some(value) = value.filter(|value| *value <= i64::MAX as u64) {
+                metrics::set_gauge_vec(
+                    &metrics::EXECUTION_LAYER_PAYLOAD_BIDS,
+                    &[source_label],
+                    value as i64,
+                );
+            }
+        }
+
+        info!(
+            self.log(),
+            "Chose execution payload";
+            "source" => ?source,
+            "local_value_gwei" => ?values.local_value,
+            "builder_value_gwei" => ?values.builder_value,
+            "builder_boost_factor" => values.builder_boost_factor,
+            "block_hash" => ?block_hash,
+        );
+
+        self.inner.payload_values.lock().put(block_hash, values);
+    }
+
+    /// Returns the values of the payloads which competed for inclusion in a block recently
+    /// produced by this node, keyed by the hash of the chosen payload.
+    pub fn payload_values(&self, block_hash: ExecutionBlockHash) -> Option<PayloadValues> {
+        self.inner.payload_values.lock().peek(&block_hash).cloned()
+    }
+
+    /// Feed the outcome of a header request to the builder circuit breaker.
+    fn record_builder_header<R, E>(
+        &self,
+        relay_index: usize,
+        slot: Slot,
+        result: &Result<R, E>,
+        duration: Duration,
+    ) {
+        let mut breaker = self.inner.builder_circuit_breaker.lock();
+        let outcome = breaker.header_outcome(result, duration);
+        let trigger = breaker.record_header(relay_index, slot, outcome);
+        drop(breaker);
+        self.log_circuit_breaker_trip(relay_index, slot, trigger);
+    }
+
+    /// Feed the outcome of a request to reveal a payload to the builder circuit breaker.
+    fn record_builder_reveal(&self, relay_index: usize, slot: Slot, success: bool) {
+        let trigger =
+            self.inner
+                .builder_circuit_breaker
+                .lock()
+                .record_reveal(relay_index, slot, success);
+        self.log_circuit_breaker_trip(relay_index, slot, trigger);
+    }
+
+    fn log_circuit_breaker_trip(
+        &self,
+        relay_index: usize,
+        slot: Slot,
+        trigger: Option<CircuitBreakerTrigger>,
+    ) {
+        if let Some(trigger) = trigger {
+            metrics::inc_counter_vec(
+                &metrics::EXECUTION_LAYER_BUILDER_CIRCUIT_BREAKER_TRIPS,
+                &[builder_circuit_breaker::trigger_label(trigger)],
+            );
+            warn!(
+                self.log(),
+                "Builder circuit breaker tripped";
+                "info" => "bids will not be requested from the relay until the cool-down ends",
+                "relay" => %self.builders()[relay_index].server(),
+                "trigger" => ?trigger,
+                "slot" => ?slot,
+            );
+        }
+    }
+
+    /// Returns the state of the builder circuit breaker at `current_slot`.
+    pub fn builder_circuit_breaker_status(
+        &self,
+        current_slot: Slot,
+    ) -> BuilderCircuitBreakerStatus {
+        let relay_names = self
+            .builders()
+            .iter()
+            .map(|builder| builder.server().to_string())
+            .collect::<Vec<_>>();
+        self.inner
+            .builder_circuit_breaker
+            .lock()
+            .status(current_slot, &relay_names)
+    }
+
+    /// Manually trip the builder circuit breaker for `relay`, or for all relays if `None`.
+    ///
+    /// The breaker remains tripped until it is reset.
+    pub fn trip_builder_circuit_breaker(&self, relay: Option<&str>) -> Result<(), Error> {
+        let relay_index = self.builder_index(relay)?;
+        self.inner.builder_circuit_breaker.lock().trip(relay_index);
+        warn!(
+            self.log(),
+            "Builder circuit breaker tripped manually";
+            "relay" => relay.unwrap_or("all"),
+        );
+        Ok(())
+    }
+
+    /// Reset the builder circuit breaker for `relay`, or for all relays if `None`.
+    pub fn reset_builder_circuit_breaker(&self, relay: Option<&str>) -> Result<(), Error> {
+        let relay_index = self.builder_index(relay)?;
+        self.inner.builder_circuit_breaker.lock().reset(relay_index);
+        info!(
+            self.log(),
+            "Builder circuit breaker reset";
+            "relay" => relay.unwrap_or("all"),
+        );
+        Ok(())
+    }
+
+    /// Returns the index of the builder whose redacted URL is `relay`.
+    fn builder_index(&self, relay: Option<&str>) -> Result<Option<usize>, Error> {
+        relay
+            .map(|relay| {
+                self.builders()
+                    .iter()
+                    .position(|builder| builder.server().to_string() == relay)
+                    .ok_or_else(|| Error::UnknownBuilder(relay.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Verify the bids returned by each builder and return the index of the relay which supplied
+    /// the best bid, the bid itself and the result of its verification. A record of every bid
+    /// received is also returned.
+    ///
+    /// Bids with an invalid payload are discarded. Of the remainder, bids which pass all
+    /// verification are preferred over those which do not (e.g. because they fall below the
+    /// profit threshold), then the bid with the highest value is chosen.
+    #[allow(clippy::type_complexity)]
+    fn select_builder_bid<Payload: AbstractExecPayload<T>>(
+        &self,
+        relay_results: Vec<(
+            usize,
+            (
+                Result<
+                    Option<ForkVersionedResponse<SignedBuilderBid<T, Payload>>>,
+                    builder_client::Error,
+                >,
+                Duration,
+            ),
+        )>,
+        parent_hash: ExecutionBlockHash,
+        payload_attributes: &PayloadAttributes,
+        local_block_number: Option<u64>,
+        current_fork: ForkName,
+        spec: &ChainSpec,
+    ) -> (
+        Option<(
+            usize,
+            ForkVersionedResponse<SignedBuilderBid<T, Payload>>,
+            Result<(), Box<InvalidBuilderPayload>>,
+        )>,
+        Vec<BuilderBidRecord>,
+    ) {
+        let mut candidates = vec![];
+        let mut records = vec![];
+
+        for (relay_index, (relay_result, relay_duration)) in relay_results {
+            let relay = self.builders()[relay_index].server();
+            match relay_result {
+                Err(e) => warn!(
+                    self.log(),
+                    "Builder error when requesting payload";
+                    "relay" => %relay,
+                    "relay_error" => ?e,
+                    "relay_response_ms" => relay_duration.as_millis(),
+                    "parent_hash" => ?parent_hash,
+                ),
+                Ok(None) => info!(
+                    self.log(),
+                    "Builder did not return a payload";
+                    "relay" => %relay,
+                    "relay_response_ms" => relay_duration.as_millis(),
+                    "parent_hash" => ?parent_hash,
+                ),
+                Ok(Some(bid)) => {
+                    let header = bid.data.message.header.to_execution_payload_header();
+                    records.push(BuilderBidRecord {
+                        relay: relay.to_string(),
+                        value: bid.data.message.value,
+                        block_hash: header.block_hash(),
+                        parent_hash: header.parent_hash(),
+                        fee_recipient: header.fee_recipient(),
+                        block_number: header.block_number(),
+                        gas_limit: header.gas_limit(),
+                        gas_used: header.gas_used(),
+                        timestamp: header.timestamp(),
+                    });
+
+                    let verification = verify_builder_bid(
+                        &bid,
+                        parent_hash,
+                        payload_attributes,
+                        local_block_number,
+                        self.inner.builder_profit_threshold,
+                        current_fork,
+                        spec,
+                    );
+
+                    match verification {
+                        Err(reason) if reason.payload_invalid() => {
+                            metrics::inc_counter_vec(
+                                &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
+                                &[reason.as_ref().as_ref()],
+                            );
+                            warn!(
+                                self.log(),
+                                "Builder returned invalid payload";
+                                "relay" => %relay,
+                                "reason" => %reason,
+                                "relay_block_hash" => ?bid.data.message.header.block_hash(),
+                                "parent_hash" => ?parent_hash,
+                            );
+                        }
+                        verification => {
+                            debug!(
+                                self.log(),
+                                "Received builder bid";
+                                "relay" => %relay,
+                                "relay_value" => %bid.data.message.value,
+                                "relay_block_hash" => ?bid.data.message.header.block_hash(),
+                                "relay_response_ms" => relay_duration.as_millis(),
+                            );
+                            candidates.push((relay_index, bid, verification));
+                        }
+                    }
+                }
+            }
+        }
+
+        let best = candidates
+            .into_iter()
+            .max_by_key(|(_, bid, verification)| (verification.is_ok(), bid.data.message.value));
+        (best, records)
+    }
+
+    /// Remember which relay supplied `bid` so the blinded block is only revealed to that relay and
+    /// its payload can be audited, and convert the bid into proposal contents.
+    async fn choose_builder_bid<Payload: AbstractExecPayload<T>>(
+        &self,
+        relay_index: usize,
+        bid: ForkVersionedResponse<SignedBuilderBid<T, Payload>>,
+        bids: Vec<BuilderBidRecord>,
+        slot: Slot,
+        proposer_fee_recipient: Address,
+    ) -> BlockProposalContents<T, Payload> {
+        let header = bid.data.message.header;
+        self.remember_builder_bids(
+            header.block_hash(),
+            Some(relay_index),
+            bids,
+            slot,
+            proposer_fee_recipient,
+        )
+        .await;
+
+        BlockProposalContents::Payload {
+            payload: header,
+            block_value: bid.data.message.value,
+            _phantom: PhantomData::default(),
+        }
+    }
+
+    /// Remember the bids received for a proposal of the payload with `block_hash`, so they can be
+    /// audited once the block is published.
+    ///
+    /// Nothing is remembered for a local payload if no relay returned a bid.
+    async fn remember_builder_bids(
+        &self,
+        block_hash: ExecutionBlockHash,
+        relay_index: Option<usize>,
+        bids: Vec<BuilderBidRecord>,
+        slot: Slot,
+        proposer_fee_recipient: Address,
+    ) {
+        if relay_index.is_none() && bids.is_empty() {
+            return;
+        }
+        self.inner.chosen_bids.lock().await.put(
+            block_hash,
+            ChosenBuilderBid {
+                relay_index,
+                slot,
+                proposer_fee_recipient,
+                bids,
+            },
+        );
+    }
+
+    /// Returns `true` if the payload with `block_hash` was supplied by a builder for a proposal by
+    /// this node.
+    pub async fn is_builder_payload(&self, block_hash: ExecutionBlockHash) -> bool {
+        self.inner
+            .chosen_bids
+            .lock()
+            .await
+            .peek(&block_hash)
+            .map_or(false, |chosen| chosen.relay_index.is_some())
     }
 
     /// Get a full payload without caching its result in the execution layer's payload cache.
@@ -1007,8 +1694,9 @@ impl<T: EthSpec> ExecutionLayer<T> {
         current_fork: ForkName,
         f: fn(&ExecutionLayer<T>, ExecutionPayloadRef<T>) -> Option<ExecutionPayload<T>>,
     ) -> Result<BlockProposalContents<T, Payload>, Error> {
-        self.engine()
-            .request(move |engine| async move {
+        let forkchoice_update_params = &forkchoice_update_params;
+        let requests = self.engines().iter().map(|engine| {
+            Box::pin(engine.request(move |engine| async move {
                 let payload_id = if let Some(id) = engine
                     .get_payload_id(&parent_hash, payload_attributes)
                     .await
@@ -1099,8 +1787,13 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     block_value,
                     _phantom: PhantomData::default(),
                 })
-            })
+            }))
+        });
+
+        // Use the payload from whichever engine produces one first.
+        select_ok(requests)
             .await
+            .map(|(contents, _remaining)| contents)
             .map_err(Box::new)
             .map_err(Error::EngineError)
     }
@@ -1109,14 +1802,15 @@ impl<T: EthSpec> ExecutionLayer<T> {
     ///
     /// ## Fallback Behaviour
     ///
-    /// The request will be broadcast to all nodes, simultaneously. It will await a response (or
-    /// failure) from all nodes and then return based on the first of these conditions which
-    /// returns true:
+    /// The request will be broadcast to all nodes, simultaneously. It will return as soon as any
+    /// node returns invalid or the execution quorum of nodes return valid, otherwise it will await
+    /// a response (or failure) from all nodes. The remaining nodes complete in the background and
+    /// an invalid response from one of them is logged. It returns based on the first of these
+    /// conditions which returns true:
     ///
-    /// - Error::ConsensusFailure if some nodes return valid and some return invalid
-    /// - Valid, if any nodes return valid.
     /// - Invalid, if any nodes return invalid.
-    /// - Syncing, if any nodes return syncing.
+    /// - Valid, if at least the execution quorum of nodes return valid.
+    /// - Syncing, if any nodes return a status.
     /// - An error, if all nodes return an error.
     pub async fn notify_new_payload(
         &self,
@@ -1135,19 +1829,31 @@ impl<T: EthSpec> ExecutionLayer<T> {
             "block_number" => execution_payload.block_number(),
         );
 
-        let result = self
-            .engine()
-            .request(|engine| engine.api.new_payload(execution_payload.clone()))
-            .await;
+        // The requests own their data so that the engines which have not responded by the time
+        // the outcome is known can complete in the background.
+        let block_hash = execution_payload.block_hash();
+        let requests = self.engines().iter().cloned().map(|engine| {
+            let execution_payload = execution_payload.clone();
+            let log = self.log().clone();
+            async move {
+                let result = engine
+                    .request(|engine| engine.api.new_payload(execution_payload))
+                    .await;
+
+                if let Ok(status) = &result {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
+                        &["new_payload", status.status.into()],
+                    );
+                }
 
-        if let Ok(status) = &result {
-            metrics::inc_counter_vec(
-                &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
-                &["new_payload", status.status.into()],
-            );
-        }
+                process_payload_status(block_hash, result, &log)
+            }
+        });
 
-        process_payload_status(execution_payload.block_hash(), result, self.log())
+        let quorum = self.execution_quorum().await;
+        await_payload_statuses(requests, quorum, self.executor(), self.log())
+            .await
             .map_err(Box::new)
             .map_err(Error::EngineError)
     }
@@ -1214,14 +1920,15 @@ impl<T: EthSpec> ExecutionLayer<T> {
     ///
     /// ## Fallback Behaviour
     ///
-    /// The request will be broadcast to all nodes, simultaneously. It will await a response (or
-    /// failure) from all nodes and then return based on the first of these conditions which
-    /// returns true:
+    /// The request will be broadcast to all nodes, simultaneously. It will return as soon as any
+    /// node returns invalid or the execution quorum of nodes return valid, otherwise it will await
+    /// a response (or failure) from all nodes. The remaining nodes complete in the background and
+    /// an invalid response from one of them is logged. It returns based on the first of these
+    /// conditions which returns true:
     ///
-    /// - Error::ConsensusFailure if some nodes return valid and some return invalid
-    /// - Valid, if any nodes return valid.
     /// - Invalid, if any nodes return invalid.
-    /// - Syncing, if any nodes return syncing.
+    /// - Valid, if at least the execution quorum of nodes return valid.
+    /// - Syncing, if any nodes return a status.
     /// - An error, if all nodes return an error.
     pub async fn notify_forkchoice_updated(
         &self,
@@ -1275,33 +1982,41 @@ impl<T: EthSpec> ExecutionLayer<T> {
             finalized_block_hash,
         };
 
-        self.engine()
-            .set_latest_forkchoice_state(forkchoice_state)
-            .await;
-
-        let result = self
-            .engine()
-            .request(|engine| async move {
-                engine
-                    .notify_forkchoice_updated(forkchoice_state, payload_attributes, self.log())
-                    .await
-            })
-            .await;
+        // The engines which have not responded by the time the outcome is known complete in the
+        // background. An engine which misses an update will receive the next forkchoice update,
+        // or the latest forkchoice state once it comes back online.
+        let requests = self.engines().iter().cloned().map(|engine| {
+            let payload_attributes = payload_attributes.clone();
+            let log = self.log().clone();
+            async move {
+                engine.set_latest_forkchoice_state(forkchoice_state).await;
+
+                let result = engine
+                    .request(|engine| {
+                        engine.notify_forkchoice_updated(forkchoice_state, payload_attributes, &log)
+                    })
+                    .await;
+
+                if let Ok(status) = &result {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
+                        &["forkchoice_updated", status.payload_status.status.into()],
+                    );
+                }
 
-        if let Ok(status) = &result {
-            metrics::inc_counter_vec(
-                &metrics::EXECUTION_LAYER_PAYLOAD_STATUS,
-                &["forkchoice_updated", status.payload_status.status.into()],
-            );
-        }
+                process_payload_status(
+                    head_block_hash,
+                    result.map(|response| response.payload_status),
+                    &log,
+                )
+            }
+        });
 
-        process_payload_status(
-            head_block_hash,
-            result.map(|response| response.payload_status),
-            self.log(),
-        )
-        .map_err(Box::new)
-        .map_err(Error::EngineError)
+        let quorum = self.execution_quorum().await;
+        await_payload_statuses(requests, quorum, self.executor(), self.log())
+            .await
+            .map_err(Box::new)
+            .map_err(Error::EngineError)
     }
 
     pub async fn exchange_transition_configuration(&self, spec: &ChainSpec) -> Result<(), Error> {
@@ -1312,8 +2027,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
         };
 
         let result = self
-            .engine()
-            .request(|engine| engine.api.exchange_transition_configuration_v1(local))
+            .request_with_fallback(|engine| engine.api.exchange_transition_configuration_v1(local))
             .await;
 
         match result {
@@ -1364,8 +2078,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
         &self,
         age_limit: Option<Duration>,
     ) -> Result<EngineCapabilities, Error> {
-        self.engine()
-            .request(|engine| engine.get_engine_capabilities(age_limit))
+        self.request_with_fallback(|engine| engine.get_engine_capabilities(age_limit))
             .await
             .map_err(Box::new)
             .map_err(Error::EngineError)
@@ -1389,8 +2102,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
         );
 
         let hash_opt = self
-            .engine()
-            .request(|engine| async move {
+            .request_with_fallback(|engine| async move {
                 let terminal_block_hash = spec.terminal_block_hash;
                 if terminal_block_hash != ExecutionBlockHash::zero() {
                     if self
@@ -1492,14 +2204,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
     ///
     /// ## Fallback Behaviour
     ///
-    /// The request will be broadcast to all nodes, simultaneously. It will await a response (or
-    /// failure) from all nodes and then return based on the first of these conditions which
-    /// returns true:
-    ///
-    /// - Terminal, if any node indicates it is terminal.
-    /// - Not terminal, if any node indicates it is non-terminal.
-    /// - Block not found, if any node cannot find the block.
-    /// - An error, if all nodes return an error.
+    /// The request is sent to each node in turn until one responds. An error is returned if all
+    /// nodes return an error.
     ///
     /// ## Specification
     ///
@@ -1516,22 +2222,19 @@ impl<T: EthSpec> ExecutionLayer<T> {
             &[metrics::IS_VALID_TERMINAL_POW_BLOCK_HASH],
         );
 
-        self.engine()
-            .request(|engine| async move {
-                if let Some(pow_block) = self.get_pow_block(engine, block_hash).await? {
-                    if let Some(pow_parent) =
-                        self.get_pow_block(engine, pow_block.parent_hash).await?
-                    {
-                        return Ok(Some(
-                            self.is_valid_terminal_pow_block(pow_block, pow_parent, spec),
-                        ));
-                    }
+        self.request_with_fallback(|engine| async move {
+            if let Some(pow_block) = self.get_pow_block(engine, block_hash).await? {
+                if let Some(pow_parent) = self.get_pow_block(engine, pow_block.parent_hash).await? {
+                    return Ok(Some(
+                        self.is_valid_terminal_pow_block(pow_block, pow_parent, spec),
+                    ));
                 }
-                Ok(None)
-            })
-            .await
-            .map_err(Box::new)
-            .map_err(Error::EngineError)
+            }
+            Ok(None)
+        })
+        .await
+        .map_err(Box::new)
+        .map_err(Error::EngineError)
     }
 
     /// This function should remain internal.
@@ -1576,14 +2279,13 @@ impl<T: EthSpec> ExecutionLayer<T> {
         hash: ExecutionBlockHash,
         fork: ForkName,
     ) -> Result<Option<ExecutionPayload<T>>, Error> {
-        self.engine()
-            .request(|engine| async move {
-                self.get_payload_by_block_hash_from_engine(engine, hash, fork)
-                    .await
-            })
-            .await
-            .map_err(Box::new)
-            .map_err(Error::EngineError)
+        self.request_with_fallback(|engine| async move {
+            self.get_payload_by_block_hash_from_engine(engine, hash, fork)
+                .await
+        })
+        .await
+        .map_err(Box::new)
+        .map_err(Error::EngineError)
     }
 
     async fn get_payload_by_block_hash_from_engine(
@@ -1686,58 +2388,202 @@ impl<T: EthSpec> ExecutionLayer<T> {
             "root" => ?block_root,
         );
 
-        if let Some(builder) = self.builder() {
-            let (payload_result, duration) =
-                timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
-                    builder
-                        .post_builder_blinded_blocks(block)
-                        .await
-                        .map_err(Error::Builder)
-                        .map(|d| d.data)
-                })
-                .await;
+        if !self.has_builder() {
+            return Err(Error::NoPayloadBuilder);
+        }
 
-            match &payload_result {
-                Ok(payload) => {
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
-                        &[metrics::SUCCESS],
-                    );
-                    info!(
-                        self.log(),
-                        "Builder successfully revealed payload";
-                        "relay_response_ms" => duration.as_millis(),
-                        "block_root" => ?block_root,
-                        "fee_recipient" => ?payload.fee_recipient(),
-                        "block_hash" => ?payload.block_hash(),
-                        "parent_hash" => ?payload.parent_hash()
-                    )
-                }
-                Err(e) => {
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
-                        &[metrics::FAILURE],
-                    );
-                    warn!(
-                        self.log(),
-                        "Builder failed to reveal payload";
-                        "info" => "this is common behaviour for some builders and may not indicate an issue",
-                        "error" => ?e,
-                        "relay_response_ms" => duration.as_millis(),
-                        "block_root" => ?block_root,
-                        "parent_hash" => ?block
-                            .message()
-                            .execution_payload()
-                            .map(|payload| format!("{}", payload.parent_hash()))
-                            .unwrap_or_else(|_| "unknown".to_string())
-                    )
-                }
+        // Only reveal the block to the relay which supplied the bid. If we don't know which relay
+        // that was (e.g. the bid was chosen before a restart) and there is more than one relay,
+        // refuse to reveal it rather than sharing the block with relays which didn't build it.
+        let block_hash = block
+            .message()
+            .execution_payload()
+            .map(|payload| payload.block_hash())
+            .ok();
+        let chosen_relay = match block_hash {
+            Some(block_hash) => self
+                .inner
+                .chosen_bids
+                .lock()
+                .await
+                .get(&block_hash)
+                .and_then(|chosen| chosen.relay_index),
+            None => None,
+        };
+        let relay_index = match chosen_relay {
+            Some(relay_index) => relay_index,
+            None if self.builders().len() == 1 => 0,
+            None => {
+                crit!(
+                    self.log(),
+                    "Unknown relay for blinded block";
+                    "info" => "the block will not be revealed to any relay",
+                    "block_root" => ?block_root,
+                    "block_hash" => ?block_hash,
+                );
+                return Err(Error::UnknownBuilderRelay(block_hash));
             }
+        };
 
-            payload_result
-        } else {
-            Err(Error::NoPayloadBuilder)
+        let builder = &self.builders()[relay_index];
+        let (result, duration) = timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
+            builder
+                .post_builder_blinded_blocks(block)
+                .await
+                .map_err(Error::Builder)
+                .map(|d| d.data)
+        })
+        .await;
+
+        match &result {
+            Ok(payload) => {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
+                    &[metrics::SUCCESS],
+                );
+                info!(
+                    self.log(),
+                    "Builder successfully revealed payload";
+                    "relay" => %builder.server(),
+                    "relay_response_ms" => duration.as_millis(),
+                    "block_root" => ?block_root,
+                    "fee_recipient" => ?payload.fee_recipient(),
+                    "block_hash" => ?payload.block_hash(),
+                    "parent_hash" => ?payload.parent_hash()
+                )
+            }
+            Err(e) => {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_REVEAL_PAYLOAD_OUTCOME,
+                    &[metrics::FAILURE],
+                );
+                warn!(
+                    self.log(),
+                    "Builder failed to reveal payload";
+                    "info" => "this is common behaviour for some builders and may not indicate an issue",
+                    "relay" => %builder.server(),
+                    "error" => ?e,
+                    "relay_response_ms" => duration.as_millis(),
+                    "block_root" => ?block_root,
+                    "parent_hash" => ?block
+                        .message()
+                        .execution_payload()
+                        .map(|payload| format!("{}", payload.parent_hash()))
+                        .unwrap_or_else(|_| "unknown".to_string())
+                )
+            }
+        }
+
+        // Only relays known to have supplied the bid are held responsible for revealing it.
+        if chosen_relay.is_some() {
+            self.record_builder_reveal(relay_index, block.slot(), result.is_ok());
         }
+
+        result
+    }
+
+    /// Check a payload revealed by a builder against the bid it was chosen for, using the
+    /// execution block produced from it.
+    ///
+    /// Verifies that the proposer's fee recipient received at least the bid value and that the
+    /// execution block contains the revealed transactions. This should only be called once the
+    /// block has been imported by the execution layer.
+    ///
+    /// If the local payload was chosen over the bids received for the proposal, the bids are
+    /// returned without checking the payload.
+    ///
+    /// Returns `None` if no bids were received for the payload's proposal by this node, or if the
+    /// bids have since been forgotten.
+    pub async fn audit_builder_payload(
+        &self,
+        block_root: Hash256,
+        payload: &ExecutionPayload<T>,
+    ) -> Option<BuilderAudit> {
+        let block_hash = payload.block_hash();
+        let chosen = self.inner.chosen_bids.lock().await.pop(&block_hash)?;
+        let revealed_transactions = payload.transactions().len() as u64;
+        if chosen.relay_index.is_none() {
+            return Some(BuilderAudit {
+                block_root,
+                slot: chosen.slot,
+                proposer_fee_recipient: chosen.proposer_fee_recipient,
+                bid: None,
+                bids: chosen.bids,
+                revealed_transactions,
+                execution_transactions: 0,
+                delivered_value: Uint256::zero(),
+                outcome: BuilderAuditOutcome::LocalPayload,
+                error: None,
+            });
+        }
+        let bid = chosen
+            .bids
+            .iter()
+            .find(|bid| bid.block_hash == block_hash)?
+            .clone();
+        let fork = match payload {
+            ExecutionPayload::Merge(_) => ForkName::Merge,
+            ExecutionPayload::Capella(_) => ForkName::Capella,
+        };
+
+        let execution_transactions = self
+            .get_payload_by_block_hash(block_hash, fork)
+            .await
+            .and_then(|execution_payload| {
+                execution_payload
+                    .map(|execution_payload| execution_payload.transactions().len() as u64)
+                    .ok_or(Error::MissingExecutionBlock(block_hash))
+            });
+        let proposer_fee_recipient = chosen.proposer_fee_recipient;
+        let parent_hash = payload.parent_hash();
+        let delivered_value = self
+            .request_with_fallback(|engine| async move {
+                let before = engine
+                    .api
+                    .get_balance(proposer_fee_recipient, parent_hash)
+                    .await?;
+                let after = engine
+                    .api
+                    .get_balance(proposer_fee_recipient, block_hash)
+                    .await?;
+                Ok(after.saturating_sub(before))
+            })
+            .await
+            .map_err(Box::new)
+            .map_err(Error::EngineError);
+
+        let (execution_transactions, delivered_value, outcome, error) =
+            match (execution_transactions, delivered_value) {
+                (Ok(execution_transactions), Ok(delivered_value)) => {
+                    let outcome = if execution_transactions != revealed_transactions {
+                        BuilderAuditOutcome::TransactionCountMismatch
+                    } else if delivered_value < bid.value {
+                        BuilderAuditOutcome::UnderDelivered
+                    } else {
+                        BuilderAuditOutcome::Delivered
+                    };
+                    (execution_transactions, delivered_value, outcome, None)
+                }
+                (Err(e), _) | (_, Err(e)) => (
+                    0,
+                    Uint256::zero(),
+                    BuilderAuditOutcome::Unavailable,
+                    Some(format!("{:?}", e)),
+                ),
+            };
+
+        Some(BuilderAudit {
+            block_root,
+            slot: chosen.slot,
+            proposer_fee_recipient,
+            bid: Some(bid),
+            bids: chosen.bids,
+            revealed_transactions,
+            execution_transactions,
+            delivered_value,
+            outcome,
+            error,
+        })
     }
 }
 
@@ -1931,11 +2777,123 @@ async fn timed_future<F: Future<Output = T>, T>(metric: &str, future: F) -> (T,
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::test_utils::MockExecutionLayer as GenericMockExecutionLayer;
+    use crate::engine_api::http::{ENGINE_FORKCHOICE_UPDATED_TIMEOUT, ENGINE_NEW_PAYLOAD_TIMEOUT};
+    use crate::test_utils::{
+        MockBuilder, MockExecutionLayer as GenericMockExecutionLayer, MockServer, TestingBuilder,
+        DEFAULT_JWT_SECRET, DEFAULT_TERMINAL_BLOCK, DEFAULT_TERMINAL_DIFFICULTY,
+    };
     use task_executor::test_utils::TestRuntime;
-    use types::MainnetEthSpec;
+    use tempfile::NamedTempFile;
+    use tokio::net::TcpStream;
+    use tokio::time::timeout;
+    use types::{
+        BeaconBlock, BeaconBlockMerge, EmptyBlock, ExecutionPayloadHeaderMerge, Keypair,
+        MainnetEthSpec, SignedRoot, SyncAggregate,
+    };
+
+    type E = MainnetEthSpec;
+    type MockExecutionLayer = GenericMockExecutionLayer<E>;
+
+    /// Start a mock builder for each relay and return an execution layer which requests bids from
+    /// all of them, alongside a handle to each builder.
+    async fn execution_layer_with_relays(
+        mock: &MockExecutionLayer,
+        relays: usize,
+    ) -> (ExecutionLayer<E>, Vec<MockBuilder<E>>) {
+        let mock_el_url = SensitiveUrl::parse(mock.server.url().as_str()).unwrap();
+        let mut builder_urls = vec![];
+        let mut builders = vec![];
+        for _ in 0..relays {
+            let builder_port = unused_port::unused_tcp4_port().unwrap();
+            let builder_url =
+                SensitiveUrl::parse(&format!("http://127.0.0.1:{builder_port}")).unwrap();
+            let beacon_port = unused_port::unused_tcp4_port().unwrap();
+            let beacon_url =
+                SensitiveUrl::parse(&format!("http://127.0.0.1:{beacon_port}")).unwrap();
+            let testing_builder = TestingBuilder::new(
+                mock_el_url.clone(),
+                builder_url.clone(),
+                beacon_url,
+                mock.spec.clone(),
+                mock.executor.clone(),
+            );
+            builders.push(testing_builder.builder.clone());
+            mock.executor.spawn(
+                async move { testing_builder.run().await },
+                "mock_builder_server",
+            );
+            while TcpStream::connect(("127.0.0.1", builder_port))
+                .await
+                .is_err()
+            {
+                sleep(Duration::from_millis(10)).await;
+            }
+            builder_urls.push(builder_url);
+        }
+
+        let jwt_file = NamedTempFile::new().unwrap();
+        std::fs::write(jwt_file.path(), hex::encode(DEFAULT_JWT_SECRET)).unwrap();
+        let config = Config {
+            execution_endpoints: vec![mock_el_url],
+            builder_urls,
+            secret_files: vec![jwt_file.path().into()],
+            ..Default::default()
+        };
+        let el =
+            ExecutionLayer::from_config(config, mock.executor.clone(), mock.executor.log().clone())
+                .unwrap();
+        (el, builders)
+    }
 
-    type MockExecutionLayer = GenericMockExecutionLayer<MainnetEthSpec>;
+    /// A Merge bid for `header`, signed by a new builder key.
+    fn builder_bid(
+        header: ExecutionPayloadHeaderMerge<E>,
+        value: u64,
+        spec: &ChainSpec,
+    ) -> ForkVersionedResponse<SignedBuilderBid<E, BlindedPayload<E>>> {
+        let keypair = Keypair::random();
+        let mut bid: ForkVersionedResponse<SignedBuilderBid<E, BlindedPayload<E>>> =
+            serde_json::from_value(serde_json::json!({
+                "version": ForkName::Merge,
+                "data": {
+                    "message": {
+                        "header": header,
+                        "value": value.to_string(),
+                        "pubkey": keypair.pk.compress(),
+                    },
+                    "signature": Signature::empty(),
+                },
+            }))
+            .unwrap();
+        let signing_root = bid.data.message.signing_root(spec.get_builder_domain());
+        bid.data.signature = keypair.sk.sign(signing_root);
+        bid
+    }
+
+    fn bid_header(block_hash: ExecutionBlockHash) -> ExecutionPayloadHeaderMerge<E> {
+        ExecutionPayloadHeaderMerge {
+            parent_hash: ExecutionBlockHash::repeat_byte(1),
+            prev_randao: Hash256::repeat_byte(2),
+            timestamp: 42,
+            block_hash,
+            ..Default::default()
+        }
+    }
+
+    fn blinded_block(
+        block_hash: ExecutionBlockHash,
+        spec: &ChainSpec,
+    ) -> SignedBeaconBlock<E, BlindedPayload<E>> {
+        let mut block = BeaconBlockMerge::<E, BlindedPayload<E>>::empty(spec);
+        block.body.randao_reveal = Signature::infinity().unwrap();
+        block.body.sync_aggregate = SyncAggregate::new();
+        block
+            .body
+            .execution_payload
+            .execution_payload_header
+            .block_hash = block_hash;
+        SignedBeaconBlock::from_block(BeaconBlock::Merge(block), Signature::infinity().unwrap())
+    }
 
     #[tokio::test]
     async fn produce_three_valid_pos_execution_blocks() {
@@ -1991,6 +2949,33 @@ mod test {
             .await;
     }
 
+    #[tokio::test]
+    async fn engine_behind_head_is_degraded() {
+        let runtime = TestRuntime::default();
+        MockExecutionLayer::default_params(runtime.task_executor.clone())
+            .move_to_terminal_block()
+            .with_terminal_block(|_, el, terminal_block| async move {
+                el.engine().upcheck().await;
+                let latest_number = terminal_block.unwrap().block_number;
+
+                let health = el.health(Some(latest_number)).await;
+                assert!(!health.degraded);
+                assert_eq!(health.engines[0].state, ExecutionEngineState::Synced);
+                assert_eq!(health.engines[0].head_lag, Some(0));
+                assert!(health.engines[0]
+                    .latencies
+                    .iter()
+                    .any(|latency| latency.method == http::ETH_SYNCING));
+
+                let health = el
+                    .health(Some(latest_number + ENGINE_HEAD_LAG_TOLERANCE + 1))
+                    .await;
+                assert!(health.degraded);
+                assert!(health.engines[0].degraded);
+            })
+            .await;
+    }
+
     #[tokio::test]
     async fn rejects_terminal_block_with_equal_timestamp() {
         let runtime = TestRuntime::default();
@@ -2073,6 +3058,331 @@ mod test {
             })
             .await;
     }
+
+    #[tokio::test]
+    async fn select_builder_bid_prefers_most_valuable_valid_bid() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone());
+        let (el, _builders) = execution_layer_with_relays(&mock, 4).await;
+        let spec = &mock.spec;
+
+        let header = bid_header(ExecutionBlockHash::repeat_byte(11));
+        let payload_attributes = PayloadAttributes::new(
+            header.timestamp,
+            header.prev_randao,
+            Address::repeat_byte(3),
+            None,
+        );
+        let mut invalid_header = bid_header(ExecutionBlockHash::repeat_byte(12));
+        invalid_header.parent_hash = ExecutionBlockHash::repeat_byte(99);
+        let relay_results = vec![
+            (
+                0,
+                (
+                    Ok(Some(builder_bid(
+                        bid_header(ExecutionBlockHash::repeat_byte(10)),
+                        2,
+                        spec,
+                    ))),
+                    Duration::ZERO,
+                ),
+            ),
+            (1, (Ok(Some(builder_bid(header, 3, spec))), Duration::ZERO)),
+            (
+                2,
+                (
+                    Ok(Some(builder_bid(invalid_header, 10, spec))),
+                    Duration::ZERO,
+                ),
+            ),
+            (3, (Ok(None), Duration::ZERO)),
+        ];
+
+        let (best, records) = el.select_builder_bid(
+            relay_results,
+            header.parent_hash,
+            &payload_attributes,
+            None,
+            ForkName::Merge,
+            spec,
+        );
+        let (relay_index, bid, verification) = best.unwrap();
+        assert_eq!(relay_index, 1);
+        assert_eq!(bid.data.message.value, Uint256::from(3));
+        assert!(verification.is_ok());
+
+        // Bids with an invalid payload are recorded but never chosen.
+        assert_eq!(records.len(), 3);
+        assert!(records
+            .iter()
+            .any(|record| record.block_hash == ExecutionBlockHash::repeat_byte(12)));
+    }
+
+    #[tokio::test]
+    async fn choose_builder_bid_remembers_relay() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone());
+        let (el, _builders) = execution_layer_with_relays(&mock, 2).await;
+
+        let block_hash = ExecutionBlockHash::repeat_byte(11);
+        let contents = el
+            .choose_builder_bid(
+                1,
+                builder_bid(bid_header(block_hash), 3, &mock.spec),
+                vec![],
+                Slot::new(1),
+                Address::repeat_byte(3),
+            )
+            .await;
+        assert_eq!(contents.payload().block_hash(), block_hash);
+        assert_eq!(*contents.block_value(), Uint256::from(3));
+
+        let chosen_bids = el.inner.chosen_bids.lock().await;
+        let chosen = chosen_bids.peek(&block_hash).unwrap();
+        assert_eq!(chosen.relay_index, Some(1));
+        assert_eq!(chosen.slot, Slot::new(1));
+    }
+
+    #[tokio::test]
+    async fn bids_are_audited_when_local_payload_chosen() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone());
+        let (el, _builders) = execution_layer_with_relays(&mock, 1).await;
+
+        let block_hash = ExecutionBlockHash::repeat_byte(11);
+        let payload = ExecutionPayload::Merge(ExecutionPayloadMerge {
+            block_hash,
+            ..Default::default()
+        });
+        let bid = BuilderBidRecord {
+            relay: el.builders()[0].server().to_string(),
+            value: Uint256::from(3),
+            block_hash: ExecutionBlockHash::repeat_byte(12),
+            parent_hash: ExecutionBlockHash::repeat_byte(1),
+            fee_recipient: Address::repeat_byte(4),
+            block_number: 1,
+            gas_limit: 30_000_000,
+            gas_used: 0,
+            timestamp: 42,
+        };
+
+        // Nothing is remembered for a local payload if no bids were received.
+        el.remember_builder_bids(
+            block_hash,
+            None,
+            vec![],
+            Slot::new(1),
+            Address::repeat_byte(3),
+        )
+        .await;
+        assert!(el
+            .audit_builder_payload(Hash256::zero(), &payload)
+            .await
+            .is_none());
+
+        el.remember_builder_bids(
+            block_hash,
+            None,
+            vec![bid.clone()],
+            Slot::new(1),
+            Address::repeat_byte(3),
+        )
+        .await;
+        assert!(!el.is_builder_payload(block_hash).await);
+
+        let audit = el
+            .audit_builder_payload(Hash256::zero(), &payload)
+            .await
+            .unwrap();
+        assert_eq!(audit.outcome, BuilderAuditOutcome::LocalPayload);
+        assert_eq!(audit.slot, Slot::new(1));
+        assert_eq!(audit.bid, None);
+        assert_eq!(audit.bids, vec![bid]);
+    }
+
+    #[tokio::test]
+    async fn blinded_block_only_revealed_to_chosen_relay() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone());
+        let (el, builders) = execution_layer_with_relays(&mock, 2).await;
+
+        let block_hash = ExecutionBlockHash::repeat_byte(11);
+        let block = blinded_block(block_hash, &mock.spec);
+
+        // The relay which supplied the bid is unknown, so the block isn't sent to either relay.
+        assert!(matches!(
+            el.propose_blinded_beacon_block(Hash256::zero(), &block).await,
+            Err(Error::UnknownBuilderRelay(Some(hash))) if hash == block_hash
+        ));
+        assert_eq!(builders[0].reveal_requests(), 0);
+        assert_eq!(builders[1].reveal_requests(), 0);
+
+        el.choose_builder_bid(
+            1,
+            builder_bid(bid_header(block_hash), 3, &mock.spec),
+            vec![],
+            Slot::new(0),
+            Address::repeat_byte(3),
+        )
+        .await;
+
+        // The mock builder didn't build the payload so it can't reveal it, but only the relay
+        // which supplied the bid is asked to.
+        assert!(el
+            .propose_blinded_beacon_block(Hash256::zero(), &block)
+            .await
+            .is_err());
+        assert_eq!(builders[0].reveal_requests(), 0);
+        assert_eq!(builders[1].reveal_requests(), 1);
+    }
+
+    /// Start a mock execution engine for each of `engines` and return an execution layer using
+    /// them plus an endpoint which accepts connections but never responds. The listener must be
+    /// kept alive for the endpoint to hang.
+    ///
+    /// The mock engines are upchecked so that they are online, whilst the hung engine is offline.
+    async fn execution_layer_with_hung_engine(
+        executor: &TaskExecutor,
+        engines: usize,
+    ) -> (ExecutionLayer<E>, Vec<MockServer<E>>, std::net::TcpListener) {
+        let handle = executor.handle().unwrap();
+        let servers = (0..engines)
+            .map(|_| {
+                MockServer::new(
+                    &handle,
+                    JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap(),
+                    DEFAULT_TERMINAL_DIFFICULTY.into(),
+                    DEFAULT_TERMINAL_BLOCK,
+                    ExecutionBlockHash::zero(),
+                    None,
+                )
+            })
+            .collect::<Vec<_>>();
+        let hung = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+
+        let mut execution_endpoints = servers
+            .iter()
+            .map(|server| SensitiveUrl::parse(&server.url()).unwrap())
+            .collect::<Vec<_>>();
+        execution_endpoints
+            .push(SensitiveUrl::parse(&format!("http://{}", hung.local_addr().unwrap())).unwrap());
+
+        let jwt_file = NamedTempFile::new().unwrap();
+        std::fs::write(jwt_file.path(), hex::encode(DEFAULT_JWT_SECRET)).unwrap();
+        let config = Config {
+            execution_endpoints,
+            secret_files: vec![jwt_file.path().into()],
+            ..Default::default()
+        };
+        let el =
+            ExecutionLayer::from_config(config, executor.clone(), executor.log().clone()).unwrap();
+        for engine in &el.engines()[..engines] {
+            engine.upcheck().await;
+        }
+        (el, servers, hung)
+    }
+
+    fn merge_payload(block_hash: ExecutionBlockHash) -> ExecutionPayload<E> {
+        ExecutionPayload::Merge(ExecutionPayloadMerge {
+            block_hash,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn engine_quorum_does_not_wait_for_hung_engine() {
+        let runtime = TestRuntime::default();
+        // The hung engine is offline, so the default quorum is both of the other engines.
+        let (el, servers, _hung) =
+            execution_layer_with_hung_engine(&runtime.task_executor, 2).await;
+        for server in &servers {
+            server.all_payloads_valid();
+        }
+        let block_hash = ExecutionBlockHash::repeat_byte(1);
+
+        let status = timeout(
+            ENGINE_NEW_PAYLOAD_TIMEOUT / 2,
+            el.notify_new_payload(&merge_payload(block_hash)),
+        )
+        .await
+        .expect("should not wait for the hung engine")
+        .unwrap();
+        assert_eq!(status, PayloadStatus::Valid);
+
+        let status = timeout(
+            ENGINE_FORKCHOICE_UPDATED_TIMEOUT / 2,
+            el.notify_forkchoice_updated(
+                block_hash,
+                ExecutionBlockHash::zero(),
+                ExecutionBlockHash::zero(),
+                Slot::new(1),
+                Hash256::zero(),
+            ),
+        )
+        .await
+        .expect("should not wait for the hung engine")
+        .unwrap();
+        assert_eq!(status, PayloadStatus::Valid);
+    }
+
+    #[tokio::test]
+    async fn engine_quorum_ignores_offline_engines() {
+        let runtime = TestRuntime::default();
+        let (el, servers, _hung) =
+            execution_layer_with_hung_engine(&runtime.task_executor, 1).await;
+        servers[0].all_payloads_valid();
+
+        // With one of two engines offline, the payload can still be verified by the other.
+        let status = timeout(
+            ENGINE_NEW_PAYLOAD_TIMEOUT / 2,
+            el.notify_new_payload(&merge_payload(ExecutionBlockHash::repeat_byte(1))),
+        )
+        .await
+        .expect("should not wait for the hung engine")
+        .unwrap();
+        assert_eq!(status, PayloadStatus::Valid);
+    }
+
+    #[tokio::test]
+    async fn engine_invalid_does_not_wait_for_hung_engine() {
+        let runtime = TestRuntime::default();
+        let (el, servers, _hung) =
+            execution_layer_with_hung_engine(&runtime.task_executor, 2).await;
+        let latest_valid_hash = ExecutionBlockHash::repeat_byte(2);
+        servers[0].all_payloads_valid();
+        servers[1].all_payloads_invalid_on_new_payload(latest_valid_hash);
+
+        let status = timeout(
+            ENGINE_NEW_PAYLOAD_TIMEOUT / 2,
+            el.notify_new_payload(&merge_payload(ExecutionBlockHash::repeat_byte(1))),
+        )
+        .await
+        .expect("should not wait for the hung engine")
+        .unwrap();
+        assert!(matches!(
+            status,
+            PayloadStatus::Invalid {
+                latest_valid_hash: Some(hash),
+                ..
+            } if hash == latest_valid_hash
+        ));
+    }
+
+    #[tokio::test]
+    async fn engine_without_quorum_is_optimistic() {
+        let runtime = TestRuntime::default();
+        let (el, servers, _hung) =
+            execution_layer_with_hung_engine(&runtime.task_executor, 2).await;
+        servers[0].all_payloads_valid();
+        servers[1].all_payloads_syncing(false);
+
+        // Quorum can't be reached without the hung engine, so it is awaited until it times out.
+        let status = el
+            .notify_new_payload(&merge_payload(ExecutionBlockHash::repeat_byte(1)))
+            .await
+            .unwrap();
+        assert_eq!(status, PayloadStatus::Syncing);
+    }
 }
 
 fn noop<T: EthSpec>(
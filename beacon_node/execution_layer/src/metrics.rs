@@ -80,4 +80,13 @@ lazy_static::lazy_static! {
         "The gwei bid value of payloads received by local EEs or builders. Only shows values up to i64::max_value.",
         &["source"]
     );
+    pub static ref EXECUTION_LAYER_BUILDER_CIRCUIT_BREAKER_TRIPS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_builder_circuit_breaker_trips",
+        "The number of times the builder circuit breaker tripped for a relay, by trigger",
+        &["trigger"]
+    );
+    pub static ref EXECUTION_LAYER_RECORDING_DROPPED_EXCHANGES: Result<IntCounter> = try_create_int_counter(
+        "execution_layer_recording_dropped_exchanges_total",
+        "The number of engine API exchanges not recorded because the recording writer fell behind"
+    );
 }
@@ -1,6 +1,9 @@
 use crate::engine_api::{Error as ApiError, PayloadStatusV1, PayloadStatusV1Status};
 use crate::engines::EngineError;
-use slog::{warn, Logger};
+use futures::stream::{FuturesUnordered, StreamExt};
+use slog::{crit, debug, warn, Logger};
+use std::future::Future;
+use task_executor::TaskExecutor;
 use types::ExecutionBlockHash;
 
 /// Provides a simpler, easier to parse version of `PayloadStatusV1` for upstream users.
@@ -108,3 +111,285 @@ pub fn process_payload_status(
         },
     }
 }
+
+/// Combines the statuses returned by several execution engines for the same payload.
+///
+/// The policy is conservative:
+///
+/// - Invalid, if any engine returns invalid.
+/// - Valid, if at least `quorum` engines return valid.
+/// - Syncing, if any engine returns a status, so that the block may be imported optimistically.
+/// - The first error, if all engines return an error.
+pub fn combine_payload_statuses(
+    results: Vec<Result<PayloadStatus, EngineError>>,
+    quorum: usize,
+    log: &Logger,
+) -> Result<PayloadStatus, EngineError> {
+    let mut valid = 0;
+    let mut statuses = 0;
+    let mut first_error = None;
+    let mut invalid = None;
+
+    for result in results {
+        match result {
+            Ok(PayloadStatus::Valid) => {
+                valid += 1;
+                statuses += 1;
+            }
+            Ok(
+                status @ (PayloadStatus::Invalid { .. } | PayloadStatus::InvalidBlockHash { .. }),
+            ) => {
+                statuses += 1;
+                invalid.get_or_insert(status);
+            }
+            Ok(PayloadStatus::Syncing | PayloadStatus::Accepted) => statuses += 1,
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+
+    if let Some(status) = invalid {
+        if valid > 0 {
+            warn!(
+                log,
+                "Execution engines disagree on payload validity";
+                "msg" => "treating the payload as invalid",
+                "valid_count" => valid,
+            );
+        }
+        Ok(status)
+    } else if valid >= quorum {
+        Ok(PayloadStatus::Valid)
+    } else if statuses > 0 {
+        if valid > 0 {
+            warn!(
+                log,
+                "Execution engines did not reach quorum";
+                "msg" => "treating the payload as optimistic",
+                "valid_count" => valid,
+                "quorum" => quorum,
+            );
+        }
+        Ok(PayloadStatus::Syncing)
+    } else {
+        Err(first_error.unwrap_or(EngineError::Offline))
+    }
+}
+
+/// Awaits the statuses returned by several execution engines for the same payload and combines
+/// them with `combine_payload_statuses`.
+///
+/// Returns as soon as any engine returns invalid or `quorum` engines have returned valid, so that
+/// a slow engine does not delay the others. Any remaining requests are completed in a spawned
+/// task, which reports an invalid status returned after the payload was considered valid.
+pub async fn await_payload_statuses<F>(
+    requests: impl IntoIterator<Item = F>,
+    quorum: usize,
+    executor: &TaskExecutor,
+    log: &Logger,
+) -> Result<PayloadStatus, EngineError>
+where
+    F: Future<Output = Result<PayloadStatus, EngineError>> + Send + 'static,
+{
+    let mut pending = requests.into_iter().collect::<FuturesUnordered<_>>();
+    let mut results = Vec::with_capacity(pending.len());
+    let mut valid = 0;
+
+    while let Some(result) = pending.next().await {
+        let invalid = matches!(
+            result,
+            Ok(PayloadStatus::Invalid { .. } | PayloadStatus::InvalidBlockHash { .. })
+        );
+        if matches!(result, Ok(PayloadStatus::Valid)) {
+            valid += 1;
+        }
+        results.push(result);
+
+        if invalid || valid >= quorum {
+            break;
+        }
+    }
+
+    let status = combine_payload_statuses(results, quorum, log);
+
+    if !pending.is_empty() {
+        debug!(
+            log,
+            "Execution engines reached an outcome";
+            "msg" => "not waiting for remaining engines",
+            "pending" => pending.len(),
+        );
+
+        // The remaining engines are still sent the payload, and an invalid status from one of
+        // them is reported if the payload was treated as valid.
+        let treated_as_valid = matches!(status, Ok(PayloadStatus::Valid));
+        let log = log.clone();
+        executor.spawn(
+            async move {
+                if let Some(late_status) = await_remaining_statuses(pending).await {
+                    if treated_as_valid {
+                        crit!(
+                            log,
+                            "Execution engine returned invalid after quorum";
+                            "msg" => "the payload was treated as valid, check the execution engines",
+                            "status" => ?late_status,
+                        );
+                    }
+                }
+            },
+            "late_payload_statuses",
+        );
+    }
+
+    status
+}
+
+/// Drives every pending request to completion, returning the first invalid status returned.
+async fn await_remaining_statuses<F>(mut pending: FuturesUnordered<F>) -> Option<PayloadStatus>
+where
+    F: Future<Output = Result<PayloadStatus, EngineError>>,
+{
+    let mut invalid = None;
+    while let Some(result) = pending.next().await {
+        if let Ok(
+            status @ (PayloadStatus::Invalid { .. } | PayloadStatus::InvalidBlockHash { .. }),
+        ) = result
+        {
+            invalid.get_or_insert(status);
+        }
+    }
+    invalid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use environment::null_logger;
+    use futures::executor::block_on;
+    use futures::future;
+    use std::pin::Pin;
+    use std::task::Poll;
+    use task_executor::test_utils::TestRuntime;
+
+    fn invalid() -> PayloadStatus {
+        PayloadStatus::Invalid {
+            latest_valid_hash: None,
+            validation_error: None,
+        }
+    }
+
+    fn combine(results: Vec<Result<PayloadStatus, EngineError>>, quorum: usize) -> PayloadStatus {
+        combine_payload_statuses(results, quorum, &null_logger().unwrap())
+            .expect("at least one engine responded")
+    }
+
+    #[test]
+    fn any_invalid_wins() {
+        let status = combine(
+            vec![
+                Ok(PayloadStatus::Valid),
+                Ok(invalid()),
+                Ok(PayloadStatus::Valid),
+            ],
+            1,
+        );
+        assert_eq!(status, invalid());
+    }
+
+    #[test]
+    fn valid_requires_quorum() {
+        let results = || {
+            vec![
+                Ok(PayloadStatus::Valid),
+                Ok(PayloadStatus::Syncing),
+                Err(EngineError::Offline),
+            ]
+        };
+        assert_eq!(combine(results(), 1), PayloadStatus::Valid);
+        assert_eq!(combine(results(), 2), PayloadStatus::Syncing);
+    }
+
+    #[test]
+    fn all_errors_is_error() {
+        let result = combine_payload_statuses(
+            vec![Err(EngineError::Offline), Err(EngineError::Auth)],
+            1,
+            &null_logger().unwrap(),
+        );
+        assert!(matches!(result, Err(EngineError::Offline)));
+    }
+
+    type Request = Pin<Box<dyn Future<Output = Result<PayloadStatus, EngineError>> + Send>>;
+
+    fn ready(status: PayloadStatus) -> Request {
+        Box::pin(future::ready(Ok(status)))
+    }
+
+    fn hung() -> Request {
+        Box::pin(future::pending())
+    }
+
+    /// Returns `status` once the other requests have had `polls` chances to complete.
+    fn slow(status: PayloadStatus, polls: usize) -> Request {
+        let mut remaining = polls;
+        Box::pin(future::poll_fn(move |cx| {
+            if remaining == 0 {
+                Poll::Ready(Ok(status.clone()))
+            } else {
+                remaining -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }))
+    }
+
+    fn await_statuses(requests: Vec<Request>, quorum: usize) -> PayloadStatus {
+        let runtime = TestRuntime::default();
+        block_on(await_payload_statuses(
+            requests,
+            quorum,
+            &runtime.task_executor,
+            &null_logger().unwrap(),
+        ))
+        .expect("at least one engine responded")
+    }
+
+    #[test]
+    fn returns_once_quorum_is_valid() {
+        let requests = vec![
+            ready(PayloadStatus::Valid),
+            hung(),
+            ready(PayloadStatus::Valid),
+        ];
+        assert_eq!(await_statuses(requests, 2), PayloadStatus::Valid);
+    }
+
+    #[test]
+    fn late_invalid_is_found_after_quorum() {
+        let pending = vec![
+            ready(PayloadStatus::Valid),
+            slow(invalid(), 2),
+            ready(PayloadStatus::Syncing),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(block_on(await_remaining_statuses(pending)), Some(invalid()));
+    }
+
+    #[test]
+    fn returns_once_any_is_invalid() {
+        let requests = vec![hung(), ready(PayloadStatus::Valid), ready(invalid())];
+        assert_eq!(await_statuses(requests, 2), invalid());
+    }
+
+    #[test]
+    fn awaits_all_without_quorum() {
+        let requests = vec![
+            ready(PayloadStatus::Valid),
+            ready(PayloadStatus::Syncing),
+            Box::pin(future::ready(Err(EngineError::Offline))),
+        ];
+        assert_eq!(await_statuses(requests, 2), PayloadStatus::Syncing);
+    }
+}
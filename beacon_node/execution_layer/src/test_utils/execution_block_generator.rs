@@ -1,5 +1,6 @@
 use crate::engines::ForkchoiceState;
 use crate::{
+    calculate_execution_block_hash,
     engine_api::{
         json_structures::{
             JsonForkchoiceUpdatedV1Response, JsonPayloadStatusV1, JsonPayloadStatusV1Status,
@@ -14,11 +15,13 @@ use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 use types::{
     EthSpec, ExecutionBlockHash, ExecutionPayload, ExecutionPayloadCapella, ExecutionPayloadMerge,
-    ForkName, Hash256, Uint256,
+    ForkName, Hash256, Transaction, Transactions, Uint256,
 };
 
 const GAS_LIMIT: u64 = 16384;
 const GAS_USED: u64 = GAS_LIMIT - 1;
+/// The length in bytes of each fake transaction included in a built payload.
+const FAKE_TRANSACTION_LENGTH: usize = 128;
 
 #[derive(Clone, Debug, PartialEq)]
 #[allow(clippy::large_enum_variant)] // This struct is only for testing.
@@ -114,6 +117,8 @@ pub struct ExecutionBlockGenerator<T: EthSpec> {
     pub pending_payloads: HashMap<ExecutionBlockHash, ExecutionPayload<T>>,
     pub next_payload_id: u64,
     pub payload_ids: HashMap<PayloadId, ExecutionPayload<T>>,
+    /// The number of opaque, fake transactions to include in each built payload.
+    pub fake_transactions: usize,
     /*
      * Post-merge fork triggers
      */
@@ -138,6 +143,7 @@ impl<T: EthSpec> ExecutionBlockGenerator<T> {
             pending_payloads: <_>::default(),
             next_payload_id: 0,
             payload_ids: <_>::default(),
+            fake_transactions: 0,
             shanghai_time,
         };
 
@@ -358,9 +364,7 @@ impl<T: EthSpec> ExecutionBlockGenerator<T> {
             // Update the block hash after modifying the block
             match &mut block {
                 Block::PoW(b) => b.block_hash = ExecutionBlockHash::from_root(b.tree_hash_root()),
-                Block::PoS(b) => {
-                    *b.block_hash_mut() = ExecutionBlockHash::from_root(b.tree_hash_root())
-                }
+                Block::PoS(b) => *b.block_hash_mut() = calculate_execution_block_hash(b.to_ref()).0,
             }
 
             // Update head.
@@ -482,7 +486,7 @@ impl<T: EthSpec> ExecutionBlockGenerator<T> {
                         extra_data: "block gen was here".as_bytes().to_vec().into(),
                         base_fee_per_gas: Uint256::one(),
                         block_hash: ExecutionBlockHash::zero(),
-                        transactions: vec![].into(),
+                        transactions: self.fake_transactions(parent.block_number() + 1)?,
                     }),
                     PayloadAttributes::V2(pa) => match self.get_fork_at_timestamp(pa.timestamp) {
                         ForkName::Merge => ExecutionPayload::Merge(ExecutionPayloadMerge {
@@ -499,7 +503,7 @@ impl<T: EthSpec> ExecutionBlockGenerator<T> {
                             extra_data: "block gen was here".as_bytes().to_vec().into(),
                             base_fee_per_gas: Uint256::one(),
                             block_hash: ExecutionBlockHash::zero(),
-                            transactions: vec![].into(),
+                            transactions: self.fake_transactions(parent.block_number() + 1)?,
                         }),
                         ForkName::Capella => ExecutionPayload::Capella(ExecutionPayloadCapella {
                             parent_hash: forkchoice_state.head_block_hash,
@@ -515,7 +519,7 @@ impl<T: EthSpec> ExecutionBlockGenerator<T> {
                             extra_data: "block gen was here".as_bytes().to_vec().into(),
                             base_fee_per_gas: Uint256::one(),
                             block_hash: ExecutionBlockHash::zero(),
-                            transactions: vec![].into(),
+                            transactions: self.fake_transactions(parent.block_number() + 1)?,
                             withdrawals: pa.withdrawals.clone().into(),
                         }),
                         _ => unreachable!(),
@@ -523,7 +527,7 @@ impl<T: EthSpec> ExecutionBlockGenerator<T> {
                 };
 
                 *execution_payload.block_hash_mut() =
-                    ExecutionBlockHash::from_root(execution_payload.tree_hash_root());
+                    calculate_execution_block_hash(execution_payload.to_ref()).0;
 
                 self.payload_ids.insert(id, execution_payload);
 
@@ -551,6 +555,23 @@ impl<T: EthSpec> ExecutionBlockGenerator<T> {
             payload_id: id.map(Into::into),
         })
     }
+
+    /// Returns `self.fake_transactions` opaque transactions which are unique to `block_number`.
+    fn fake_transactions(&self, block_number: u64) -> Result<Transactions<T>, String> {
+        (0..self.fake_transactions as u64)
+            .map(|i| {
+                let mut bytes = vec![0; FAKE_TRANSACTION_LENGTH];
+                bytes[..8].copy_from_slice(&block_number.to_le_bytes());
+                bytes[8..16].copy_from_slice(&i.to_le_bytes());
+                Transaction::<T::MaxBytesPerTransaction>::new(bytes)
+                    .map_err(|e| format!("invalid fake transaction: {:?}", e))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|transactions| {
+                Transactions::<T>::new(transactions)
+                    .map_err(|e| format!("too many fake transactions: {:?}", e))
+            })
+    }
 }
 
 fn payload_id_from_u64(n: u64) -> PayloadId {
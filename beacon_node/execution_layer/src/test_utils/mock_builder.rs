@@ -237,6 +237,7 @@ pub struct MockBuilder<E: EthSpec> {
     builder_sk: SecretKey,
     operations: Arc<RwLock<Vec<Operation>>>,
     invalidate_signatures: Arc<RwLock<bool>>,
+    reveal_requests: Arc<RwLock<usize>>,
 }
 
 impl<E: EthSpec> MockBuilder<E> {
@@ -257,6 +258,7 @@ impl<E: EthSpec> MockBuilder<E> {
             builder_sk: sk,
             operations: Arc::new(RwLock::new(vec![])),
             invalidate_signatures: Arc::new(RwLock::new(false)),
+            reveal_requests: Arc::new(RwLock::new(0)),
         }
     }
 
@@ -274,6 +276,11 @@ impl<E: EthSpec> MockBuilder<E> {
         *self.invalidate_signatures.write() = false;
     }
 
+    /// The number of blinded blocks which have been sent to this builder to be revealed.
+    pub fn reveal_requests(&self) -> usize {
+        *self.reveal_requests.read()
+    }
+
     fn apply_operations<B: BidStuff>(&self, bid: &mut B) -> Result<(), BlindedBlockProviderError> {
         let mut guard = self.operations.write();
         while let Some(op) = guard.pop() {
@@ -476,6 +483,8 @@ impl<E: EthSpec> mev_rs::BlindedBlockProvider for MockBuilder<E> {
         &self,
         signed_block: &mut SignedBlindedBeaconBlock,
     ) -> Result<ServerPayload, BlindedBlockProviderError> {
+        *self.reveal_requests.write() += 1;
+
         let node = match signed_block {
             SignedBlindedBeaconBlock::Bellatrix(block) => {
                 block.message.body.execution_payload_header.hash_tree_root()
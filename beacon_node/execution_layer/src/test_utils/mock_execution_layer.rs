@@ -29,6 +29,7 @@ impl<T: EthSpec> MockExecutionLayer<T> {
             DEFAULT_TERMINAL_BLOCK,
             None,
             None,
+            None,
             Some(JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap()),
             spec,
             None,
@@ -41,6 +42,7 @@ impl<T: EthSpec> MockExecutionLayer<T> {
         terminal_block: u64,
         shanghai_time: Option<u64>,
         builder_threshold: Option<u128>,
+        builder_boost_factor: Option<u64>,
         jwt_key: Option<JwtKey>,
         spec: ChainSpec,
         builder_url: Option<SensitiveUrl>,
@@ -65,10 +67,11 @@ impl<T: EthSpec> MockExecutionLayer<T> {
 
         let config = Config {
             execution_endpoints: vec![url],
-            builder_url,
+            builder_urls: builder_url.into_iter().collect(),
             secret_files: vec![path],
             suggested_fee_recipient: Some(Address::repeat_byte(42)),
             builder_profit_threshold: builder_threshold.unwrap_or(DEFAULT_BUILDER_THRESHOLD_WEI),
+            builder_boost_factor,
             ..Default::default()
         };
         let el =
@@ -190,6 +193,11 @@ impl<T: EthSpec> MockExecutionLayer<T> {
         assert_eq!(payload_header.timestamp(), timestamp);
         assert_eq!(payload_header.prev_randao(), prev_randao);
 
+        // Ensure the values of the competing payloads were recorded.
+        let payload_values = self.el.payload_values(block_hash).unwrap();
+        assert_eq!(payload_values.source, PayloadSource::Local);
+        assert_eq!(payload_values.builder_value, None);
+
         // Ensure the payload cache has the correct payload.
         assert_eq!(
             self.el
@@ -2,7 +2,10 @@
 
 use crate::engine_api::auth::JwtKey;
 use crate::engine_api::{
-    auth::Auth, http::JSONRPC_VERSION, ExecutionBlock, PayloadStatusV1, PayloadStatusV1Status,
+    auth::Auth,
+    http::{JSONRPC_VERSION, METHOD_NOT_FOUND_CODE},
+    recording::RecordedOutcome,
+    ExecutionBlock, PayloadStatusV1, PayloadStatusV1Status,
 };
 use bytes::Bytes;
 use environment::null_logger;
@@ -27,6 +30,7 @@ pub use execution_block_generator::{generate_pow_block, Block, ExecutionBlockGen
 pub use hook::Hook;
 pub use mock_builder::{Context as MockBuilderContext, MockBuilder, Operation, TestingBuilder};
 pub use mock_execution_layer::MockExecutionLayer;
+pub use replay::ReplaySession;
 
 pub const DEFAULT_TERMINAL_DIFFICULTY: u64 = 6400;
 pub const DEFAULT_TERMINAL_BLOCK: u64 = 64;
@@ -49,6 +53,7 @@ mod handle_rpc;
 mod hook;
 mod mock_builder;
 mod mock_execution_layer;
+mod replay;
 
 /// Configuration for the MockExecutionLayer.
 pub struct MockExecutionConfig {
@@ -125,6 +130,7 @@ impl<T: EthSpec> MockServer<T> {
             new_payload_statuses: <_>::default(),
             fcu_payload_statuses: <_>::default(),
             engine_capabilities: Arc::new(RwLock::new(DEFAULT_ENGINE_CAPABILITIES)),
+            replay_session: <_>::default(),
             _phantom: PhantomData,
         });
 
@@ -159,6 +165,16 @@ impl<T: EthSpec> MockServer<T> {
         *self.ctx.engine_capabilities.write() = engine_capabilities;
     }
 
+    /// Respond to all requests from `session` rather than the execution block generator.
+    pub fn start_replay(&self, session: ReplaySession) {
+        *self.ctx.replay_session.lock() = Some(session);
+    }
+
+    /// Stop replaying, returning the session so its progress can be inspected.
+    pub fn stop_replay(&self) -> Option<ReplaySession> {
+        self.ctx.replay_session.lock().take()
+    }
+
     pub fn new(
         handle: &runtime::Handle,
         jwt_key: JwtKey,
@@ -480,6 +496,8 @@ pub struct Context<T: EthSpec> {
     pub fcu_payload_statuses: Arc<Mutex<HashMap<ExecutionBlockHash, PayloadStatusV1>>>,
 
     pub engine_capabilities: Arc<RwLock<EngineCapabilities>>,
+    /// When set, all requests are answered from a recorded session.
+    pub replay_session: Arc<Mutex<Option<ReplaySession>>>,
     pub _phantom: PhantomData<T>,
 }
 
@@ -613,6 +631,43 @@ pub fn serve<T: EthSpec>(
                 .get("id")
                 .and_then(serde_json::Value::as_u64)
                 .ok_or_else(|| warp::reject::custom(MissingIdField))?;
+            if let Some(session) = ctx.replay_session.lock().as_mut() {
+                let method = body
+                    .get("method")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default();
+                let params = body.get("params").cloned().unwrap_or_default();
+                let (status, body) = match session.next_outcome(method, &params) {
+                    Some(RecordedOutcome::Response { status, body }) => {
+                        // Answer with the id of this request, if the recorded body is valid JSON.
+                        let body = match serde_json::from_str::<serde_json::Value>(&body) {
+                            Ok(mut response) if response.is_object() => {
+                                response["id"] = json!(id);
+                                response.to_string()
+                            }
+                            _ => body,
+                        };
+                        (status, body)
+                    }
+                    // A failure to connect cannot be reproduced, so respond with an error status.
+                    Some(RecordedOutcome::Error { error }) => (503, error),
+                    None => (
+                        200,
+                        json!({
+                            "id": id,
+                            "jsonrpc": JSONRPC_VERSION,
+                            "error": {
+                                "code": METHOD_NOT_FOUND_CODE,
+                                "message": format!("{} is not in the replayed session", method)
+                            }
+                        })
+                        .to_string(),
+                    ),
+                };
+
+                return Ok(warp::http::Response::builder().status(status).body(body));
+            }
+
             let preloaded_response = {
                 let mut preloaded_responses = ctx.preloaded_responses.lock();
                 if !preloaded_responses.is_empty() {
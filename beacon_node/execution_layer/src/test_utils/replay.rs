@@ -0,0 +1,85 @@
+//! Replays a session recorded by an `EngineRecorder`, so that interop issues observed against a
+//! real execution engine can be reproduced without running one.
+
+use crate::engine_api::recording::{read_recording, RecordedExchange, RecordedOutcome};
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+
+/// The recorded exchanges of a single engine, replayed in the order their requests were sent.
+///
+/// Each request is answered with the earliest unreplayed exchange for its method. If that exchange
+/// is not the next one in the recording, the request arrived out of order and is reported as a
+/// divergence. Once no exchanges remain for a method, its last exchange is returned for every
+/// subsequent request for that method. This accommodates polled methods such as `eth_syncing`
+/// being called a different number of times than during the recording.
+#[derive(Debug, Default)]
+pub struct ReplaySession {
+    /// Exchanges which have not been replayed, in the order their requests were sent.
+    pending: VecDeque<RecordedExchange>,
+    /// The most recently replayed exchange for each method.
+    last: HashMap<String, RecordedExchange>,
+    /// The methods of requests which were out of order, or whose params differed from those
+    /// recorded.
+    divergences: Vec<String>,
+}
+
+impl ReplaySession {
+    /// Build a session from the exchanges with `engine`.
+    pub fn new(exchanges: Vec<RecordedExchange>, engine: usize) -> Self {
+        let mut pending = exchanges
+            .into_iter()
+            .filter(|exchange| exchange.engine == engine)
+            .collect::<Vec<_>>();
+        pending.sort_by_key(|exchange| exchange.sequence);
+        Self {
+            pending: pending.into(),
+            last: HashMap::new(),
+            divergences: vec![],
+        }
+    }
+
+    pub fn from_file(path: &Path, engine: usize) -> Result<Self, String> {
+        read_recording(path).map(|exchanges| Self::new(exchanges, engine))
+    }
+
+    /// Returns the recorded outcome of the next request for `method`, or `None` if the method was
+    /// never recorded.
+    pub fn next_outcome(
+        &mut self,
+        method: &str,
+        params: &serde_json::Value,
+    ) -> Option<RecordedOutcome> {
+        let exchange = match self
+            .pending
+            .iter()
+            .position(|exchange| exchange.method == method)
+        {
+            Some(index) => {
+                if index != 0 {
+                    self.divergences.push(method.to_string());
+                }
+                let exchange = self.pending.remove(index)?;
+                self.last.insert(method.to_string(), exchange.clone());
+                exchange
+            }
+            None => self.last.get(method)?.clone(),
+        };
+
+        if exchange.params != *params {
+            self.divergences.push(method.to_string());
+        }
+
+        Some(exchange.outcome)
+    }
+
+    /// The methods of requests which were out of order or whose params differed from the
+    /// recording, in the order they were received.
+    pub fn divergences(&self) -> &[String] {
+        &self.divergences
+    }
+
+    /// The number of recorded exchanges which have not been replayed.
+    pub fn remaining(&self) -> usize {
+        self.pending.len()
+    }
+}
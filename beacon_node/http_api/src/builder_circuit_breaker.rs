@@ -0,0 +1,50 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{BuilderCircuitBreakerStatus, CircuitBreakerRequest};
+use execution_layer::ExecutionLayer;
+
+fn execution_layer<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<&ExecutionLayer<T::EthSpec>, warp::Rejection> {
+    chain
+        .execution_layer
+        .as_ref()
+        .filter(|execution_layer| execution_layer.has_builder())
+        .ok_or_else(|| {
+            warp_utils::reject::custom_not_found(
+                "No builders are connected. See the --builder CLI flag.".to_string(),
+            )
+        })
+}
+
+/// Returns the state of the builder circuit breaker, for the
+/// `/lighthouse/builder/circuit_breaker` endpoint.
+pub fn get_status<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<BuilderCircuitBreakerStatus, warp::Rejection> {
+    let current_slot = chain
+        .slot()
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+    Ok(execution_layer(chain)?.builder_circuit_breaker_status(current_slot))
+}
+
+/// Trips the builder circuit breaker until it is reset.
+pub fn trip<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    request: &CircuitBreakerRequest,
+) -> Result<BuilderCircuitBreakerStatus, warp::Rejection> {
+    execution_layer(chain)?
+        .trip_builder_circuit_breaker(request.relay.as_deref())
+        .map_err(|e| warp_utils::reject::custom_bad_request(format!("{:?}", e)))?;
+    get_status(chain)
+}
+
+/// Resets the builder circuit breaker, forgetting the recent behaviour of the relays.
+pub fn reset<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    request: &CircuitBreakerRequest,
+) -> Result<BuilderCircuitBreakerStatus, warp::Rejection> {
+    execution_layer(chain)?
+        .reset_builder_circuit_breaker(request.relay.as_deref())
+        .map_err(|e| warp_utils::reject::custom_bad_request(format!("{:?}", e)))?;
+    get_status(chain)
+}
@@ -0,0 +1,45 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::ExecutionLayerHealth;
+use types::ExecutionBlockHash;
+
+/// Returns the execution block number of the head, or `None` if the head is prior to the merge.
+fn head_execution_block_number<T: BeaconChainTypes>(chain: &BeaconChain<T>) -> Option<u64> {
+    let cached_head = chain.canonical_head.cached_head();
+    cached_head
+        .forkchoice_update_parameters()
+        .head_hash
+        .filter(|head_hash| *head_hash != ExecutionBlockHash::zero())
+        .and_then(|_| cached_head.head_block_number().ok())
+}
+
+/// Returns the health of the execution layer, for the `/lighthouse/eth1/engine` endpoint.
+pub async fn get_execution_layer_health<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<ExecutionLayerHealth, warp::Rejection> {
+    let execution_layer = chain.execution_layer.as_ref().ok_or_else(|| {
+        warp_utils::reject::custom_not_found(
+            "The execution layer is not enabled. See the --execution-endpoint CLI flag."
+                .to_string(),
+        )
+    })?;
+    Ok(execution_layer
+        .health(head_execution_block_number(chain))
+        .await)
+}
+
+/// Returns `true` if the head is post-merge and fewer than the execution quorum of engines are
+/// healthy, in which case the node is unable to verify new blocks.
+pub async fn is_execution_layer_degraded<T: BeaconChainTypes>(chain: &BeaconChain<T>) -> bool {
+    match (
+        chain.execution_layer.as_ref(),
+        head_execution_block_number(chain),
+    ) {
+        (Some(execution_layer), Some(head_block_number)) => {
+            execution_layer
+                .health(Some(head_block_number))
+                .await
+                .degraded
+        }
+        _ => false,
+    }
+}
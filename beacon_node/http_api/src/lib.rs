@@ -10,8 +10,12 @@ mod attester_duties;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
+mod builder_circuit_breaker;
 mod database;
+mod engine_health;
 mod metrics;
+mod op_pool;
+mod optimistic_blocks;
 mod proposer_duties;
 mod publish_blocks;
 mod standard_block_rewards;
@@ -20,6 +24,7 @@ mod sync_committee_rewards;
 mod sync_committees;
 mod ui;
 mod validator_inclusion;
+mod validator_monitor_history;
 mod version;
 
 use beacon_chain::{
@@ -54,7 +59,7 @@ use tokio::sync::mpsc::{Sender, UnboundedSender};
 use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 use types::{
     Attestation, AttestationData, AttesterSlashing, BeaconStateError, BlindedPayload,
-    CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName, FullPayload,
+    CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName, FullPayload, Hash256,
     ProposerPreparationData, ProposerSlashing, RelativeEpoch, SignedAggregateAndProof,
     SignedBeaconBlock, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
     SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
@@ -1418,6 +1423,8 @@ pub fn serve<T: BeaconChainTypes>(
                                 format!("Naive aggregation pool: {:?}", e),
                             ));
                         }
+
+                        chain.add_unaggregated_to_block_inclusion_pool(attestation);
                     }
 
                     if num_already_known > 0 {
@@ -2163,24 +2170,38 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("health"))
         .and(warp::path::end())
         .and(network_globals.clone())
-        .and_then(|network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
-            blocking_response_task(move || match *network_globals.sync_state.read() {
-                SyncState::SyncingFinalized { .. }
-                | SyncState::SyncingHead { .. }
-                | SyncState::SyncTransition
-                | SyncState::BackFillSyncing { .. } => Ok(warp::reply::with_status(
-                    warp::reply(),
-                    warp::http::StatusCode::PARTIAL_CONTENT,
-                )),
-                SyncState::Synced => Ok(warp::reply::with_status(
-                    warp::reply(),
-                    warp::http::StatusCode::OK,
-                )),
-                SyncState::Stalled => Err(warp_utils::reject::not_synced(
-                    "sync stalled, beacon chain may not yet be initialized.".to_string(),
-                )),
-            })
-        });
+        .and(chain_filter.clone())
+        .and_then(
+            |network_globals: Arc<NetworkGlobals<T::EthSpec>>, chain: Arc<BeaconChain<T>>| async move {
+                let sync_state = network_globals.sync_state.read().clone();
+                match sync_state {
+                    SyncState::SyncingFinalized { .. }
+                    | SyncState::SyncingHead { .. }
+                    | SyncState::SyncTransition
+                    | SyncState::BackFillSyncing { .. } => Ok(warp::reply::with_status(
+                        warp::reply(),
+                        warp::http::StatusCode::PARTIAL_CONTENT,
+                    )),
+                    // A synced node whose execution layer is degraded can serve data, but can't
+                    // verify new blocks.
+                    SyncState::Synced
+                        if engine_health::is_execution_layer_degraded(&chain).await =>
+                    {
+                        Ok(warp::reply::with_status(
+                            warp::reply(),
+                            warp::http::StatusCode::PARTIAL_CONTENT,
+                        ))
+                    }
+                    SyncState::Synced => Ok(warp::reply::with_status(
+                        warp::reply(),
+                        warp::http::StatusCode::OK,
+                    )),
+                    SyncState::Stalled => Err(warp_utils::reject::not_synced(
+                        "sync stalled, beacon chain may not yet be initialized.".to_string(),
+                    )),
+                }
+            },
+        );
 
     // GET node/peers/{peer_id}
     let get_node_peers_by_id = eth_v1
@@ -2952,29 +2973,24 @@ pub fn serve<T: BeaconChainTypes>(
                         ))
                     })?;
 
-                let builder = execution_layer
-                    .builder()
-                    .as_ref()
-                    .ok_or(BeaconChainError::BuilderMissing)
-                    .map_err(warp_utils::reject::beacon_chain_error)?;
+                if !execution_layer.has_builder() {
+                    return Err(warp_utils::reject::beacon_chain_error(
+                        BeaconChainError::BuilderMissing,
+                    ));
+                }
 
                 info!(
                     log,
-                    "Forwarding register validator request to connected builder";
+                    "Forwarding register validator request to connected builders";
                     "count" => filtered_registration_data.len(),
+                    "relays" => execution_layer.builders().len(),
                 );
 
-                builder
+                execution_layer
                     .post_builder_validators(&filtered_registration_data)
                     .await
                     .map(|resp| warp::reply::json(&resp).into_response())
                     .map_err(|e| {
-                        warn!(
-                            log,
-                            "Relay error when registering validator(s)";
-                            "num_registrations" => filtered_registration_data.len(),
-                            "error" => ?e
-                        );
                         // Forward the HTTP status code if we are able to, otherwise fall back
                         // to a server error.
                         if let eth2::Error::ServerMessage(message) = e {
@@ -3203,6 +3219,50 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/gossip/validation_log
+    let get_lighthouse_gossip_validation_log = warp::path("lighthouse")
+        .and(warp::path("gossip"))
+        .and(warp::path("validation_log"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::GossipValidationLogQuery>())
+        .and(network_globals.clone())
+        .and_then(
+            |query: eth2::lighthouse::GossipValidationLogQuery,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                blocking_json_task(move || {
+                    let log = network_globals
+                        .gossip_validation_log
+                        .as_ref()
+                        .ok_or_else(|| {
+                            warp_utils::reject::custom_not_found(
+                                "gossip validation log is disabled, enable it with \
+                                --gossip-validation-log"
+                                    .to_string(),
+                            )
+                        })?
+                        .read();
+                    let mut entries = log
+                        .entries()
+                        .rev()
+                        .filter(|entry| query.result.map_or(true, |result| entry.result == result))
+                        .filter(|entry| {
+                            query.topic.as_ref().map_or(true, |topic| {
+                                entry
+                                    .topic
+                                    .as_ref()
+                                    .map_or(false, |entry_topic| entry_topic.contains(topic))
+                            })
+                        })
+                        .take(query.limit.unwrap_or(usize::MAX))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    // Return the entries oldest first.
+                    entries.reverse();
+                    Ok(api_types::GenericResponse::from(entries))
+                })
+            },
+        );
+
     // GET lighthouse/peers/connected
     let get_lighthouse_peers_connected = warp::path("lighthouse")
         .and(warp::path("peers"))
@@ -3240,6 +3300,160 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/optimistic_blocks
+    let get_lighthouse_optimistic_blocks = warp::path("lighthouse")
+        .and(warp::path("optimistic_blocks"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                optimistic_blocks::get_optimistic_blocks(&chain)
+                    .map(api_types::GenericResponse::from)
+            })
+        });
+
+    // GET lighthouse/optimistic_blocks/health
+    let get_lighthouse_optimistic_blocks_health = warp::path("lighthouse")
+        .and(warp::path("optimistic_blocks"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| async move {
+            let health = optimistic_blocks::get_optimistic_health(chain).await?;
+            Ok::<_, warp::reject::Rejection>(
+                warp::reply::json(&api_types::GenericResponse::from(health)).into_response(),
+            )
+        });
+
+    // POST lighthouse/optimistic_blocks/revalidate
+    let post_lighthouse_optimistic_blocks_revalidate = warp::path("lighthouse")
+        .and(warp::path("optimistic_blocks"))
+        .and(warp::path("revalidate"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(
+            |block_roots: Vec<Hash256>, chain: Arc<BeaconChain<T>>| async move {
+                let results =
+                    optimistic_blocks::revalidate_optimistic_blocks(chain, block_roots).await?;
+                Ok::<_, warp::reject::Rejection>(
+                    warp::reply::json(&api_types::GenericResponse::from(results)).into_response(),
+                )
+            },
+        );
+
+    // GET lighthouse/builder/audits
+    let get_lighthouse_builder_audits = warp::path("lighthouse")
+        .and(warp::path("builder"))
+        .and(warp::path("audits"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                chain
+                    .builder_audits()
+                    .map(api_types::GenericResponse::from)
+                    .map_err(warp_utils::reject::beacon_chain_error)
+            })
+        });
+
+    // GET lighthouse/builder/circuit_breaker
+    let get_lighthouse_builder_circuit_breaker = warp::path("lighthouse")
+        .and(warp::path("builder"))
+        .and(warp::path("circuit_breaker"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                builder_circuit_breaker::get_status(&chain).map(api_types::GenericResponse::from)
+            })
+        });
+
+    // POST lighthouse/builder/circuit_breaker/trip
+    let post_lighthouse_builder_circuit_breaker_trip = warp::path("lighthouse")
+        .and(warp::path("builder"))
+        .and(warp::path("circuit_breaker"))
+        .and(warp::path("trip"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(
+            |request: eth2::lighthouse::CircuitBreakerRequest, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    builder_circuit_breaker::trip(&chain, &request)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // POST lighthouse/builder/circuit_breaker/reset
+    let post_lighthouse_builder_circuit_breaker_reset = warp::path("lighthouse")
+        .and(warp::path("builder"))
+        .and(warp::path("circuit_breaker"))
+        .and(warp::path("reset"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(
+            |request: eth2::lighthouse::CircuitBreakerRequest, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    builder_circuit_breaker::reset(&chain, &request)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/validator_monitor/{index}/history
+    let get_lighthouse_validator_monitor_history = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path("history"))
+        .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::ValidatorMonitorHistoryQuery>())
+        .and(chain_filter.clone())
+        .and_then(
+            |validator_index: u64,
+             query: eth2::lighthouse::ValidatorMonitorHistoryQuery,
+             chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    validator_monitor_history::get_validator_monitor_history(
+                        &chain,
+                        validator_index,
+                        query,
+                    )
+                    .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/op_pool/summary
+    let get_lighthouse_op_pool_summary = warp::path("lighthouse")
+        .and(warp::path("op_pool"))
+        .and(warp::path("summary"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                chain
+                    .op_pool_summary()
+                    .map(api_types::GenericResponse::from)
+                    .map_err(warp_utils::reject::beacon_chain_error)
+            })
+        });
+
+    // GET lighthouse/op_pool/dry_run/{slot}
+    let get_lighthouse_op_pool_dry_run = warp::path("lighthouse")
+        .and(warp::path("op_pool"))
+        .and(warp::path("dry_run"))
+        .and(warp::path::param::<Slot>())
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|slot: Slot, chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                op_pool::get_op_pool_dry_run(&chain, slot).map(api_types::GenericResponse::from)
+            })
+        });
+
     // GET lighthouse/validator_inclusion/{epoch}/{validator_id}
     let get_lighthouse_validator_inclusion_global = warp::path("lighthouse")
         .and(warp::path("validator_inclusion"))
@@ -3300,6 +3514,19 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/eth1/engine
+    let get_lighthouse_eth1_engine = warp::path("lighthouse")
+        .and(warp::path("eth1"))
+        .and(warp::path("engine"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| async move {
+            let health = engine_health::get_execution_layer_health(&chain).await?;
+            Ok::<_, warp::reject::Rejection>(
+                warp::reply::json(&api_types::GenericResponse::from(health)).into_response(),
+            )
+        });
+
     // GET lighthouse/eth1/block_cache
     let get_lighthouse_eth1_block_cache = warp::path("lighthouse")
         .and(warp::path("eth1"))
@@ -3532,6 +3759,24 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::BlockReward => {
                                     event_handler.subscribe_block_reward()
                                 }
+                                api_types::EventTopic::GossipValidation => {
+                                    event_handler.subscribe_gossip_validation()
+                                }
+                                api_types::EventTopic::BackFillProgress => {
+                                    event_handler.subscribe_backfill_progress()
+                                }
+                                api_types::EventTopic::ValidatorMissedAttestation => {
+                                    event_handler.subscribe_validator_missed_attestation()
+                                }
+                                api_types::EventTopic::ValidatorMissedProposal => {
+                                    event_handler.subscribe_validator_missed_proposal()
+                                }
+                                api_types::EventTopic::ValidatorSlashed => {
+                                    event_handler.subscribe_validator_slashed()
+                                }
+                                api_types::EventTopic::ValidatorBalanceDecrease => {
+                                    event_handler.subscribe_validator_balance_decrease()
+                                }
                             };
 
                             receivers.push(BroadcastStream::new(receiver).map(|msg| {
@@ -3616,10 +3861,19 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_nat)
                 .uor(get_lighthouse_peers)
                 .uor(get_lighthouse_peers_connected)
+                .uor(get_lighthouse_gossip_validation_log)
                 .uor(get_lighthouse_proto_array)
+                .uor(get_lighthouse_optimistic_blocks)
+                .uor(get_lighthouse_optimistic_blocks_health)
+                .uor(get_lighthouse_builder_audits)
+                .uor(get_lighthouse_builder_circuit_breaker)
                 .uor(get_lighthouse_validator_inclusion_global)
+                .uor(get_lighthouse_validator_monitor_history)
+                .uor(get_lighthouse_op_pool_summary)
+                .uor(get_lighthouse_op_pool_dry_run)
                 .uor(get_lighthouse_validator_inclusion)
                 .uor(get_lighthouse_eth1_syncing)
+                .uor(get_lighthouse_eth1_engine)
                 .uor(get_lighthouse_eth1_block_cache)
                 .uor(get_lighthouse_eth1_deposit_cache)
                 .uor(get_lighthouse_beacon_states_ssz)
@@ -3657,6 +3911,9 @@ pub fn serve<T: BeaconChainTypes>(
                     .uor(post_lighthouse_database_reconstruct)
                     .uor(post_lighthouse_database_historical_blocks)
                     .uor(post_lighthouse_block_rewards)
+                    .uor(post_lighthouse_optimistic_blocks_revalidate)
+                    .uor(post_lighthouse_builder_circuit_breaker_trip)
+                    .uor(post_lighthouse_builder_circuit_breaker_reset)
                     .uor(post_lighthouse_ui_validator_metrics)
                     .uor(post_lighthouse_ui_validator_info)
                     .recover(warp_utils::reject::handle_rejection),
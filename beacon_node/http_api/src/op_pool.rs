@@ -0,0 +1,33 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::OpPoolDryRun;
+use types::{EthSpec, Slot};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request};
+
+/// The maximum number of epochs past the current slot which a dry run may be requested for.
+const MAX_DRY_RUN_LOOKAHEAD_EPOCHS: u64 = 1;
+
+/// Returns the operations which would be packed into a block at `slot`, for the
+/// `/lighthouse/op_pool/dry_run/{slot}` endpoint.
+pub fn get_op_pool_dry_run<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    slot: Slot,
+) -> Result<OpPoolDryRun<T::EthSpec>, warp::Rejection> {
+    let head_slot = chain.canonical_head.cached_head().head_slot();
+    if slot <= head_slot {
+        return Err(custom_bad_request(format!(
+            "slot ({}) must be later than the head slot ({})",
+            slot, head_slot
+        )));
+    }
+
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+    let max_slot = current_slot + MAX_DRY_RUN_LOOKAHEAD_EPOCHS * T::EthSpec::slots_per_epoch();
+    if slot > max_slot {
+        return Err(custom_bad_request(format!(
+            "slot ({}) must not be later than {}",
+            slot, max_slot
+        )));
+    }
+
+    chain.op_pool_dry_run(slot).map_err(beacon_chain_error)
+}
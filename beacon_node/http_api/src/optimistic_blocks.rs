@@ -0,0 +1,151 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    OptimisticBlock, OptimisticBlockRevalidation, OptimisticHeadStatus, OptimisticHealth,
+    RevalidationStatus,
+};
+use execution_layer::PayloadStatus;
+use proto_array::ExecutionStatus;
+use std::collections::HashSet;
+use std::sync::Arc;
+use types::Hash256;
+use warp_utils::reject::{beacon_chain_error, custom_bad_request};
+use warp_utils::task::blocking_task;
+
+/// The maximum number of blocks re-validated by a single request.
+///
+/// Each block is sent to the execution layer in turn, so this bounds the duration of a request.
+pub const MAX_REVALIDATION_BLOCKS: usize = 32;
+
+/// Returns the blocks in fork choice which have been imported optimistically, oldest first.
+pub fn get_optimistic_blocks<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<Vec<OptimisticBlock>, warp::Rejection> {
+    let current_slot = chain.slot().map_err(beacon_chain_error)?;
+    let head_root = chain.canonical_head.cached_head().head_block_root();
+
+    let fork_choice = chain.canonical_head.fork_choice_read_lock();
+    let proto_array = fork_choice.proto_array().core_proto_array();
+    let canonical = proto_array
+        .iter_block_roots(&head_root)
+        .map(|(root, _slot)| root)
+        .collect::<HashSet<_>>();
+
+    let mut blocks = proto_array
+        .nodes
+        .iter()
+        .filter_map(|node| match node.execution_status {
+            ExecutionStatus::Optimistic(execution_block_hash) => Some(OptimisticBlock {
+                block_root: node.root,
+                slot: node.slot,
+                execution_block_hash,
+                age: current_slot.saturating_sub(node.slot).as_u64(),
+                canonical: canonical.contains(&node.root),
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    blocks.sort_by_key(|block| block.slot);
+
+    Ok(blocks)
+}
+
+/// Explains whether the head is optimistic and why.
+pub async fn get_optimistic_health<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<OptimisticHealth, warp::Rejection> {
+    let inner_chain = chain.clone();
+    let blocks = blocking_task(move || get_optimistic_blocks(&inner_chain)).await?;
+
+    let cached_head = chain.canonical_head.cached_head();
+    let is_optimistic = chain
+        .is_optimistic_or_invalid_head()
+        .map_err(beacon_chain_error)?;
+
+    let status = if !is_optimistic {
+        OptimisticHeadStatus::Verified
+    } else {
+        match chain.execution_layer.as_ref() {
+            None => OptimisticHeadStatus::NoExecutionEngine,
+            Some(el) if el.is_offline().await => OptimisticHeadStatus::ExecutionEngineOffline,
+            Some(el) if !el.is_synced().await => OptimisticHeadStatus::ExecutionEngineSyncing,
+            Some(_) => OptimisticHeadStatus::AwaitingValidation,
+        }
+    };
+
+    Ok(OptimisticHealth {
+        head_root: cached_head.head_block_root(),
+        head_slot: cached_head.head_slot(),
+        is_optimistic,
+        status,
+        optimistic_blocks: blocks.len(),
+        oldest_optimistic_slot: blocks.first().map(|block| block.slot),
+    })
+}
+
+/// Re-submits the payloads of the given optimistic blocks to the execution layer, or of the
+/// `MAX_REVALIDATION_BLOCKS` newest optimistic blocks if `block_roots` is empty.
+///
+/// Blocks are re-validated newest first, since a valid payload also validates its ancestors.
+pub async fn revalidate_optimistic_blocks<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    block_roots: Vec<Hash256>,
+) -> Result<Vec<OptimisticBlockRevalidation>, warp::Rejection> {
+    if block_roots.len() > MAX_REVALIDATION_BLOCKS {
+        return Err(custom_bad_request(format!(
+            "at most {} blocks can be re-validated at once",
+            MAX_REVALIDATION_BLOCKS
+        )));
+    }
+
+    let inner_chain = chain.clone();
+    let mut blocks = blocking_task(move || get_optimistic_blocks(&inner_chain)).await?;
+
+    if block_roots.is_empty() {
+        let excess = blocks.len().saturating_sub(MAX_REVALIDATION_BLOCKS);
+        blocks.drain(..excess);
+    } else {
+        if let Some(root) = block_roots
+            .iter()
+            .find(|root| !blocks.iter().any(|block| block.block_root == **root))
+        {
+            return Err(custom_bad_request(format!(
+                "block {:?} is not optimistic",
+                root
+            )));
+        }
+        blocks.retain(|block| block_roots.contains(&block.block_root));
+    }
+
+    let mut results = Vec::with_capacity(blocks.len());
+    for block in blocks.into_iter().rev() {
+        let block_root = block.block_root;
+
+        // The block may have been resolved by the re-validation of one of its relatives.
+        let execution_status = chain
+            .canonical_head
+            .fork_choice_read_lock()
+            .get_block_execution_status(&block_root);
+        let (status, error) = match execution_status {
+            Some(ExecutionStatus::Valid(_)) => (RevalidationStatus::Valid, None),
+            Some(ExecutionStatus::Invalid(_)) => (RevalidationStatus::Invalid, None),
+            _ => match chain.revalidate_optimistic_block(block_root).await {
+                Ok(PayloadStatus::Valid) => (RevalidationStatus::Valid, None),
+                Ok(PayloadStatus::Invalid { .. } | PayloadStatus::InvalidBlockHash { .. }) => {
+                    (RevalidationStatus::Invalid, None)
+                }
+                Ok(PayloadStatus::Syncing | PayloadStatus::Accepted) => {
+                    (RevalidationStatus::Optimistic, None)
+                }
+                Err(e) => (RevalidationStatus::Error, Some(format!("{:?}", e))),
+            },
+        };
+
+        results.push(OptimisticBlockRevalidation {
+            block_root,
+            status,
+            error,
+        });
+    }
+
+    Ok(results)
+}
@@ -189,6 +189,7 @@ async fn reconstruct_block<T: BeaconChainTypes>(
             el.get_payload_by_root(&payload_header.tree_hash_root())
         {
             info!(log, "Reconstructing a full block using a local payload"; "block_hash" => ?cached_payload.block_hash());
+            chain.spawn_builder_audit(block_root, cached_payload.clone());
             cached_payload
             // Otherwise, this means we are attempting a blind block proposal.
         } else {
@@ -202,6 +203,7 @@ async fn reconstruct_block<T: BeaconChainTypes>(
                     ))
                 })?;
             info!(log, "Successfully published a block to the builder network"; "block_hash" => ?full_payload.block_hash());
+            chain.spawn_builder_audit(block_root, full_payload.clone());
             full_payload
         };
 
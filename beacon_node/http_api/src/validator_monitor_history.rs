@@ -0,0 +1,43 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{ValidatorMonitorEpochRecord, ValidatorMonitorHistoryQuery};
+use types::Epoch;
+use warp_utils::reject::{beacon_chain_error, custom_bad_request};
+
+/// The number of epochs returned if `from_epoch` is not specified (~1 day on mainnet).
+const DEFAULT_HISTORY_EPOCHS: u64 = 225;
+
+/// The maximum number of epochs which may be requested at once.
+const MAX_HISTORY_EPOCHS: u64 = 8192;
+
+/// Returns the stored validator monitor records of `validator_index`, for the
+/// `/lighthouse/validator_monitor/{index}/history` endpoint.
+pub fn get_validator_monitor_history<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    validator_index: u64,
+    query: ValidatorMonitorHistoryQuery,
+) -> Result<Vec<ValidatorMonitorEpochRecord>, warp::Rejection> {
+    let to_epoch = match query.to_epoch {
+        Some(epoch) => epoch,
+        None => chain.epoch().map_err(beacon_chain_error)?,
+    };
+    let from_epoch = query
+        .from_epoch
+        .unwrap_or_else(|| to_epoch.saturating_sub(DEFAULT_HISTORY_EPOCHS - 1));
+
+    if from_epoch > to_epoch {
+        return Err(custom_bad_request(format!(
+            "from_epoch ({}) must not be greater than to_epoch ({})",
+            from_epoch, to_epoch
+        )));
+    }
+    if to_epoch - from_epoch >= Epoch::new(MAX_HISTORY_EPOCHS) {
+        return Err(custom_bad_request(format!(
+            "at most {} epochs may be requested",
+            MAX_HISTORY_EPOCHS
+        )));
+    }
+
+    chain
+        .validator_monitor_history(validator_index, from_epoch, to_epoch)
+        .map_err(beacon_chain_error)
+}
@@ -17,11 +17,12 @@ use lighthouse_network::{
         },
     },
     rpc::methods::{MetaData, MetaDataV2},
-    types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield, SyncState},
+    types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield, GossipValidationLog, SyncState},
     ConnectedPoint, Enr, NetworkGlobals, PeerId, PeerManager,
 };
 use logging::test_logger;
 use network::{NetworkReceivers, NetworkSenders};
+use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
 use slog::Logger;
 use std::future::Future;
@@ -55,6 +56,7 @@ pub struct ApiServer<E: EthSpec, SFut: Future<Output = ()>> {
     pub network_rx: NetworkReceivers<E>,
     pub local_enr: Enr,
     pub external_peer_id: PeerId,
+    pub network_globals: Arc<NetworkGlobals<E>>,
 }
 
 type Initializer<E> = Box<
@@ -149,14 +151,10 @@ pub async fn create_api_server_on_port<T: BeaconChainTypes>(
     });
     let enr_key = CombinedKey::generate_secp256k1();
     let enr = EnrBuilder::new("v4").build(&enr_key).unwrap();
-    let network_globals = Arc::new(NetworkGlobals::new(
-        enr.clone(),
-        Some(TCP_PORT),
-        None,
-        meta_data,
-        vec![],
-        &log,
-    ));
+    let mut network_globals =
+        NetworkGlobals::new(enr.clone(), Some(TCP_PORT), None, meta_data, vec![], &log);
+    network_globals.gossip_validation_log = Some(RwLock::new(GossipValidationLog::default()));
+    let network_globals = Arc::new(network_globals);
 
     // Only a peer manager can add peers, so we create a dummy manager.
     let config = lighthouse_network::peer_manager::config::Config::default();
@@ -195,7 +193,7 @@ pub async fn create_api_server_on_port<T: BeaconChainTypes>(
         },
         chain: Some(chain.clone()),
         network_senders: Some(network_senders),
-        network_globals: Some(network_globals),
+        network_globals: Some(network_globals.clone()),
         eth1_service: Some(eth1_service),
         log,
     });
@@ -214,5 +212,6 @@ pub async fn create_api_server_on_port<T: BeaconChainTypes>(
         network_rx: network_receivers,
         local_enr: enr,
         external_peer_id: peer_id,
+        network_globals,
     }
 }
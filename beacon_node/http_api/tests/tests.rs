@@ -6,6 +6,12 @@ use beacon_chain::{
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::{
+        BlockRewardsQuery, BuilderAuditOutcome, CircuitBreakerRequest, CircuitBreakerTrigger,
+        GossipValidationLogQuery, GossipValidationResult, OptimisticHeadStatus, PayloadSource,
+        PayloadValues, RevalidationStatus, ValidatorMonitorEpochRecord,
+        ValidatorMonitorHistoryQuery,
+    },
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{BlockId as CoreBlockId, StateId as CoreStateId, *},
@@ -19,7 +25,9 @@ use execution_layer::test_utils::{
 use futures::stream::{Stream, StreamExt};
 use futures::FutureExt;
 use http_api::{BlockId, StateId};
-use lighthouse_network::{Enr, EnrExt, PeerId};
+use lighthouse_network::{
+    Enr, EnrExt, MessageAcceptance, MessageId, NetworkGlobals, PeerId, TopicHash,
+};
 use network::NetworkReceivers;
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
@@ -72,12 +80,14 @@ struct ApiTester {
     network_rx: NetworkReceivers<E>,
     local_enr: Enr,
     external_peer_id: PeerId,
+    network_globals: Arc<NetworkGlobals<E>>,
     mock_builder: Option<Arc<TestingBuilder<E>>>,
 }
 
 struct ApiTesterConfig {
     spec: ChainSpec,
     builder_threshold: Option<u128>,
+    builder_boost_factor: Option<u64>,
 }
 
 impl Default for ApiTesterConfig {
@@ -87,6 +97,7 @@ impl Default for ApiTesterConfig {
         Self {
             spec,
             builder_threshold: None,
+            builder_boost_factor: None,
         }
     }
 }
@@ -121,7 +132,11 @@ impl ApiTester {
                 .logger(logging::test_logger())
                 .deterministic_keypairs(VALIDATOR_COUNT)
                 .fresh_ephemeral_store()
-                .mock_execution_layer_with_builder(beacon_url.clone(), config.builder_threshold)
+                .mock_execution_layer_with_builder(
+                    beacon_url.clone(),
+                    config.builder_threshold,
+                    config.builder_boost_factor,
+                )
                 .build(),
         );
 
@@ -236,6 +251,7 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
+            network_globals,
         } = create_api_server_on_port(chain.clone(), log, port).await;
 
         harness.runtime.task_executor.spawn(server, "api_server");
@@ -268,6 +284,7 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
+            network_globals,
             mock_builder,
         }
     }
@@ -322,6 +339,7 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
+            network_globals,
         } = create_api_server(chain.clone(), log).await;
 
         harness.runtime.task_executor.spawn(server, "api_server");
@@ -351,6 +369,7 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
+            network_globals,
             mock_builder: None,
         }
     }
@@ -377,8 +396,15 @@ impl ApiTester {
     }
 
     pub async fn new_mev_tester_no_builder_threshold() -> Self {
+        Self::new_mev_tester_with_builder_boost_factor(None).await
+    }
+
+    pub async fn new_mev_tester_with_builder_boost_factor(
+        builder_boost_factor: Option<u64>,
+    ) -> Self {
         let mut config = ApiTesterConfig {
             builder_threshold: Some(0),
+            builder_boost_factor,
             spec: E::default_spec(),
         };
         config.spec.altair_fork_epoch = Some(Epoch::new(0));
@@ -3074,6 +3100,79 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_builder_circuit_breaker_manual_trip(self) -> Self {
+        let status = self
+            .client
+            .get_lighthouse_builder_circuit_breaker()
+            .await
+            .unwrap()
+            .data;
+        assert!(!status.tripped);
+        assert_eq!(status.relays.len(), 1);
+        assert!(!status.relays[0].tripped);
+
+        // Tripping an unknown relay is rejected.
+        self.client
+            .post_lighthouse_builder_circuit_breaker_trip(&CircuitBreakerRequest {
+                relay: Some("http://unknown.relay".to_string()),
+            })
+            .await
+            .unwrap_err();
+
+        let relay = status.relays[0].relay.clone();
+        let status = self
+            .client
+            .post_lighthouse_builder_circuit_breaker_trip(&CircuitBreakerRequest {
+                relay: Some(relay),
+            })
+            .await
+            .unwrap()
+            .data;
+        assert!(!status.tripped);
+        assert!(status.relays[0].tripped);
+        assert_eq!(
+            status.relays[0].trigger,
+            Some(CircuitBreakerTrigger::Manual)
+        );
+        assert_eq!(status.relays[0].cooldown_until, None);
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        let payload: BlindedPayload<E> = self
+            .client
+            .get_validator_blinded_blocks::<E, BlindedPayload<E>>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data
+            .body()
+            .execution_payload()
+            .unwrap()
+            .into();
+
+        // If this cache is populated, it indicates fallback to the local EE was correctly used.
+        assert!(self
+            .chain
+            .execution_layer
+            .as_ref()
+            .unwrap()
+            .get_payload_by_root(&payload.tree_hash_root())
+            .is_some());
+
+        let status = self
+            .client
+            .post_lighthouse_builder_circuit_breaker_reset(&CircuitBreakerRequest::default())
+            .await
+            .unwrap()
+            .data;
+        assert!(!status.tripped);
+        assert!(!status.relays[0].tripped);
+        assert_eq!(status.relays[0].trigger, None);
+
+        self
+    }
+
     pub async fn test_builder_chain_health_skips_per_epoch(self) -> Self {
         // Fill an epoch with `builder_fallback_skips_per_epoch` skip slots.
         for i in 0..E::slots_per_epoch() {
@@ -3318,31 +3417,65 @@ impl ApiTester {
         self
     }
 
-    pub async fn test_builder_payload_chosen_when_more_profitable(self) -> Self {
+    /// Sign and publish `block`, returning the payload values reported alongside its rewards.
+    async fn publish_blinded_block_and_get_payload_values(
+        &self,
+        proposer_index: u64,
+        block: BlindedBeaconBlock<E>,
+    ) -> Option<PayloadValues> {
+        let fork = self.chain.canonical_head.cached_head().head_fork();
+        let genesis_validators_root = self.chain.genesis_validators_root;
+        let sk = &self.validator_keypairs()[proposer_index as usize].sk;
+        let slot = block.slot();
+
+        let signed_block = block.sign(sk, &fork, genesis_validators_root, &self.chain.spec);
+        self.client
+            .post_beacon_blinded_blocks(&signed_block)
+            .await
+            .unwrap();
+
+        let block_rewards = self
+            .client
+            .get_lighthouse_analysis_block_rewards(&BlockRewardsQuery {
+                start_slot: slot,
+                end_slot: slot,
+                include_attestations: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(block_rewards.len(), 1);
+        assert_eq!(block_rewards[0].block_root, signed_block.canonical_root());
+        block_rewards[0].payload_values.clone()
+    }
+
+    pub async fn test_builder_payload_chosen_when_more_profitable(
+        self,
+        builder_boost_factor: u64,
+    ) -> Self {
+        // The smallest bid which is more valuable than the local payload once boosted.
+        let builder_value =
+            ((DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 1) * 100 + builder_boost_factor as u128 - 1)
+                / builder_boost_factor as u128;
+
         // Mutate value.
         self.mock_builder
             .as_ref()
             .unwrap()
             .builder
-            .add_operation(Operation::Value(Uint256::from(
-                DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 1,
-            )));
+            .add_operation(Operation::Value(Uint256::from(builder_value)));
 
         let slot = self.chain.slot().unwrap();
         let epoch = self.chain.epoch().unwrap();
 
-        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
 
-        let payload: BlindedPayload<E> = self
+        let block = self
             .client
             .get_validator_blinded_blocks::<E, BlindedPayload<E>>(slot, &randao_reveal, None)
             .await
             .unwrap()
-            .data
-            .body()
-            .execution_payload()
-            .unwrap()
-            .into();
+            .data;
+        let payload: BlindedPayload<E> = block.body().execution_payload().unwrap().into();
 
         // The builder's payload should've been chosen, so this cache should not be populated
         assert!(self
@@ -3352,7 +3485,21 @@ impl ApiTester {
             .unwrap()
             .get_payload_by_root(&payload.tree_hash_root())
             .is_none());
-        self
+
+        let payload_values = self
+            .publish_blinded_block_and_get_payload_values(proposer_index, block)
+            .await;
+        assert_eq!(
+            payload_values,
+            Some(PayloadValues {
+                source: PayloadSource::Builder,
+                local_value: Some((DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI / 1_000_000_000) as u64),
+                builder_value: Some((builder_value / 1_000_000_000) as u64),
+                builder_boost_factor,
+            })
+        );
+
+        self.skip_slots(1)
     }
 
     pub async fn test_local_payload_chosen_when_equally_profitable(self) -> Self {
@@ -3392,31 +3539,33 @@ impl ApiTester {
         self
     }
 
-    pub async fn test_local_payload_chosen_when_more_profitable(self) -> Self {
+    pub async fn test_local_payload_chosen_when_more_profitable(
+        self,
+        builder_boost_factor: u64,
+    ) -> Self {
+        // The largest bid which is less valuable than the local payload once boosted.
+        let builder_value =
+            DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI * 100 / builder_boost_factor as u128 - 1;
+
         // Mutate value.
         self.mock_builder
             .as_ref()
             .unwrap()
             .builder
-            .add_operation(Operation::Value(Uint256::from(
-                DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI - 1,
-            )));
+            .add_operation(Operation::Value(Uint256::from(builder_value)));
 
         let slot = self.chain.slot().unwrap();
         let epoch = self.chain.epoch().unwrap();
 
-        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
 
-        let payload: BlindedPayload<E> = self
+        let block = self
             .client
             .get_validator_blinded_blocks::<E, BlindedPayload<E>>(slot, &randao_reveal, None)
             .await
             .unwrap()
-            .data
-            .body()
-            .execution_payload()
-            .unwrap()
-            .into();
+            .data;
+        let payload: BlindedPayload<E> = block.body().execution_payload().unwrap().into();
 
         // The local payload should've been chosen, so this cache should be populated
         assert!(self
@@ -3426,6 +3575,90 @@ impl ApiTester {
             .unwrap()
             .get_payload_by_root(&payload.tree_hash_root())
             .is_some());
+
+        let payload_values = self
+            .publish_blinded_block_and_get_payload_values(proposer_index, block)
+            .await;
+        assert_eq!(
+            payload_values,
+            Some(PayloadValues {
+                source: PayloadSource::Local,
+                local_value: Some((DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI / 1_000_000_000) as u64),
+                builder_value: Some((builder_value / 1_000_000_000) as u64),
+                builder_boost_factor,
+            })
+        );
+
+        self.skip_slots(1)
+    }
+
+    pub async fn test_get_lighthouse_builder_audits(self) -> Self {
+        assert!(self
+            .client
+            .get_lighthouse_builder_audits()
+            .await
+            .unwrap()
+            .data
+            .is_empty());
+
+        // Make the local payload more profitable than the builder's bid.
+        self.mock_builder
+            .as_ref()
+            .unwrap()
+            .builder
+            .add_operation(Operation::Value(Uint256::from(
+                DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI - 1,
+            )));
+
+        let fork = self.chain.canonical_head.cached_head().head_fork();
+        let genesis_validators_root = self.chain.genesis_validators_root;
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
+        let sk = self.validator_keypairs()[proposer_index as usize]
+            .sk
+            .clone();
+
+        let block = self
+            .client
+            .get_validator_blinded_blocks::<E, BlindedPayload<E>>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data;
+        let signed_block = block.sign(&sk, &fork, genesis_validators_root, &self.chain.spec);
+        self.client
+            .post_beacon_blinded_blocks(&signed_block)
+            .await
+            .unwrap();
+
+        // The audit of a local payload is stored as soon as the block is published.
+        let mut audits = vec![];
+        for _ in 0..50 {
+            audits = self
+                .client
+                .get_lighthouse_builder_audits()
+                .await
+                .unwrap()
+                .data;
+            if !audits.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        assert_eq!(audits.len(), 1);
+        let audit = &audits[0];
+        assert_eq!(audit.block_root, signed_block.canonical_root());
+        assert_eq!(audit.slot, slot);
+        assert_eq!(audit.outcome, BuilderAuditOutcome::LocalPayload);
+        assert_eq!(audit.bid, None);
+        assert_eq!(audit.bids.len(), 1);
+        assert_eq!(
+            audit.bids[0].value,
+            Uint256::from(DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI - 1)
+        );
+
         self
     }
 
@@ -3541,12 +3774,309 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_gossip_validation_log(self) -> Self {
+        let peer_id = PeerId::random();
+        let block_topic = TopicHash::from_raw("/eth2/00000000/beacon_block/ssz_snappy");
+        let attestation_topic =
+            TopicHash::from_raw("/eth2/00000000/beacon_attestation_0/ssz_snappy");
+        let messages = [
+            (&block_topic, MessageAcceptance::Ignore, Some("FutureSlot")),
+            (&attestation_topic, MessageAcceptance::Accept, None),
+            (
+                &attestation_topic,
+                MessageAcceptance::Reject,
+                Some("InvalidSignature"),
+            ),
+            (
+                &block_topic,
+                MessageAcceptance::Reject,
+                Some("ProposalSignatureInvalid"),
+            ),
+        ];
+        {
+            let mut log = self
+                .network_globals
+                .gossip_validation_log
+                .as_ref()
+                .unwrap()
+                .write();
+            for (i, (topic, acceptance, error)) in messages.iter().enumerate() {
+                let message_id = MessageId::new(&[i as u8]);
+                log.message_received(message_id.clone(), topic);
+                if let Some(error) = error {
+                    log.note_error(&message_id, error.to_string());
+                }
+                log.message_validated(&message_id, &peer_id, acceptance);
+            }
+        }
+
+        // The accepted message is not recorded, the others are returned oldest first.
+        let entries = self
+            .client
+            .get_lighthouse_gossip_validation_log(&GossipValidationLogQuery::default())
+            .await
+            .unwrap()
+            .data;
+        let errors = entries
+            .iter()
+            .map(|entry| entry.error.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec!["FutureSlot", "InvalidSignature", "ProposalSignatureInvalid"]
+        );
+        assert!(entries
+            .iter()
+            .all(|entry| entry.peer_id == peer_id.to_string()));
+
+        let query = GossipValidationLogQuery {
+            result: Some(GossipValidationResult::Reject),
+            topic: Some("beacon_block".into()),
+            limit: None,
+        };
+        let entries = self
+            .client
+            .get_lighthouse_gossip_validation_log(&query)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message_id, MessageId::new(&[3]).to_string());
+        assert_eq!(entries[0].topic.as_deref(), Some(block_topic.as_str()));
+
+        let query = GossipValidationLogQuery {
+            limit: Some(1),
+            ..GossipValidationLogQuery::default()
+        };
+        let entries = self
+            .client
+            .get_lighthouse_gossip_validation_log(&query)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].error.as_deref(),
+            Some("ProposalSignatureInvalid")
+        );
+
+        self
+    }
+
     pub async fn test_get_lighthouse_proto_array(self) -> Self {
         self.client.get_lighthouse_proto_array().await.unwrap();
 
         self
     }
 
+    pub async fn test_get_lighthouse_optimistic_blocks(self) -> Self {
+        let blocks = self
+            .client
+            .get_lighthouse_optimistic_blocks()
+            .await
+            .unwrap()
+            .data;
+        assert!(blocks.is_empty());
+
+        let head_root = self.chain.head_beacon_block_root();
+        let health = self
+            .client
+            .get_lighthouse_optimistic_blocks_health()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(health.head_root, head_root);
+        assert!(!health.is_optimistic);
+        assert_eq!(health.status, OptimisticHeadStatus::Verified);
+        assert_eq!(health.optimistic_blocks, 0);
+
+        let results = self
+            .client
+            .post_lighthouse_optimistic_blocks_revalidate(&[])
+            .await
+            .unwrap()
+            .data;
+        assert!(results.is_empty());
+
+        // Blocks which are not optimistic can't be re-validated.
+        assert!(self
+            .client
+            .post_lighthouse_optimistic_blocks_revalidate(&[head_root])
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_revalidate_optimistic_blocks(self) -> Self {
+        let mock_el = self.harness.mock_execution_layer.as_ref().unwrap();
+
+        // Import two blocks whilst the execution layer is syncing.
+        mock_el.server.all_payloads_syncing(true);
+        let mut block_roots = vec![];
+        for _ in 0..2 {
+            self.harness.advance_slot();
+            block_roots.push(
+                self.harness
+                    .extend_chain(
+                        1,
+                        BlockStrategy::OnCanonicalHead,
+                        AttestationStrategy::AllValidators,
+                    )
+                    .await,
+            );
+        }
+
+        let blocks = self
+            .client
+            .get_lighthouse_optimistic_blocks()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            blocks
+                .iter()
+                .map(|block| block.block_root)
+                .collect::<Vec<_>>(),
+            block_roots
+        );
+        assert!(blocks.iter().all(|block| block.canonical));
+        let first_block_hash = blocks[0].execution_block_hash;
+
+        let health = self
+            .client
+            .get_lighthouse_optimistic_blocks_health()
+            .await
+            .unwrap()
+            .data;
+        assert!(health.is_optimistic);
+        assert_eq!(health.optimistic_blocks, 2);
+        assert_eq!(health.oldest_optimistic_slot, Some(blocks[0].slot));
+
+        // Too many blocks can't be re-validated at once.
+        assert!(self
+            .client
+            .post_lighthouse_optimistic_blocks_revalidate(&vec![block_roots[0]; 33])
+            .await
+            .is_err());
+
+        // The execution layer has synced and finds the first payload valid.
+        mock_el.server.all_payloads_valid();
+        let results = self
+            .client
+            .post_lighthouse_optimistic_blocks_revalidate(&block_roots[..1])
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].block_root, block_roots[0]);
+        assert_eq!(results[0].status, RevalidationStatus::Valid);
+
+        let blocks = self
+            .client
+            .get_lighthouse_optimistic_blocks()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].block_root, block_roots[1]);
+
+        // The second payload is invalid, descending from the first.
+        mock_el
+            .server
+            .all_payloads_invalid_on_new_payload(first_block_hash);
+        let results = self
+            .client
+            .post_lighthouse_optimistic_blocks_revalidate(&[])
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].block_root, block_roots[1]);
+        assert_eq!(results[0].status, RevalidationStatus::Invalid);
+
+        assert!(self
+            .client
+            .get_lighthouse_optimistic_blocks()
+            .await
+            .unwrap()
+            .data
+            .is_empty());
+        assert_eq!(self.chain.head_beacon_block_root(), block_roots[0]);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_op_pool_summary(self) -> Self {
+        let summary = self
+            .client
+            .get_lighthouse_op_pool_summary()
+            .await
+            .unwrap()
+            .data;
+
+        let reward_per_slashing =
+            self.chain.spec.max_effective_balance / self.chain.spec.whistleblower_reward_quotient;
+        assert!(summary.slot > self.chain.head_snapshot().beacon_block.slot());
+        assert_eq!(summary.proposer_slashings.count, 1);
+        assert_eq!(summary.proposer_slashings.total_reward, reward_per_slashing);
+        assert_eq!(summary.attester_slashings.count, 1);
+        assert_eq!(
+            summary.attester_slashings.total_reward,
+            2 * reward_per_slashing
+        );
+        assert_eq!(summary.voluntary_exits.count, 0);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_op_pool_dry_run(self) -> Self {
+        let head_slot = self.chain.head_snapshot().beacon_block.slot();
+
+        // Blocks can only be dry-run on top of the head.
+        self.client
+            .get_lighthouse_op_pool_dry_run::<E>(head_slot)
+            .await
+            .unwrap_err();
+
+        let dry_run = self
+            .client
+            .get_lighthouse_op_pool_dry_run::<E>(head_slot + 1)
+            .await
+            .unwrap()
+            .data;
+
+        let reward_per_slashing =
+            self.chain.spec.max_effective_balance / self.chain.spec.whistleblower_reward_quotient;
+        assert_eq!(dry_run.slot, head_slot + 1);
+        assert_eq!(dry_run.proposer_slashings.len(), 1);
+        assert_eq!(
+            dry_run.proposer_slashings[0].operation,
+            self.proposer_slashing
+        );
+        assert_eq!(dry_run.proposer_slashings[0].reward, reward_per_slashing);
+        assert_eq!(dry_run.attester_slashings.len(), 1);
+        assert_eq!(
+            dry_run.attester_slashings[0].operation,
+            self.attester_slashing
+        );
+        assert_eq!(
+            dry_run.attester_slashings[0].reward,
+            2 * reward_per_slashing
+        );
+        assert_eq!(
+            dry_run.total_reward,
+            dry_run
+                .attestations
+                .iter()
+                .map(|packed| packed.reward)
+                .sum::<u64>()
+                + 3 * reward_per_slashing
+        );
+
+        self
+    }
+
     pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
         let epoch = self.chain.epoch().unwrap() - 1;
         self.client
@@ -3567,12 +4097,75 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_validator_monitor_history(self) -> Self {
+        let record = |epoch: u64| ValidatorMonitorEpochRecord {
+            epoch: Epoch::new(epoch),
+            validator_index: 1,
+            attestation_hit: true,
+            attestation_head_hit: true,
+            attestation_target_hit: false,
+            attestation_inclusion_distance: Some(2),
+            sync_signature_inclusions: 0,
+            blocks_proposed: 1,
+            balance: Some(32_000_000_000),
+            balance_delta: Some(-1_000),
+        };
+        self.chain
+            .persist_validator_monitor_history((0..4).map(record).collect());
+
+        let query = |from_epoch: u64, to_epoch: u64| ValidatorMonitorHistoryQuery {
+            from_epoch: Some(Epoch::new(from_epoch)),
+            to_epoch: Some(Epoch::new(to_epoch)),
+        };
+
+        let history = self
+            .client
+            .get_lighthouse_validator_monitor_history(1, &query(1, 2))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(history, vec![record(1), record(2)]);
+
+        // Validators without records have an empty history.
+        assert!(self
+            .client
+            .get_lighthouse_validator_monitor_history(2, &query(0, 3))
+            .await
+            .unwrap()
+            .data
+            .is_empty());
+
+        assert_eq!(
+            self.client
+                .get_lighthouse_validator_monitor_history(1, &query(3, 2))
+                .await
+                .unwrap_err()
+                .status()
+                .map(Into::into),
+            Some(400),
+            "should not accept a from_epoch after the to_epoch"
+        );
+
+        self
+    }
+
     pub async fn test_get_lighthouse_eth1_syncing(self) -> Self {
         self.client.get_lighthouse_eth1_syncing().await.unwrap();
 
         self
     }
 
+    pub async fn test_get_lighthouse_eth1_engine(self) -> Self {
+        let health = self.client.get_lighthouse_eth1_engine().await.unwrap().data;
+
+        // The head is prior to the merge.
+        assert_eq!(health.head_block_number, None);
+        assert_eq!(health.engines.len(), 1);
+        assert_eq!(health.engines[0].head_lag, None);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_eth1_block_cache(self) -> Self {
         let blocks = self.client.get_lighthouse_eth1_block_cache().await.unwrap();
 
@@ -4129,6 +4722,20 @@ async fn beacon_pools_post_voluntary_exits_invalid() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn lighthouse_op_pool() {
+    ApiTester::new()
+        .await
+        .test_post_beacon_pool_attester_slashings_valid()
+        .await
+        .test_post_beacon_pool_proposer_slashings_valid()
+        .await
+        .test_get_lighthouse_op_pool_summary()
+        .await
+        .test_get_lighthouse_op_pool_dry_run()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn config_get() {
     ApiTester::new()
@@ -4473,6 +5080,14 @@ async fn builder_chain_health_skips() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_circuit_breaker_manual_trip() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_builder_circuit_breaker_manual_trip()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn builder_chain_health_skips_per_epoch() {
     ApiTester::new_mev_tester()
@@ -4489,6 +5104,14 @@ async fn builder_chain_health_epochs_since_finalization() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn revalidate_optimistic_blocks() {
+    ApiTester::new_with_hard_forks(true, true)
+        .await
+        .test_revalidate_optimistic_blocks()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn builder_chain_health_optimistic_head() {
     ApiTester::new_mev_tester()
@@ -4509,11 +5132,31 @@ async fn builder_inadequate_builder_threshold() {
 async fn builder_payload_chosen_by_profit() {
     ApiTester::new_mev_tester_no_builder_threshold()
         .await
-        .test_builder_payload_chosen_when_more_profitable()
+        .test_builder_payload_chosen_when_more_profitable(100)
         .await
         .test_local_payload_chosen_when_equally_profitable()
         .await
-        .test_local_payload_chosen_when_more_profitable()
+        .test_local_payload_chosen_when_more_profitable(100)
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_payload_chosen_by_boosted_profit() {
+    for builder_boost_factor in [50, 120] {
+        ApiTester::new_mev_tester_with_builder_boost_factor(Some(builder_boost_factor))
+            .await
+            .test_builder_payload_chosen_when_more_profitable(builder_boost_factor)
+            .await
+            .test_local_payload_chosen_when_more_profitable(builder_boost_factor)
+            .await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_audits() {
+    ApiTester::new_mev_tester_no_builder_threshold()
+        .await
+        .test_get_lighthouse_builder_audits()
         .await;
 }
 
@@ -4521,6 +5164,7 @@ async fn builder_payload_chosen_by_profit() {
 async fn builder_works_post_capella() {
     let mut config = ApiTesterConfig {
         builder_threshold: Some(0),
+        builder_boost_factor: None,
         spec: E::default_spec(),
     };
     config.spec.altair_fork_epoch = Some(Epoch::new(0));
@@ -4545,14 +5189,22 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_syncing()
         .await
+        .test_get_lighthouse_gossip_validation_log()
+        .await
         .test_get_lighthouse_proto_array()
         .await
+        .test_get_lighthouse_optimistic_blocks()
+        .await
         .test_get_lighthouse_validator_inclusion()
         .await
         .test_get_lighthouse_validator_inclusion_global()
         .await
+        .test_get_lighthouse_validator_monitor_history()
+        .await
         .test_get_lighthouse_eth1_syncing()
         .await
+        .test_get_lighthouse_eth1_engine()
+        .await
         .test_get_lighthouse_eth1_block_cache()
         .await
         .test_get_lighthouse_eth1_deposit_cache()
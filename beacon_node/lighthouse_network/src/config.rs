@@ -1,3 +1,4 @@
+use crate::discovery::{DEFAULT_MAX_CONCURRENT_SUBNET_QUERIES, DEFAULT_MAX_SUBNETS_IN_QUERY};
 use crate::listen_addr::{ListenAddr, ListenAddress};
 use crate::rpc::config::OutboundRateLimiterConfig;
 use crate::types::GossipKind;
@@ -38,6 +39,9 @@ const GOSSIP_MAX_SIZE_POST_MERGE: usize = 10 * 1_048_576; // 10M
 /// another 500ms for "fudge factor".
 pub const DUPLICATE_CACHE_TIME: Duration = Duration::from_secs(33 * 12 + 1);
 
+/// The default maximum size of a network recording, in megabytes.
+pub const DEFAULT_RECORD_NETWORK_TRAFFIC_MAX_SIZE_MBS: usize = 16 * 1_024;
+
 // We treat uncompressed messages as invalid and never use the INVALID_SNAPPY_DOMAIN as in the
 // specification. We leave it here for posterity.
 // const MESSAGE_DOMAIN_INVALID_SNAPPY: [u8; 4] = [0, 0, 0, 0];
@@ -107,6 +111,16 @@ pub struct Config {
     /// Disables the discovery protocol from starting.
     pub disable_discovery: bool,
 
+    /// The maximum number of subnet discovery queries to run concurrently.
+    pub discovery_max_concurrent_subnet_queries: usize,
+
+    /// The maximum number of subnets to search for in a single subnet discovery query.
+    pub discovery_max_subnets_in_query: usize,
+
+    /// When pruning excess peers, retain a diverse mix of clients and IP prefixes and remove peers
+    /// that are not prepared for an upcoming fork.
+    pub peer_diversity: bool,
+
     /// Attempt to construct external port mappings with UPnP.
     pub upnp_enabled: bool,
 
@@ -137,8 +151,25 @@ pub struct Config {
     /// Whether light client protocols should be enabled.
     pub enable_light_client_server: bool,
 
+    /// Whether to serve finalized states to peers performing checkpoint sync over libp2p.
+    pub enable_finalized_state_server: bool,
+
+    /// Whether range sync should confirm each downloaded batch with a second peer before
+    /// processing it.
+    pub range_sync_cross_check: bool,
+
     /// Configuration for the outbound rate limiter (requests made by this node).
     pub outbound_rate_limiter_config: Option<OutboundRateLimiterConfig>,
+
+    /// If set, all inbound gossip messages and RPC responses are recorded to this file.
+    pub record_network_traffic: Option<PathBuf>,
+
+    /// The maximum size of the network recording, in megabytes. Recording stops once it is
+    /// reached.
+    pub record_network_traffic_max_size_mbs: usize,
+
+    /// Whether to keep a log of recent gossip messages which were ignored or rejected.
+    pub gossip_validation_log: bool,
 }
 
 impl Config {
@@ -311,6 +342,9 @@ impl Default for Config {
             trusted_peers: vec![],
             client_version: lighthouse_version::version_with_platform(),
             disable_discovery: false,
+            discovery_max_concurrent_subnet_queries: DEFAULT_MAX_CONCURRENT_SUBNET_QUERIES,
+            discovery_max_subnets_in_query: DEFAULT_MAX_SUBNETS_IN_QUERY,
+            peer_diversity: false,
             upnp_enabled: true,
             network_load: 3,
             private: false,
@@ -320,7 +354,12 @@ impl Default for Config {
             topics: Vec::new(),
             metrics_enabled: false,
             enable_light_client_server: false,
+            enable_finalized_state_server: false,
+            range_sync_cross_check: false,
             outbound_rate_limiter_config: None,
+            record_network_traffic: None,
+            record_network_traffic_max_size_mbs: DEFAULT_RECORD_NETWORK_TRAFFIC_MAX_SIZE_MBS,
+            gossip_validation_log: false,
         }
     }
 }
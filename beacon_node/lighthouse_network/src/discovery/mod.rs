@@ -44,7 +44,7 @@ use std::{
     time::{Duration, Instant},
 };
 use tokio::sync::mpsc;
-use types::{EnrForkId, EthSpec};
+use types::{ChainSpec, EnrForkId, Epoch, EthSpec};
 
 mod subnet_predicate;
 pub use subnet_predicate::subnet_predicate;
@@ -55,13 +55,13 @@ pub const ENR_FILENAME: &str = "enr.dat";
 const TARGET_PEERS_FOR_GROUPED_QUERY: usize = 6;
 /// Number of times to attempt a discovery request.
 const MAX_DISCOVERY_RETRY: usize = 3;
-/// The maximum number of concurrent subnet discovery queries.
+/// The default maximum number of concurrent subnet discovery queries.
 /// Note: we always allow a single FindPeers query, so we would be
-/// running a maximum of `MAX_CONCURRENT_SUBNET_QUERIES + 1`
+/// running a maximum of `max_concurrent_subnet_queries + 1`
 /// discovery queries at a time.
-const MAX_CONCURRENT_SUBNET_QUERIES: usize = 2;
-/// The max number of subnets to search for in a single subnet discovery query.
-const MAX_SUBNETS_IN_QUERY: usize = 3;
+pub const DEFAULT_MAX_CONCURRENT_SUBNET_QUERIES: usize = 2;
+/// The default max number of subnets to search for in a single subnet discovery query.
+pub const DEFAULT_MAX_SUBNETS_IN_QUERY: usize = 3;
 /// The number of closest peers to search for when doing a regular peer search.
 ///
 /// We could reduce this constant to speed up queries however at the cost of security. It will
@@ -160,6 +160,12 @@ pub struct Discovery<TSpec: EthSpec> {
     /// A collection of network constants that can be read from other threads.
     network_globals: Arc<NetworkGlobals<TSpec>>,
 
+    /// The chain spec, used to compute the long-lived attestation subnets of discovered peers.
+    spec: ChainSpec,
+
+    /// The current epoch, used to compute the long-lived attestation subnets of discovered peers.
+    current_epoch: Epoch,
+
     /// Indicates if we are actively searching for peers. We only allow a single FindPeers query at
     /// a time, regardless of the query concurrency.
     find_peer_active: bool,
@@ -167,6 +173,12 @@ pub struct Discovery<TSpec: EthSpec> {
     /// A queue of subnet queries to be processed.
     queued_queries: VecDeque<SubnetQuery>,
 
+    /// The maximum number of subnet queries that may be active at once.
+    max_concurrent_subnet_queries: usize,
+
+    /// The maximum number of subnets that are grouped into a single query.
+    max_subnets_in_query: usize,
+
     /// Active discovery queries.
     active_queries: FuturesUnordered<std::pin::Pin<Box<dyn Future<Output = QueryResult> + Send>>>,
 
@@ -187,6 +199,7 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
         local_key: &Keypair,
         config: &NetworkConfig,
         network_globals: Arc<NetworkGlobals<TSpec>>,
+        spec: &ChainSpec,
         log: &slog::Logger,
     ) -> error::Result<Self> {
         let log = log.clone();
@@ -298,8 +311,15 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
         Ok(Self {
             cached_enrs: LruCache::new(50),
             network_globals,
+            spec: spec.clone(),
+            current_epoch: spec.genesis_slot.epoch(TSpec::slots_per_epoch()),
             find_peer_active: false,
             queued_queries: VecDeque::with_capacity(10),
+            max_concurrent_subnet_queries: std::cmp::max(
+                config.discovery_max_concurrent_subnet_queries,
+                1,
+            ),
+            max_subnets_in_query: std::cmp::max(config.discovery_max_subnets_in_query, 1),
             active_queries: FuturesUnordered::new(),
             discv5,
             event_stream,
@@ -309,6 +329,16 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
         })
     }
 
+    /// Updates the epoch used to compute the long-lived attestation subnets of discovered peers.
+    pub fn update_current_epoch(&mut self, epoch: Epoch) {
+        self.current_epoch = epoch;
+    }
+
+    /// Returns the predicate which matches peers on any of the given subnets.
+    pub fn subnet_predicate(&self, subnets: Vec<Subnet>) -> impl Fn(&Enr) -> bool + Send {
+        subnet_predicate::<TSpec>(subnets, self.current_epoch, &self.spec, &self.log)
+    }
+
     /// Return the nodes local ENR.
     pub fn local_enr(&self) -> Enr {
         self.discv5.local_enr()
@@ -374,6 +404,18 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
         self.discv5.table_entries_enr()
     }
 
+    /// Requests the ENRs at the given log `distances` directly from the node `enr`, bypassing the
+    /// query queue. This is used to crawl the DHT.
+    pub fn find_node_designated_peer(
+        &self,
+        enr: Enr,
+        distances: Vec<u64>,
+    ) -> impl Future<Output = Result<Vec<Enr>, String>> {
+        self.discv5
+            .find_node_designated_peer(enr, distances)
+            .map(|result| result.map_err(|e| format!("{:?}", e)))
+    }
+
     /// Returns the ENR of a known peer if it exists.
     pub fn enr_of_peer(&mut self, peer_id: &PeerId) -> Option<Enr> {
         // first search the local cache
@@ -650,9 +692,11 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
                 subnet_queries.push(subnet_query);
 
                 // We want to start a grouped subnet query if:
-                //  1. We've grouped MAX_SUBNETS_IN_QUERY subnets together.
+                //  1. We've grouped `max_subnets_in_query` subnets together.
                 //  2. There are no more messages in the queue.
-                if subnet_queries.len() == MAX_SUBNETS_IN_QUERY || self.queued_queries.is_empty() {
+                if subnet_queries.len() == self.max_subnets_in_query
+                    || self.queued_queries.is_empty()
+                {
                     // This query is for searching for peers of a particular subnet
                     // Drain subnet_queries so we can re-use it as we continue to process the queue
                     let grouped_queries: Vec<SubnetQuery> = subnet_queries.drain(..).collect();
@@ -672,7 +716,7 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
         self.active_queries
             .len()
             .saturating_sub(self.find_peer_active as usize) // We only count active subnet queries
-            >= MAX_CONCURRENT_SUBNET_QUERIES
+            >= self.max_concurrent_subnet_queries
     }
 
     /// Runs a discovery request for a given group of subnets.
@@ -715,7 +759,7 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
         // Only start a discovery query if we have a subnet to look for.
         if !filtered_subnet_queries.is_empty() {
             // build the subnet predicate as a combination of the eth2_fork_predicate and the subnet predicate
-            let subnet_predicate = subnet_predicate::<TSpec>(filtered_subnets, &self.log);
+            let subnet_predicate = self.subnet_predicate(filtered_subnets);
 
             debug!(
                 self.log,
@@ -855,8 +899,7 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
                             self.add_subnet_query(query.subnet, query.min_ttl, query.retries + 1);
 
                             // Check the specific subnet against the enr
-                            let subnet_predicate =
-                                subnet_predicate::<TSpec>(vec![query.subnet], &self.log);
+                            let subnet_predicate = self.subnet_predicate(vec![query.subnet]);
 
                             r.iter()
                                 .filter(|enr| subnet_predicate(enr))
@@ -1104,7 +1147,7 @@ mod tests {
     use crate::rpc::methods::{MetaData, MetaDataV2};
     use enr::EnrBuilder;
     use slog::{o, Drain};
-    use types::{BitVector, MinimalEthSpec, SubnetId};
+    use types::{BitVector, MinimalEthSpec, SubnetId, Uint256};
 
     type E = MinimalEthSpec;
 
@@ -1139,9 +1182,15 @@ mod tests {
             vec![],
             &log,
         );
-        Discovery::new(&keypair, &config, Arc::new(globals), &log)
-            .await
-            .unwrap()
+        Discovery::new(
+            &keypair,
+            &config,
+            Arc::new(globals),
+            &E::default_spec(),
+            &log,
+        )
+        .await
+        .unwrap()
     }
 
     #[tokio::test]
@@ -1183,46 +1232,72 @@ mod tests {
         assert_eq!(discovery.queued_queries.len(), 0);
     }
 
-    fn make_enr(subnet_ids: Vec<usize>) -> Enr {
+    fn make_enr(enr_key: &CombinedKey, subnet_ids: &[SubnetId]) -> Enr {
         let mut builder = EnrBuilder::new("v4");
-        let keypair = libp2p::identity::Keypair::generate_secp256k1();
-        let enr_key: CombinedKey = CombinedKey::from_libp2p(&keypair).unwrap();
 
         // set the "attnets" field on our ENR
         let mut bitfield = BitVector::<ssz_types::typenum::U64>::new();
         for id in subnet_ids {
-            bitfield.set(id, true).unwrap();
+            bitfield.set(**id as usize, true).unwrap();
         }
 
         builder.add_value(ATTESTATION_BITFIELD_ENR_KEY, &bitfield.as_ssz_bytes());
-        builder.build(&enr_key).unwrap()
+        builder.build(enr_key).unwrap()
+    }
+
+    /// Generates an ENR key along with the long-lived subnets of its node ID.
+    fn long_lived_enr_key(discovery: &Discovery<E>) -> (CombinedKey, Vec<SubnetId>) {
+        let keypair = libp2p::identity::Keypair::generate_secp256k1();
+        let enr_key: CombinedKey = CombinedKey::from_libp2p(&keypair).unwrap();
+        let node_id: Uint256 = make_enr(&enr_key, &[]).node_id().raw().into();
+        let (subnets, _) = SubnetId::compute_subnets_for_epoch::<E>(
+            node_id,
+            discovery.current_epoch,
+            &discovery.spec,
+        )
+        .unwrap();
+        (enr_key, subnets.collect())
     }
 
     #[tokio::test]
     async fn test_completed_subnet_queries() {
         let mut discovery = build_discovery().await;
+        // Give each peer two long-lived subnets, so that a peer can be on both queried subnets.
+        discovery.spec.subnets_per_node = 2;
+
+        // A peer which is subscribed to both queried subnets.
+        let (enr_key1, subnets) = long_lived_enr_key(&discovery);
+        let (subnet1, subnet2) = (subnets[0], subnets[1]);
+        // A peer which is only subscribed to the second queried subnet.
+        let (enr_key2, _) = std::iter::repeat_with(|| long_lived_enr_key(&discovery))
+            .find(|(_, subnets)| subnets.contains(&subnet2) && !subnets.contains(&subnet1))
+            .unwrap();
+        // A peer which advertises the first queried subnet, but is not subscribed to it.
+        let (enr_key3, _) = std::iter::repeat_with(|| long_lived_enr_key(&discovery))
+            .find(|(_, subnets)| !subnets.contains(&subnet1))
+            .unwrap();
+
         let now = Instant::now();
         let instant1 = Some(now + Duration::from_secs(10));
         let instant2 = Some(now + Duration::from_secs(5));
 
         let query = QueryType::Subnet(vec![
             SubnetQuery {
-                subnet: Subnet::Attestation(SubnetId::new(1)),
+                subnet: Subnet::Attestation(subnet1),
                 min_ttl: instant1,
                 retries: 0,
             },
             SubnetQuery {
-                subnet: Subnet::Attestation(SubnetId::new(2)),
+                subnet: Subnet::Attestation(subnet2),
                 min_ttl: instant2,
                 retries: 0,
             },
         ]);
 
-        // Create enr which is subscribed to subnets 1 and 2
-        let enr1 = make_enr(vec![1, 2]);
-        let enr2 = make_enr(vec![2]);
+        let enr1 = make_enr(&enr_key1, &[subnet1, subnet2]);
+        let enr2 = make_enr(&enr_key2, &[subnet2]);
         // Unwanted enr for the given grouped query
-        let enr3 = make_enr(vec![3]);
+        let enr3 = make_enr(&enr_key3, &[subnet1]);
 
         let enrs: Vec<Enr> = vec![enr1.clone(), enr2, enr3];
         let results = discovery
@@ -3,16 +3,24 @@ use super::*;
 use crate::types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield};
 use slog::trace;
 use std::ops::Deref;
+use types::{ChainSpec, Epoch, SubnetId, Uint256};
 
 /// Returns the predicate for a given subnet.
+///
+/// A peer is only considered to be on an attestation subnet if its ENR `attnets` advertises the
+/// subnet and the subnet is one of the long-lived subnets derived from the peer's node ID for
+/// `epoch`.
 pub fn subnet_predicate<TSpec>(
     subnets: Vec<Subnet>,
+    epoch: Epoch,
+    spec: &ChainSpec,
     log: &slog::Logger,
 ) -> impl Fn(&Enr) -> bool + Send
 where
     TSpec: EthSpec,
 {
     let log_clone = log.clone();
+    let spec = spec.clone();
 
     move |enr: &Enr| {
         let attestation_bitfield: EnrAttestationBitfield<TSpec> =
@@ -26,10 +34,15 @@ where
         let sync_committee_bitfield: Result<EnrSyncCommitteeBitfield<TSpec>, _> =
             enr.sync_committee_bitfield::<TSpec>();
 
+        let long_lived_subnets = compute_long_lived_subnets::<TSpec>(enr, epoch, &spec);
+
         let predicate = subnets.iter().any(|subnet| match subnet {
-            Subnet::Attestation(s) => attestation_bitfield
-                .get(*s.deref() as usize)
-                .unwrap_or(false),
+            Subnet::Attestation(s) => {
+                attestation_bitfield
+                    .get(*s.deref() as usize)
+                    .unwrap_or(false)
+                    && long_lived_subnets.contains(s)
+            }
             Subnet::SyncCommittee(s) => sync_committee_bitfield
                 .as_ref()
                 .map_or(false, |b| b.get(*s.deref() as usize).unwrap_or(false)),
@@ -45,3 +58,71 @@ where
         predicate
     }
 }
+
+/// Returns the long-lived attestation subnets of the peer with the given ENR.
+///
+/// The subnets of the previous subscription period are included, since a cached ENR may not yet
+/// advertise the subnets of the current period.
+fn compute_long_lived_subnets<TSpec: EthSpec>(
+    enr: &Enr,
+    epoch: Epoch,
+    spec: &ChainSpec,
+) -> Vec<SubnetId> {
+    let node_id = Uint256::from(enr.node_id().raw());
+    let previous_epoch = epoch.saturating_sub(spec.epochs_per_subnet_subscription);
+
+    [previous_epoch, epoch]
+        .into_iter()
+        .filter_map(|epoch| SubnetId::compute_subnets_for_epoch::<TSpec>(node_id, epoch, spec).ok())
+        .flat_map(|(subnets, _)| subnets)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::enr::EnrBuilder;
+    use types::{BitVector, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    fn make_enr(enr_key: &CombinedKey, subnet_ids: &[SubnetId]) -> Enr {
+        let mut bitfield = BitVector::<ssz_types::typenum::U64>::new();
+        for id in subnet_ids {
+            bitfield.set(**id as usize, true).unwrap();
+        }
+
+        let mut builder = EnrBuilder::new("v4");
+        builder.add_value(ATTESTATION_BITFIELD_ENR_KEY, &bitfield.as_ssz_bytes());
+        builder.build(enr_key).unwrap()
+    }
+
+    #[test]
+    fn attestation_subnet_must_be_long_lived() {
+        let spec = E::default_spec();
+        let log = slog::Logger::root(slog::Discard, slog::o!());
+        let epoch = Epoch::new(1024);
+        let keypair = libp2p::identity::Keypair::generate_secp256k1();
+        let enr_key = CombinedKey::from_libp2p(&keypair).unwrap();
+
+        let long_lived = compute_long_lived_subnets::<E>(&make_enr(&enr_key, &[]), epoch, &spec);
+        let subnet = long_lived[0];
+        let other = (0..spec.attestation_subnet_count)
+            .map(SubnetId::new)
+            .find(|subnet| !long_lived.contains(subnet))
+            .unwrap();
+
+        let matches = |enr: &Enr, subnet: SubnetId| {
+            subnet_predicate::<E>(vec![Subnet::Attestation(subnet)], epoch, &spec, &log)(enr)
+        };
+
+        // Only the advertised subnets which are long-lived subnets of the peer are accepted.
+        let enr = make_enr(&enr_key, &[subnet, other]);
+        assert!(matches(&enr, subnet));
+        assert!(!matches(&enr, other));
+
+        // A long-lived subnet of the peer which its ENR does not advertise is rejected.
+        let enr = make_enr(&enr_key, &[other]);
+        assert!(!matches(&enr, subnet));
+    }
+}
@@ -13,6 +13,7 @@ pub mod discovery;
 pub mod listen_addr;
 pub mod metrics;
 pub mod peer_manager;
+pub mod recorder;
 pub mod rpc;
 pub mod types;
 
@@ -64,8 +65,8 @@ impl<'de> Deserialize<'de> for PeerIdSerialized {
 }
 
 pub use crate::types::{
-    error, Enr, EnrSyncCommitteeBitfield, GossipTopic, NetworkGlobals, PubsubMessage, Subnet,
-    SubnetDiscovery,
+    error, Enr, EnrSyncCommitteeBitfield, GossipTopic, GossipValidationEntry,
+    GossipValidationResult, NetworkGlobals, PubsubMessage, Subnet, SubnetDiscovery,
 };
 
 pub use prometheus_client;
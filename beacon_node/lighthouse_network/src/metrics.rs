@@ -144,6 +144,14 @@ lazy_static! {
         "Number of peer reports per msg",
         &["msg"]
     );
+
+    /*
+     * Network recording
+     */
+    pub static ref NETWORK_RECORDER_DROPPED_MESSAGES: Result<IntCounter> = try_create_int_counter(
+        "network_recorder_dropped_messages_total",
+        "Number of inbound messages not recorded because the recording writer fell behind"
+    );
 }
 
 /// Checks if we consider the NAT open.
@@ -30,6 +30,11 @@ pub struct Config {
     pub ping_interval_inbound: u64,
     /// Interval between PING events for peers dialed by us.
     pub ping_interval_outbound: u64,
+
+    /* Pruning related configurations */
+    /// When pruning, favour retaining a diverse set of client implementations and IP prefixes and
+    /// remove peers that are not prepared for the next fork.
+    pub peer_diversity_enabled: bool,
 }
 
 impl Default for Config {
@@ -41,6 +46,7 @@ impl Default for Config {
             status_interval: DEFAULT_STATUS_INTERVAL,
             ping_interval_inbound: DEFAULT_PING_INTERVAL_INBOUND,
             ping_interval_outbound: DEFAULT_PING_INTERVAL_OUTBOUND,
+            peer_diversity_enabled: false,
         }
     }
 }
@@ -3,7 +3,7 @@
 use crate::rpc::{GoodbyeReason, MetaData, Protocol, RPCError, RPCResponseErrorCode};
 use crate::service::TARGET_SUBNET_PEERS;
 use crate::{error, metrics, Gossipsub};
-use crate::{NetworkGlobals, PeerId};
+use crate::{Eth2Enr, NetworkGlobals, PeerId};
 use crate::{Subnet, SubnetDiscovery};
 use delay_map::HashSetDelay;
 use discv5::Enr;
@@ -19,7 +19,7 @@ use std::{
     time::{Duration, Instant},
 };
 use strum::IntoEnumIterator;
-use types::{EthSpec, SyncSubnetId};
+use types::{EnrForkId, Epoch, EthSpec, SyncSubnetId};
 
 pub use libp2p::core::{identity::Keypair, Multiaddr};
 
@@ -31,8 +31,8 @@ pub use peerdb::peer_info::{
 };
 use peerdb::score::{PeerAction, ReportSource};
 pub use peerdb::sync_status::{SyncInfo, SyncStatus};
-use std::collections::{hash_map::Entry, HashMap};
-use std::net::IpAddr;
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 pub mod config;
 mod network_behaviour;
 
@@ -61,6 +61,13 @@ pub const MIN_OUTBOUND_ONLY_FACTOR: f32 = 0.2;
 /// limit is 55, and we are at 55 peers, the following parameter provisions a few more slots of
 /// dialing priority peers we need for validator duties.
 pub const PRIORITY_PEER_EXCESS: f32 = 0.2;
+/// When peer diversity is enabled, the maximum fraction of `PeerManager::target_peers` that we
+/// retain from any single client implementation during pruning.
+pub const MAX_CLIENT_PEER_FACTOR: f32 = 0.5;
+/// When peer diversity is enabled, the maximum number of peers sharing an IP prefix (a /24 for
+/// IPv4, a /48 for IPv6) that we retain during pruning. Prefixes are used as a cheap proxy for
+/// the hosting provider or ASN of a peer.
+pub const MAX_PEERS_PER_IP_PREFIX: usize = 3;
 
 /// The main struct that handles peer's reputation and connection status.
 pub struct PeerManager<TSpec: EthSpec> {
@@ -103,6 +110,8 @@ pub struct PeerManager<TSpec: EthSpec> {
     discovery_enabled: bool,
     /// Keeps track if the current instance is reporting metrics or not.
     metrics_enabled: bool,
+    /// Whether pruning favours a diverse set of clients and IP prefixes.
+    peer_diversity_enabled: bool,
     /// The logger associated with the `PeerManager`.
     log: slog::Logger,
 }
@@ -148,6 +157,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             status_interval,
             ping_interval_inbound,
             ping_interval_outbound,
+            peer_diversity_enabled,
         } = cfg;
 
         // Set up the peer manager heartbeat interval
@@ -166,6 +176,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             heartbeat,
             discovery_enabled,
             metrics_enabled,
+            peer_diversity_enabled,
             log: log.clone(),
         })
     }
@@ -535,6 +546,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     Protocol::BlocksByRange => PeerAction::MidToleranceError,
                     Protocol::BlocksByRoot => PeerAction::MidToleranceError,
                     Protocol::LightClientBootstrap => PeerAction::LowToleranceError,
+                    Protocol::FinalizedStateChunk => PeerAction::MidToleranceError,
                     Protocol::Goodbye => PeerAction::LowToleranceError,
                     Protocol::MetaData => PeerAction::LowToleranceError,
                     Protocol::Status => PeerAction::LowToleranceError,
@@ -552,6 +564,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     Protocol::BlocksByRoot => return,
                     Protocol::Goodbye => return,
                     Protocol::LightClientBootstrap => return,
+                    Protocol::FinalizedStateChunk => return,
                     Protocol::MetaData => PeerAction::LowToleranceError,
                     Protocol::Status => PeerAction::LowToleranceError,
                 }
@@ -567,6 +580,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     Protocol::BlocksByRange => PeerAction::MidToleranceError,
                     Protocol::BlocksByRoot => PeerAction::MidToleranceError,
                     Protocol::LightClientBootstrap => return,
+                    Protocol::FinalizedStateChunk => PeerAction::MidToleranceError,
                     Protocol::Goodbye => return,
                     Protocol::MetaData => return,
                     Protocol::Status => return,
@@ -907,6 +921,64 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         }
     }
 
+    /// Returns our ENR fork id if we are expecting a fork, otherwise `None`.
+    fn upcoming_enr_fork_id(&self) -> Option<EnrForkId> {
+        self.network_globals
+            .local_enr()
+            .eth2()
+            .ok()
+            .filter(|fork_id| fork_id.next_fork_epoch != Epoch::max_value())
+    }
+
+    /// Returns the connected peers, not already marked for pruning, that belong to an
+    /// over-represented client implementation or IP prefix.
+    ///
+    /// Peers are considered from worst to best score, so that the best peers of each group are
+    /// retained.
+    fn over_represented_peers(&self, peers_to_prune: &HashSet<PeerId>) -> HashSet<PeerId> {
+        let peers = self.network_globals.peers.read();
+        let candidates = peers
+            .worst_connected_peers()
+            .into_iter()
+            .filter(|(peer_id, _)| !peers_to_prune.contains(*peer_id))
+            .collect::<Vec<_>>();
+
+        let mut client_counts: HashMap<ClientKind, usize> = HashMap::new();
+        let mut prefix_counts: HashMap<IpAddr, usize> = HashMap::new();
+        for (_, info) in &candidates {
+            *client_counts.entry(info.client().kind).or_default() += 1;
+            for prefix in ip_prefixes(info) {
+                *prefix_counts.entry(prefix).or_default() += 1;
+            }
+        }
+
+        let max_client_peers = (self.target_peers as f32 * MAX_CLIENT_PEER_FACTOR).ceil() as usize;
+        let mut over_represented = HashSet::new();
+        for (peer_id, info) in candidates {
+            let client_kind = info.client().kind;
+            // Peers we have not yet identified are not counted against any client.
+            let client_excess = client_kind != ClientKind::Unknown
+                && client_counts.get(&client_kind).copied().unwrap_or_default() > max_client_peers;
+            let prefixes = ip_prefixes(info);
+            let prefix_excess = prefixes.iter().any(|prefix| {
+                prefix_counts.get(prefix).copied().unwrap_or_default() > MAX_PEERS_PER_IP_PREFIX
+            });
+
+            if client_excess || prefix_excess {
+                over_represented.insert(*peer_id);
+                if let Some(count) = client_counts.get_mut(&client_kind) {
+                    *count = count.saturating_sub(1);
+                }
+                for prefix in &prefixes {
+                    if let Some(count) = prefix_counts.get_mut(prefix) {
+                        *count = count.saturating_sub(1);
+                    }
+                }
+            }
+        }
+        over_represented
+    }
+
     /// Remove excess peers back down to our target values.
     /// This prioritises peers with a good score and uniform distribution of peers across
     /// subnets.
@@ -934,9 +1006,11 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     ///
     /// Prune peers in the following order:
     /// 1. Remove worst scoring peers
-    /// 2. Remove peers that are not subscribed to a subnet (they have less value)
-    /// 3. Remove peers that we have many on any particular subnet
-    /// 4. Randomly remove peers if all the above are satisfied
+    /// 2. If peer diversity is enabled, remove peers advertising a different next fork
+    /// 3. Remove peers that are not subscribed to a subnet (they have less value)
+    /// 4. If peer diversity is enabled, remove peers of over-represented clients or IP prefixes
+    /// 5. Remove peers that we have many on any particular subnet
+    /// 6. Randomly remove peers if all the above are satisfied
     ///
     fn prune_excess_peers(&mut self) {
         // The current number of connected peers.
@@ -961,7 +1035,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     .read()
                     .worst_connected_peers()
                     .iter()
-                    .filter(|(_, info)| !info.has_future_duty() && $filter(*info))
+                    .filter(|(peer_id, info)| !info.has_future_duty() && $filter(*peer_id, *info))
                 {
                     if peers_to_prune.len()
                         >= connected_peer_count.saturating_sub(self.target_peers)
@@ -988,15 +1062,36 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         }
 
         // 1. Look through peers that have the worst score (ignoring non-penalized scored peers).
-        prune_peers!(|info: &PeerInfo<TSpec>| { info.score().score() < 0.0 });
+        prune_peers!(|_, info: &PeerInfo<TSpec>| { info.score().score() < 0.0 });
+
+        // 2. If peer diversity is enabled, remove peers that advertise a different next fork to
+        //    us ahead of a scheduled fork. These peers will be on a different network after it.
+        if self.peer_diversity_enabled
+            && peers_to_prune.len() < connected_peer_count.saturating_sub(self.target_peers)
+        {
+            if let Some(local_fork_id) = self.upcoming_enr_fork_id() {
+                prune_peers!(|_, info: &PeerInfo<TSpec>| {
+                    has_mismatched_next_fork(info, &local_fork_id)
+                });
+            }
+        }
 
-        // 2. Attempt to remove peers that are not subscribed to a subnet, if we still need to
+        // 3. Attempt to remove peers that are not subscribed to a subnet, if we still need to
         //    prune more.
         if peers_to_prune.len() < connected_peer_count.saturating_sub(self.target_peers) {
-            prune_peers!(|info: &PeerInfo<TSpec>| { !info.has_long_lived_subnet() });
+            prune_peers!(|_, info: &PeerInfo<TSpec>| { !info.has_long_lived_subnet() });
+        }
+
+        // 4. If peer diversity is enabled, remove the worst peers of any client implementation or
+        //    IP prefix that is over-represented amongst the peers we would otherwise retain.
+        if self.peer_diversity_enabled
+            && peers_to_prune.len() < connected_peer_count.saturating_sub(self.target_peers)
+        {
+            let over_represented = self.over_represented_peers(&peers_to_prune);
+            prune_peers!(|peer_id: &PeerId, _| { over_represented.contains(peer_id) });
         }
 
-        // 3. and 4. Remove peers that are too grouped on any given subnet. If all subnets are
+        // 5. and 6. Remove peers that are too grouped on any given subnet. If all subnets are
         //    uniformly distributed, remove random peers.
         if peers_to_prune.len() < connected_peer_count.saturating_sub(self.target_peers) {
             // Of our connected peers, build a map from subnet_id -> Vec<(PeerId, PeerInfo)>
@@ -1257,6 +1352,42 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     }
 }
 
+/// Returns true if the peer's ENR agrees with our current fork digest but advertises a different
+/// next fork.
+fn has_mismatched_next_fork<TSpec: EthSpec>(
+    info: &PeerInfo<TSpec>,
+    local_fork_id: &EnrForkId,
+) -> bool {
+    match info.enr().and_then(|enr| enr.eth2().ok()) {
+        Some(fork_id) => {
+            fork_id.fork_digest == local_fork_id.fork_digest
+                && (fork_id.next_fork_version != local_fork_id.next_fork_version
+                    || fork_id.next_fork_epoch != local_fork_id.next_fork_epoch)
+        }
+        None => false,
+    }
+}
+
+/// Returns the distinct IP prefixes a peer has been seen on, a /24 for IPv4 and a /48 for IPv6.
+fn ip_prefixes<TSpec: EthSpec>(info: &PeerInfo<TSpec>) -> Vec<IpAddr> {
+    let mut prefixes = info
+        .seen_ip_addresses()
+        .map(|ip| match ip {
+            IpAddr::V4(ip) => {
+                let [a, b, c, _] = ip.octets();
+                IpAddr::V4(Ipv4Addr::new(a, b, c, 0))
+            }
+            IpAddr::V6(ip) => {
+                let [a, b, c, ..] = ip.segments();
+                IpAddr::V6(Ipv6Addr::new(a, b, c, 0, 0, 0, 0, 0))
+            }
+        })
+        .collect::<Vec<_>>();
+    prefixes.sort();
+    prefixes.dedup();
+    prefixes
+}
+
 enum ConnectingType {
     /// We are in the process of dialing this peer.
     Dialing,
@@ -1831,6 +1962,235 @@ mod tests {
         assert!(!connected_peers.contains(&peers[5]));
     }
 
+    async fn build_diverse_peer_manager(target_peer_count: usize) -> PeerManager<E> {
+        let config = config::Config {
+            target_peer_count,
+            discovery_enabled: false,
+            peer_diversity_enabled: true,
+            ..Default::default()
+        };
+        let log = build_log(slog::Level::Debug, false);
+        let globals = NetworkGlobals::new_test_globals(&log);
+        PeerManager::new(config, Arc::new(globals), &log).unwrap()
+    }
+
+    /// Connects a peer that is subscribed to the single long-lived attestation subnet given.
+    fn connect_peer_on_subnet(
+        peer_manager: &mut PeerManager<E>,
+        address: &str,
+        subnet: u64,
+        enr: Option<Enr>,
+    ) -> PeerId {
+        let peer = PeerId::random();
+        peer_manager.inject_connect_ingoing(&peer, address.parse().unwrap(), enr);
+
+        let mut attnets = crate::types::EnrAttestationBitfield::<E>::new();
+        attnets.set(subnet as usize, true).unwrap();
+        let metadata = crate::rpc::MetaDataV2 {
+            seq_number: 0,
+            attnets,
+            syncnets: Default::default(),
+        };
+        peer_manager
+            .network_globals
+            .peers
+            .write()
+            .peer_info_mut(&peer)
+            .unwrap()
+            .set_meta_data(MetaData::V2(metadata));
+        peer_manager
+            .network_globals
+            .peers
+            .write()
+            .add_subscription(&peer, Subnet::Attestation(subnet.into()));
+        peer
+    }
+
+    /// Builds an ENR advertising the given fork id.
+    fn enr_with_fork_id(fork_id: &EnrForkId) -> Enr {
+        use ssz::Encode;
+        let enr_key = discv5::enr::CombinedKey::generate_secp256k1();
+        discv5::enr::EnrBuilder::new("v4")
+            .add_value(crate::discovery::enr::ETH2_ENR_KEY, &fork_id.as_ssz_bytes())
+            .build(&enr_key)
+            .unwrap()
+    }
+
+    /// Test that, with peer diversity enabled, peers sharing an over-represented IP prefix are
+    /// pruned before peers on their own prefix.
+    #[tokio::test]
+    async fn test_peer_manager_prune_over_represented_ip_prefix() {
+        let target = 4;
+        let mut peer_manager = build_diverse_peer_manager(target).await;
+
+        // Six peers share a /24 and one peer is on its own. Every peer is on its own subnet so
+        // subnet based pruning has no preference between them.
+        let mut peers = Vec::new();
+        for x in 0..7u64 {
+            let address = if x < 6 {
+                format!("/ip4/10.0.0.{}/tcp/9000", x + 1)
+            } else {
+                "/ip4/10.1.0.1/tcp/9000".to_string()
+            };
+            peers.push(connect_peer_on_subnet(&mut peer_manager, &address, x, None));
+        }
+
+        // Perform the heartbeat.
+        peer_manager.heartbeat();
+
+        assert_eq!(
+            peer_manager.network_globals.connected_or_dialing_peers(),
+            target
+        );
+
+        let connected_peers: std::collections::HashSet<_> = peer_manager
+            .network_globals
+            .peers
+            .read()
+            .connected_or_dialing_peers()
+            .cloned()
+            .collect();
+
+        // The peer on its own prefix is retained, along with `MAX_PEERS_PER_IP_PREFIX` of the
+        // grouped peers.
+        assert!(connected_peers.contains(&peers[6]));
+        assert_eq!(
+            peers[..6]
+                .iter()
+                .filter(|peer| connected_peers.contains(peer))
+                .count(),
+            MAX_PEERS_PER_IP_PREFIX
+        );
+    }
+
+    /// Test that, with peer diversity enabled and a fork scheduled, peers that agree on the
+    /// current fork but advertise a different next fork are pruned first.
+    ///
+    /// Create 6 peers on distinct prefixes and subnets.
+    /// Peer0, Peer1: Same fork digest, different next fork epoch
+    /// Peer2, Peer3, Peer4: Same fork id as us
+    /// Peer5: Different fork digest and next fork epoch
+    ///
+    /// Prune 3 peers: Should include Peer0 and Peer1. Peer5 is not on our current fork, so the
+    /// next fork rule does not apply to it.
+    #[tokio::test]
+    async fn test_peer_manager_prune_mismatched_next_fork() {
+        let target = 3;
+        let mut peer_manager = build_diverse_peer_manager(target).await;
+
+        let local_fork_id = EnrForkId {
+            fork_digest: [1, 2, 3, 4],
+            next_fork_version: [2, 0, 0, 0],
+            next_fork_epoch: Epoch::new(100),
+        };
+        *peer_manager.network_globals.local_enr.write() = enr_with_fork_id(&local_fork_id);
+
+        let mut peers = Vec::new();
+        for x in 0..6u64 {
+            let fork_id = match x {
+                0 | 1 => EnrForkId {
+                    next_fork_epoch: Epoch::new(200),
+                    ..local_fork_id.clone()
+                },
+                2..=4 => local_fork_id.clone(),
+                5 => EnrForkId {
+                    fork_digest: [5, 6, 7, 8],
+                    next_fork_epoch: Epoch::new(200),
+                    ..local_fork_id.clone()
+                },
+                _ => unreachable!(),
+            };
+            peers.push(connect_peer_on_subnet(
+                &mut peer_manager,
+                &format!("/ip4/10.{}.0.1/tcp/9000", x),
+                x,
+                Some(enr_with_fork_id(&fork_id)),
+            ));
+        }
+
+        // Perform the heartbeat.
+        peer_manager.heartbeat();
+
+        assert_eq!(
+            peer_manager.network_globals.connected_or_dialing_peers(),
+            target
+        );
+
+        let connected_peers: std::collections::HashSet<_> = peer_manager
+            .network_globals
+            .peers
+            .read()
+            .connected_or_dialing_peers()
+            .cloned()
+            .collect();
+
+        assert!(!connected_peers.contains(&peers[0]));
+        assert!(!connected_peers.contains(&peers[1]));
+    }
+
+    /// Test that, with peer diversity enabled, the worst peers of an over-represented client are
+    /// pruned.
+    ///
+    /// Create 7 peers on distinct prefixes and subnets, 5 Lighthouse and 2 Teku. With a target of
+    /// 4 peers, at most 2 peers of any one client are retained.
+    ///
+    /// Prune 3 peers: Should be the 3 Lighthouse peers with the worst score.
+    #[tokio::test]
+    async fn test_peer_manager_prune_over_represented_client() {
+        let target = 4;
+        let mut peer_manager = build_diverse_peer_manager(target).await;
+
+        let mut peers = Vec::new();
+        for x in 0..7u64 {
+            let peer = connect_peer_on_subnet(
+                &mut peer_manager,
+                &format!("/ip4/10.{}.0.1/tcp/9000", x),
+                x,
+                None,
+            );
+            let kind = if x < 5 {
+                ClientKind::Lighthouse
+            } else {
+                ClientKind::Teku
+            };
+            let mut peers_db = peer_manager.network_globals.peers.write();
+            let info = peers_db.peer_info_mut(&peer).unwrap();
+            info.set_client(peerdb::client::Client {
+                kind,
+                ..Default::default()
+            });
+            // Give the Lighthouse peers a positive score that increases with their index, so the
+            // first ones are the worst.
+            if x < 5 {
+                info.add_to_score(x as f64);
+            }
+            drop(peers_db);
+            peers.push(peer);
+        }
+
+        // Perform the heartbeat.
+        peer_manager.heartbeat();
+
+        assert_eq!(
+            peer_manager.network_globals.connected_or_dialing_peers(),
+            target
+        );
+
+        let connected_peers: std::collections::HashSet<_> = peer_manager
+            .network_globals
+            .peers
+            .read()
+            .connected_or_dialing_peers()
+            .cloned()
+            .collect();
+
+        assert!(!connected_peers.contains(&peers[0]));
+        assert!(!connected_peers.contains(&peers[1]));
+        assert!(!connected_peers.contains(&peers[2]));
+        assert!(connected_peers.contains(&peers[5]));
+        assert!(connected_peers.contains(&peers[6]));
+    }
+
     /// Test the pruning logic to prioritise peers with the most subnets, but not at the expense of
     /// removing our few sync-committee subnets.
     ///
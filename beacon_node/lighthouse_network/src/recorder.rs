@@ -0,0 +1,644 @@
+//! Records inbound network traffic to disk so that it can be replayed offline.
+//!
+//! A recording is a flat file containing a sequence of `RecordedMessage`s. Each message is SSZ
+//! encoded and prefixed by its length as a little-endian `u32`. Gossip messages are stored as the
+//! decompressed bytes received on the wire, along with their topic. Each successful RPC response
+//! chunk is stored as the SSZ encoding of the response, along with the name of the protocol it was
+//! received on. Error responses and stream terminations are not recorded.
+//!
+//! If the writer falls behind, messages are dropped rather than stalling the network. A gap marker
+//! holding the number of dropped messages is then recorded in their place, so that a replay can
+//! tell that the recording is incomplete.
+//!
+//! Recordings are written by the `Network` service when `NetworkConfig::record_network_traffic`
+//! is set, and can be read back with a `RecordingReader`. The `Network` service only timestamps
+//! each message and hands it to a `NetworkRecorder`, which encodes and writes it on a blocking
+//! task so that disk I/O never stalls the network poll loop.
+use crate::metrics;
+use crate::rpc::methods::RPCResponse;
+use libp2p::gossipsub::TopicHash;
+use libp2p::PeerId;
+use slog::{debug, error, warn, Logger};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError, SyncSender, TrySendError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use task_executor::TaskExecutor;
+use types::EthSpec;
+
+/// The maximum size of a single recorded message.
+///
+/// This comfortably exceeds the largest gossip message and the largest RPC response chunk.
+pub const MAX_RECORDED_MESSAGE_SIZE: usize = 32 * 1_048_576;
+
+/// The recorder flushes its buffer to disk after this many messages.
+const FLUSH_INTERVAL: usize = 64;
+
+/// The recorder flushes its buffer to disk if no message has been received for this long.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The number of messages that may be queued for the writer before new messages are dropped.
+const RECORDER_QUEUE_LEN: usize = 4_096;
+
+/// The minimum time between warnings about messages dropped from the recording.
+const DROPPED_MESSAGES_WARN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The kind of traffic captured by a `RecordedMessage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordedMessageKind {
+    /// A gossipsub message. The topic is the full gossipsub topic string.
+    Gossip,
+    /// A single RPC response chunk. The topic is the name of the RPC protocol.
+    RpcResponse,
+    /// Marks messages which were dropped from the recording at this point. The data is the number
+    /// of dropped messages as a little-endian `u64` and the peer id and topic are empty.
+    Gap,
+}
+
+impl RecordedMessageKind {
+    fn as_u8(self) -> u8 {
+        match self {
+            RecordedMessageKind::Gossip => 0,
+            RecordedMessageKind::RpcResponse => 1,
+            RecordedMessageKind::Gap => 2,
+        }
+    }
+
+    fn from_u8(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(RecordedMessageKind::Gossip),
+            1 => Some(RecordedMessageKind::RpcResponse),
+            2 => Some(RecordedMessageKind::Gap),
+            _ => None,
+        }
+    }
+}
+
+/// A single message captured from the network.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct RecordedMessage {
+    /// Milliseconds since the UNIX epoch at which the message was received.
+    pub timestamp_millis: u64,
+    /// The `RecordedMessageKind` of this message, as a byte.
+    pub kind: u8,
+    /// The peer we received this message from, as `PeerId` bytes.
+    pub peer_id: Vec<u8>,
+    /// The gossipsub topic or RPC protocol name, as UTF-8.
+    pub topic: Vec<u8>,
+    /// The message payload.
+    pub data: Vec<u8>,
+}
+
+impl RecordedMessage {
+    /// Builds a new message, timestamped with the current system time.
+    pub fn new(kind: RecordedMessageKind, peer_id: &PeerId, topic: &str, data: Vec<u8>) -> Self {
+        Self {
+            timestamp_millis: now_millis(),
+            kind: kind.as_u8(),
+            peer_id: peer_id.to_bytes(),
+            topic: topic.as_bytes().to_vec(),
+            data,
+        }
+    }
+
+    /// Builds a gap marker for `dropped` messages, timestamped with the current system time.
+    pub fn gap(dropped: u64) -> Self {
+        Self {
+            timestamp_millis: now_millis(),
+            kind: RecordedMessageKind::Gap.as_u8(),
+            peer_id: vec![],
+            topic: vec![],
+            data: dropped.to_le_bytes().to_vec(),
+        }
+    }
+
+    /// Returns the kind of this message, or `None` if the recording contains an unknown kind.
+    pub fn kind(&self) -> Option<RecordedMessageKind> {
+        RecordedMessageKind::from_u8(self.kind)
+    }
+
+    /// Returns the time at which this message was received, as a duration since the UNIX epoch.
+    pub fn timestamp(&self) -> Duration {
+        Duration::from_millis(self.timestamp_millis)
+    }
+
+    /// Returns the peer that sent us this message, if the recorded bytes are valid.
+    pub fn peer_id(&self) -> Option<PeerId> {
+        PeerId::from_bytes(&self.peer_id).ok()
+    }
+
+    /// Returns the topic or protocol of this message, if the recorded bytes are valid UTF-8.
+    pub fn topic(&self) -> Option<&str> {
+        std::str::from_utf8(&self.topic).ok()
+    }
+
+    /// Returns the number of messages dropped at this point of the recording, if this is a gap
+    /// marker.
+    pub fn dropped_messages(&self) -> Option<u64> {
+        if self.kind() != Some(RecordedMessageKind::Gap) {
+            return None;
+        }
+        self.data.as_slice().try_into().ok().map(u64::from_le_bytes)
+    }
+}
+
+/// Returns the current system time in milliseconds since the UNIX epoch.
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// The payload of a message waiting to be written, encoded by the writer rather than the network
+/// poll loop.
+enum PendingData<TSpec: EthSpec> {
+    Bytes(Vec<u8>),
+    Response(RPCResponse<TSpec>),
+}
+
+/// A message received from the network that has not yet been written to the recording.
+struct PendingMessage<TSpec: EthSpec> {
+    timestamp_millis: u64,
+    kind: RecordedMessageKind,
+    /// The sender of the message, which is `None` for gap markers.
+    peer_id: Option<PeerId>,
+    topic: String,
+    data: PendingData<TSpec>,
+}
+
+impl<TSpec: EthSpec> PendingMessage<TSpec> {
+    fn into_recorded(self) -> RecordedMessage {
+        let data = match self.data {
+            PendingData::Bytes(bytes) => bytes,
+            PendingData::Response(response) => encode_rpc_response(&response),
+        };
+        RecordedMessage {
+            timestamp_millis: self.timestamp_millis,
+            kind: self.kind.as_u8(),
+            peer_id: self
+                .peer_id
+                .map(|peer_id| peer_id.to_bytes())
+                .unwrap_or_default(),
+            topic: self.topic.into_bytes(),
+            data,
+        }
+    }
+}
+
+/// Returns the SSZ encoding of `response`, as sent on the wire before compression.
+fn encode_rpc_response<TSpec: EthSpec>(response: &RPCResponse<TSpec>) -> Vec<u8> {
+    match response {
+        RPCResponse::Status(status) => status.as_ssz_bytes(),
+        RPCResponse::BlocksByRange(block) | RPCResponse::BlocksByRoot(block) => {
+            block.as_ssz_bytes()
+        }
+        RPCResponse::LightClientBootstrap(bootstrap) => bootstrap.as_ssz_bytes(),
+        RPCResponse::FinalizedStateChunk(chunk) => chunk.as_ssz_bytes(),
+        RPCResponse::Pong(ping) => ping.data.as_ssz_bytes(),
+        RPCResponse::MetaData(metadata) => metadata.as_ssz_bytes(),
+    }
+}
+
+/// Queues inbound messages to be appended to a recording by a blocking writer task.
+///
+/// Messages are dropped, rather than blocking the caller, if the writer falls behind. A gap marker
+/// is queued in their place as soon as there is room. The writer stops once the recording reaches
+/// its maximum size or a write fails, after which every call to `record_*` returns an error.
+pub struct NetworkRecorder<TSpec: EthSpec> {
+    sender: SyncSender<PendingMessage<TSpec>>,
+    /// The number of messages dropped since the last gap marker was queued.
+    dropped: u64,
+    /// The time at which we last warned about dropped messages.
+    last_dropped_warning: Option<Instant>,
+    log: Logger,
+}
+
+impl<TSpec: EthSpec> NetworkRecorder<TSpec> {
+    /// Opens the recording at `path` and spawns a task to write to it. The recording is not
+    /// extended beyond `max_size` bytes.
+    pub fn spawn(
+        path: &Path,
+        max_size: u64,
+        executor: &TaskExecutor,
+        log: Logger,
+    ) -> Result<Self, String> {
+        let mut writer = RecordingWriter::open(path, max_size)?;
+        let (sender, receiver) = mpsc::sync_channel::<PendingMessage<TSpec>>(RECORDER_QUEUE_LEN);
+
+        let writer_log = log.clone();
+        executor.spawn_blocking(
+            move || {
+                loop {
+                    let result = match receiver.recv_timeout(FLUSH_TIMEOUT) {
+                        Ok(message) => writer.record(&message.into_recorded()),
+                        Err(RecvTimeoutError::Timeout) => writer.flush(),
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    };
+                    if let Err(e) = result {
+                        error!(writer_log, "Network recording stopped"; "error" => e);
+                        break;
+                    }
+                }
+                debug!(writer_log, "Network recorder shutting down"; "size" => writer.size);
+            },
+            "network_recorder",
+        );
+
+        Ok(Self {
+            sender,
+            dropped: 0,
+            last_dropped_warning: None,
+            log,
+        })
+    }
+
+    /// Records a gossip message received on `topic` from `peer_id`.
+    pub fn record_gossip(
+        &mut self,
+        peer_id: &PeerId,
+        topic: &TopicHash,
+        data: &[u8],
+    ) -> Result<(), String> {
+        self.send(
+            RecordedMessageKind::Gossip,
+            peer_id,
+            topic.as_str(),
+            PendingData::Bytes(data.to_vec()),
+        )
+    }
+
+    /// Records an RPC response chunk received from `peer_id`.
+    pub fn record_rpc_response(
+        &mut self,
+        peer_id: &PeerId,
+        response: &RPCResponse<TSpec>,
+    ) -> Result<(), String> {
+        self.send(
+            RecordedMessageKind::RpcResponse,
+            peer_id,
+            response.protocol().as_ref(),
+            PendingData::Response(response.clone()),
+        )
+    }
+
+    fn send(
+        &mut self,
+        kind: RecordedMessageKind,
+        peer_id: &PeerId,
+        topic: &str,
+        data: PendingData<TSpec>,
+    ) -> Result<(), String> {
+        // Mark any dropped messages before recording the next one, so that the gap appears where
+        // the messages were lost.
+        self.send_gap()?;
+
+        let message = PendingMessage {
+            timestamp_millis: now_millis(),
+            kind,
+            peer_id: Some(*peer_id),
+            topic: topic.to_string(),
+            data,
+        };
+        // A message must not be recorded ahead of an earlier gap which is still waiting for room.
+        if self.dropped > 0 || !self.try_send(message)? {
+            self.on_dropped_message();
+        }
+        Ok(())
+    }
+
+    /// Queues a gap marker for the messages dropped since the last one, if there is room.
+    fn send_gap(&mut self) -> Result<(), String> {
+        if self.dropped == 0 {
+            return Ok(());
+        }
+        let gap = PendingMessage {
+            timestamp_millis: now_millis(),
+            kind: RecordedMessageKind::Gap,
+            peer_id: None,
+            topic: String::new(),
+            data: PendingData::Bytes(self.dropped.to_le_bytes().to_vec()),
+        };
+        if self.try_send(gap)? {
+            self.dropped = 0;
+        }
+        Ok(())
+    }
+
+    /// Queues `message` for the writer, returning `false` if the queue is full.
+    fn try_send(&self, message: PendingMessage<TSpec>) -> Result<bool, String> {
+        match self.sender.try_send(message) {
+            Ok(()) => Ok(true),
+            Err(TrySendError::Full(_)) => Ok(false),
+            Err(TrySendError::Disconnected(_)) => {
+                Err("The network recording writer has stopped".to_string())
+            }
+        }
+    }
+
+    fn on_dropped_message(&mut self) {
+        metrics::inc_counter(&metrics::NETWORK_RECORDER_DROPPED_MESSAGES);
+        self.dropped += 1;
+
+        let warn_due = self.last_dropped_warning.map_or(true, |last| {
+            last.elapsed() >= DROPPED_MESSAGES_WARN_INTERVAL
+        });
+        if warn_due {
+            warn!(
+                self.log,
+                "Dropping messages from network recording";
+                "msg" => "the recording writer is falling behind, the recording will be incomplete",
+                "dropped" => self.dropped,
+            );
+            self.last_dropped_warning = Some(Instant::now());
+        }
+    }
+}
+
+impl<TSpec: EthSpec> Drop for NetworkRecorder<TSpec> {
+    fn drop(&mut self) {
+        // Mark messages dropped at the end of the recording, if the writer has caught up.
+        let _ = self.send_gap();
+    }
+}
+
+/// Appends `RecordedMessage`s to a recording file, up to a maximum file size.
+pub struct RecordingWriter {
+    writer: BufWriter<File>,
+    /// The size of the recording, including any buffered messages.
+    size: u64,
+    max_size: u64,
+    /// Number of messages written since the buffer was last flushed.
+    unflushed: usize,
+}
+
+impl RecordingWriter {
+    /// Opens the recording at `path`, creating it if necessary. New messages are appended to any
+    /// existing recording, until the file reaches `max_size` bytes.
+    pub fn open(path: &Path, max_size: u64) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Unable to open network recording {:?}: {}", path, e))?;
+        let size = file
+            .metadata()
+            .map_err(|e| format!("Unable to read network recording {:?}: {}", path, e))?
+            .len();
+        Ok(Self {
+            writer: BufWriter::new(file),
+            size,
+            max_size,
+            unflushed: 0,
+        })
+    }
+
+    /// Appends a message to the recording.
+    ///
+    /// Returns an error without writing the message if it would take the recording beyond its
+    /// maximum size.
+    pub fn record(&mut self, message: &RecordedMessage) -> Result<(), String> {
+        let bytes = message.as_ssz_bytes();
+        if bytes.len() > MAX_RECORDED_MESSAGE_SIZE {
+            return Err(format!(
+                "Message of {} bytes exceeds the maximum recorded size",
+                bytes.len()
+            ));
+        }
+        let record_size = (bytes.len() + 4) as u64;
+        if self.size.saturating_add(record_size) > self.max_size {
+            return Err(format!(
+                "Recording has reached its maximum size of {} bytes",
+                self.max_size
+            ));
+        }
+        self.writer
+            .write_all(&(bytes.len() as u32).to_le_bytes())
+            .and_then(|_| self.writer.write_all(&bytes))
+            .map_err(|e| format!("Unable to write network recording: {}", e))?;
+        self.size += record_size;
+
+        self.unflushed += 1;
+        if self.unflushed >= FLUSH_INTERVAL {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Flushes any buffered messages to disk.
+    pub fn flush(&mut self) -> Result<(), String> {
+        self.unflushed = 0;
+        self.writer
+            .flush()
+            .map_err(|e| format!("Unable to flush network recording: {}", e))
+    }
+}
+
+impl Drop for RecordingWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Reads `RecordedMessage`s from a recording, in the order they were recorded.
+pub struct RecordingReader<R: Read> {
+    reader: R,
+    /// Set once the end of the recording or an error has been reached.
+    finished: bool,
+}
+
+impl RecordingReader<BufReader<File>> {
+    /// Opens the recording at `path` for reading.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let file = File::open(path)
+            .map_err(|e| format!("Unable to open network recording {:?}: {}", path, e))?;
+        Ok(Self::new(BufReader::new(file)))
+    }
+}
+
+impl<R: Read> RecordingReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            finished: false,
+        }
+    }
+
+    fn read_message(&mut self) -> Result<Option<RecordedMessage>, String> {
+        let mut len_bytes = [0; 4];
+        match self.reader.read_exact(&mut len_bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(format!("Unable to read network recording: {}", e)),
+        }
+
+        let len = u32::from_le_bytes(len_bytes) as usize;
+        if len > MAX_RECORDED_MESSAGE_SIZE {
+            return Err(format!("Recorded message length {} is too large", len));
+        }
+
+        let mut bytes = vec![0; len];
+        self.reader
+            .read_exact(&mut bytes)
+            .map_err(|e| format!("Truncated network recording: {}", e))?;
+
+        RecordedMessage::from_ssz_bytes(&bytes)
+            .map(Some)
+            .map_err(|e| format!("Invalid recorded message: {:?}", e))
+    }
+}
+
+impl<R: Read> Iterator for RecordingReader<R> {
+    type Item = Result<RecordedMessage, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.read_message() {
+            Ok(Some(message)) => Some(Ok(message)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    #[test]
+    fn round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.ssz");
+        let peer_id = PeerId::random();
+        let topic = "/eth2/00000000/beacon_block/ssz_snappy";
+
+        {
+            let mut writer = RecordingWriter::open(&path, u64::MAX).unwrap();
+            writer
+                .record(&RecordedMessage::new(
+                    RecordedMessageKind::Gossip,
+                    &peer_id,
+                    topic,
+                    vec![1, 2, 3],
+                ))
+                .unwrap();
+            writer
+                .record(&RecordedMessage::new(
+                    RecordedMessageKind::RpcResponse,
+                    &peer_id,
+                    "beacon_blocks_by_range",
+                    vec![4, 5],
+                ))
+                .unwrap();
+        }
+
+        let messages = RecordingReader::open(&path)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].kind(), Some(RecordedMessageKind::Gossip));
+        assert_eq!(messages[0].peer_id(), Some(peer_id));
+        assert_eq!(messages[0].topic(), Some(topic));
+        assert_eq!(messages[0].data, vec![1, 2, 3]);
+        assert_eq!(messages[1].kind(), Some(RecordedMessageKind::RpcResponse));
+        assert_eq!(messages[1].topic(), Some("beacon_blocks_by_range"));
+        assert_eq!(messages[1].data, vec![4, 5]);
+    }
+
+    #[test]
+    fn recording_is_capped_at_max_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.ssz");
+        let message = RecordedMessage::new(
+            RecordedMessageKind::Gossip,
+            &PeerId::random(),
+            "topic",
+            vec![0; 16],
+        );
+        let record_size = message.as_ssz_bytes().len() as u64 + 4;
+
+        {
+            let mut writer = RecordingWriter::open(&path, record_size * 2).unwrap();
+            writer.record(&message).unwrap();
+            writer.record(&message).unwrap();
+            assert!(writer.record(&message).is_err());
+        }
+
+        // Re-opening the recording takes its existing size into account.
+        let mut writer = RecordingWriter::open(&path, record_size * 2).unwrap();
+        assert!(writer.record(&message).is_err());
+        drop(writer);
+
+        let messages = RecordingReader::open(&path)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(messages, vec![message.clone(), message]);
+    }
+
+    #[test]
+    fn dropped_messages_are_marked_with_a_gap() {
+        let (sender, receiver) = mpsc::sync_channel(2);
+        let mut recorder = NetworkRecorder::<MainnetEthSpec> {
+            sender,
+            dropped: 0,
+            last_dropped_warning: None,
+            log: slog::Logger::root(slog::Discard, slog::o!()),
+        };
+        let peer_id = PeerId::random();
+        let topic = TopicHash::from_raw("topic");
+
+        for byte in 0..4 {
+            recorder.record_gossip(&peer_id, &topic, &[byte]).unwrap();
+        }
+        assert_eq!(recorder.dropped, 2);
+
+        // Once there is room, the gap is queued ahead of the next message.
+        let mut recorded = receiver
+            .try_iter()
+            .map(PendingMessage::into_recorded)
+            .collect::<Vec<_>>();
+        recorder.record_gossip(&peer_id, &topic, &[4]).unwrap();
+        assert_eq!(recorder.dropped, 0);
+        recorded.extend(receiver.try_iter().map(PendingMessage::into_recorded));
+
+        assert_eq!(recorded.len(), 4);
+        assert_eq!(recorded[0].data, vec![0]);
+        assert_eq!(recorded[1].data, vec![1]);
+        assert_eq!(recorded[2].dropped_messages(), Some(2));
+        assert_eq!(recorded[2].peer_id(), None);
+        assert_eq!(recorded[3].data, vec![4]);
+        assert_eq!(recorded[3].dropped_messages(), None);
+    }
+
+    #[test]
+    fn truncated_recording_is_an_error() {
+        let message = RecordedMessage::new(
+            RecordedMessageKind::Gossip,
+            &PeerId::random(),
+            "topic",
+            vec![0; 16],
+        );
+        let bytes = message.as_ssz_bytes();
+        let mut recording = (bytes.len() as u32).to_le_bytes().to_vec();
+        recording.extend_from_slice(&bytes[..bytes.len() - 1]);
+
+        let mut reader = RecordingReader::new(recording.as_slice());
+        assert!(matches!(reader.next(), Some(Err(_))));
+        assert!(reader.next().is_none());
+    }
+}
@@ -72,6 +72,7 @@ impl<TSpec: EthSpec> Encoder<RPCCodedResponse<TSpec>> for SSZSnappyInboundCodec<
                 RPCResponse::BlocksByRange(res) => res.as_ssz_bytes(),
                 RPCResponse::BlocksByRoot(res) => res.as_ssz_bytes(),
                 RPCResponse::LightClientBootstrap(res) => res.as_ssz_bytes(),
+                RPCResponse::FinalizedStateChunk(res) => res.as_ssz_bytes(),
                 RPCResponse::Pong(res) => res.data.as_ssz_bytes(),
                 RPCResponse::MetaData(res) =>
                 // Encode the correct version of the MetaData response based on the negotiated version.
@@ -233,6 +234,7 @@ impl<TSpec: EthSpec> Encoder<OutboundRequest<TSpec>> for SSZSnappyOutboundCodec<
             OutboundRequest::Ping(req) => req.as_ssz_bytes(),
             OutboundRequest::MetaData(_) => return Ok(()), // no metadata to encode
             OutboundRequest::LightClientBootstrap(req) => req.as_ssz_bytes(),
+            OutboundRequest::FinalizedStateChunk(req) => req.as_ssz_bytes(),
         };
         // SSZ encoded bytes should be within `max_packet_size`
         if bytes.len() > self.max_packet_size {
@@ -484,6 +486,9 @@ fn handle_v1_request<T: EthSpec>(
                 root: Hash256::from_ssz_bytes(decoded_buffer)?,
             },
         ))),
+        Protocol::FinalizedStateChunk => Ok(Some(InboundRequest::FinalizedStateChunk(
+            FinalizedStateChunkRequest::from_ssz_bytes(decoded_buffer)?,
+        ))),
         // MetaData requests return early from InboundUpgrade and do not reach the decoder.
         // Handle this case just for completeness.
         Protocol::MetaData => {
@@ -558,6 +563,9 @@ fn handle_v1_response<T: EthSpec>(
         Protocol::LightClientBootstrap => Ok(Some(RPCResponse::LightClientBootstrap(
             LightClientBootstrap::from_ssz_bytes(decoded_buffer)?,
         ))),
+        Protocol::FinalizedStateChunk => Ok(Some(RPCResponse::FinalizedStateChunk(
+            FinalizedStateChunk::from_ssz_bytes(decoded_buffer)?,
+        ))),
     }
 }
 
@@ -897,6 +905,9 @@ mod tests {
                 OutboundRequest::LightClientBootstrap(bootstrap) => {
                     assert_eq!(decoded, InboundRequest::LightClientBootstrap(bootstrap))
                 }
+                OutboundRequest::FinalizedStateChunk(chunk) => {
+                    assert_eq!(decoded, InboundRequest::FinalizedStateChunk(chunk))
+                }
             }
         }
     }
@@ -986,6 +997,22 @@ mod tests {
             Ok(Some(RPCResponse::MetaData(metadata()))),
         );
 
+        let state_chunk = FinalizedStateChunk {
+            block_root: Hash256::repeat_byte(1),
+            offset: 0,
+            total_len: 3,
+            data: VariableList::from(vec![1, 2, 3]),
+        };
+        assert_eq!(
+            encode_then_decode_response(
+                Protocol::FinalizedStateChunk,
+                Version::V1,
+                RPCCodedResponse::Success(RPCResponse::FinalizedStateChunk(state_chunk.clone())),
+                ForkName::Capella,
+            ),
+            Ok(Some(RPCResponse::FinalizedStateChunk(state_chunk))),
+        );
+
         assert_eq!(
             encode_then_decode_response(
                 Protocol::MetaData,
@@ -1373,6 +1400,10 @@ mod tests {
             OutboundRequest::BlocksByRange(bbrange_request()),
             OutboundRequest::BlocksByRoot(bbroot_request()),
             OutboundRequest::MetaData(PhantomData::<Spec>),
+            OutboundRequest::FinalizedStateChunk(FinalizedStateChunkRequest {
+                block_root: Hash256::zero(),
+                offset: 1024,
+            }),
         ];
         for req in requests.iter() {
             for fork_name in ForkName::list_all() {
@@ -67,6 +67,7 @@ pub struct OutboundRateLimiterConfig {
     pub(super) goodbye_quota: Quota,
     pub(super) blocks_by_range_quota: Quota,
     pub(super) blocks_by_root_quota: Quota,
+    pub(super) finalized_state_chunk_quota: Quota,
 }
 
 impl OutboundRateLimiterConfig {
@@ -77,6 +78,7 @@ impl OutboundRateLimiterConfig {
     pub const DEFAULT_BLOCKS_BY_RANGE_QUOTA: Quota =
         Quota::n_every(methods::MAX_REQUEST_BLOCKS, 10);
     pub const DEFAULT_BLOCKS_BY_ROOT_QUOTA: Quota = Quota::n_every(128, 10);
+    pub const DEFAULT_FINALIZED_STATE_CHUNK_QUOTA: Quota = Quota::n_every(64, 10);
 }
 
 impl Default for OutboundRateLimiterConfig {
@@ -88,6 +90,7 @@ impl Default for OutboundRateLimiterConfig {
             goodbye_quota: Self::DEFAULT_GOODBYE_QUOTA,
             blocks_by_range_quota: Self::DEFAULT_BLOCKS_BY_RANGE_QUOTA,
             blocks_by_root_quota: Self::DEFAULT_BLOCKS_BY_ROOT_QUOTA,
+            finalized_state_chunk_quota: Self::DEFAULT_FINALIZED_STATE_CHUNK_QUOTA,
         }
     }
 }
@@ -111,6 +114,10 @@ impl Debug for OutboundRateLimiterConfig {
             .field("goodbye", fmt_q!(&self.goodbye_quota))
             .field("blocks_by_range", fmt_q!(&self.blocks_by_range_quota))
             .field("blocks_by_root", fmt_q!(&self.blocks_by_root_quota))
+            .field(
+                "finalized_state_chunk",
+                fmt_q!(&self.finalized_state_chunk_quota),
+            )
             .finish()
     }
 }
@@ -129,6 +136,7 @@ impl FromStr for OutboundRateLimiterConfig {
         let mut goodbye_quota = None;
         let mut blocks_by_range_quota = None;
         let mut blocks_by_root_quota = None;
+        let mut finalized_state_chunk_quota = None;
         for proto_def in s.split(';') {
             let ProtocolQuota { protocol, quota } = proto_def.parse()?;
             let quota = Some(quota);
@@ -139,6 +147,9 @@ impl FromStr for OutboundRateLimiterConfig {
                 Protocol::BlocksByRoot => blocks_by_root_quota = blocks_by_root_quota.or(quota),
                 Protocol::Ping => ping_quota = ping_quota.or(quota),
                 Protocol::MetaData => meta_data_quota = meta_data_quota.or(quota),
+                Protocol::FinalizedStateChunk => {
+                    finalized_state_chunk_quota = finalized_state_chunk_quota.or(quota)
+                }
                 Protocol::LightClientBootstrap => return Err("Lighthouse does not send LightClientBootstrap requests. Quota should not be set."),
             }
         }
@@ -151,6 +162,8 @@ impl FromStr for OutboundRateLimiterConfig {
                 .unwrap_or(Self::DEFAULT_BLOCKS_BY_RANGE_QUOTA),
             blocks_by_root_quota: blocks_by_root_quota
                 .unwrap_or(Self::DEFAULT_BLOCKS_BY_ROOT_QUOTA),
+            finalized_state_chunk_quota: finalized_state_chunk_quota
+                .unwrap_or(Self::DEFAULT_FINALIZED_STATE_CHUNK_QUOTA),
         })
     }
 }
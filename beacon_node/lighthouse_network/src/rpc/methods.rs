@@ -5,7 +5,7 @@ use regex::bytes::Regex;
 use serde::Serialize;
 use ssz_derive::{Decode, Encode};
 use ssz_types::{
-    typenum::{U1024, U256},
+    typenum::{U1024, U256, U524288},
     VariableList,
 };
 use std::ops::Deref;
@@ -20,6 +20,10 @@ use types::{
 pub type MaxRequestBlocks = U1024;
 pub const MAX_REQUEST_BLOCKS: u64 = 1024;
 
+/// Maximum number of bytes of an SSZ-encoded state in a single `FinalizedStateChunk`.
+pub type MaxFinalizedStateChunkLen = U524288;
+pub const MAX_FINALIZED_STATE_CHUNK_LEN: u64 = 524_288;
+
 /// Maximum length of error message.
 pub type MaxErrorLen = U256;
 pub const MAX_ERROR_LEN: u64 = 256;
@@ -230,6 +234,15 @@ pub struct BlocksByRootRequest {
     pub block_roots: VariableList<Hash256, MaxRequestBlocks>,
 }
 
+/// Request a chunk of the SSZ-encoded finalized state of the block with the given root.
+#[derive(Encode, Decode, Clone, Debug, PartialEq)]
+pub struct FinalizedStateChunkRequest {
+    /// The root of the finalized block at the start of an epoch whose post-state is requested.
+    pub block_root: Hash256,
+    /// The byte offset into the SSZ-encoded state at which the chunk starts.
+    pub offset: u64,
+}
+
 /* RPC Handling and Grouping */
 // Collection of enums and structs used by the Codecs to encode/decode RPC messages
 
@@ -248,6 +261,9 @@ pub enum RPCResponse<T: EthSpec> {
     /// A response to a get LIGHTCLIENT_BOOTSTRAP request.
     LightClientBootstrap(LightClientBootstrap<T>),
 
+    /// A response to a get FINALIZED_STATE_CHUNK request.
+    FinalizedStateChunk(FinalizedStateChunk),
+
     /// A PONG response to a PING request.
     Pong(Ping),
 
@@ -284,6 +300,16 @@ pub struct LightClientBootstrapRequest {
     pub root: Hash256,
 }
 
+/// A chunk of an SSZ-encoded finalized state.
+#[derive(Encode, Decode, Clone, Debug, PartialEq)]
+pub struct FinalizedStateChunk {
+    pub block_root: Hash256,
+    pub offset: u64,
+    /// The length of the entire SSZ-encoded state.
+    pub total_len: u64,
+    pub data: VariableList<u8, MaxFinalizedStateChunkLen>,
+}
+
 /// The code assigned to an erroneous `RPCResponse`.
 #[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
@@ -333,6 +359,7 @@ impl<T: EthSpec> RPCCodedResponse<T> {
                 RPCResponse::Pong(_) => false,
                 RPCResponse::MetaData(_) => false,
                 RPCResponse::LightClientBootstrap(_) => false,
+                RPCResponse::FinalizedStateChunk(_) => false,
             },
             RPCCodedResponse::Error(_, _) => true,
             // Stream terminations are part of responses that have chunks
@@ -368,6 +395,7 @@ impl<T: EthSpec> RPCResponse<T> {
             RPCResponse::Pong(_) => Protocol::Ping,
             RPCResponse::MetaData(_) => Protocol::MetaData,
             RPCResponse::LightClientBootstrap(_) => Protocol::LightClientBootstrap,
+            RPCResponse::FinalizedStateChunk(_) => Protocol::FinalizedStateChunk,
         }
     }
 }
@@ -406,6 +434,11 @@ impl<T: EthSpec> std::fmt::Display for RPCResponse<T> {
             RPCResponse::LightClientBootstrap(bootstrap) => {
                 write!(f, "LightClientBootstrap Slot: {}", bootstrap.header.slot)
             }
+            RPCResponse::FinalizedStateChunk(chunk) => write!(
+                f,
+                "FinalizedStateChunk: Block root: {}, Offset: {}, Total length: {}",
+                chunk.block_root, chunk.offset, chunk.total_len
+            ),
         }
     }
 }
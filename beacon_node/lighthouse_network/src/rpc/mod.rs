@@ -26,8 +26,9 @@ pub(crate) use protocol::{InboundRequest, RPCProtocol};
 
 pub use handler::SubstreamId;
 pub use methods::{
-    BlocksByRangeRequest, BlocksByRootRequest, GoodbyeReason, LightClientBootstrapRequest,
-    MaxRequestBlocks, RPCResponseErrorCode, ResponseTermination, StatusMessage, MAX_REQUEST_BLOCKS,
+    BlocksByRangeRequest, BlocksByRootRequest, FinalizedStateChunk, FinalizedStateChunkRequest,
+    GoodbyeReason, LightClientBootstrapRequest, MaxRequestBlocks, RPCResponseErrorCode,
+    ResponseTermination, StatusMessage, MAX_FINALIZED_STATE_CHUNK_LEN, MAX_REQUEST_BLOCKS,
 };
 pub(crate) use outbound::OutboundRequest;
 pub use protocol::{max_rpc_size, Protocol, RPCError};
@@ -119,6 +120,7 @@ pub struct RPC<Id: ReqId, TSpec: EthSpec> {
     events: Vec<BehaviourAction<Id, TSpec>>,
     fork_context: Arc<ForkContext>,
     enable_light_client_server: bool,
+    enable_finalized_state_server: bool,
     /// Slog logger for RPC behaviour.
     log: slog::Logger,
 }
@@ -127,6 +129,7 @@ impl<Id: ReqId, TSpec: EthSpec> RPC<Id, TSpec> {
     pub fn new(
         fork_context: Arc<ForkContext>,
         enable_light_client_server: bool,
+        enable_finalized_state_server: bool,
         outbound_rate_limiter_config: Option<OutboundRateLimiterConfig>,
         log: slog::Logger,
     ) -> Self {
@@ -138,6 +141,7 @@ impl<Id: ReqId, TSpec: EthSpec> RPC<Id, TSpec> {
             .n_every(Protocol::Status, 5, Duration::from_secs(15))
             .one_every(Protocol::Goodbye, Duration::from_secs(10))
             .one_every(Protocol::LightClientBootstrap, Duration::from_secs(10))
+            .n_every(Protocol::FinalizedStateChunk, 64, Duration::from_secs(10))
             .n_every(
                 Protocol::BlocksByRange,
                 methods::MAX_REQUEST_BLOCKS,
@@ -157,6 +161,7 @@ impl<Id: ReqId, TSpec: EthSpec> RPC<Id, TSpec> {
             events: Vec::new(),
             fork_context,
             enable_light_client_server,
+            enable_finalized_state_server,
             log,
         }
     }
@@ -226,6 +231,7 @@ where
                     fork_context: self.fork_context.clone(),
                     max_rpc_size: max_rpc_size(&self.fork_context),
                     enable_light_client_server: self.enable_light_client_server,
+                    enable_finalized_state_server: self.enable_finalized_state_server,
                     phantom: PhantomData,
                 },
                 (),
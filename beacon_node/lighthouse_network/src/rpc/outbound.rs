@@ -39,6 +39,7 @@ pub enum OutboundRequest<TSpec: EthSpec> {
     BlocksByRange(OldBlocksByRangeRequest),
     BlocksByRoot(BlocksByRootRequest),
     LightClientBootstrap(LightClientBootstrapRequest),
+    FinalizedStateChunk(FinalizedStateChunkRequest),
     Ping(Ping),
     MetaData(PhantomData<TSpec>),
 }
@@ -89,6 +90,11 @@ impl<TSpec: EthSpec> OutboundRequest<TSpec> {
             // that we generate from the beacon state.
             // We do not make light client rpc requests from the beacon node
             OutboundRequest::LightClientBootstrap(_) => vec![],
+            OutboundRequest::FinalizedStateChunk(_) => vec![ProtocolId::new(
+                Protocol::FinalizedStateChunk,
+                Version::V1,
+                Encoding::SSZSnappy,
+            )],
         }
     }
     /* These functions are used in the handler for stream management */
@@ -103,6 +109,7 @@ impl<TSpec: EthSpec> OutboundRequest<TSpec> {
             OutboundRequest::Ping(_) => 1,
             OutboundRequest::MetaData(_) => 1,
             OutboundRequest::LightClientBootstrap(_) => 1,
+            OutboundRequest::FinalizedStateChunk(_) => 1,
         }
     }
 
@@ -116,6 +123,7 @@ impl<TSpec: EthSpec> OutboundRequest<TSpec> {
             OutboundRequest::Ping(_) => Protocol::Ping,
             OutboundRequest::MetaData(_) => Protocol::MetaData,
             OutboundRequest::LightClientBootstrap(_) => Protocol::LightClientBootstrap,
+            OutboundRequest::FinalizedStateChunk(_) => Protocol::FinalizedStateChunk,
         }
     }
 
@@ -128,6 +136,7 @@ impl<TSpec: EthSpec> OutboundRequest<TSpec> {
             OutboundRequest::BlocksByRange(_) => ResponseTermination::BlocksByRange,
             OutboundRequest::BlocksByRoot(_) => ResponseTermination::BlocksByRoot,
             OutboundRequest::LightClientBootstrap(_) => unreachable!(),
+            OutboundRequest::FinalizedStateChunk(_) => unreachable!(),
             OutboundRequest::Status(_) => unreachable!(),
             OutboundRequest::Goodbye(_) => unreachable!(),
             OutboundRequest::Ping(_) => unreachable!(),
@@ -188,6 +197,11 @@ impl<TSpec: EthSpec> std::fmt::Display for OutboundRequest<TSpec> {
             OutboundRequest::LightClientBootstrap(bootstrap) => {
                 write!(f, "Lightclient Bootstrap: {}", bootstrap.root)
             }
+            OutboundRequest::FinalizedStateChunk(req) => write!(
+                f,
+                "Finalized state chunk: {}, Offset: {}",
+                req.block_root, req.offset
+            ),
         }
     }
 }
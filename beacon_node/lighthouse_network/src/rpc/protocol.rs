@@ -107,6 +107,22 @@ lazy_static! {
         ])
     .as_ssz_bytes()
     .len();
+    pub static ref FINALIZED_STATE_CHUNK_MIN: usize = FinalizedStateChunk {
+        block_root: Hash256::zero(),
+        offset: 0,
+        total_len: 0,
+        data: VariableList::empty(),
+    }
+    .as_ssz_bytes()
+    .len();
+    pub static ref FINALIZED_STATE_CHUNK_MAX: usize = FinalizedStateChunk {
+        block_root: Hash256::zero(),
+        offset: 0,
+        total_len: 0,
+        data: VariableList::from(vec![0u8; MAX_FINALIZED_STATE_CHUNK_LEN as usize]),
+    }
+    .as_ssz_bytes()
+    .len();
 }
 
 /// The maximum bytes that can be sent across the RPC pre-merge.
@@ -177,6 +193,9 @@ pub enum Protocol {
     /// The `LightClientBootstrap` protocol name.
     #[strum(serialize = "light_client_bootstrap")]
     LightClientBootstrap,
+    /// The `FinalizedStateChunk` protocol name.
+    #[strum(serialize = "finalized_state_chunk")]
+    FinalizedStateChunk,
 }
 
 /// RPC Versions
@@ -218,6 +237,7 @@ pub struct RPCProtocol<TSpec: EthSpec> {
     pub fork_context: Arc<ForkContext>,
     pub max_rpc_size: usize,
     pub enable_light_client_server: bool,
+    pub enable_finalized_state_server: bool,
     pub phantom: PhantomData<TSpec>,
 }
 
@@ -246,6 +266,13 @@ impl<TSpec: EthSpec> UpgradeInfo for RPCProtocol<TSpec> {
                 Encoding::SSZSnappy,
             ));
         }
+        if self.enable_finalized_state_server {
+            supported_protocols.push(ProtocolId::new(
+                Protocol::FinalizedStateChunk,
+                Version::V1,
+                Encoding::SSZSnappy,
+            ));
+        }
         supported_protocols
     }
 }
@@ -312,6 +339,10 @@ impl ProtocolId {
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
             ),
+            Protocol::FinalizedStateChunk => RpcLimits::new(
+                <FinalizedStateChunkRequest as Encode>::ssz_fixed_len(),
+                <FinalizedStateChunkRequest as Encode>::ssz_fixed_len(),
+            ),
             Protocol::MetaData => RpcLimits::new(0, 0), // Metadata requests are empty
         }
     }
@@ -338,6 +369,9 @@ impl ProtocolId {
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
                 <LightClientBootstrapRequest as Encode>::ssz_fixed_len(),
             ),
+            Protocol::FinalizedStateChunk => {
+                RpcLimits::new(*FINALIZED_STATE_CHUNK_MIN, *FINALIZED_STATE_CHUNK_MAX)
+            }
         }
     }
 
@@ -352,7 +386,11 @@ impl ProtocolId {
             Protocol::LightClientBootstrap => match self.version {
                 Version::V2 | Version::V1 => true,
             },
-            Protocol::Goodbye | Protocol::Ping | Protocol::Status | Protocol::MetaData => false,
+            Protocol::Goodbye
+            | Protocol::Ping
+            | Protocol::Status
+            | Protocol::MetaData
+            | Protocol::FinalizedStateChunk => false,
         }
     }
 }
@@ -447,6 +485,7 @@ pub enum InboundRequest<TSpec: EthSpec> {
     BlocksByRange(OldBlocksByRangeRequest),
     BlocksByRoot(BlocksByRootRequest),
     LightClientBootstrap(LightClientBootstrapRequest),
+    FinalizedStateChunk(FinalizedStateChunkRequest),
     Ping(Ping),
     MetaData(PhantomData<TSpec>),
 }
@@ -465,6 +504,7 @@ impl<TSpec: EthSpec> InboundRequest<TSpec> {
             InboundRequest::Ping(_) => 1,
             InboundRequest::MetaData(_) => 1,
             InboundRequest::LightClientBootstrap(_) => 1,
+            InboundRequest::FinalizedStateChunk(_) => 1,
         }
     }
 
@@ -478,6 +518,7 @@ impl<TSpec: EthSpec> InboundRequest<TSpec> {
             InboundRequest::Ping(_) => Protocol::Ping,
             InboundRequest::MetaData(_) => Protocol::MetaData,
             InboundRequest::LightClientBootstrap(_) => Protocol::LightClientBootstrap,
+            InboundRequest::FinalizedStateChunk(_) => Protocol::FinalizedStateChunk,
         }
     }
 
@@ -494,6 +535,7 @@ impl<TSpec: EthSpec> InboundRequest<TSpec> {
             InboundRequest::Ping(_) => unreachable!(),
             InboundRequest::MetaData(_) => unreachable!(),
             InboundRequest::LightClientBootstrap(_) => unreachable!(),
+            InboundRequest::FinalizedStateChunk(_) => unreachable!(),
         }
     }
 }
@@ -600,6 +642,11 @@ impl<TSpec: EthSpec> std::fmt::Display for InboundRequest<TSpec> {
             InboundRequest::LightClientBootstrap(bootstrap) => {
                 write!(f, "LightClientBootstrap: {}", bootstrap.root)
             }
+            InboundRequest::FinalizedStateChunk(req) => write!(
+                f,
+                "FinalizedStateChunk: {}, Offset: {}",
+                req.block_root, req.offset
+            ),
         }
     }
 }
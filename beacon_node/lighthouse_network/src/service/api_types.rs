@@ -6,8 +6,9 @@ use types::{EthSpec, SignedBeaconBlock};
 
 use crate::rpc::{
     methods::{
-        BlocksByRangeRequest, BlocksByRootRequest, LightClientBootstrapRequest,
-        OldBlocksByRangeRequest, RPCCodedResponse, RPCResponse, ResponseTermination, StatusMessage,
+        BlocksByRangeRequest, BlocksByRootRequest, FinalizedStateChunk, FinalizedStateChunkRequest,
+        LightClientBootstrapRequest, OldBlocksByRangeRequest, RPCCodedResponse, RPCResponse,
+        ResponseTermination, StatusMessage,
     },
     OutboundRequest, SubstreamId,
 };
@@ -37,6 +38,8 @@ pub enum Request {
     BlocksByRoot(BlocksByRootRequest),
     // light client bootstrap request
     LightClientBootstrap(LightClientBootstrapRequest),
+    /// A request for a chunk of a finalized state.
+    FinalizedStateChunk(FinalizedStateChunkRequest),
 }
 
 impl<TSpec: EthSpec> std::convert::From<Request> for OutboundRequest<TSpec> {
@@ -51,6 +54,7 @@ impl<TSpec: EthSpec> std::convert::From<Request> for OutboundRequest<TSpec> {
                 })
             }
             Request::LightClientBootstrap(b) => OutboundRequest::LightClientBootstrap(b),
+            Request::FinalizedStateChunk(r) => OutboundRequest::FinalizedStateChunk(r),
             Request::Status(s) => OutboundRequest::Status(s),
         }
     }
@@ -72,6 +76,8 @@ pub enum Response<TSpec: EthSpec> {
     BlocksByRoot(Option<Arc<SignedBeaconBlock<TSpec>>>),
     /// A response to a LightClientUpdate request.
     LightClientBootstrap(LightClientBootstrap<TSpec>),
+    /// A response to a FinalizedStateChunk request.
+    FinalizedStateChunk(FinalizedStateChunk),
 }
 
 impl<TSpec: EthSpec> std::convert::From<Response<TSpec>> for RPCCodedResponse<TSpec> {
@@ -89,6 +95,9 @@ impl<TSpec: EthSpec> std::convert::From<Response<TSpec>> for RPCCodedResponse<TS
             Response::LightClientBootstrap(b) => {
                 RPCCodedResponse::Success(RPCResponse::LightClientBootstrap(b))
             }
+            Response::FinalizedStateChunk(c) => {
+                RPCCodedResponse::Success(RPCResponse::FinalizedStateChunk(c))
+            }
         }
     }
 }
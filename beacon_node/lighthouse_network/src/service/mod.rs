@@ -1,24 +1,23 @@
 use self::behaviour::Behaviour;
 use self::gossip_cache::GossipCache;
 use crate::config::{gossipsub_config, NetworkLoad};
-use crate::discovery::{
-    subnet_predicate, DiscoveredPeers, Discovery, FIND_NODE_QUERY_CLOSEST_PEERS,
-};
+use crate::discovery::{DiscoveredPeers, Discovery, FIND_NODE_QUERY_CLOSEST_PEERS};
 use crate::peer_manager::{
     config::Config as PeerManagerCfg, peerdb::score::PeerAction, peerdb::score::ReportSource,
     ConnectionDirection, PeerManager, PeerManagerEvent,
 };
 use crate::peer_manager::{MIN_OUTBOUND_ONLY_FACTOR, PEER_EXCESS_FACTOR, PRIORITY_PEER_EXCESS};
+use crate::recorder::NetworkRecorder;
 use crate::rpc::*;
 use crate::service::behaviour::BehaviourEvent;
 pub use crate::service::behaviour::Gossipsub;
 use crate::types::{
     fork_core_topics, subnet_from_topic_hash, GossipEncoding, GossipKind, GossipTopic,
-    SnappyTransform, Subnet, SubnetDiscovery,
+    GossipValidationLog, SnappyTransform, Subnet, SubnetDiscovery,
 };
 use crate::EnrExt;
 use crate::Eth2Enr;
-use crate::{error, metrics, Enr, NetworkGlobals, PubsubMessage, TopicHash};
+use crate::{error, metrics, Enr, GossipValidationEntry, NetworkGlobals, PubsubMessage, TopicHash};
 use api_types::{PeerRequestId, Request, RequestId, Response};
 use futures::stream::StreamExt;
 use gossipsub_scoring_parameters::{lighthouse_gossip_thresholds, PeerScoreSettings};
@@ -33,6 +32,7 @@ use libp2p::identify::{Behaviour as Identify, Config as IdentifyConfig, Event as
 use libp2p::multiaddr::{Multiaddr, Protocol as MProtocol};
 use libp2p::swarm::{ConnectionLimits, Swarm, SwarmBuilder, SwarmEvent};
 use libp2p::PeerId;
+use parking_lot::RwLock;
 use slog::{crit, debug, info, o, trace, warn};
 use std::path::PathBuf;
 use std::pin::Pin;
@@ -129,6 +129,8 @@ pub struct Network<AppReqId: ReqId, TSpec: EthSpec> {
     /// The interval for updating gossipsub scores
     update_gossipsub_scores: tokio::time::Interval,
     gossip_cache: GossipCache,
+    /// Records inbound gossip messages and RPC responses to disk, if enabled.
+    recorder: Option<NetworkRecorder<TSpec>>,
     /// The bandwidth logger for the underlying libp2p transport.
     pub bandwidth: Arc<BandwidthSinks>,
     /// This node's PeerId.
@@ -161,7 +163,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             )?;
             // Construct the metadata
             let meta_data = utils::load_or_build_metadata(&config.network_dir, &log);
-            let globals = NetworkGlobals::new(
+            let mut globals = NetworkGlobals::new(
                 enr,
                 config.listen_addrs().v4().map(|v4_addr| v4_addr.tcp_port),
                 config.listen_addrs().v6().map(|v6_addr| v6_addr.tcp_port),
@@ -173,6 +175,9 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                     .collect(),
                 &log,
             );
+            if config.gossip_validation_log {
+                globals.gossip_validation_log = Some(RwLock::new(GossipValidationLog::default()));
+            }
             Arc::new(globals)
         };
 
@@ -265,14 +270,21 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         let eth2_rpc = RPC::new(
             ctx.fork_context.clone(),
             config.enable_light_client_server,
+            config.enable_finalized_state_server,
             config.outbound_rate_limiter_config.clone(),
             log.clone(),
         );
 
         let discovery = {
             // Build and start the discovery sub-behaviour
-            let mut discovery =
-                Discovery::new(&local_keypair, &config, network_globals.clone(), &log).await?;
+            let mut discovery = Discovery::new(
+                &local_keypair,
+                &config,
+                network_globals.clone(),
+                ctx.chain_spec,
+                &log,
+            )
+            .await?;
             // start searching for peers
             discovery.discover_peers(FIND_NODE_QUERY_CLOSEST_PEERS);
             discovery
@@ -298,6 +310,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                 discovery_enabled: !config.disable_discovery,
                 metrics_enabled: config.metrics_enabled,
                 target_peer_count: config.target_peers,
+                peer_diversity_enabled: config.peer_diversity,
                 ..Default::default()
             };
             PeerManager::new(peer_manager_cfg, network_globals.clone(), &log)?
@@ -349,7 +362,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                     transport,
                     behaviour,
                     local_peer_id,
-                    Executor(executor),
+                    Executor(executor.clone()),
                 )
                 .notify_handler_buffer_size(std::num::NonZeroUsize::new(7).expect("Not zero"))
                 .connection_event_buffer_size(64)
@@ -359,6 +372,20 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             )
         };
 
+        let recorder = match &config.record_network_traffic {
+            Some(path) => {
+                let max_size = config.record_network_traffic_max_size_mbs as u64 * 1_048_576;
+                info!(log, "Recording network traffic"; "path" => ?path, "max_size" => max_size);
+                Some(NetworkRecorder::spawn(
+                    path,
+                    max_size,
+                    &executor,
+                    log.clone(),
+                )?)
+            }
+            None => None,
+        };
+
         let mut network = Network {
             swarm,
             network_globals,
@@ -368,6 +395,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             score_settings,
             update_gossipsub_scores,
             gossip_cache,
+            recorder,
             bandwidth,
             local_peer_id,
             log,
@@ -667,12 +695,15 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
 
     /// Informs the gossipsub about the result of a message validation.
     /// If the message is valid it will get propagated by gossipsub.
+    ///
+    /// If the gossip validation log is enabled and the message was not accepted, the outcome is
+    /// recorded in the log and returned.
     pub fn report_message_validation_result(
         &mut self,
         propagation_source: &PeerId,
         message_id: MessageId,
         validation_result: MessageAcceptance,
-    ) {
+    ) -> Option<GossipValidationEntry> {
         if let Some(result) = match validation_result {
             MessageAcceptance::Accept => None,
             MessageAcceptance::Ignore => Some("ignore"),
@@ -692,6 +723,15 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             }
         }
 
+        let entry = self
+            .network_globals
+            .gossip_validation_log
+            .as_ref()
+            .and_then(|log| {
+                log.write()
+                    .message_validated(&message_id, propagation_source, &validation_result)
+            });
+
         if let Err(e) = self.gossipsub_mut().report_message_validation_result(
             &message_id,
             propagation_source,
@@ -699,6 +739,8 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         ) {
             warn!(self.log, "Failed to report message validation"; "message_id" => %message_id, "peer_id" => %propagation_source, "error" => ?e);
         }
+
+        entry
     }
 
     /// Updates the current gossipsub scoring parameters based on the validator count and current
@@ -996,6 +1038,9 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             Request::BlocksByRoot { .. } => {
                 metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["blocks_by_root"])
             }
+            Request::FinalizedStateChunk(_) => {
+                metrics::inc_counter_vec(&metrics::TOTAL_RPC_REQUESTS, &["finalized_state_chunk"])
+            }
         }
         NetworkEvent::RequestReceived {
             peer_id,
@@ -1007,7 +1052,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
     /// Dial cached enrs in discovery service that are in the given `subnet_id` and aren't
     /// in Connected, Dialing or Banned state.
     fn dial_cached_enrs_in_subnet(&mut self, subnet: Subnet) {
-        let predicate = subnet_predicate::<TSpec>(vec![subnet], &self.log);
+        let predicate = self.discovery().subnet_predicate(vec![subnet]);
         let peers_to_dial: Vec<PeerId> = self
             .discovery()
             .cached_enrs()
@@ -1031,6 +1076,22 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         }
     }
 
+    /// Records an inbound message with the network recorder, if one is enabled.
+    ///
+    /// Recording is disabled once the recorder stops, either because writing to the recording
+    /// failed or because it reached its maximum size.
+    fn record_message(
+        &mut self,
+        record: impl FnOnce(&mut NetworkRecorder<TSpec>) -> Result<(), String>,
+    ) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            if let Err(e) = record(recorder) {
+                warn!(self.log, "Network traffic recording disabled"; "error" => e);
+                self.recorder = None;
+            }
+        }
+    }
+
     /* Sub-behaviour event handling functions */
 
     /// Handle a gossipsub event.
@@ -1041,19 +1102,27 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                 message_id: id,
                 message: gs_msg,
             } => {
+                self.record_message(|recorder| {
+                    recorder.record_gossip(&propagation_source, &gs_msg.topic, &gs_msg.data)
+                });
+                if let Some(log) = self.network_globals.gossip_validation_log.as_ref() {
+                    log.write().message_received(id.clone(), &gs_msg.topic);
+                }
+
                 // Note: We are keeping track here of the peer that sent us the message, not the
                 // peer that originally published the message.
                 match PubsubMessage::decode(&gs_msg.topic, &gs_msg.data, &self.fork_context) {
                     Err(e) => {
-                        debug!(self.log, "Could not decode gossipsub message"; "topic" => ?gs_msg.topic,"error" => e);
+                        debug!(self.log, "Could not decode gossipsub message"; "topic" => ?gs_msg.topic,"error" => &e);
+                        if let Some(log) = self.network_globals.gossip_validation_log.as_ref() {
+                            log.write().note_error(&id, format!("Decode error: {}", e));
+                        }
                         //reject the message
-                        if let Err(e) = self.gossipsub_mut().report_message_validation_result(
-                            &id,
+                        self.report_message_validation_result(
                             &propagation_source,
+                            id,
                             MessageAcceptance::Reject,
-                        ) {
-                            warn!(self.log, "Failed to report message validation"; "message_id" => %id, "peer_id" => %propagation_source, "error" => ?e);
-                        }
+                        );
                     }
                     Ok(msg) => {
                         // Notify the network
@@ -1267,9 +1336,18 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                         );
                         Some(event)
                     }
+                    InboundRequest::FinalizedStateChunk(req) => {
+                        let event = self.build_request(
+                            peer_request_id,
+                            peer_id,
+                            Request::FinalizedStateChunk(req),
+                        );
+                        Some(event)
+                    }
                 }
             }
             Ok(RPCReceived::Response(id, resp)) => {
+                self.record_message(|recorder| recorder.record_rpc_response(&peer_id, &resp));
                 match resp {
                     /* Behaviour managed protocols */
                     RPCResponse::Pong(ping) => {
@@ -1298,6 +1376,9 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                     RPCResponse::LightClientBootstrap(bootstrap) => {
                         self.build_response(id, peer_id, Response::LightClientBootstrap(bootstrap))
                     }
+                    RPCResponse::FinalizedStateChunk(chunk) => {
+                        self.build_response(id, peer_id, Response::FinalizedStateChunk(chunk))
+                    }
                 }
             }
             Ok(RPCReceived::EndOfStream(id, termination)) => {
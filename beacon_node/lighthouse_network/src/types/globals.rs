@@ -1,7 +1,7 @@
 //! A collection of variables that are accessible outside of the network thread itself.
 use crate::peer_manager::peerdb::PeerDB;
 use crate::rpc::{MetaData, MetaDataV2};
-use crate::types::{BackFillState, SyncState};
+use crate::types::{BackFillState, GossipValidationLog, SyncState};
 use crate::Client;
 use crate::EnrExt;
 use crate::{Enr, GossipTopic, Multiaddr, PeerId};
@@ -30,6 +30,8 @@ pub struct NetworkGlobals<TSpec: EthSpec> {
     pub sync_state: RwLock<SyncState>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
+    /// The outcome of validating recent gossip messages which were not accepted, if enabled.
+    pub gossip_validation_log: Option<RwLock<GossipValidationLog>>,
 }
 
 impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
@@ -52,6 +54,7 @@ impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
             sync_state: RwLock::new(SyncState::Stalled),
             backfill_state: RwLock::new(BackFillState::NotRequired),
+            gossip_validation_log: None,
         }
     }
 
@@ -0,0 +1,229 @@
+//! A bounded log of the outcome of validating recent gossip messages.
+//!
+//! Entries are created when a message is received, annotated with an error if validation fails and
+//! completed when the validation result is reported back to gossipsub. Only messages which were
+//! ignored or rejected are retained, as accepted messages vastly outnumber them and would quickly
+//! flush them from the log. Completed entries are exposed most recent last.
+//!
+//! Tracking every received message has a cost on the gossip hot path, so the log is only kept
+//! when enabled in the network config.
+use crate::PeerId;
+use libp2p::gossipsub::{MessageAcceptance, MessageId, TopicHash};
+use lru::LruCache;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// The number of ignored or rejected messages retained.
+pub const GOSSIP_VALIDATION_LOG_SIZE: usize = 1_024;
+/// The number of received messages awaiting a validation result that we keep track of. Messages
+/// that are dropped without being validated are eventually evicted.
+const MAX_PENDING_VALIDATIONS: usize = 16_384;
+
+/// The result of validating a gossip message, as reported to gossipsub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GossipValidationResult {
+    Accept,
+    Ignore,
+    Reject,
+}
+
+impl From<&MessageAcceptance> for GossipValidationResult {
+    fn from(acceptance: &MessageAcceptance) -> Self {
+        match acceptance {
+            MessageAcceptance::Accept => GossipValidationResult::Accept,
+            MessageAcceptance::Ignore => GossipValidationResult::Ignore,
+            MessageAcceptance::Reject => GossipValidationResult::Reject,
+        }
+    }
+}
+
+/// The outcome of validating a single gossip message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GossipValidationEntry {
+    /// Milliseconds since the UNIX epoch at which the result was reported.
+    pub timestamp_millis: u64,
+    pub message_id: String,
+    /// The topic the message was received on, if it was seen arriving.
+    pub topic: Option<String>,
+    /// The peer that forwarded the message to us.
+    pub peer_id: String,
+    pub result: GossipValidationResult,
+    /// The error that caused the message to be ignored or rejected, if known.
+    pub error: Option<String>,
+    /// The time between receiving the message and reporting the result.
+    pub latency_ms: Option<u64>,
+}
+
+/// A message that has been received but not yet validated.
+#[derive(Default)]
+struct PendingValidation {
+    topic: Option<TopicHash>,
+    received: Option<Instant>,
+    error: Option<String>,
+}
+
+pub struct GossipValidationLog {
+    pending: LruCache<MessageId, PendingValidation>,
+    entries: VecDeque<GossipValidationEntry>,
+    capacity: usize,
+}
+
+impl Default for GossipValidationLog {
+    fn default() -> Self {
+        Self::new(GOSSIP_VALIDATION_LOG_SIZE)
+    }
+}
+
+impl GossipValidationLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            pending: LruCache::new(MAX_PENDING_VALIDATIONS),
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Registers the arrival of a message on `topic`.
+    pub fn message_received(&mut self, message_id: MessageId, topic: &TopicHash) {
+        self.pending.put(
+            message_id,
+            PendingValidation {
+                topic: Some(topic.clone()),
+                received: Some(Instant::now()),
+                error: None,
+            },
+        );
+    }
+
+    /// Records the reason a message failed validation. The reason is included in the entry once
+    /// the validation result is reported.
+    pub fn note_error(&mut self, message_id: &MessageId, error: String) {
+        match self.pending.get_mut(message_id) {
+            Some(pending) => pending.error = Some(error),
+            None => {
+                self.pending.put(
+                    message_id.clone(),
+                    PendingValidation {
+                        error: Some(error),
+                        ..PendingValidation::default()
+                    },
+                );
+            }
+        }
+    }
+
+    /// Completes the entry for `message_id`, returning it if the message was not accepted.
+    pub fn message_validated(
+        &mut self,
+        message_id: &MessageId,
+        peer_id: &PeerId,
+        acceptance: &MessageAcceptance,
+    ) -> Option<GossipValidationEntry> {
+        let pending = self.pending.pop(message_id).unwrap_or_default();
+        if matches!(acceptance, MessageAcceptance::Accept) {
+            return None;
+        }
+
+        let timestamp_millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+
+        let entry = GossipValidationEntry {
+            timestamp_millis,
+            message_id: message_id.to_string(),
+            topic: pending.topic.map(|topic| topic.to_string()),
+            peer_id: peer_id.to_string(),
+            result: acceptance.into(),
+            error: pending.error,
+            latency_ms: pending
+                .received
+                .map(|received| received.elapsed().as_millis() as u64),
+        };
+
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry.clone());
+        Some(entry)
+    }
+
+    /// Returns the completed entries, oldest first.
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &GossipValidationEntry> + '_ {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_bounded_and_annotated() {
+        let mut log = GossipValidationLog::new(2);
+        let peer_id = PeerId::random();
+        let topic = TopicHash::from_raw("/eth2/00000000/beacon_block/ssz_snappy");
+
+        for i in 0..3u8 {
+            let message_id = MessageId::new(&[i]);
+            log.message_received(message_id.clone(), &topic);
+            if i == 2 {
+                log.note_error(&message_id, "FutureSlot".into());
+            }
+            log.message_validated(&message_id, &peer_id, &MessageAcceptance::Ignore);
+        }
+
+        let entries = log.entries().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message_id, MessageId::new(&[1]).to_string());
+        assert_eq!(entries[1].error.as_deref(), Some("FutureSlot"));
+        assert_eq!(entries[1].topic.as_deref(), Some(topic.as_str()));
+        assert_eq!(entries[1].result, GossipValidationResult::Ignore);
+        assert!(entries[1].latency_ms.is_some());
+    }
+
+    #[test]
+    fn unseen_message() {
+        let mut log = GossipValidationLog::default();
+        let message_id = MessageId::new(&[0]);
+        log.note_error(&message_id, "UnknownHeadBlock".into());
+        let entry = log
+            .message_validated(&message_id, &PeerId::random(), &MessageAcceptance::Reject)
+            .unwrap();
+        assert_eq!(entry.topic, None);
+        assert_eq!(entry.latency_ms, None);
+        assert_eq!(entry.error.as_deref(), Some("UnknownHeadBlock"));
+    }
+
+    #[test]
+    fn accepted_messages_are_not_retained() {
+        let mut log = GossipValidationLog::new(2);
+        let peer_id = PeerId::random();
+        let topic = TopicHash::from_raw("/eth2/00000000/beacon_attestation_0/ssz_snappy");
+
+        let rejected = MessageId::new(&[0]);
+        log.message_received(rejected.clone(), &topic);
+        log.note_error(&rejected, "InvalidSignature".into());
+        assert!(log
+            .message_validated(&rejected, &peer_id, &MessageAcceptance::Reject)
+            .is_some());
+
+        for i in 1..10u8 {
+            let message_id = MessageId::new(&[i]);
+            log.message_received(message_id.clone(), &topic);
+            assert_eq!(
+                log.message_validated(&message_id, &peer_id, &MessageAcceptance::Accept),
+                None
+            );
+        }
+
+        // The accepted messages neither displace the rejected one nor linger as pending.
+        let entries = log.entries().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].message_id, rejected.to_string());
+        assert_eq!(entries[0].result, GossipValidationResult::Reject);
+        assert!(log.pending.is_empty());
+    }
+}
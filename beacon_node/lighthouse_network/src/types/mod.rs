@@ -1,5 +1,6 @@
 pub mod error;
 mod globals;
+mod gossip_validation_log;
 mod pubsub;
 mod subnet;
 mod sync_state;
@@ -13,9 +14,12 @@ pub type EnrSyncCommitteeBitfield<T> = BitVector<<T as EthSpec>::SyncCommitteeSu
 pub type Enr = discv5::enr::Enr<discv5::enr::CombinedKey>;
 
 pub use globals::NetworkGlobals;
+pub use gossip_validation_log::{
+    GossipValidationEntry, GossipValidationLog, GossipValidationResult, GOSSIP_VALIDATION_LOG_SIZE,
+};
 pub use pubsub::{PubsubMessage, SnappyTransform};
 pub use subnet::{Subnet, SubnetDiscovery};
-pub use sync_state::{BackFillState, SyncState};
+pub use sync_state::{BackFillProgress, BackFillState, SyncState};
 pub use topics::{
     core_topics_to_subscribe, fork_core_topics, subnet_from_topic_hash, GossipEncoding, GossipKind,
     GossipTopic, LIGHT_CLIENT_GOSSIP_TOPICS,
@@ -14,7 +14,15 @@ pub enum SyncState {
     /// state. The node first syncs "forward" by downloading blocks up to the current head as
     /// specified by its peers. Once completed, the node enters this sync state and attempts to
     /// download all required historical blocks to complete its chain.
-    BackFillSyncing { completed: usize, remaining: usize },
+    BackFillSyncing {
+        /// The number of slots imported since the backfill was started or resumed.
+        completed: usize,
+        /// The number of slots between genesis and the oldest imported block.
+        remaining: usize,
+        /// The estimated time until the backfill completes, based on its progress so far.
+        #[serde(default)]
+        estimated_seconds_remaining: Option<u64>,
+    },
     /// The node has completed syncing a finalized chain and is in the process of re-evaluating
     /// which sync state to progress to.
     SyncTransition,
@@ -41,6 +49,21 @@ pub enum BackFillState {
     Failed,
 }
 
+/// The progress of an ongoing backfill sync.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BackFillProgress {
+    /// The number of slots backfilled since the sync was started or resumed.
+    pub completed: usize,
+    /// The number of slots between genesis and the oldest block in the database.
+    pub remaining: usize,
+    /// The slot of the oldest block in the database.
+    pub oldest_block_slot: Slot,
+    /// The average number of slots backfilled per second since the sync was started or resumed.
+    pub slots_per_second: f64,
+    /// The estimated time until the backfill completes, if any progress has been made.
+    pub estimated_seconds_remaining: Option<u64>,
+}
+
 impl PartialEq for SyncState {
     fn eq(&self, other: &Self) -> bool {
         matches!(
@@ -45,7 +45,7 @@ use beacon_chain::{BeaconChain, BeaconChainTypes, GossipVerifiedBlock, NotifyExe
 use derivative::Derivative;
 use futures::stream::{Stream, StreamExt};
 use futures::task::Poll;
-use lighthouse_network::rpc::LightClientBootstrapRequest;
+use lighthouse_network::rpc::{FinalizedStateChunkRequest, LightClientBootstrapRequest};
 use lighthouse_network::{
     rpc::{BlocksByRangeRequest, BlocksByRootRequest, StatusMessage},
     Client, MessageId, NetworkGlobals, PeerId, PeerRequestId,
@@ -180,6 +180,10 @@ const MAX_BLS_TO_EXECUTION_CHANGE_QUEUE_LEN: usize = 16_384;
 /// will be stored before we start dropping them.
 const MAX_LIGHT_CLIENT_BOOTSTRAP_QUEUE_LEN: usize = 1_024;
 
+/// The maximum number of queued `FinalizedStateChunkRequest` objects received from the network RPC
+/// that will be stored before we start dropping them.
+const MAX_FINALIZED_STATE_CHUNK_QUEUE_LEN: usize = 256;
+
 /// The name of the manager tokio task.
 const MANAGER_TASK_NAME: &str = "beacon_processor_manager";
 
@@ -222,6 +226,7 @@ pub const STATUS_PROCESSING: &str = "status_processing";
 pub const BLOCKS_BY_RANGE_REQUEST: &str = "blocks_by_range_request";
 pub const BLOCKS_BY_ROOTS_REQUEST: &str = "blocks_by_roots_request";
 pub const LIGHT_CLIENT_BOOTSTRAP_REQUEST: &str = "light_client_bootstrap";
+pub const FINALIZED_STATE_CHUNK_REQUEST: &str = "finalized_state_chunk_request";
 pub const UNKNOWN_BLOCK_ATTESTATION: &str = "unknown_block_attestation";
 pub const UNKNOWN_BLOCK_AGGREGATE: &str = "unknown_block_aggregate";
 pub const UNKNOWN_LIGHT_CLIENT_UPDATE: &str = "unknown_light_client_update";
@@ -360,6 +365,43 @@ impl DuplicateCache {
     }
 }
 
+/// The number of encoded finalized states kept by the `FinalizedStateCache`.
+///
+/// Downloads usually request the state of a recent finalized checkpoint, so a couple of states are
+/// enough for concurrent downloads to share them.
+const FINALIZED_STATE_CACHE_SIZE: usize = 2;
+
+/// Holds the SSZ encoding of the finalized states most recently served to peers, so that each chunk
+/// request doesn't require loading and encoding the state again.
+#[derive(Clone, Default)]
+pub struct FinalizedStateCache {
+    inner: Arc<Mutex<VecDeque<(Hash256, Arc<Vec<u8>>)>>>,
+}
+
+impl FinalizedStateCache {
+    /// Returns the encoded state for the given block root, if it is cached.
+    pub fn get(&self, block_root: &Hash256) -> Option<Arc<Vec<u8>>> {
+        self.inner
+            .lock()
+            .iter()
+            .find(|(root, _)| root == block_root)
+            .map(|(_, bytes)| bytes.clone())
+    }
+
+    /// Adds the encoded state for the given block root, evicting the oldest state if the cache is
+    /// full.
+    pub fn insert(&self, block_root: Hash256, state_bytes: Arc<Vec<u8>>) {
+        let mut inner = self.inner.lock();
+        if inner.iter().any(|(root, _)| *root == block_root) {
+            return;
+        }
+        if inner.len() >= FINALIZED_STATE_CACHE_SIZE {
+            inner.pop_front();
+        }
+        inner.push_back((block_root, state_bytes));
+    }
+}
+
 /// An event to be processed by the manager task.
 #[derive(Derivative)]
 #[derivative(Debug(bound = "T: BeaconChainTypes"))]
@@ -654,6 +696,22 @@ impl<T: BeaconChainTypes> WorkEvent<T> {
         }
     }
 
+    /// Create a new work event to process `FinalizedStateChunk`s from the RPC network.
+    pub fn finalized_state_chunk_request(
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: FinalizedStateChunkRequest,
+    ) -> Self {
+        Self {
+            drop_during_sync: true,
+            work: Work::FinalizedStateChunkRequest {
+                peer_id,
+                request_id,
+                request,
+            },
+        }
+    }
+
     /// Get a `str` representation of the type of work this `WorkEvent` contains.
     pub fn work_type(&self) -> &'static str {
         self.work.str_id()
@@ -873,6 +931,11 @@ pub enum Work<T: BeaconChainTypes> {
         request_id: PeerRequestId,
         request: LightClientBootstrapRequest,
     },
+    FinalizedStateChunkRequest {
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: FinalizedStateChunkRequest,
+    },
 }
 
 impl<T: BeaconChainTypes> Work<T> {
@@ -898,6 +961,7 @@ impl<T: BeaconChainTypes> Work<T> {
             Work::BlocksByRangeRequest { .. } => BLOCKS_BY_RANGE_REQUEST,
             Work::BlocksByRootsRequest { .. } => BLOCKS_BY_ROOTS_REQUEST,
             Work::LightClientBootstrapRequest { .. } => LIGHT_CLIENT_BOOTSTRAP_REQUEST,
+            Work::FinalizedStateChunkRequest { .. } => FINALIZED_STATE_CHUNK_REQUEST,
             Work::UnknownBlockAttestation { .. } => UNKNOWN_BLOCK_ATTESTATION,
             Work::UnknownBlockAggregate { .. } => UNKNOWN_BLOCK_AGGREGATE,
             Work::GossipBlsToExecutionChange { .. } => GOSSIP_BLS_TO_EXECUTION_CHANGE,
@@ -984,6 +1048,7 @@ pub struct BeaconProcessor<T: BeaconChainTypes> {
     pub max_workers: usize,
     pub current_workers: usize,
     pub importing_blocks: DuplicateCache,
+    pub finalized_state_cache: FinalizedStateCache,
     pub log: Logger,
 }
 
@@ -998,7 +1063,7 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
     /// started with `spawn_blocking`.
     ///
     /// The optional `work_journal_tx` allows for an outside process to receive a log of all work
-    /// events processed by `self`. This should only be used during testing.
+    /// events processed by `self`. This should only be used during testing and network replays.
     pub fn spawn_manager(
         mut self,
         event_rx: mpsc::Receiver<WorkEvent<T>>,
@@ -1054,6 +1119,7 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
             FifoQueue::new(MAX_BLS_TO_EXECUTION_CHANGE_QUEUE_LEN);
 
         let mut lcbootstrap_queue = FifoQueue::new(MAX_LIGHT_CLIENT_BOOTSTRAP_QUEUE_LEN);
+        let mut finalized_state_chunk_queue = FifoQueue::new(MAX_FINALIZED_STATE_CHUNK_QUEUE_LEN);
         // Channels for sending work to the re-process scheduler (`work_reprocessing_tx`) and to
         // receive them back once they are ready (`ready_work_rx`).
         let (ready_work_tx, ready_work_rx) = mpsc::channel(MAX_SCHEDULED_WORK_QUEUE_LEN);
@@ -1061,6 +1127,7 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
             if let Some(chain) = self.beacon_chain.upgrade() {
                 spawn_reprocess_scheduler(
                     ready_work_tx,
+                    self.sync_tx.clone(),
                     &self.executor,
                     chain.slot_clock.clone(),
                     self.log.clone(),
@@ -1297,6 +1364,8 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                         // This statement should always be the final else statement.
                         } else if let Some(item) = lcbootstrap_queue.pop() {
                             self.spawn_worker(item, toolbox);
+                        } else if let Some(item) = finalized_state_chunk_queue.pop() {
+                            self.spawn_worker(item, toolbox);
                         } else {
                             // Let the journal know that a worker is freed and there's nothing else
                             // for it to do.
@@ -1406,6 +1475,9 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                             Work::LightClientBootstrapRequest { .. } => {
                                 lcbootstrap_queue.push(work, work_id, &self.log)
                             }
+                            Work::FinalizedStateChunkRequest { .. } => {
+                                finalized_state_chunk_queue.push(work, work_id, &self.log)
+                            }
                             Work::UnknownBlockAttestation { .. } => {
                                 unknown_block_attestation_queue.push(work)
                             }
@@ -1544,10 +1616,12 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
             chain,
             network_tx: self.network_tx.clone(),
             sync_tx: self.sync_tx.clone(),
+            network_globals: self.network_globals.clone(),
             log: self.log.clone(),
         };
 
         let duplicate_cache = self.importing_blocks.clone();
+        let finalized_state_cache = self.finalized_state_cache.clone();
 
         trace!(
             self.log,
@@ -1694,6 +1768,7 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                     *sync_signature,
                     subnet_id,
                     seen_timestamp,
+                    work_reprocessing_tx,
                 )
             }),
             /*
@@ -1710,6 +1785,7 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                     peer_id,
                     *sync_contribution,
                     seen_timestamp,
+                    work_reprocessing_tx,
                 )
             }),
             /*
@@ -1846,6 +1922,21 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
             } => task_spawner.spawn_blocking(move || {
                 worker.handle_light_client_bootstrap(peer_id, request_id, request)
             }),
+            /*
+             * Processing of finalized state chunk requests from other peers.
+             */
+            Work::FinalizedStateChunkRequest {
+                peer_id,
+                request_id,
+                request,
+            } => task_spawner.spawn_blocking(move || {
+                worker.handle_finalized_state_chunk_request(
+                    peer_id,
+                    request_id,
+                    request,
+                    finalized_state_cache,
+                )
+            }),
             Work::UnknownBlockAttestation {
                 message_id,
                 peer_id,
@@ -9,22 +9,25 @@ use crate::{service::NetworkMessage, sync::SyncMessage};
 use beacon_chain::test_utils::{
     AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
 };
-use beacon_chain::{BeaconChain, MAXIMUM_GOSSIP_CLOCK_DISPARITY};
+use beacon_chain::{BeaconChain, WhenSlotSkipped, MAXIMUM_GOSSIP_CLOCK_DISPARITY};
 use lighthouse_network::{
     discv5::enr::{CombinedKey, EnrBuilder},
-    rpc::methods::{MetaData, MetaDataV2},
+    libp2p::core::connection::ConnectionId,
+    rpc::methods::{MetaData, MetaDataV2, MAX_FINALIZED_STATE_CHUNK_LEN},
+    rpc::{RPCResponseErrorCode, SubstreamId},
     types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield},
-    MessageId, NetworkGlobals, PeerId,
+    MessageId, NetworkGlobals, PeerId, Response,
 };
 use slot_clock::SlotClock;
+use ssz::Encode;
 use std::cmp;
 use std::iter::Iterator;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use types::{
-    Attestation, AttesterSlashing, EthSpec, MainnetEthSpec, ProposerSlashing, SignedBeaconBlock,
-    SignedVoluntaryExit, SubnetId,
+    Attestation, AttesterSlashing, EthSpec, Hash256, MainnetEthSpec, ProposerSlashing,
+    SignedBeaconBlock, SignedVoluntaryExit, Slot, SubnetId,
 };
 
 type E = MainnetEthSpec;
@@ -34,6 +37,7 @@ const SLOTS_PER_EPOCH: u64 = 32;
 const VALIDATOR_COUNT: usize = SLOTS_PER_EPOCH as usize;
 const SMALL_CHAIN: u64 = 2;
 const LONG_CHAIN: u64 = SLOTS_PER_EPOCH * 2;
+const FINALIZED_CHAIN: u64 = SLOTS_PER_EPOCH * 5;
 
 const TCP_PORT: u16 = 42;
 const SEQ_NUMBER: u64 = 0;
@@ -53,8 +57,8 @@ struct TestRig {
     voluntary_exit: SignedVoluntaryExit,
     beacon_processor_tx: mpsc::Sender<WorkEvent<T>>,
     work_journal_rx: mpsc::Receiver<&'static str>,
-    _network_rx: mpsc::UnboundedReceiver<NetworkMessage<E>>,
-    _sync_rx: mpsc::UnboundedReceiver<SyncMessage<E>>,
+    network_rx: mpsc::UnboundedReceiver<NetworkMessage<E>>,
+    sync_rx: mpsc::UnboundedReceiver<SyncMessage<E>>,
     duplicate_cache: DuplicateCache,
     _harness: BeaconChainHarness<T>,
 }
@@ -159,12 +163,12 @@ impl TestRig {
 
         let chain = harness.chain.clone();
 
-        let (network_tx, _network_rx) = mpsc::unbounded_channel();
+        let (network_tx, network_rx) = mpsc::unbounded_channel();
 
         let log = harness.logger().clone();
 
         let (beacon_processor_tx, beacon_processor_rx) = mpsc::channel(MAX_WORK_EVENT_QUEUE_LEN);
-        let (sync_tx, _sync_rx) = mpsc::unbounded_channel();
+        let (sync_tx, sync_rx) = mpsc::unbounded_channel();
 
         // Default metadata
         let meta_data = MetaData::V2(MetaDataV2 {
@@ -197,6 +201,7 @@ impl TestRig {
             max_workers: cmp::max(1, num_cpus::get()),
             current_workers: 0,
             importing_blocks: duplicate_cache.clone(),
+            finalized_state_cache: Default::default(),
             log: log.clone(),
         }
         .spawn_manager(beacon_processor_rx, Some(work_journal_tx));
@@ -212,8 +217,8 @@ impl TestRig {
             voluntary_exit,
             beacon_processor_tx,
             work_journal_rx,
-            _network_rx,
-            _sync_rx,
+            network_rx,
+            sync_rx,
             duplicate_cache,
             _harness: harness,
         }
@@ -335,6 +340,26 @@ impl TestRig {
             .unwrap();
     }
 
+    pub fn enqueue_finalized_state_chunk_request(&self, block_root: Hash256, offset: u64) {
+        self.beacon_processor_tx
+            .try_send(WorkEvent::finalized_state_chunk_request(
+                junk_peer_id(),
+                (ConnectionId::new(0), SubstreamId::new(0)),
+                FinalizedStateChunkRequest { block_root, offset },
+            ))
+            .unwrap();
+    }
+
+    /// Returns the SSZ encoding of the post-state of `block_root`.
+    pub fn state_bytes(&self, block_root: Hash256) -> Vec<u8> {
+        let block = self.chain.get_blinded_block(&block_root).unwrap().unwrap();
+        self.chain
+            .get_state(&block.state_root(), Some(block.slot()))
+            .unwrap()
+            .unwrap()
+            .as_ssz_bytes()
+    }
+
     /// Assert that the `BeaconProcessor` doesn't produce any events in the given `duration`.
     pub async fn assert_no_events_for(&mut self, duration: Duration) {
         tokio::select! {
@@ -590,6 +615,7 @@ async fn attestation_to_unknown_block_processed(import_method: BlockImportMethod
     // Send the attestation but not the block, and check that it was not imported.
 
     let initial_attns = rig.chain.naive_aggregation_pool.read().num_items();
+    let initial_votes = rig.chain.op_pool.attestation_stats().num_unaggregated_votes;
 
     rig.enqueue_next_block_unaggregated_attestation();
 
@@ -633,6 +659,14 @@ async fn attestation_to_unknown_block_processed(import_method: BlockImportMethod
         initial_attns + 1,
         "Attestation should have been included."
     );
+    rig.chain
+        .op_pool
+        .insert_buffered_unaggregated_attestations();
+    assert_eq!(
+        rig.chain.op_pool.attestation_stats().num_unaggregated_votes,
+        initial_votes + 1,
+        "Attestation should have been added to the op pool."
+    );
 }
 
 #[tokio::test]
@@ -714,6 +748,81 @@ async fn aggregate_attestation_to_unknown_block_processed_after_rpc_block() {
     aggregate_attestation_to_unknown_block(BlockImportMethod::Rpc).await
 }
 
+/// Ensure that messages referencing the same unknown block are deduplicated into lookups of a
+/// single root, requested from every peer that referenced it.
+#[tokio::test]
+async fn unknown_block_lookups_deduplicated_by_root() {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+
+    rig.enqueue_next_block_unaggregated_attestation();
+    rig.enqueue_next_block_aggregated_attestation();
+
+    // Collect the lookups sent to sync until none arrive for a while.
+    let mut lookups = vec![];
+    while let Ok(Some(message)) =
+        tokio::time::timeout(Duration::from_secs(1), rig.sync_rx.recv()).await
+    {
+        match message {
+            SyncMessage::UnknownBlockHashes {
+                lookups: batch,
+                accepted_tx,
+            } => {
+                accepted_tx.send(true).unwrap();
+                lookups.extend(batch)
+            }
+            other => panic!("unexpected sync message {:?}", other),
+        }
+    }
+
+    let next_block_root = rig.next_block.canonical_root();
+    assert!(
+        lookups.iter().all(|(root, _)| *root == next_block_root),
+        "Only the unknown block should be looked up."
+    );
+    assert_eq!(
+        lookups.iter().map(|(_, peers)| peers.len()).sum::<usize>(),
+        2,
+        "Each peer should be sent to sync once."
+    );
+}
+
+/// Receives the next batch of unknown block lookups sent to sync and answers it with `accepted`.
+async fn answer_unknown_block_lookups(
+    sync_rx: &mut mpsc::UnboundedReceiver<SyncMessage<E>>,
+    accepted: bool,
+) -> Vec<(Hash256, Vec<PeerId>)> {
+    match tokio::time::timeout(Duration::from_secs(1), sync_rx.recv()).await {
+        Ok(Some(SyncMessage::UnknownBlockHashes {
+            lookups,
+            accepted_tx,
+        })) => {
+            accepted_tx.send(accepted).unwrap();
+            lookups
+        }
+        other => panic!("expected unknown block lookups, got {:?}", other),
+    }
+}
+
+/// Ensure that lookups ignored by sync, e.g. because the node is not synced, are sent again.
+#[tokio::test]
+async fn ignored_unknown_block_lookups_are_resent() {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+
+    rig.enqueue_next_block_unaggregated_attestation();
+
+    let ignored = answer_unknown_block_lookups(&mut rig.sync_rx, false).await;
+    let resent = answer_unknown_block_lookups(&mut rig.sync_rx, true).await;
+    assert_eq!(ignored.len(), 1);
+    assert_eq!(ignored, resent, "Ignored lookups should be sent again.");
+
+    assert!(
+        tokio::time::timeout(Duration::from_secs(1), rig.sync_rx.recv())
+            .await
+            .is_err(),
+        "Accepted lookups should not be sent again."
+    );
+}
+
 /// Ensure that attestations that reference an unknown block get properly re-queued and re-processed
 /// when the block is not seen.
 #[tokio::test]
@@ -873,3 +982,119 @@ async fn test_rpc_block_reprocessing() {
     // cache handle was dropped.
     assert_eq!(next_block_root, rig.head_root());
 }
+
+/// Chunks of the state of the latest finalized checkpoint are served.
+#[tokio::test]
+async fn finalized_state_chunk_served() {
+    let mut rig = TestRig::new(FINALIZED_CHAIN).await;
+    let finalized_checkpoint = rig
+        .chain
+        .canonical_head
+        .cached_head()
+        .finalized_checkpoint();
+    assert!(
+        finalized_checkpoint.epoch > 0,
+        "precondition: chain is finalized"
+    );
+    let state_bytes = rig.state_bytes(finalized_checkpoint.root);
+    assert!(
+        state_bytes.len() as u64 > MAX_FINALIZED_STATE_CHUNK_LEN,
+        "precondition: state spans multiple chunks"
+    );
+
+    rig.enqueue_finalized_state_chunk_request(
+        finalized_checkpoint.root,
+        MAX_FINALIZED_STATE_CHUNK_LEN,
+    );
+    rig.assert_event_journal(&[FINALIZED_STATE_CHUNK_REQUEST, WORKER_FREED, NOTHING_TO_DO])
+        .await;
+
+    match rig.network_rx.try_recv() {
+        Ok(NetworkMessage::SendResponse {
+            response: Response::FinalizedStateChunk(chunk),
+            ..
+        }) => {
+            let start = MAX_FINALIZED_STATE_CHUNK_LEN as usize;
+            let end = cmp::min(start * 2, state_bytes.len());
+            assert_eq!(chunk.block_root, finalized_checkpoint.root);
+            assert_eq!(chunk.offset, MAX_FINALIZED_STATE_CHUNK_LEN);
+            assert_eq!(chunk.total_len, state_bytes.len() as u64);
+            assert_eq!(&chunk.data[..], &state_bytes[start..end]);
+        }
+        other => panic!("expected a state chunk, got {:?}", other),
+    }
+}
+
+/// States of earlier finalized checkpoints are served.
+#[tokio::test]
+async fn finalized_state_chunk_for_old_checkpoint_served() {
+    let mut rig = TestRig::new(FINALIZED_CHAIN).await;
+    let old_root = rig
+        .chain
+        .block_root_at_slot(Slot::new(SLOTS_PER_EPOCH), WhenSlotSkipped::None)
+        .unwrap()
+        .unwrap();
+    assert_ne!(
+        old_root,
+        rig.chain
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+            .root,
+        "precondition: block is not the latest finalized checkpoint"
+    );
+    let state_bytes = rig.state_bytes(old_root);
+
+    rig.enqueue_finalized_state_chunk_request(old_root, 0);
+    rig.assert_event_journal(&[FINALIZED_STATE_CHUNK_REQUEST, WORKER_FREED, NOTHING_TO_DO])
+        .await;
+
+    match rig.network_rx.try_recv() {
+        Ok(NetworkMessage::SendResponse {
+            response: Response::FinalizedStateChunk(chunk),
+            ..
+        }) => {
+            let end = cmp::min(MAX_FINALIZED_STATE_CHUNK_LEN as usize, state_bytes.len());
+            assert_eq!(chunk.block_root, old_root);
+            assert_eq!(chunk.total_len, state_bytes.len() as u64);
+            assert_eq!(&chunk.data[..], &state_bytes[..end]);
+        }
+        other => panic!("expected a state chunk, got {:?}", other),
+    }
+}
+
+/// States of blocks which are not at the start of an epoch, or are not finalized, are not served.
+#[tokio::test]
+async fn finalized_state_chunk_for_unaligned_or_unfinalized_block_rejected() {
+    let unaligned_slot = Slot::new(1);
+    let unfinalized_slot = Slot::new(SLOTS_PER_EPOCH * 4);
+    for slot in [unaligned_slot, unfinalized_slot] {
+        let mut rig = TestRig::new(FINALIZED_CHAIN).await;
+        let finalized_epoch = rig
+            .chain
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+            .epoch;
+        assert!(
+            finalized_epoch < unfinalized_slot.epoch(SLOTS_PER_EPOCH),
+            "precondition: block is not finalized"
+        );
+        let block_root = rig
+            .chain
+            .block_root_at_slot(slot, WhenSlotSkipped::None)
+            .unwrap()
+            .unwrap();
+
+        rig.enqueue_finalized_state_chunk_request(block_root, 0);
+        rig.assert_event_journal(&[FINALIZED_STATE_CHUNK_REQUEST, WORKER_FREED, NOTHING_TO_DO])
+            .await;
+
+        match rig.network_rx.try_recv() {
+            Ok(NetworkMessage::SendErrorResponse { error, .. }) => {
+                assert_eq!(error, RPCResponseErrorCode::ResourceUnavailable)
+            }
+            other => panic!("expected an error response, got {:?}", other),
+        }
+    }
+}
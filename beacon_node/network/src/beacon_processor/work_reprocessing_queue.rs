@@ -10,9 +10,16 @@
 //!
 //! Aggregated and unaggregated attestations that failed verification due to referencing an unknown
 //! block will be re-queued until their block is imported, or until they expire.
+//!
+//! The roots referenced by these attestations, and by sync committee messages, are tracked as
+//! unknown roots. Lookups for them are deduplicated by root and sent to the sync manager in
+//! batches, and the time taken for each root to be imported is recorded. Once the sync manager has
+//! accepted the lookup of a root, the messages referencing it are held for a further
+//! `QUEUED_ATTESTATION_DELAY` to give the lookup time to complete.
 use super::MAX_SCHEDULED_WORK_QUEUE_LEN;
 use crate::metrics;
 use crate::sync::manager::BlockProcessType;
+use crate::sync::SyncMessage;
 use beacon_chain::{BeaconChainTypes, GossipVerifiedBlock, MAXIMUM_GOSSIP_CLOCK_DISPARITY};
 use fnv::FnvHashMap;
 use futures::task::Poll;
@@ -22,13 +29,16 @@ use logging::TimeLatch;
 use slog::{crit, debug, error, trace, warn, Logger};
 use slot_clock::SlotClock;
 use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::Context;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use task_executor::TaskExecutor;
-use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::mpsc::{self, Receiver, Sender, UnboundedSender};
+use tokio::sync::oneshot;
 use tokio::time::error::Error as TimeError;
+use tokio::time::{sleep, Sleep};
 use tokio_util::time::delay_queue::{DelayQueue, Key as DelayKey};
 use types::{
     Attestation, EthSpec, Hash256, LightClientOptimisticUpdate, SignedAggregateAndProof,
@@ -40,6 +50,7 @@ const GOSSIP_BLOCKS: &str = "gossip_blocks";
 const RPC_BLOCKS: &str = "rpc_blocks";
 const ATTESTATIONS: &str = "attestations";
 const LIGHT_CLIENT_UPDATES: &str = "lc_updates";
+const UNKNOWN_ROOTS: &str = "unknown_roots";
 
 /// Queue blocks for re-processing with an `ADDITIONAL_QUEUED_BLOCK_DELAY` after the slot starts.
 /// This is to account for any slight drift in the system clock.
@@ -65,6 +76,16 @@ const MAXIMUM_QUEUED_ATTESTATIONS: usize = 16_384;
 /// How many light client updates we keep before new ones get dropped.
 const MAXIMUM_QUEUED_LIGHT_CLIENT_UPDATES: usize = 128;
 
+/// How many unknown block roots we track before the oldest are evicted to make room for new ones.
+const MAXIMUM_UNKNOWN_ROOTS: usize = 1_024;
+
+/// How many peers we remember per unknown root to request the block from.
+const MAXIMUM_LOOKUP_PEERS_PER_ROOT: usize = 8;
+
+/// How long after a peer first references an unknown root its lookup is sent to the sync manager,
+/// so that the peers referencing the same roots are batched.
+const UNKNOWN_ROOT_LOOKUP_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Messages that the scheduler can receive.
 pub enum ReprocessQueueMessage<T: BeaconChainTypes> {
     /// A block that has been received early and we should queue for later processing.
@@ -84,6 +105,12 @@ pub enum ReprocessQueueMessage<T: BeaconChainTypes> {
     UnknownBlockAggregate(QueuedAggregate<T::EthSpec>),
     /// A light client optimistic update that references a parent root that has not been seen as a parent.
     UnknownLightClientOptimisticUpdate(QueuedLightClientUpdate<T::EthSpec>),
+    /// A sync committee message or contribution that references an unknown block. The message
+    /// has already been processed so only a lookup of the block is scheduled.
+    UnknownBlockSyncMessage {
+        peer_id: PeerId,
+        beacon_block_root: Hash256,
+    },
 }
 
 /// Events sent by the scheduler once they are ready for re-processing.
@@ -155,6 +182,12 @@ enum InboundEvent<T: BeaconChainTypes> {
     ReadyAttestation(QueuedAttestationId),
     /// A light client update that is ready for re-processing.
     ReadyLightClientUpdate(QueuedLightClientUpdateId),
+    /// An unknown root has not been imported in time and is no longer tracked.
+    ExpiredUnknownRoot(Hash256),
+    /// Lookups for newly referenced unknown roots should be sent to the sync manager.
+    UnknownRootLookupTick,
+    /// The sync manager has accepted or ignored the lookups sent to it.
+    UnknownRootLookupResult(PendingLookups, bool),
     /// A `DelayQueue` returned an error.
     DelayQueueError(TimeError, &'static str),
     /// A message sent to the `ReprocessQueue`
@@ -167,6 +200,8 @@ struct ReprocessQueue<T: BeaconChainTypes> {
     work_reprocessing_rx: Receiver<ReprocessQueueMessage<T>>,
     /// Sender of works once they become ready
     ready_work_tx: Sender<ReadyWork<T>>,
+    /// Sender of block lookups for unknown roots.
+    sync_tx: UnboundedSender<SyncMessage<T::EthSpec>>,
 
     /* Queues */
     /// Queue to manage scheduled early blocks.
@@ -177,6 +212,8 @@ struct ReprocessQueue<T: BeaconChainTypes> {
     attestations_delay_queue: DelayQueue<QueuedAttestationId>,
     /// Queue to manage scheduled light client updates.
     lc_updates_delay_queue: DelayQueue<QueuedLightClientUpdateId>,
+    /// Queue to manage the expiry of unknown roots.
+    unknown_roots_delay_queue: DelayQueue<Hash256>,
 
     /* Queued items */
     /// Queued blocks.
@@ -191,8 +228,15 @@ struct ReprocessQueue<T: BeaconChainTypes> {
     queued_lc_updates: FnvHashMap<usize, (QueuedLightClientUpdate<T::EthSpec>, DelayKey)>,
     /// Light Client Updates per parent_root.
     awaiting_lc_updates_per_parent_root: HashMap<Hash256, Vec<QueuedLightClientUpdateId>>,
+    /// Block roots referenced by queued messages which have not been imported yet.
+    unknown_roots: HashMap<Hash256, UnknownRoot>,
 
     /* Aux */
+    /// Fires when lookups for unknown roots should be sent. Only armed while there are peers
+    /// which have not been sent to the sync manager.
+    unknown_root_lookup_timer: Option<Pin<Box<Sleep>>>,
+    /// Lookups sent to the sync manager which it has not yet accepted or ignored.
+    pending_lookups: Option<(PendingLookups, oneshot::Receiver<bool>)>,
     /// Next attestation id, used for both aggregated and unaggregated attestations
     next_attestation: usize,
     next_lc_update: usize,
@@ -200,8 +244,26 @@ struct ReprocessQueue<T: BeaconChainTypes> {
     rpc_block_debounce: TimeLatch,
     attestation_delay_debounce: TimeLatch,
     lc_update_delay_debounce: TimeLatch,
+    unknown_roots_debounce: TimeLatch,
+}
+
+/// A block root referenced by queued messages which is not known to fork choice.
+struct UnknownRoot {
+    /// When the root was first referenced, used to measure the lookup latency.
+    first_seen: Instant,
+    /// Peers which referenced the root, and so should be able to serve the block.
+    peers: Vec<PeerId>,
+    /// How many of `peers` have been accepted by the sync manager.
+    peers_sent: usize,
+    /// Expires the root a fixed time after it was first referenced. Later references do not
+    /// extend this, so that repeatedly referenced roots can't occupy the tracker indefinitely. It
+    /// is only extended once, when the sync manager first accepts a lookup of the root.
+    delay_key: DelayKey,
 }
 
+/// The roots sent to the sync manager, with the number of their peers sent.
+type PendingLookups = Vec<(Hash256, usize)>;
+
 pub type QueuedLightClientUpdateId = usize;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -287,6 +349,40 @@ impl<T: BeaconChainTypes> Stream for ReprocessQueue<T> {
             Poll::Ready(None) | Poll::Pending => (),
         }
 
+        match self.unknown_roots_delay_queue.poll_expired(cx) {
+            Poll::Ready(Some(Ok(root))) => {
+                return Poll::Ready(Some(InboundEvent::ExpiredUnknownRoot(root.into_inner())));
+            }
+            Poll::Ready(Some(Err(e))) => {
+                return Poll::Ready(Some(InboundEvent::DelayQueueError(
+                    e,
+                    "unknown_roots_queue",
+                )));
+            }
+            // `Poll::Ready(None)` means that there are no more entries in the delay queue and we
+            // will continue to get this result until something else is added into the queue.
+            Poll::Ready(None) | Poll::Pending => (),
+        }
+
+        if let Some((_, accepted_rx)) = self.pending_lookups.as_mut() {
+            if let Poll::Ready(result) = Pin::new(accepted_rx).poll(cx) {
+                if let Some((lookups, _)) = self.pending_lookups.take() {
+                    // A dropped sender means the sync manager is gone, so nothing was accepted.
+                    let accepted = result.unwrap_or(false);
+                    return Poll::Ready(Some(InboundEvent::UnknownRootLookupResult(
+                        lookups, accepted,
+                    )));
+                }
+            }
+        }
+
+        if let Some(timer) = self.unknown_root_lookup_timer.as_mut() {
+            if timer.as_mut().poll(cx).is_ready() {
+                self.unknown_root_lookup_timer = None;
+                return Poll::Ready(Some(InboundEvent::UnknownRootLookupTick));
+            }
+        }
+
         // Last empty the messages channel.
         match self.work_reprocessing_rx.poll_recv(cx) {
             Poll::Ready(Some(message)) => return Poll::Ready(Some(InboundEvent::Msg(message))),
@@ -302,6 +398,7 @@ impl<T: BeaconChainTypes> Stream for ReprocessQueue<T> {
 /// via `ready_work_tx`.
 pub fn spawn_reprocess_scheduler<T: BeaconChainTypes>(
     ready_work_tx: Sender<ReadyWork<T>>,
+    sync_tx: UnboundedSender<SyncMessage<T::EthSpec>>,
     executor: &TaskExecutor,
     slot_clock: T::SlotClock,
     log: Logger,
@@ -310,29 +407,35 @@ pub fn spawn_reprocess_scheduler<T: BeaconChainTypes>(
     // Basic sanity check.
     assert!(ADDITIONAL_QUEUED_BLOCK_DELAY < MAXIMUM_GOSSIP_CLOCK_DISPARITY);
 
-    let mut queue = ReprocessQueue {
-        work_reprocessing_rx,
-        ready_work_tx,
-        gossip_block_delay_queue: DelayQueue::new(),
-        rpc_block_delay_queue: DelayQueue::new(),
-        attestations_delay_queue: DelayQueue::new(),
-        lc_updates_delay_queue: DelayQueue::new(),
-        queued_gossip_block_roots: HashSet::new(),
-        queued_lc_updates: FnvHashMap::default(),
-        queued_aggregates: FnvHashMap::default(),
-        queued_unaggregates: FnvHashMap::default(),
-        awaiting_attestations_per_root: HashMap::new(),
-        awaiting_lc_updates_per_parent_root: HashMap::new(),
-        next_attestation: 0,
-        next_lc_update: 0,
-        early_block_debounce: TimeLatch::default(),
-        rpc_block_debounce: TimeLatch::default(),
-        attestation_delay_debounce: TimeLatch::default(),
-        lc_update_delay_debounce: TimeLatch::default(),
-    };
-
     executor.spawn(
         async move {
+            let mut queue = ReprocessQueue {
+                work_reprocessing_rx,
+                ready_work_tx,
+                sync_tx,
+                gossip_block_delay_queue: DelayQueue::new(),
+                rpc_block_delay_queue: DelayQueue::new(),
+                attestations_delay_queue: DelayQueue::new(),
+                lc_updates_delay_queue: DelayQueue::new(),
+                unknown_roots_delay_queue: DelayQueue::new(),
+                queued_gossip_block_roots: HashSet::new(),
+                queued_lc_updates: FnvHashMap::default(),
+                queued_aggregates: FnvHashMap::default(),
+                queued_unaggregates: FnvHashMap::default(),
+                awaiting_attestations_per_root: HashMap::new(),
+                awaiting_lc_updates_per_parent_root: HashMap::new(),
+                unknown_roots: HashMap::new(),
+                unknown_root_lookup_timer: None,
+                pending_lookups: None,
+                next_attestation: 0,
+                next_lc_update: 0,
+                early_block_debounce: TimeLatch::default(),
+                rpc_block_debounce: TimeLatch::default(),
+                attestation_delay_debounce: TimeLatch::default(),
+                lc_update_delay_debounce: TimeLatch::default(),
+                unknown_roots_debounce: TimeLatch::default(),
+            };
+
             while let Some(msg) = queue.next().await {
                 queue.handle_message(msg, &slot_clock, &log);
             }
@@ -478,6 +581,9 @@ impl<T: BeaconChainTypes> ReprocessQueue<T> {
                     return;
                 }
 
+                let beacon_block_root = *queued_aggregate.beacon_block_root();
+                self.track_unknown_root(beacon_block_root, queued_aggregate.peer_id, log);
+
                 let att_id = QueuedAttestationId::Aggregate(self.next_attestation);
 
                 // Register the delay.
@@ -487,7 +593,7 @@ impl<T: BeaconChainTypes> ReprocessQueue<T> {
 
                 // Register this attestation for the corresponding root.
                 self.awaiting_attestations_per_root
-                    .entry(*queued_aggregate.beacon_block_root())
+                    .entry(beacon_block_root)
                     .or_default()
                     .push(att_id);
 
@@ -511,6 +617,9 @@ impl<T: BeaconChainTypes> ReprocessQueue<T> {
                     return;
                 }
 
+                let beacon_block_root = *queued_unaggregate.beacon_block_root();
+                self.track_unknown_root(beacon_block_root, queued_unaggregate.peer_id, log);
+
                 let att_id = QueuedAttestationId::Unaggregate(self.next_attestation);
 
                 // Register the delay.
@@ -520,7 +629,7 @@ impl<T: BeaconChainTypes> ReprocessQueue<T> {
 
                 // Register this attestation for the corresponding root.
                 self.awaiting_attestations_per_root
-                    .entry(*queued_unaggregate.beacon_block_root())
+                    .entry(beacon_block_root)
                     .or_default()
                     .push(att_id);
 
@@ -567,10 +676,29 @@ impl<T: BeaconChainTypes> ReprocessQueue<T> {
 
                 self.next_lc_update += 1;
             }
+            InboundEvent::Msg(UnknownBlockSyncMessage {
+                peer_id,
+                beacon_block_root,
+            }) => {
+                self.track_unknown_root(beacon_block_root, peer_id, log);
+            }
             InboundEvent::Msg(BlockImported {
                 block_root,
                 parent_root,
             }) => {
+                // Stop tracking the root, recording how long it took to be imported.
+                if let Some(unknown_root) = self.unknown_roots.remove(&block_root) {
+                    self.unknown_roots_delay_queue
+                        .remove(&unknown_root.delay_key);
+                    metrics::inc_counter(
+                        &metrics::BEACON_PROCESSOR_UNKNOWN_ROOT_LOOKUPS_RESOLVED_TOTAL,
+                    );
+                    metrics::observe_duration(
+                        &metrics::BEACON_PROCESSOR_UNKNOWN_ROOT_LOOKUP_SECONDS,
+                        unknown_root.first_seen.elapsed(),
+                    );
+                }
+
                 // Unqueue the attestations we have for this root, if any.
                 if let Some(queued_ids) = self.awaiting_attestations_per_root.remove(&block_root) {
                     for id in queued_ids {
@@ -735,9 +863,83 @@ impl<T: BeaconChainTypes> ReprocessQueue<T> {
                         if let Some(index) = queued_atts.iter().position(|&id| id == queued_id) {
                             queued_atts.swap_remove(index);
                         }
+                        if queued_atts.is_empty() {
+                            self.awaiting_attestations_per_root.remove(&root);
+                        }
                     }
                 }
             }
+            InboundEvent::ExpiredUnknownRoot(root) => {
+                if self.unknown_roots.remove(&root).is_some() {
+                    metrics::inc_counter(
+                        &metrics::BEACON_PROCESSOR_UNKNOWN_ROOT_LOOKUPS_EXPIRED_TOTAL,
+                    );
+                    debug!(
+                        log,
+                        "Unknown block root expired";
+                        "block_root" => ?root,
+                    );
+                }
+            }
+            InboundEvent::UnknownRootLookupTick => {
+                // Only one batch is sent at a time, the next is armed once it has been answered.
+                if self.pending_lookups.is_some() {
+                    return;
+                }
+
+                // Batch the peers which have referenced each root since the last tick, so that
+                // the sync manager sees every root once regardless of how many messages
+                // referenced it.
+                let mut lookups = vec![];
+                let mut pending = vec![];
+                for (root, unknown_root) in self.unknown_roots.iter() {
+                    if unknown_root.peers_sent == unknown_root.peers.len() {
+                        continue;
+                    }
+                    lookups.push((
+                        *root,
+                        unknown_root.peers[unknown_root.peers_sent..].to_vec(),
+                    ));
+                    pending.push((*root, unknown_root.peers.len()));
+                }
+
+                if lookups.is_empty() {
+                    return;
+                }
+
+                let (accepted_tx, accepted_rx) = oneshot::channel();
+                if self
+                    .sync_tx
+                    .send(SyncMessage::UnknownBlockHashes {
+                        lookups,
+                        accepted_tx,
+                    })
+                    .is_err()
+                {
+                    error!(
+                        log,
+                        "Failed to send unknown block lookups";
+                    );
+                    return;
+                }
+                self.pending_lookups = Some((pending, accepted_rx));
+            }
+            InboundEvent::UnknownRootLookupResult(lookups, accepted) => {
+                // Peers are only marked as sent once the sync manager has accepted them, ignored
+                // lookups are sent again on a later tick.
+                if accepted {
+                    for (root, peers_sent) in lookups {
+                        self.on_unknown_root_lookup_accepted(root, peers_sent);
+                    }
+                }
+                if self
+                    .unknown_roots
+                    .values()
+                    .any(|unknown_root| unknown_root.peers_sent < unknown_root.peers.len())
+                {
+                    self.arm_unknown_root_lookup_timer();
+                }
+            }
             InboundEvent::ReadyLightClientUpdate(queued_id) => {
                 metrics::inc_counter(
                     &metrics::BEACON_PROCESSOR_REPROCESSING_QUEUE_EXPIRED_OPTIMISTIC_UPDATES,
@@ -792,5 +994,113 @@ impl<T: BeaconChainTypes> ReprocessQueue<T> {
             &[LIGHT_CLIENT_UPDATES],
             self.lc_updates_delay_queue.len() as i64,
         );
+        metrics::set_gauge_vec(
+            &metrics::BEACON_PROCESSOR_REPROCESSING_QUEUE_TOTAL,
+            &[UNKNOWN_ROOTS],
+            self.unknown_roots.len() as i64,
+        );
+    }
+
+    /// Records that the sync manager has accepted the lookup of `root` from its first `peers_sent`
+    /// peers.
+    ///
+    /// The first time a lookup of `root` is accepted, the root and the attestations referencing
+    /// it are held for a further `QUEUED_ATTESTATION_DELAY`, so that they are not dropped while
+    /// the lookup is in flight.
+    fn on_unknown_root_lookup_accepted(&mut self, root: Hash256, peers_sent: usize) {
+        let unknown_root = match self.unknown_roots.get_mut(&root) {
+            Some(unknown_root) => unknown_root,
+            // The root has been imported or expired in the meantime.
+            None => return,
+        };
+
+        let first_lookup = unknown_root.peers_sent == 0;
+        unknown_root.peers_sent = peers_sent;
+        if !first_lookup {
+            return;
+        }
+
+        metrics::inc_counter(&metrics::BEACON_PROCESSOR_UNKNOWN_ROOT_LOOKUPS_REQUESTED_TOTAL);
+        self.unknown_roots_delay_queue
+            .reset(&unknown_root.delay_key, QUEUED_ATTESTATION_DELAY);
+
+        for id in self
+            .awaiting_attestations_per_root
+            .get(&root)
+            .into_iter()
+            .flatten()
+        {
+            let delay_key = match id {
+                QueuedAttestationId::Aggregate(id) => {
+                    self.queued_aggregates.get(id).map(|(_, key)| key)
+                }
+                QueuedAttestationId::Unaggregate(id) => {
+                    self.queued_unaggregates.get(id).map(|(_, key)| key)
+                }
+            };
+            if let Some(delay_key) = delay_key {
+                self.attestations_delay_queue
+                    .reset(delay_key, QUEUED_ATTESTATION_DELAY);
+            }
+        }
+    }
+
+    /// Arms the timer which sends lookups for unknown roots, unless it is already armed or a
+    /// batch of lookups is awaiting the sync manager.
+    fn arm_unknown_root_lookup_timer(&mut self) {
+        if self.unknown_root_lookup_timer.is_none() && self.pending_lookups.is_none() {
+            self.unknown_root_lookup_timer = Some(Box::pin(sleep(UNKNOWN_ROOT_LOOKUP_INTERVAL)));
+        }
+    }
+
+    /// Registers `peer_id` as referencing the unknown block `root`, so that the block is looked
+    /// up from them.
+    ///
+    /// If the tracker is full, the root which was first referenced the longest time ago is
+    /// evicted to make room for `root`.
+    fn track_unknown_root(&mut self, root: Hash256, peer_id: PeerId, log: &Logger) {
+        if let Some(unknown_root) = self.unknown_roots.get_mut(&root) {
+            if unknown_root.peers.len() < MAXIMUM_LOOKUP_PEERS_PER_ROOT
+                && !unknown_root.peers.contains(&peer_id)
+            {
+                unknown_root.peers.push(peer_id);
+                self.arm_unknown_root_lookup_timer();
+            }
+            return;
+        }
+
+        if self.unknown_roots.len() >= MAXIMUM_UNKNOWN_ROOTS {
+            let oldest_root = self
+                .unknown_roots
+                .iter()
+                .min_by_key(|(_, unknown_root)| unknown_root.first_seen)
+                .map(|(oldest_root, _)| *oldest_root);
+            if let Some(oldest) = oldest_root.and_then(|root| self.unknown_roots.remove(&root)) {
+                self.unknown_roots_delay_queue.remove(&oldest.delay_key);
+            }
+            if self.unknown_roots_debounce.elapsed() {
+                warn!(
+                    log,
+                    "Unknown block roots tracker is full";
+                    "roots" => MAXIMUM_UNKNOWN_ROOTS,
+                    "msg" => "evicting the oldest roots, check sync status"
+                );
+            }
+            metrics::inc_counter(&metrics::BEACON_PROCESSOR_UNKNOWN_ROOTS_EVICTED_TOTAL);
+        }
+
+        let delay_key = self
+            .unknown_roots_delay_queue
+            .insert(root, QUEUED_ATTESTATION_DELAY);
+        self.unknown_roots.insert(
+            root,
+            UnknownRoot {
+                first_seen: Instant::now(),
+                peers: vec![peer_id],
+                peers_sent: 0,
+                delay_key,
+            },
+        );
+        self.arm_unknown_root_lookup_timer();
     }
 }
@@ -231,6 +231,14 @@ impl<T: BeaconChainTypes> Worker<T> {
         })
     }
 
+    /// Records the reason the given message failed validation in the gossip validation log, if it
+    /// is enabled. This should be called before the result is propagated to the network.
+    fn note_validation_error(&self, message_id: &MessageId, error: &impl std::fmt::Debug) {
+        if let Some(log) = self.network_globals.gossip_validation_log.as_ref() {
+            log.write().note_error(message_id, format!("{:?}", error));
+        }
+    }
+
     /* Processing functions */
 
     /// Process the unaggregated attestation received from the gossip network and:
@@ -423,6 +431,9 @@ impl<T: BeaconChainTypes> Worker<T> {
                     )
                 }
 
+                self.chain
+                    .add_unaggregated_to_block_inclusion_pool(verified_attestation);
+
                 metrics::inc_counter(
                     &metrics::BEACON_PROCESSOR_UNAGGREGATED_ATTESTATION_IMPORTED_TOTAL,
                 );
@@ -795,6 +806,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "Gossip block beacon chain error";
                     "error" => ?e,
                 );
+                self.note_validation_error(&message_id, &e);
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
                 return None;
             }
@@ -811,12 +823,14 @@ impl<T: BeaconChainTypes> Worker<T> {
                     PeerAction::HighToleranceError,
                     "gossip_block_high",
                 );
+                self.note_validation_error(&message_id, &e);
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
                 return None;
             }
             Err(ref e @ BlockError::ExecutionPayloadError(ref epe)) if !epe.penalize_peer() => {
                 debug!(self.log, "Could not verify block for gossip. Ignoring the block";
                             "error" => %e);
+                self.note_validation_error(&message_id, &e);
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
                 return None;
             }
@@ -839,6 +853,7 @@ impl<T: BeaconChainTypes> Worker<T> {
             | Err(e @ BlockError::GenesisBlock) => {
                 warn!(self.log, "Could not verify block for gossip. Rejecting the block";
                             "error" => %e);
+                self.note_validation_error(&message_id, &e);
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
                 self.gossip_penalize_peer(
                     peer_id,
@@ -1048,6 +1063,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                 );
                 // These errors occur due to a fault in the beacon chain. It is not necessarily
                 // the fault on the peer.
+                self.note_validation_error(&message_id, &e);
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
                 // We still penalize a peer slightly to prevent overuse of invalids.
                 self.gossip_penalize_peer(
@@ -1110,6 +1126,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer" => %peer_id,
                     "error" => ?e
                 );
+                self.note_validation_error(&message_id, &e);
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
 
                 // Penalize peer slightly for invalids.
@@ -1166,6 +1183,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer" => %peer_id,
                     "error" => ?e
                 );
+                self.note_validation_error(&message_id, &e);
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
                 // Penalize peer slightly for invalids.
                 self.gossip_penalize_peer(
@@ -1224,6 +1242,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer" => %peer_id,
                     "error" => ?e
                 );
+                self.note_validation_error(&message_id, &e);
                 // We ignore pre-capella messages without penalizing peers.
                 if matches!(e, BeaconChainError::BlsToExecutionPriorToCapella) {
                     self.propagate_validation_result(
@@ -1282,6 +1301,7 @@ impl<T: BeaconChainTypes> Worker<T> {
         sync_signature: SyncCommitteeMessage,
         subnet_id: SyncSubnetId,
         seen_timestamp: Duration,
+        reprocess_tx: mpsc::Sender<ReprocessQueueMessage<T>>,
     ) {
         let message_slot = sync_signature.slot;
         let sync_signature = match self
@@ -1305,6 +1325,12 @@ impl<T: BeaconChainTypes> Worker<T> {
         // If the message is still timely, propagate it.
         self.propagate_sync_message_if_timely(message_slot, message_id, peer_id);
 
+        self.lookup_unknown_sync_message_root(
+            peer_id,
+            sync_signature.sync_message().beacon_block_root,
+            &reprocess_tx,
+        );
+
         // Register the sync signature with any monitored validators.
         self.chain
             .validator_monitor
@@ -1344,6 +1370,7 @@ impl<T: BeaconChainTypes> Worker<T> {
         peer_id: PeerId,
         sync_contribution: SignedContributionAndProof<T::EthSpec>,
         seen_timestamp: Duration,
+        reprocess_tx: mpsc::Sender<ReprocessQueueMessage<T>>,
     ) {
         let contribution_slot = sync_contribution.message.contribution.slot;
         let sync_contribution = match self
@@ -1368,6 +1395,16 @@ impl<T: BeaconChainTypes> Worker<T> {
         // If the message is still timely, propagate it.
         self.propagate_sync_message_if_timely(contribution_slot, message_id, peer_id);
 
+        self.lookup_unknown_sync_message_root(
+            peer_id,
+            sync_contribution
+                .aggregate()
+                .message
+                .contribution
+                .beacon_block_root,
+            &reprocess_tx,
+        );
+
         self.chain
             .validator_monitor
             .read()
@@ -1393,6 +1430,32 @@ impl<T: BeaconChainTypes> Worker<T> {
         metrics::inc_counter(&metrics::BEACON_PROCESSOR_SYNC_CONTRIBUTION_IMPORTED_TOTAL);
     }
 
+    /// Sync committee messages are not rejected for referencing an unknown block, but the block
+    /// is likely to be on the canonical chain so have the re-process queue look it up.
+    fn lookup_unknown_sync_message_root(
+        &self,
+        peer_id: PeerId,
+        beacon_block_root: Hash256,
+        reprocess_tx: &mpsc::Sender<ReprocessQueueMessage<T>>,
+    ) {
+        if self.chain.block_is_known_to_fork_choice(&beacon_block_root) {
+            return;
+        }
+
+        if reprocess_tx
+            .try_send(ReprocessQueueMessage::UnknownBlockSyncMessage {
+                peer_id,
+                beacon_block_root,
+            })
+            .is_err()
+        {
+            error!(
+                self.log,
+                "Failed to send sync message root for lookup";
+            )
+        }
+    }
+
     pub fn process_gossip_finality_update(
         self,
         message_id: MessageId,
@@ -1454,6 +1517,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                         "error" => ?e,
                     ),
                 }
+                self.note_validation_error(&message_id, &e);
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
             }
         };
@@ -1584,6 +1648,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                         )
                     }
                 }
+                self.note_validation_error(&message_id, &e);
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
             }
         };
@@ -1603,6 +1668,7 @@ impl<T: BeaconChainTypes> Worker<T> {
         let beacon_block_root = failed_att.beacon_block_root();
         let attestation_type = failed_att.kind();
         metrics::register_attestation_error(&error);
+        self.note_validation_error(&message_id, &error);
         match &error {
             AttnError::FutureSlot { .. } => {
                 /*
@@ -1809,17 +1875,9 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "block" => ?beacon_block_root
                 );
                 if let Some(sender) = reprocess_tx {
-                    // We don't know the block, get the sync manager to handle the block lookup, and
-                    // send the attestation to be scheduled for re-processing.
-                    self.sync_tx
-                        .send(SyncMessage::UnknownBlockHash(peer_id, *beacon_block_root))
-                        .unwrap_or_else(|_| {
-                            warn!(
-                                self.log,
-                                "Failed to send to sync service";
-                                "msg" => "UnknownBlockHash"
-                            )
-                        });
+                    // We don't know the block, send the attestation to be scheduled for
+                    // re-processing. The re-process queue will get the sync manager to handle the
+                    // block lookup.
                     let msg = match failed_att {
                         FailedAtt::Aggregate {
                             attestation,
@@ -2112,6 +2170,7 @@ impl<T: BeaconChainTypes> Worker<T> {
         seen_timestamp: Duration,
     ) {
         metrics::register_sync_committee_error(&error);
+        self.note_validation_error(&message_id, &error);
 
         match &error {
             SyncCommitteeError::FutureSlot { .. } => {
@@ -1,4 +1,4 @@
-use crate::beacon_processor::{worker::FUTURE_SLOT_TOLERANCE, SendOnDrop};
+use crate::beacon_processor::{worker::FUTURE_SLOT_TOLERANCE, FinalizedStateCache, SendOnDrop};
 use crate::service::NetworkMessage;
 use crate::status::ToStatusMessage;
 use crate::sync::SyncMessage;
@@ -9,6 +9,8 @@ use lighthouse_network::rpc::*;
 use lighthouse_network::{PeerId, PeerRequestId, ReportSource, Response, SyncInfo};
 use slog::{debug, error, warn};
 use slot_clock::SlotClock;
+use ssz::Encode;
+use ssz_types::VariableList;
 use std::sync::Arc;
 use task_executor::TaskExecutor;
 use types::{light_client_bootstrap::LightClientBootstrap, Epoch, EthSpec, Hash256, Slot};
@@ -277,6 +279,113 @@ impl<T: BeaconChainTypes> Worker<T> {
         )
     }
 
+    /// Handle a `FinalizedStateChunk` request from the peer.
+    ///
+    /// Only the post-states of finalized blocks at the start of an epoch are served, which are the
+    /// epoch-boundary states of finalized checkpoints. They are loaded from the hot or freezer
+    /// database if still available there. Recently served states are kept encoded in memory so
+    /// that subsequent chunks do not reload them.
+    pub fn handle_finalized_state_chunk_request(
+        self,
+        peer_id: PeerId,
+        request_id: PeerRequestId,
+        request: FinalizedStateChunkRequest,
+        state_cache: FinalizedStateCache,
+    ) {
+        let block_root = request.block_root;
+        let state_bytes = match state_cache.get(&block_root) {
+            Some(state_bytes) => Ok(state_bytes),
+            None => self
+                .load_finalized_state_bytes(block_root)
+                .map(|state_bytes| {
+                    let state_bytes = Arc::new(state_bytes);
+                    state_cache.insert(block_root, state_bytes.clone());
+                    state_bytes
+                }),
+        };
+        let state_bytes = match state_bytes {
+            Ok(state_bytes) => state_bytes,
+            Err(reason) => {
+                debug!(
+                    self.log,
+                    "Unable to serve finalized state";
+                    "peer" => %peer_id,
+                    "block_root" => ?block_root,
+                    "reason" => &reason,
+                );
+                self.send_error_response(
+                    peer_id,
+                    RPCResponseErrorCode::ResourceUnavailable,
+                    reason,
+                    request_id,
+                );
+                return;
+            }
+        };
+
+        let total_len = state_bytes.len();
+        let start = request.offset as usize;
+        if start >= total_len {
+            self.send_error_response(
+                peer_id,
+                RPCResponseErrorCode::InvalidRequest,
+                "Offset exceeds state length".into(),
+                request_id,
+            );
+            return;
+        }
+        let end = std::cmp::min(
+            start.saturating_add(MAX_FINALIZED_STATE_CHUNK_LEN as usize),
+            total_len,
+        );
+
+        self.send_response(
+            peer_id,
+            Response::FinalizedStateChunk(FinalizedStateChunk {
+                block_root,
+                offset: request.offset,
+                total_len: total_len as u64,
+                data: VariableList::from(state_bytes[start..end].to_vec()),
+            }),
+            request_id,
+        )
+    }
+
+    /// Loads and encodes the post-state of the block `block_root`, if it is a finalized block at
+    /// the start of an epoch.
+    fn load_finalized_state_bytes(&self, block_root: Hash256) -> Result<Vec<u8>, String> {
+        let block = self
+            .chain
+            .get_blinded_block(&block_root)
+            .map_err(|e| format!("Unable to load finalized block: {:?}", e))?
+            .ok_or("Finalized block not found")?;
+
+        if block.slot() % T::EthSpec::slots_per_epoch() != 0 {
+            return Err("Only epoch-boundary states are served".into());
+        }
+        let finalized_slot = self
+            .chain
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+        let canonical_root = self
+            .chain
+            .block_root_at_slot(block.slot(), WhenSlotSkipped::None)
+            .map_err(|e| format!("Unable to load finalized block root: {:?}", e))?;
+        if block.slot() > finalized_slot || canonical_root != Some(block_root) {
+            return Err("Block is not finalized".into());
+        }
+        let state = self
+            .chain
+            .get_state(&block.state_root(), Some(block.slot()))
+            .map_err(|e| format!("Unable to load finalized state: {:?}", e))?
+            .ok_or("Finalized state not found")?;
+
+        Ok(state.as_ssz_bytes())
+    }
+
     /// Handle a `BlocksByRange` request from the peer.
     pub fn handle_blocks_by_range_request(
         self,
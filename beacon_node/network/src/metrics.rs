@@ -383,6 +383,30 @@ lazy_static! {
         "Number of queued attestations where as matching block has been imported."
     );
 
+    /*
+     * Unknown block root tracking metrics.
+     */
+    pub static ref BEACON_PROCESSOR_UNKNOWN_ROOT_LOOKUPS_REQUESTED_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_processor_unknown_root_lookups_requested_total",
+        "Number of unknown block roots referenced by gossip messages that were sent to sync for lookup."
+    );
+    pub static ref BEACON_PROCESSOR_UNKNOWN_ROOT_LOOKUPS_RESOLVED_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_processor_unknown_root_lookups_resolved_total",
+        "Number of unknown block roots whose block was imported whilst being tracked."
+    );
+    pub static ref BEACON_PROCESSOR_UNKNOWN_ROOT_LOOKUPS_EXPIRED_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_processor_unknown_root_lookups_expired_total",
+        "Number of unknown block roots which expired before their block was imported."
+    );
+    pub static ref BEACON_PROCESSOR_UNKNOWN_ROOTS_EVICTED_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_processor_unknown_roots_evicted_total",
+        "Number of unknown block roots evicted to make room for newly referenced roots."
+    );
+    pub static ref BEACON_PROCESSOR_UNKNOWN_ROOT_LOOKUP_SECONDS: Result<Histogram> = try_create_histogram(
+        "beacon_processor_unknown_root_lookup_seconds",
+        "Time from an unknown block root first being referenced until its block is imported."
+    );
+
     /*
      * Light client update reprocessing queue metrics.
      */
@@ -0,0 +1,337 @@
+//! Replays a network recording against a beacon chain.
+//!
+//! Recordings are produced by running a node with `--record-network-traffic` (see
+//! `lighthouse_network::recorder`). Replaying a recording against a fresh `BeaconChain`, started
+//! from the same checkpoint as the recording node, reproduces the sequence of blocks, attestations
+//! and other operations that node processed, allowing fork choice and block processing issues to be
+//! investigated offline.
+//!
+//! Gossip messages are fed through the `Router` and `BeaconProcessor`, exactly as they would be
+//! when received from the network, and are decoded under the fork at the slot they were received
+//! in. Blocks received via RPC are imported directly, since there is no sync request for them to be
+//! associated with. Other RPC responses are counted but not replayed. Each message is processed to
+//! completion before the next one is replayed, so the order of processing matches the order of the
+//! recording.
+//!
+//! Messages which the recording node dropped because its writer fell behind are reported in the
+//! `ReplaySummary`, since the replayed chain may diverge from the recording node after them.
+//!
+//! The `lcli replay-network` command runs a replay against a chain started from a checkpoint.
+use crate::beacon_processor::NOTHING_TO_DO;
+use crate::router::{Router, RouterMessage};
+use beacon_chain::{BeaconChain, BeaconChainTypes, CountUnrealized, NotifyExecutionLayer};
+use lighthouse_network::discv5::enr::{CombinedKey, EnrBuilder};
+use lighthouse_network::recorder::{RecordedMessage, RecordedMessageKind};
+use lighthouse_network::rpc::methods::{MetaData, MetaDataV2};
+use lighthouse_network::rpc::Protocol;
+use lighthouse_network::{MessageId, NetworkGlobals, PubsubMessage, TopicHash};
+use slog::{debug, info, warn, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use types::{EthSpec, ForkContext, SignedBeaconBlock};
+
+/// The maximum time to wait for the beacon processor to finish the work generated by a single
+/// replayed message.
+const PROCESSING_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The size of the beacon processor work journal buffer.
+const WORK_JOURNAL_LEN: usize = 1_024;
+
+/// A summary of a completed replay.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReplaySummary {
+    /// The number of gossip messages passed to the router.
+    pub gossip_messages: usize,
+    /// The number of blocks received via RPC that were imported.
+    pub rpc_blocks_imported: usize,
+    /// The number of blocks received via RPC that failed to import.
+    pub rpc_blocks_failed: usize,
+    /// The number of RPC responses other than blocks, which are not replayed.
+    pub rpc_responses_ignored: usize,
+    /// The number of messages the recording node dropped from the recording.
+    pub dropped_messages: u64,
+    /// The number of messages that could not be decoded or were of an unknown kind.
+    pub skipped: usize,
+}
+
+/// Replays `recording` against `chain`.
+///
+/// Before each message is replayed, `set_time` is called with the time at which it was originally
+/// received (as a duration since the UNIX epoch). Callers using a `ManualSlotClock` should use this
+/// to move the chain's clock, so that messages are validated as they were when first received.
+pub async fn replay_recording<T, I>(
+    chain: Arc<BeaconChain<T>>,
+    recording: I,
+    mut set_time: impl FnMut(Duration),
+    executor: task_executor::TaskExecutor,
+    log: Logger,
+) -> Result<ReplaySummary, String>
+where
+    T: BeaconChainTypes,
+    I: IntoIterator<Item = Result<RecordedMessage, String>>,
+{
+    let network_globals = Arc::new(replay_network_globals(&log)?);
+
+    // Messages from the router back to the network (e.g. gossip validation results) have nowhere
+    // to go during a replay.
+    let (network_send, mut network_recv) = mpsc::unbounded_channel();
+    executor.spawn(
+        async move { while network_recv.recv().await.is_some() {} },
+        "replay_network_sink",
+    );
+
+    let (work_journal_tx, mut work_journal_rx) = mpsc::channel(WORK_JOURNAL_LEN);
+    let router_send = Router::spawn(
+        chain.clone(),
+        network_globals,
+        network_send,
+        executor,
+        Some(work_journal_tx),
+        false,
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to start router: {:?}", e))?;
+
+    let fork_context = ForkContext::new::<T::EthSpec>(
+        chain.spec.genesis_slot,
+        chain.genesis_validators_root,
+        &chain.spec,
+    );
+
+    let mut summary = ReplaySummary::default();
+
+    for (index, message) in recording.into_iter().enumerate() {
+        let message = message?;
+
+        if let Some(dropped) = message.dropped_messages() {
+            warn!(
+                log,
+                "Recording is missing messages";
+                "index" => index,
+                "dropped" => dropped,
+                "msg" => "the recording node dropped messages, the replay may diverge from it",
+            );
+            summary.dropped_messages += dropped;
+            continue;
+        }
+
+        set_time(message.timestamp());
+
+        // The recording may span a fork boundary, so decode each message under the fork at the
+        // slot in which it was received.
+        let slot = chain
+            .slot_clock
+            .slot_of(message.timestamp())
+            .unwrap_or(chain.spec.genesis_slot);
+        fork_context.update_current_fork(chain.spec.fork_name_at_slot::<T::EthSpec>(slot));
+
+        let peer_id = match message.peer_id() {
+            Some(peer_id) => peer_id,
+            None => {
+                warn!(log, "Skipping recorded message with invalid peer id"; "index" => index);
+                summary.skipped += 1;
+                continue;
+            }
+        };
+        let topic = message.topic().unwrap_or_default();
+
+        match message.kind() {
+            Some(RecordedMessageKind::Gossip) => {
+                let topic_hash = TopicHash::from_raw(topic);
+                let gossip = match PubsubMessage::decode(&topic_hash, &message.data, &fork_context)
+                {
+                    Ok(gossip) => gossip,
+                    Err(e) => {
+                        debug!(log, "Skipping undecodable gossip message"; "index" => index, "topic" => topic, "error" => e);
+                        summary.skipped += 1;
+                        continue;
+                    }
+                };
+
+                // Clear any stale journal entries so we wait for the work of this message only.
+                while work_journal_rx.try_recv().is_ok() {}
+
+                let message_id = MessageId::new(&(index as u64).to_le_bytes());
+                router_send
+                    .send(RouterMessage::PubsubMessage(
+                        message_id, peer_id, gossip, true,
+                    ))
+                    .map_err(|_| "Router stopped during replay".to_string())?;
+                summary.gossip_messages += 1;
+
+                wait_for_idle(&mut work_journal_rx, &log).await;
+            }
+            Some(RecordedMessageKind::RpcResponse)
+                if topic == Protocol::BlocksByRange.as_ref()
+                    || topic == Protocol::BlocksByRoot.as_ref() =>
+            {
+                let block = match SignedBeaconBlock::from_ssz_bytes(&message.data, &chain.spec) {
+                    Ok(block) => Arc::new(block),
+                    Err(e) => {
+                        debug!(log, "Skipping undecodable RPC block"; "index" => index, "error" => ?e);
+                        summary.skipped += 1;
+                        continue;
+                    }
+                };
+                let block_root = block.canonical_root();
+                match chain
+                    .process_block(
+                        block_root,
+                        block,
+                        CountUnrealized::True,
+                        NotifyExecutionLayer::Yes,
+                    )
+                    .await
+                {
+                    Ok(_) => summary.rpc_blocks_imported += 1,
+                    Err(e) => {
+                        debug!(log, "Replayed RPC block failed to import"; "block_root" => ?block_root, "error" => ?e);
+                        summary.rpc_blocks_failed += 1;
+                    }
+                }
+                chain.recompute_head_at_current_slot().await;
+            }
+            Some(RecordedMessageKind::RpcResponse) => {
+                summary.rpc_responses_ignored += 1;
+            }
+            Some(RecordedMessageKind::Gap) | None => {
+                summary.skipped += 1;
+            }
+        }
+    }
+
+    info!(
+        log,
+        "Network recording replayed";
+        "gossip_messages" => summary.gossip_messages,
+        "rpc_blocks_imported" => summary.rpc_blocks_imported,
+        "rpc_blocks_failed" => summary.rpc_blocks_failed,
+        "rpc_responses_ignored" => summary.rpc_responses_ignored,
+        "dropped_messages" => summary.dropped_messages,
+        "skipped" => summary.skipped,
+    );
+
+    Ok(summary)
+}
+
+/// Builds the `NetworkGlobals` for a replay, under a freshly generated identity that is never
+/// advertised on the network.
+fn replay_network_globals<E: EthSpec>(log: &Logger) -> Result<NetworkGlobals<E>, String> {
+    let enr_key = CombinedKey::generate_secp256k1();
+    let enr = EnrBuilder::new("v4")
+        .build(&enr_key)
+        .map_err(|e| format!("Unable to build replay ENR: {:?}", e))?;
+    let metadata = MetaData::V2(MetaDataV2 {
+        seq_number: 0,
+        attnets: Default::default(),
+        syncnets: Default::default(),
+    });
+    Ok(NetworkGlobals::new(enr, None, None, metadata, vec![], log))
+}
+
+/// Waits until the beacon processor reports that it has no more work to do.
+async fn wait_for_idle(work_journal_rx: &mut mpsc::Receiver<&'static str>, log: &Logger) {
+    let wait = async {
+        while let Some(event) = work_journal_rx.recv().await {
+            if event == NOTHING_TO_DO {
+                break;
+            }
+        }
+    };
+    if tokio::time::timeout(PROCESSING_TIMEOUT, wait)
+        .await
+        .is_err()
+    {
+        warn!(
+            log,
+            "Timed out waiting for replayed message to be processed"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+    use lighthouse_network::PeerId;
+    use ssz::Encode;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    const VALIDATOR_COUNT: usize = 32;
+
+    fn get_harness() -> BeaconChainHarness<EphemeralHarnessType<E>> {
+        let harness = BeaconChainHarness::builder(E)
+            .default_spec()
+            .deterministic_keypairs(VALIDATOR_COUNT)
+            .fresh_ephemeral_store()
+            .build();
+        harness.advance_slot();
+        harness
+    }
+
+    #[tokio::test]
+    async fn replay_gossip_block() {
+        let recording_harness = get_harness();
+        let replay_harness = get_harness();
+
+        let head = recording_harness.chain.head_snapshot();
+        let slot = recording_harness.chain.slot().unwrap();
+        let (block, _) = recording_harness
+            .make_block(head.beacon_state.clone(), slot)
+            .await;
+
+        let fork_digest = recording_harness.chain.enr_fork_id().fork_digest;
+        let topic = format!("/eth2/{}/beacon_block/ssz_snappy", hex::encode(fork_digest));
+        let recording = vec![
+            Ok(RecordedMessage::new(
+                RecordedMessageKind::Gossip,
+                &PeerId::random(),
+                &topic,
+                block.as_ssz_bytes(),
+            )),
+            Ok(RecordedMessage::new(
+                RecordedMessageKind::Gossip,
+                &PeerId::random(),
+                &topic,
+                vec![0xff; 4],
+            )),
+            Ok(RecordedMessage::gap(3)),
+            Ok(RecordedMessage::new(
+                RecordedMessageKind::RpcResponse,
+                &PeerId::random(),
+                Protocol::Ping.as_ref(),
+                0u64.as_ssz_bytes(),
+            )),
+        ];
+
+        let summary = replay_recording(
+            replay_harness.chain.clone(),
+            recording,
+            |_| {},
+            replay_harness.runtime.task_executor.clone(),
+            replay_harness.logger().clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            summary,
+            ReplaySummary {
+                gossip_messages: 1,
+                rpc_blocks_imported: 0,
+                rpc_blocks_failed: 0,
+                rpc_responses_ignored: 1,
+                dropped_messages: 3,
+                skipped: 1,
+            }
+        );
+        assert_eq!(
+            replay_harness.chain.head_snapshot().beacon_block_root,
+            block.canonical_root()
+        );
+    }
+}
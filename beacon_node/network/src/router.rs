@@ -76,11 +76,16 @@ pub enum RouterMessage<T: EthSpec> {
 
 impl<T: BeaconChainTypes> Router<T> {
     /// Initializes and runs the Router.
+    ///
+    /// The optional `beacon_processor_journal` receives a log of all work events processed by the
+    /// `BeaconProcessor`. It is only used when replaying network recordings.
     pub fn spawn(
         beacon_chain: Arc<BeaconChain<T>>,
         network_globals: Arc<NetworkGlobals<T::EthSpec>>,
         network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
         executor: task_executor::TaskExecutor,
+        beacon_processor_journal: Option<mpsc::Sender<&'static str>>,
+        range_sync_cross_check: bool,
         log: slog::Logger,
     ) -> error::Result<mpsc::UnboundedSender<RouterMessage<T::EthSpec>>> {
         let message_handler_log = log.new(o!("service"=> "router"));
@@ -100,6 +105,7 @@ impl<T: BeaconChainTypes> Router<T> {
             network_globals.clone(),
             network_send.clone(),
             beacon_processor_send.clone(),
+            range_sync_cross_check,
             sync_logger,
         );
 
@@ -112,9 +118,10 @@ impl<T: BeaconChainTypes> Router<T> {
             max_workers: cmp::max(1, num_cpus::get()),
             current_workers: 0,
             importing_blocks: Default::default(),
+            finalized_state_cache: Default::default(),
             log: log.clone(),
         }
-        .spawn_manager(beacon_processor_receive, None);
+        .spawn_manager(beacon_processor_receive, beacon_processor_journal);
 
         // generate the Message handler
         let mut handler = Router {
@@ -199,6 +206,9 @@ impl<T: BeaconChainTypes> Router<T> {
             Request::LightClientBootstrap(request) => self.send_beacon_processor_work(
                 BeaconWorkEvent::lightclient_bootstrap_request(peer_id, request_id, request),
             ),
+            Request::FinalizedStateChunk(request) => self.send_beacon_processor_work(
+                BeaconWorkEvent::finalized_state_chunk_request(peer_id, request_id, request),
+            ),
         }
     }
 
@@ -223,7 +233,7 @@ impl<T: BeaconChainTypes> Router<T> {
             Response::BlocksByRoot(beacon_block) => {
                 self.on_blocks_by_root_response(peer_id, request_id, beacon_block);
             }
-            Response::LightClientBootstrap(_) => unreachable!(),
+            Response::LightClientBootstrap(_) | Response::FinalizedStateChunk(_) => unreachable!(),
         }
     }
 
@@ -424,7 +434,9 @@ impl<T: BeaconChainTypes> Router<T> {
                 SyncId::SingleBlock { .. } | SyncId::ParentLookup { .. } => {
                     unreachable!("Block lookups do not request BBRange requests")
                 }
-                id @ (SyncId::BackFillSync { .. } | SyncId::RangeSync { .. }) => id,
+                id @ (SyncId::BackFillSync { .. }
+                | SyncId::RangeSync { .. }
+                | SyncId::RangeCrossCheck { .. }) => id,
             },
             RequestId::Router => unreachable!("All BBRange requests belong to sync"),
         };
@@ -453,7 +465,9 @@ impl<T: BeaconChainTypes> Router<T> {
         let request_id = match request_id {
             RequestId::Sync(sync_id) => match sync_id {
                 id @ (SyncId::SingleBlock { .. } | SyncId::ParentLookup { .. }) => id,
-                SyncId::BackFillSync { .. } | SyncId::RangeSync { .. } => {
+                SyncId::BackFillSync { .. }
+                | SyncId::RangeSync { .. }
+                | SyncId::RangeCrossCheck { .. } => {
                     unreachable!("Batch syncing do not request BBRoot requests")
                 }
             },
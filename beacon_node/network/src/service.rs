@@ -7,7 +7,7 @@ use crate::{
     subnet_service::{AttestationService, SubnetServiceMessage},
     NetworkConfig,
 };
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use beacon_chain::{events::EventKind, BeaconChain, BeaconChainTypes};
 use futures::channel::mpsc::Sender;
 use futures::future::OptionFuture;
 use futures::prelude::*;
@@ -302,13 +302,14 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             network_globals.clone(),
             network_senders.network_send(),
             executor.clone(),
+            None,
+            config.range_sync_cross_check,
             network_log.clone(),
         )?;
 
         // attestation subnet service
         let attestation_service = AttestationService::new(
             beacon_chain.clone(),
-            #[cfg(feature = "deterministic_long_lived_attnets")]
             network_globals.local_enr().node_id().raw().into(),
             config,
             &network_log,
@@ -420,7 +421,10 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                         metrics::update_sync_metrics(&self.network_globals);
                     }
 
-                    _ = self.gossipsub_parameter_update.tick() => self.update_gossipsub_parameters(),
+                    _ = self.gossipsub_parameter_update.tick() => {
+                        self.update_gossipsub_parameters();
+                        self.update_discovery_epoch();
+                    }
 
                     // handle a message sent to the network
                     Some(msg) = self.network_recv.recv() => self.on_network_msg(msg, &mut shutdown_sender).await,
@@ -638,11 +642,18 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                     "message_id" => %message_id,
                     "validation_result" => ?validation_result
                 );
-                self.libp2p.report_message_validation_result(
+                let entry = self.libp2p.report_message_validation_result(
                     &propagation_source,
                     message_id,
                     validation_result,
                 );
+                if let (Some(entry), Some(event_handler)) =
+                    (entry, self.beacon_chain.event_handler.as_ref())
+                {
+                    if event_handler.has_gossip_validation_subscribers() {
+                        event_handler.register(EventKind::GossipValidation(entry));
+                    }
+                }
             }
             NetworkMessage::Publish { messages } => {
                 let mut topic_kinds = Vec::new();
@@ -822,6 +833,13 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         }
     }
 
+    /// Keeps the epoch used by discovery to compute the long-lived subnets of peers up to date.
+    fn update_discovery_epoch(&mut self) {
+        if let Ok(epoch) = self.beacon_chain.epoch() {
+            self.libp2p.discovery_mut().update_current_epoch(epoch);
+        }
+    }
+
     fn on_attestation_service_msg(&mut self, msg: SubnetServiceMessage) {
         match msg {
             SubnetServiceMessage::Subscribe(subnet) => {
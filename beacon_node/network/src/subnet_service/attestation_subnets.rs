@@ -3,9 +3,7 @@
 //! determines whether attestations should be aggregated and/or passed to the beacon node.
 
 use super::SubnetServiceMessage;
-#[cfg(any(test, feature = "deterministic_long_lived_attnets"))]
-use std::collections::HashSet;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -15,8 +13,6 @@ use beacon_chain::{BeaconChain, BeaconChainTypes};
 use delay_map::{HashMapDelay, HashSetDelay};
 use futures::prelude::*;
 use lighthouse_network::{NetworkConfig, Subnet, SubnetDiscovery};
-#[cfg(not(feature = "deterministic_long_lived_attnets"))]
-use rand::seq::SliceRandom;
 use slog::{debug, error, o, trace, warn};
 use slot_clock::SlotClock;
 use types::{Attestation, EthSpec, Slot, SubnetId, ValidatorSubscription};
@@ -27,10 +23,6 @@ use crate::metrics;
 /// slot is less than this number, skip the peer discovery process.
 /// Subnet discovery query takes at most 30 secs, 2 slots take 24s.
 pub(crate) const MIN_PEER_DISCOVERY_SLOT_LOOK_AHEAD: u64 = 2;
-/// The time (in slots) before a last seen validator is considered absent and we unsubscribe from
-/// the random gossip topics that we subscribed to due to the validator connection.
-#[cfg(not(feature = "deterministic_long_lived_attnets"))]
-const LAST_SEEN_VALIDATOR_TIMEOUT_SLOTS: u32 = 150;
 /// The fraction of a slot that we subscribe to a subnet before the required slot.
 ///
 /// Currently a whole slot ahead.
@@ -71,11 +63,9 @@ pub struct AttestationService<T: BeaconChainTypes> {
 
     /// Subnets we are currently subscribed to as long lived subscriptions.
     ///
-    /// We advertise these in our ENR. When these expire, the subnet is removed from our ENR.
-    #[cfg(feature = "deterministic_long_lived_attnets")]
+    /// We advertise these in our ENR. These are a deterministic function of our node ID and the
+    /// current epoch, recomputed every `EPOCHS_PER_SUBNET_SUBSCRIPTION` epochs.
     long_lived_subscriptions: HashSet<SubnetId>,
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    long_lived_subscriptions: HashMapDelay<SubnetId, Slot>,
 
     /// Short lived subscriptions that need to be done in the future.
     scheduled_short_lived_subscriptions: HashSetDelay<ExactSubnet>,
@@ -84,13 +74,6 @@ pub struct AttestationService<T: BeaconChainTypes> {
     /// `ExactSubnet`.
     aggregate_validators_on_subnet: Option<HashSetDelay<ExactSubnet>>,
 
-    /// A collection of seen validators. These dictate how many random subnets we should be
-    /// subscribed to. As these time out, we unsubscribe for the required random subnets and update
-    /// our ENR.
-    /// This is a set of validator indices.
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    known_validators: HashSetDelay<u64>,
-
     /// The waker for the current thread.
     waker: Option<std::task::Waker>,
 
@@ -100,16 +83,10 @@ pub struct AttestationService<T: BeaconChainTypes> {
     /// We are always subscribed to all subnets.
     subscribe_all_subnets: bool,
 
-    /// For how many slots we subscribe to long lived subnets.
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    long_lived_subnet_subscription_slots: u64,
-
     /// Our Discv5 node_id.
-    #[cfg(feature = "deterministic_long_lived_attnets")]
     node_id: ethereum_types::U256,
 
     /// Future used to manage subscribing and unsubscribing from long lived subnets.
-    #[cfg(feature = "deterministic_long_lived_attnets")]
     next_long_lived_subscription_event: Pin<Box<tokio::time::Sleep>>,
 
     /// The logger for the attestation service.
@@ -119,49 +96,6 @@ pub struct AttestationService<T: BeaconChainTypes> {
 impl<T: BeaconChainTypes> AttestationService<T> {
     /* Public functions */
 
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    pub fn new(
-        beacon_chain: Arc<BeaconChain<T>>,
-        config: &NetworkConfig,
-        log: &slog::Logger,
-    ) -> Self {
-        let log = log.new(o!("service" => "attestation_service"));
-
-        // Calculate the random subnet duration from the spec constants.
-        let spec = &beacon_chain.spec;
-        let slot_duration = beacon_chain.slot_clock.slot_duration();
-        let long_lived_subnet_subscription_slots = spec
-            .epochs_per_random_subnet_subscription
-            .saturating_mul(T::EthSpec::slots_per_epoch());
-        let long_lived_subscription_duration = Duration::from_millis(
-            slot_duration.as_millis() as u64 * long_lived_subnet_subscription_slots,
-        );
-
-        // Panics on overflow. Ensure LAST_SEEN_VALIDATOR_TIMEOUT_SLOTS is not too large.
-        let last_seen_val_timeout = slot_duration
-            .checked_mul(LAST_SEEN_VALIDATOR_TIMEOUT_SLOTS)
-            .expect("LAST_SEEN_VALIDATOR_TIMEOUT must not be ridiculously large");
-
-        let track_validators = !config.import_all_attestations;
-        let aggregate_validators_on_subnet =
-            track_validators.then(|| HashSetDelay::new(slot_duration));
-        AttestationService {
-            events: VecDeque::with_capacity(10),
-            beacon_chain,
-            short_lived_subscriptions: HashMapDelay::new(slot_duration),
-            long_lived_subscriptions: HashMapDelay::new(long_lived_subscription_duration),
-            scheduled_short_lived_subscriptions: HashSetDelay::default(),
-            aggregate_validators_on_subnet,
-            known_validators: HashSetDelay::new(last_seen_val_timeout),
-            waker: None,
-            discovery_disabled: config.disable_discovery,
-            subscribe_all_subnets: config.subscribe_all_subnets,
-            long_lived_subnet_subscription_slots,
-            log,
-        }
-    }
-
-    #[cfg(feature = "deterministic_long_lived_attnets")]
     pub fn new(
         beacon_chain: Arc<BeaconChain<T>>,
         node_id: ethereum_types::U256,
@@ -170,11 +104,8 @@ impl<T: BeaconChainTypes> AttestationService<T> {
     ) -> Self {
         let log = log.new(o!("service" => "attestation_service"));
 
-        // Calculate the random subnet duration from the spec constants.
         let slot_duration = beacon_chain.slot_clock.slot_duration();
 
-        slog::info!(log, "Deterministic long lived subnets enabled"; "subnets_per_node" => beacon_chain.spec.subnets_per_node);
-
         let track_validators = !config.import_all_attestations;
         let aggregate_validators_on_subnet =
             track_validators.then(|| HashSetDelay::new(slot_duration));
@@ -206,21 +137,11 @@ impl<T: BeaconChainTypes> AttestationService<T> {
         if self.subscribe_all_subnets {
             self.beacon_chain.spec.attestation_subnet_count as usize
         } else {
-            #[cfg(feature = "deterministic_long_lived_attnets")]
-            let count = self
-                .short_lived_subscriptions
+            self.short_lived_subscriptions
                 .keys()
                 .chain(self.long_lived_subscriptions.iter())
                 .collect::<HashSet<_>>()
-                .len();
-            #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-            let count = self
-                .short_lived_subscriptions
-                .keys()
-                .chain(self.long_lived_subscriptions.keys())
-                .collect::<HashSet<_>>()
-                .len();
-            count
+                .len()
         }
     }
 
@@ -232,10 +153,7 @@ impl<T: BeaconChainTypes> AttestationService<T> {
         subscription_kind: SubscriptionKind,
     ) -> bool {
         match subscription_kind {
-            #[cfg(feature = "deterministic_long_lived_attnets")]
             SubscriptionKind::LongLived => self.long_lived_subscriptions.contains(subnet_id),
-            #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-            SubscriptionKind::LongLived => self.long_lived_subscriptions.contains_key(subnet_id),
             SubscriptionKind::ShortLived => self.short_lived_subscriptions.contains_key(subnet_id),
         }
     }
@@ -243,9 +161,6 @@ impl<T: BeaconChainTypes> AttestationService<T> {
     /// Processes a list of validator subscriptions.
     ///
     /// This will:
-    /// - Register new validators as being known.
-    /// - Subscribe to the required number of random subnets.
-    /// - Update the local ENR for new random subnets due to seeing new validators.
     /// - Search for peers for required subnets.
     /// - Request subscriptions for subnets on specific slots when required.
     /// - Build the timeouts for each of these events.
@@ -261,14 +176,10 @@ impl<T: BeaconChainTypes> AttestationService<T> {
         for subscription in subscriptions {
             metrics::inc_counter(&metrics::SUBNET_SUBSCRIPTION_REQUESTS);
 
-            // Registers the validator with the attestation service.
-            // This will subscribe to long-lived random subnets if required.
             trace!(self.log,
                 "Validator subscription";
                 "subscription" => ?subscription,
             );
-            #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-            self.add_known_validator(subscription.validator_index);
 
             let subnet_id = match SubnetId::compute_subnet::<T::EthSpec>(
                 subscription.slot,
@@ -338,7 +249,6 @@ impl<T: BeaconChainTypes> AttestationService<T> {
         Ok(())
     }
 
-    #[cfg(feature = "deterministic_long_lived_attnets")]
     fn recompute_long_lived_subnets(&mut self) {
         // Ensure the next computation is scheduled even if assigning subnets fails.
         let next_subscription_event = self
@@ -356,7 +266,6 @@ impl<T: BeaconChainTypes> AttestationService<T> {
 
     /// Gets the long lived subnets the node should be subscribed to during the current epoch and
     /// the remaining duration for which they remain valid.
-    #[cfg(feature = "deterministic_long_lived_attnets")]
     fn recompute_long_lived_subnets_inner(&mut self) -> Result<Duration, ()> {
         let current_epoch = self.beacon_chain.epoch().map_err(
             |e| error!(self.log, "Failed to get the current epoch from clock"; "err" => ?e),
@@ -387,7 +296,7 @@ impl<T: BeaconChainTypes> AttestationService<T> {
         Ok(next_subscription_event)
     }
 
-    #[cfg(all(test, feature = "deterministic_long_lived_attnets"))]
+    #[cfg(test)]
     pub fn update_long_lived_subnets_testing(&mut self, subnets: HashSet<SubnetId>) {
         self.update_long_lived_subnets(subnets)
     }
@@ -396,7 +305,6 @@ impl<T: BeaconChainTypes> AttestationService<T> {
     ///
     /// New subnets are registered as subscribed, removed subnets as unsubscribed and the Enr
     /// updated accordingly.
-    #[cfg(feature = "deterministic_long_lived_attnets")]
     fn update_long_lived_subnets(&mut self, mut subnets: HashSet<SubnetId>) {
         for subnet in &subnets {
             // Add the events for those subnets that are new as long lived subscriptions.
@@ -438,7 +346,7 @@ impl<T: BeaconChainTypes> AttestationService<T> {
     }
 
     /// Overwrites the long lived subscriptions for testing.
-    #[cfg(all(test, feature = "deterministic_long_lived_attnets"))]
+    #[cfg(test)]
     pub fn set_long_lived_subscriptions(&mut self, subnets: HashSet<SubnetId>) {
         self.long_lived_subscriptions = subnets
     }
@@ -551,12 +459,7 @@ impl<T: BeaconChainTypes> AttestationService<T> {
         // immediately.
         if time_to_subscription_start.is_zero() {
             // This is a current or past slot, we subscribe immediately.
-            self.subscribe_to_subnet_immediately(
-                subnet_id,
-                #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-                SubscriptionKind::ShortLived,
-                slot + 1,
-            )?;
+            self.subscribe_to_subnet_immediately(subnet_id, slot + 1)?;
         } else {
             // This is a future slot, schedule subscribing.
             trace!(self.log, "Scheduling subnet subscription"; "subnet" => ?subnet_id, "time_to_subscription_start" => ?time_to_subscription_start);
@@ -567,79 +470,6 @@ impl<T: BeaconChainTypes> AttestationService<T> {
         Ok(())
     }
 
-    /// Updates the `known_validators` mapping and subscribes to long lived subnets if required.
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    fn add_known_validator(&mut self, validator_index: u64) {
-        let previously_known = self.known_validators.contains_key(&validator_index);
-        // Add the new validator or update the current timeout for a known validator.
-        self.known_validators.insert(validator_index);
-        if !previously_known {
-            // New validator has subscribed.
-            // Subscribe to random topics and update the ENR if needed.
-            self.subscribe_to_random_subnets();
-        }
-    }
-
-    /// Subscribe to long-lived random subnets and update the local ENR bitfield.
-    /// The number of subnets to subscribe depends on the number of active validators and number of
-    /// current subscriptions.
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    fn subscribe_to_random_subnets(&mut self) {
-        if self.subscribe_all_subnets {
-            // This case is not handled by this service.
-            return;
-        }
-
-        let max_subnets = self.beacon_chain.spec.attestation_subnet_count;
-        // Calculate how many subnets we need,
-        let required_long_lived_subnets = {
-            let subnets_for_validators = self
-                .known_validators
-                .len()
-                .saturating_mul(self.beacon_chain.spec.random_subnets_per_validator as usize);
-            subnets_for_validators // How many subnets we need
-                .min(max_subnets as usize) // Capped by the max
-                .saturating_sub(self.long_lived_subscriptions.len()) // Minus those we have
-        };
-
-        if required_long_lived_subnets == 0 {
-            // Nothing to do.
-            return;
-        }
-
-        // Build a list of the subnets that we are not currently advertising.
-        let available_subnets = (0..max_subnets)
-            .map(SubnetId::new)
-            .filter(|subnet_id| !self.long_lived_subscriptions.contains_key(subnet_id))
-            .collect::<Vec<_>>();
-
-        let subnets_to_subscribe: Vec<_> = available_subnets
-            .choose_multiple(&mut rand::thread_rng(), required_long_lived_subnets)
-            .cloned()
-            .collect();
-
-        // Calculate in which slot does this subscription end.
-        let end_slot = match self.beacon_chain.slot_clock.now() {
-            Some(slot) => slot + self.long_lived_subnet_subscription_slots,
-            None => {
-                return debug!(
-                    self.log,
-                    "Failed to calculate end slot of long lived subnet subscriptions."
-                )
-            }
-        };
-
-        for subnet_id in &subnets_to_subscribe {
-            if let Err(e) = self.subscribe_to_subnet_immediately(
-                *subnet_id,
-                SubscriptionKind::LongLived,
-                end_slot,
-            ) {
-                debug!(self.log, "Failed to subscribe to long lived subnet"; "subnet" => ?subnet_id, "err" => e);
-            }
-        }
-    }
-
     /* A collection of functions that handle the various timeouts */
 
     /// Registers a subnet as subscribed.
@@ -648,12 +478,10 @@ impl<T: BeaconChainTypes> AttestationService<T> {
     /// already subscribed, extends the timeout if necessary. If this is a new subscription, we send
     /// out the appropriate events.
     ///
-    /// On determinist long lived subnets, this is only used for short lived subscriptions.
+    /// Long lived subnets are deterministic, so this is only used for short lived subscriptions.
     fn subscribe_to_subnet_immediately(
         &mut self,
         subnet_id: SubnetId,
-        #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-        subscription_kind: SubscriptionKind,
         end_slot: Slot,
     ) -> Result<(), &'static str> {
         if self.subscribe_all_subnets {
@@ -672,25 +500,11 @@ impl<T: BeaconChainTypes> AttestationService<T> {
             return Err("Time when subscription would end has already passed.");
         }
 
-        #[cfg(feature = "deterministic_long_lived_attnets")]
         let subscription_kind = SubscriptionKind::ShortLived;
 
         // We need to check and add a subscription for the right kind, regardless of the presence
         // of the subnet as a subscription of the other kind. This is mainly since long lived
-        // subscriptions can be removed at any time when a validator goes offline.
-        #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-        let (subscriptions, already_subscribed_as_other_kind) = match subscription_kind {
-            SubscriptionKind::ShortLived => (
-                &mut self.short_lived_subscriptions,
-                self.long_lived_subscriptions.contains_key(&subnet_id),
-            ),
-            SubscriptionKind::LongLived => (
-                &mut self.long_lived_subscriptions,
-                self.short_lived_subscriptions.contains_key(&subnet_id),
-            ),
-        };
-
-        #[cfg(feature = "deterministic_long_lived_attnets")]
+        // subscriptions can be removed at any time when the subscription period ends.
         let (subscriptions, already_subscribed_as_other_kind) = (
             &mut self.short_lived_subscriptions,
             self.long_lived_subscriptions.contains(&subnet_id),
@@ -725,57 +539,19 @@ impl<T: BeaconChainTypes> AttestationService<T> {
                         subnet_id,
                     )));
                 }
-
-                // If this is a new long lived subscription, send out the appropriate events.
-                #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-                if SubscriptionKind::LongLived == subscription_kind {
-                    let subnet = Subnet::Attestation(subnet_id);
-                    // Advertise this subnet in our ENR.
-                    self.long_lived_subscriptions.insert_at(
-                        subnet_id,
-                        end_slot,
-                        time_to_subscription_end,
-                    );
-                    self.queue_event(SubnetServiceMessage::EnrAdd(subnet));
-
-                    if !self.discovery_disabled {
-                        self.queue_event(SubnetServiceMessage::DiscoverPeers(vec![
-                            SubnetDiscovery {
-                                subnet,
-                                min_ttl: None,
-                            },
-                        ]))
-                    }
-                }
             }
         }
 
         Ok(())
     }
 
-    /// A random subnet has expired.
-    ///
-    /// This function selects a new subnet to join, or extends the expiry if there are no more
-    /// available subnets to choose from.
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    fn handle_random_subnet_expiry(&mut self, subnet_id: SubnetId) {
-        self.handle_removed_subnet(subnet_id, SubscriptionKind::LongLived);
-
-        // Remove the ENR bitfield bit and choose a new random on from the available subnets
-        // Subscribe to a new random subnet.
-        self.subscribe_to_random_subnets();
-    }
-
     // Unsubscribes from a subnet that was removed if it does not continue to exist as a
     // subscription of the other kind. For long lived subscriptions, it also removes the
     // advertisement from our ENR.
     fn handle_removed_subnet(&mut self, subnet_id: SubnetId, subscription_kind: SubscriptionKind) {
         let exists_in_other_subscriptions = match subscription_kind {
             SubscriptionKind::LongLived => self.short_lived_subscriptions.contains_key(&subnet_id),
-            #[cfg(feature = "deterministic_long_lived_attnets")]
             SubscriptionKind::ShortLived => self.long_lived_subscriptions.contains(&subnet_id),
-            #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-            SubscriptionKind::ShortLived => self.long_lived_subscriptions.contains_key(&subnet_id),
         };
 
         if !exists_in_other_subscriptions {
@@ -793,48 +569,6 @@ impl<T: BeaconChainTypes> AttestationService<T> {
             )));
         }
     }
-
-    /// A known validator has not sent a subscription in a while. They are considered offline and the
-    /// beacon node no longer needs to be subscribed to the allocated random subnets.
-    ///
-    /// We don't keep track of a specific validator to random subnet, rather the ratio of active
-    /// validators to random subnets. So when a validator goes offline, we can simply remove the
-    /// allocated amount of random subnets.
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    fn handle_known_validator_expiry(&mut self) {
-        // Calculate how many subnets should we remove.
-        let extra_subnet_count = {
-            let max_subnets = self.beacon_chain.spec.attestation_subnet_count;
-            let subnets_for_validators = self
-                .known_validators
-                .len()
-                .saturating_mul(self.beacon_chain.spec.random_subnets_per_validator as usize)
-                .min(max_subnets as usize);
-
-            self.long_lived_subscriptions
-                .len()
-                .saturating_sub(subnets_for_validators)
-        };
-
-        if extra_subnet_count == 0 {
-            // Nothing to do
-            return;
-        }
-
-        let advertised_subnets = self
-            .long_lived_subscriptions
-            .keys()
-            .cloned()
-            .collect::<Vec<_>>();
-        let to_remove_subnets = advertised_subnets
-            .choose_multiple(&mut rand::thread_rng(), extra_subnet_count)
-            .cloned();
-
-        for subnet_id in to_remove_subnets {
-            self.long_lived_subscriptions.remove(&subnet_id);
-            self.handle_removed_subnet(subnet_id, SubscriptionKind::LongLived);
-        }
-    }
 }
 
 impl<T: BeaconChainTypes> Stream for AttestationService<T> {
@@ -855,20 +589,8 @@ impl<T: BeaconChainTypes> Stream for AttestationService<T> {
             return Poll::Ready(Some(event));
         }
 
-        // Process first any known validator expiries, since these affect how many long lived
-        // subnets we need.
-        #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-        match self.known_validators.poll_next_unpin(cx) {
-            Poll::Ready(Some(Ok(_validator_index))) => {
-                self.handle_known_validator_expiry();
-            }
-            Poll::Ready(Some(Err(e))) => {
-                error!(self.log, "Failed to check for random subnet cycles"; "error"=> e);
-            }
-            Poll::Ready(None) | Poll::Pending => {}
-        }
-
-        #[cfg(feature = "deterministic_long_lived_attnets")]
+        // Process first any long lived subscription changes, since these affect which short lived
+        // subscriptions need to be announced.
         match self.next_long_lived_subscription_event.as_mut().poll(cx) {
             Poll::Ready(_) => self.recompute_long_lived_subnets(),
             Poll::Pending => {}
@@ -878,12 +600,7 @@ impl<T: BeaconChainTypes> Stream for AttestationService<T> {
         // expire subscription.
         match self.scheduled_short_lived_subscriptions.poll_next_unpin(cx) {
             Poll::Ready(Some(Ok(ExactSubnet { subnet_id, slot }))) => {
-                if let Err(e) = self.subscribe_to_subnet_immediately(
-                    subnet_id,
-                    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-                    SubscriptionKind::ShortLived,
-                    slot + 1,
-                ) {
+                if let Err(e) = self.subscribe_to_subnet_immediately(subnet_id, slot + 1) {
                     debug!(self.log, "Failed to subscribe to short lived subnet"; "subnet" => ?subnet_id, "err" => e);
                 }
             }
@@ -904,18 +621,6 @@ impl<T: BeaconChainTypes> Stream for AttestationService<T> {
             Poll::Ready(None) | Poll::Pending => {}
         }
 
-        // Process any random subnet expiries.
-        #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-        match self.long_lived_subscriptions.poll_next_unpin(cx) {
-            Poll::Ready(Some(Ok((subnet_id, _end_slot)))) => {
-                self.handle_random_subnet_expiry(subnet_id)
-            }
-            Poll::Ready(Some(Err(e))) => {
-                error!(self.log, "Failed to check for random subnet cycles"; "error"=> e);
-            }
-            Poll::Ready(None) | Poll::Pending => {}
-        }
-
         // Poll to remove entries on expiration, no need to act on expiration events.
         if let Some(tracked_vals) = self.aggregate_validators_on_subnet.as_mut() {
             if let Poll::Ready(Some(Err(e))) = tracked_vals.poll_next_unpin(cx) {
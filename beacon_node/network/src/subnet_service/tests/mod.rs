@@ -126,7 +126,6 @@ fn get_attestation_service(
 
     AttestationService::new(
         beacon_chain,
-        #[cfg(feature = "deterministic_long_lived_attnets")]
         lighthouse_network::discv5::enr::NodeId::random()
             .raw()
             .into(),
@@ -179,7 +178,6 @@ async fn get_events<S: Stream<Item = SubnetServiceMessage> + Unpin>(
 
 mod attestation_service {
 
-    #[cfg(feature = "deterministic_long_lived_attnets")]
     use std::collections::HashSet;
 
     #[cfg(not(windows))]
@@ -203,24 +201,6 @@ mod attestation_service {
         }
     }
 
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    fn get_subscriptions(
-        validator_count: u64,
-        slot: Slot,
-        committee_count_at_slot: u64,
-    ) -> Vec<ValidatorSubscription> {
-        (0..validator_count)
-            .map(|validator_index| {
-                get_subscription(
-                    validator_index,
-                    validator_index,
-                    slot,
-                    committee_count_at_slot,
-                )
-            })
-            .collect()
-    }
-
     #[tokio::test]
     async fn subscribe_current_slot_wait_for_unsubscribe() {
         // subscription config
@@ -399,122 +379,6 @@ mod attestation_service {
         assert_eq!(attestation_service.subscription_count(), 1);
     }
 
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    #[tokio::test]
-    async fn subscribe_all_random_subnets() {
-        let attestation_subnet_count = MainnetEthSpec::default_spec().attestation_subnet_count;
-        let subscription_slot = 10;
-        let subscription_count = attestation_subnet_count;
-        let committee_count = 1;
-
-        // create the attestation service and subscriptions
-        let mut attestation_service = get_attestation_service(None);
-        let current_slot = attestation_service
-            .beacon_chain
-            .slot_clock
-            .now()
-            .expect("Could not get current slot");
-
-        let subscriptions = get_subscriptions(
-            subscription_count,
-            current_slot + subscription_slot,
-            committee_count,
-        );
-
-        // submit the subscriptions
-        attestation_service
-            .validator_subscriptions(subscriptions)
-            .unwrap();
-
-        let events = get_events(&mut attestation_service, None, 3).await;
-        let mut discover_peer_count = 0;
-        let mut enr_add_count = 0;
-        let mut unexpected_msg_count = 0;
-
-        for event in &events {
-            match event {
-                SubnetServiceMessage::DiscoverPeers(_) => discover_peer_count += 1,
-                SubnetServiceMessage::Subscribe(_any_subnet) => {}
-                SubnetServiceMessage::EnrAdd(_any_subnet) => enr_add_count += 1,
-                _ => unexpected_msg_count += 1,
-            }
-        }
-
-        // The bulk discovery request length should be equal to validator_count
-        let bulk_discovery_event = events.last().unwrap();
-        if let SubnetServiceMessage::DiscoverPeers(d) = bulk_discovery_event {
-            assert_eq!(d.len(), attestation_subnet_count as usize);
-        } else {
-            panic!("Unexpected event {:?}", bulk_discovery_event);
-        }
-
-        // 64 `DiscoverPeer` requests of length 1 corresponding to random subnets
-        // and 1 `DiscoverPeer` request corresponding to bulk subnet discovery.
-        assert_eq!(discover_peer_count, subscription_count + 1);
-        assert_eq!(attestation_service.subscription_count(), 64);
-        assert_eq!(enr_add_count, 64);
-        assert_eq!(unexpected_msg_count, 0);
-        // test completed successfully
-    }
-
-    #[cfg(not(feature = "deterministic_long_lived_attnets"))]
-    #[tokio::test]
-    async fn subscribe_all_random_subnets_plus_one() {
-        let attestation_subnet_count = MainnetEthSpec::default_spec().attestation_subnet_count;
-        let subscription_slot = 10;
-        // the 65th subscription should result in no more messages than the previous scenario
-        let subscription_count = attestation_subnet_count + 1;
-        let committee_count = 1;
-
-        // create the attestation service and subscriptions
-        let mut attestation_service = get_attestation_service(None);
-        let current_slot = attestation_service
-            .beacon_chain
-            .slot_clock
-            .now()
-            .expect("Could not get current slot");
-
-        let subscriptions = get_subscriptions(
-            subscription_count,
-            current_slot + subscription_slot,
-            committee_count,
-        );
-
-        // submit the subscriptions
-        attestation_service
-            .validator_subscriptions(subscriptions)
-            .unwrap();
-
-        let events = get_events(&mut attestation_service, None, 3).await;
-        let mut discover_peer_count = 0;
-        let mut enr_add_count = 0;
-        let mut unexpected_msg_count = 0;
-
-        for event in &events {
-            match event {
-                SubnetServiceMessage::DiscoverPeers(_) => discover_peer_count += 1,
-                SubnetServiceMessage::Subscribe(_any_subnet) => {}
-                SubnetServiceMessage::EnrAdd(_any_subnet) => enr_add_count += 1,
-                _ => unexpected_msg_count += 1,
-            }
-        }
-
-        // The bulk discovery request length shouldn't exceed max attestation_subnet_count
-        let bulk_discovery_event = events.last().unwrap();
-        if let SubnetServiceMessage::DiscoverPeers(d) = bulk_discovery_event {
-            assert_eq!(d.len(), attestation_subnet_count as usize);
-        } else {
-            panic!("Unexpected event {:?}", bulk_discovery_event);
-        }
-        // 64 `DiscoverPeer` requests of length 1 corresponding to random subnets
-        // and 1 `DiscoverPeer` request corresponding to the bulk subnet discovery.
-        // For the 65th subscription, the call to `subscribe_to_random_subnets` is not made because we are at capacity.
-        assert_eq!(discover_peer_count, 64 + 1);
-        assert_eq!(attestation_service.subscription_count(), 64);
-        assert_eq!(enr_add_count, 64);
-        assert_eq!(unexpected_msg_count, 0);
-    }
-
     #[cfg(not(windows))]
     #[tokio::test]
     async fn test_subscribe_same_subnet_several_slots_apart() {
@@ -633,7 +497,6 @@ mod attestation_service {
     }
 
     #[tokio::test]
-    #[cfg(feature = "deterministic_long_lived_attnets")]
     async fn test_update_deterministic_long_lived_subnets() {
         let mut attestation_service = get_attestation_service(None);
         let new_subnet = SubnetId::new(1);
@@ -7,6 +7,11 @@
 //!
 //! If a batch fails, the backfill sync cannot progress. In this scenario, we mark the backfill
 //! sync as failed, log an error and attempt to retry once a new peer joins the node.
+//!
+//! Batches are downloaded concurrently from all synced peers, and consecutive downloaded batches
+//! are processed together so that their proposer signatures are verified in a single batch. The
+//! progress of the sync is persisted by the anchor in the database, so a restarted node resumes
+//! from the oldest block it has imported.
 
 use crate::beacon_processor::{ChainSegmentProcessId, WorkEvent as BeaconWorkEvent};
 use crate::sync::manager::{BatchProcessResult, Id};
@@ -14,8 +19,8 @@ use crate::sync::network_context::SyncNetworkContext;
 use crate::sync::range_sync::{
     BatchConfig, BatchId, BatchInfo, BatchOperationOutcome, BatchProcessingResult, BatchState,
 };
-use beacon_chain::{BeaconChain, BeaconChainTypes};
-use lighthouse_network::types::{BackFillState, NetworkGlobals};
+use beacon_chain::{events::EventKind, BeaconChain, BeaconChainTypes};
+use lighthouse_network::types::{BackFillProgress, BackFillState, NetworkGlobals, SyncState};
 use lighthouse_network::{PeerAction, PeerId};
 use rand::seq::SliceRandom;
 use slog::{crit, debug, error, info, warn};
@@ -24,7 +29,8 @@ use std::collections::{
     HashMap, HashSet,
 };
 use std::sync::Arc;
-use types::{Epoch, EthSpec, SignedBeaconBlock};
+use std::time::Instant;
+use types::{Epoch, EthSpec, SignedBeaconBlock, Slot};
 
 /// Blocks are downloaded in batches from peers. This constant specifies how many epochs worth of
 /// blocks per batch are requested _at most_. A batch may request less blocks to account for
@@ -37,6 +43,13 @@ pub const BACKFILL_EPOCHS_PER_BATCH: u64 = 2;
 /// The maximum number of batches to queue before requesting more.
 const BACKFILL_BATCH_BUFFER_SIZE: u8 = 20;
 
+/// The maximum number of batches which may be downloaded from a single peer at once.
+const BACKFILL_MAX_REQUESTS_PER_PEER: usize = 2;
+
+/// The maximum number of consecutive downloaded batches which are sent to the processor as a
+/// single chain segment. Larger segments allow more signatures to be verified in one batch.
+const BACKFILL_BATCHES_PER_SEGMENT: usize = 4;
+
 /// The number of times to retry a batch before it is considered failed.
 const MAX_BATCH_DOWNLOAD_ATTEMPTS: u8 = 10;
 
@@ -71,6 +84,8 @@ pub enum SyncStart {
         completed: usize,
         /// The number of slots still to be processed.
         remaining: usize,
+        /// The estimated time until the sync completes, if any progress has been made.
+        estimated_seconds_remaining: Option<u64>,
     },
     /// The chain didn't start syncing.
     NotSyncing,
@@ -123,6 +138,18 @@ pub struct BackFillSync<T: BeaconChainTypes> {
     /// The current processing batch, if any.
     current_processing_batch: Option<BatchId>,
 
+    /// The batches sent to the processor alongside `current_processing_batch` (which is the oldest
+    /// of them), in ascending order, with the blocks of each.
+    processing_group: Vec<(BatchId, Vec<Arc<SignedBeaconBlock<T::EthSpec>>>)>,
+
+    /// Batches which were part of a segment that failed processing. These are processed on their
+    /// own, so that the failure can be attributed to the batch that caused it.
+    isolated_batches: HashSet<BatchId>,
+
+    /// When the backfill was last started or resumed, and the oldest block slot at the time. Used
+    /// to estimate the time until completion.
+    progress_start: Option<(Instant, Slot)>,
+
     /// Batches validated by this chain.
     validated_batches: u64,
 
@@ -184,6 +211,9 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             to_be_downloaded: current_start,
             network_globals,
             current_processing_batch: None,
+            processing_group: Vec::new(),
+            isolated_batches: HashSet::new(),
+            progress_start: None,
             validated_batches: 0,
             participating_peers: HashSet::new(),
             restart_failed_sync: false,
@@ -226,6 +256,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
                     // If there are peers to resume with, begin the resume.
                     debug!(self.log, "Resuming backfill sync"; "start_epoch" => self.current_start, "awaiting_batches" => self.batches.len(), "processing_target" => self.processing_target);
                     self.set_state(BackFillState::Syncing);
+                    self.progress_start = Some((Instant::now(), self.oldest_block_slot()));
                     // Resume any previously failed batches.
                     self.resume_batches(network)?;
                     // begin requesting blocks from the peer pool, until all peers are exhausted.
@@ -271,6 +302,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
                 }
 
                 debug!(self.log, "Resuming a failed backfill sync"; "start_epoch" => self.current_start);
+                self.progress_start = Some((Instant::now(), self.oldest_block_slot()));
 
                 // begin requesting blocks from the peer pool, until all peers are exhausted.
                 self.request_batches(network)?;
@@ -280,14 +312,11 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             }
         }
 
+        let progress = self.progress();
         Ok(SyncStart::Syncing {
-            completed: (self.validated_batches
-                * BACKFILL_EPOCHS_PER_BATCH
-                * T::EthSpec::slots_per_epoch()) as usize,
-            remaining: self
-                .current_start
-                .start_slot(T::EthSpec::slots_per_epoch())
-                .as_usize(),
+            completed: progress.completed,
+            remaining: progress.remaining,
+            estimated_seconds_remaining: progress.estimated_seconds_remaining,
         })
     }
 
@@ -484,6 +513,8 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         self.to_be_downloaded = self.current_start;
         self.last_batch_downloaded = false;
         self.current_processing_batch = None;
+        self.processing_group.clear();
+        self.isolated_batches.clear();
 
         // NOTE: Lets keep validated_batches for posterity
 
@@ -507,30 +538,72 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             return Ok(ProcessResult::Successful);
         }
 
-        let batch = match self.batches.get_mut(&batch_id) {
-            Some(batch) => batch,
-            None => {
-                return self
-                    .fail_sync(BackFillError::InvalidSyncState(format!(
-                        "Trying to process a batch that does not exist: {}",
-                        batch_id
-                    )))
-                    .map(|_| ProcessResult::Successful);
+        // Include any consecutive older batches which are also ready to be processed, so that their
+        // signatures are verified together. Batches from a segment which failed are processed on
+        // their own.
+        let mut group = vec![batch_id];
+        while group.len() < BACKFILL_BATCHES_PER_SEGMENT
+            && !self.isolated_batches.contains(&batch_id)
+        {
+            let newer_id = group[group.len() - 1];
+            let older_id = newer_id.saturating_sub(BACKFILL_EPOCHS_PER_BATCH);
+            // The final batch may overlap the one before it if the anchor is not aligned to the
+            // batch size, such batches are processed separately.
+            if older_id + BACKFILL_EPOCHS_PER_BATCH != newer_id {
+                break;
             }
-        };
+            match self.batches.get(&older_id) {
+                Some(batch)
+                    if matches!(batch.state(), BatchState::AwaitingProcessing(..))
+                        && !self.isolated_batches.contains(&older_id) =>
+                {
+                    group.push(older_id)
+                }
+                _ => break,
+            }
+        }
 
         // NOTE: We send empty batches to the processor in order to trigger the block processor
         // result callback. This is done, because an empty batch could end a chain and the logic
         // for removing chains and checking completion is in the callback.
 
-        let blocks = match batch.start_processing() {
-            Err(e) => {
-                return self
-                    .fail_sync(BackFillError::BatchInvalidState(batch_id, e.0))
-                    .map(|_| ProcessResult::Successful)
+        // Blocks must be sent to the processor in ascending slot order, oldest batch first.
+        let mut blocks = Vec::new();
+        let mut processing_group = Vec::with_capacity(group.len());
+        for id in group.into_iter().rev() {
+            self.isolated_batches.remove(&id);
+            let batch = match self.batches.get_mut(&id) {
+                Some(batch) => batch,
+                None => {
+                    return self
+                        .fail_sync(BackFillError::InvalidSyncState(format!(
+                            "Trying to process a batch that does not exist: {}",
+                            id
+                        )))
+                        .map(|_| ProcessResult::Successful);
+                }
+            };
+
+            match batch.start_processing() {
+                Err(e) => {
+                    return self
+                        .fail_sync(BackFillError::BatchInvalidState(id, e.0))
+                        .map(|_| ProcessResult::Successful)
+                }
+                Ok(batch_blocks) => {
+                    blocks.extend(batch_blocks.iter().cloned());
+                    processing_group.push((id, batch_blocks));
+                }
             }
-            Ok(v) => v,
-        };
+        }
+
+        // The segment is identified by its oldest batch.
+        let batch_id = processing_group[0].0;
+        if processing_group.len() > 1 {
+            debug!(self.log, "Processing backfill batches together";
+                "oldest_batch" => batch_id, "batches" => processing_group.len(), "blocks" => blocks.len());
+        }
+        self.processing_group = processing_group;
 
         let process_id = ChainSegmentProcessId::BackSyncBatchId(batch_id);
         self.current_processing_batch = Some(batch_id);
@@ -564,7 +637,7 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         // The first two cases are possible in regular sync, should not occur in backfill, but we
         // keep this logic for handling potential processing race conditions.
         // result
-        let batch = match &self.current_processing_batch {
+        match &self.current_processing_batch {
             Some(processing_id) if *processing_id != batch_id => {
                 debug!(self.log, "Unexpected batch result";
                     "batch_epoch" => batch_id, "expected_batch_epoch" => processing_id);
@@ -578,19 +651,57 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             _ => {
                 // batch_id matches, continue
                 self.current_processing_batch = None;
+            }
+        }
+
+        let group = std::mem::take(&mut self.processing_group);
+        let newest_batch_id = group.last().map_or(batch_id, |(id, _)| *id);
 
-                match self.batches.get_mut(&batch_id) {
+        // A faulty segment of several batches doesn't tell which of them is invalid. Rather than
+        // penalizing the peers of all of them, the batches are processed again one at a time.
+        if group.len() > 1 && matches!(result, BatchProcessResult::FaultyFailure { .. }) {
+            debug!(self.log, "Backfill segment failed, processing its batches individually";
+                "oldest_batch" => batch_id, "batches" => group.len());
+            for (id, blocks) in group {
+                let batch = match self.batches.get_mut(&id) {
                     Some(batch) => batch,
                     None => {
-                        // This is an error. Fail the sync algorithm.
                         return self
                             .fail_sync(BackFillError::InvalidSyncState(format!(
-                                "Current processing batch not found: {}",
-                                batch_id
+                                "Processed batch not found: {}",
+                                id
                             )))
                             .map(|_| ProcessResult::Successful);
                     }
+                };
+                if let Err(e) = batch.processing_deferred(blocks) {
+                    return self
+                        .fail_sync(BackFillError::BatchInvalidState(id, e.0))
+                        .map(|_| ProcessResult::Successful);
                 }
+                self.isolated_batches.insert(id);
+            }
+            return self.process_completed_batches(network);
+        }
+
+        // Resolve the newer batches which were processed in the same segment. If the segment could
+        // not be processed through no fault of its peers, they are re-downloaded alongside it.
+        let redownload = self.complete_grouped_batches(
+            batch_id,
+            &group,
+            matches!(result, BatchProcessResult::Success { .. }),
+        )?;
+
+        let batch = match self.batches.get_mut(&batch_id) {
+            Some(batch) => batch,
+            None => {
+                // This is an error. Fail the sync algorithm.
+                return self
+                    .fail_sync(BackFillError::InvalidSyncState(format!(
+                        "Current processing batch not found: {}",
+                        batch_id
+                    )))
+                    .map(|_| ProcessResult::Successful);
             }
         };
 
@@ -615,17 +726,23 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
                     self.fail_sync(BackFillError::BatchInvalidState(batch_id, e.0))?;
                 }
                 // If the processed batch was not empty, we can validate previous unvalidated
-                // blocks.
+                // blocks. When several batches were processed together, the oldest non-empty one
+                // validates those before it.
                 if *was_non_empty {
-                    self.advance_chain(network, batch_id);
+                    let validating_batch = group
+                        .iter()
+                        .find(|(_, blocks)| !blocks.is_empty())
+                        .map_or(batch_id, |(id, _)| *id);
+                    self.advance_chain(network, validating_batch);
                 }
 
-                if batch_id == self.processing_target {
-                    self.processing_target = self
-                        .processing_target
-                        .saturating_sub(BACKFILL_EPOCHS_PER_BATCH);
+                // Advancing the chain may have moved the target to any batch of the segment.
+                if (batch_id..=newest_batch_id).contains(&self.processing_target) {
+                    self.processing_target = batch_id.saturating_sub(BACKFILL_EPOCHS_PER_BATCH);
                 }
 
+                self.report_progress();
+
                 // check if the chain has completed syncing
                 if self.check_completed() {
                     // chain is completed
@@ -689,6 +806,9 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
                 {
                     self.fail_sync(BackFillError::BatchInvalidState(batch_id, e.0))?;
                 }
+                for id in redownload {
+                    self.retry_batch_download(network, id)?;
+                }
                 self.retry_batch_download(network, batch_id)
                     .map(|_| ProcessResult::Successful)
             }
@@ -751,6 +871,50 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         Ok(ProcessResult::Successful)
     }
 
+    /// Completes the processing of the batches in `group` other than `batch_id`, which were
+    /// processed in the same chain segment as `batch_id`.
+    ///
+    /// If the segment failed through no fault of its peers, the batches are returned so that they
+    /// can be downloaded again.
+    fn complete_grouped_batches(
+        &mut self,
+        batch_id: BatchId,
+        group: &[(BatchId, Vec<Arc<SignedBeaconBlock<T::EthSpec>>>)],
+        success: bool,
+    ) -> Result<Vec<BatchId>, BackFillError> {
+        let mut redownload = Vec::new();
+        for &(id, _) in group.iter().filter(|(id, _)| *id != batch_id) {
+            let result = if success {
+                BatchProcessingResult::Success
+            } else {
+                BatchProcessingResult::NonFaultyFailure
+            };
+            let outcome = match self.batches.get_mut(&id) {
+                Some(batch) => batch.processing_completed(result),
+                None => {
+                    return self
+                        .fail_sync(BackFillError::InvalidSyncState(format!(
+                            "Processed batch not found: {}",
+                            id
+                        )))
+                        .map(|_| redownload);
+                }
+            };
+            match outcome {
+                Ok(BatchOperationOutcome::Continue) => {
+                    if !success {
+                        redownload.push(id);
+                    }
+                }
+                Ok(BatchOperationOutcome::Failed { blacklist: _ }) => {
+                    self.fail_sync(BackFillError::BatchProcessingFailed(id))?;
+                }
+                Err(e) => self.fail_sync(BackFillError::BatchInvalidState(id, e.0))?,
+            }
+        }
+        Ok(redownload)
+    }
+
     /// Removes any batches previous to the given `validating_epoch` and updates the current
     /// boundaries of the chain.
     ///
@@ -1037,21 +1201,33 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
 
         // randomize the peers for load balancing
         let mut rng = rand::thread_rng();
-        let mut idle_peers = self
+        let mut peers = self
             .network_globals
             .peers
             .read()
             .synced_peers()
-            .filter(|peer_id| {
-                self.active_requests
+            .map(|peer_id| {
+                let active = self
+                    .active_requests
                     .get(peer_id)
-                    .map(|requests| requests.is_empty())
-                    .unwrap_or(true)
+                    .map(|requests| requests.len())
+                    .unwrap_or(0);
+                (*peer_id, active)
             })
-            .cloned()
             .collect::<Vec<_>>();
 
-        idle_peers.shuffle(&mut rng);
+        peers.shuffle(&mut rng);
+
+        // Spread the requests across all peers before sending a further request to any one peer.
+        let mut idle_peers = (0..BACKFILL_MAX_REQUESTS_PER_PEER)
+            .flat_map(|round| {
+                peers
+                    .iter()
+                    .filter(move |(_, active)| active + round < BACKFILL_MAX_REQUESTS_PER_PEER)
+                    .map(|(peer_id, _)| *peer_id)
+            })
+            .collect::<Vec<_>>();
+        idle_peers.reverse();
 
         while let Some(peer) = idle_peers.pop() {
             if let Some(batch_id) = self.include_next_batch() {
@@ -1155,6 +1331,70 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         false
     }
 
+    /// Returns the slot of the oldest block in the database.
+    fn oldest_block_slot(&self) -> Slot {
+        self.beacon_chain
+            .store
+            .get_anchor_info()
+            .map_or(Slot::new(0), |anchor_info| anchor_info.oldest_block_slot)
+    }
+
+    /// Computes the progress of the backfill since it was last started or resumed.
+    fn progress(&self) -> BackFillProgress {
+        let oldest_block_slot = self.oldest_block_slot();
+        let (started, start_slot) = self
+            .progress_start
+            .unwrap_or_else(|| (Instant::now(), oldest_block_slot));
+        let completed = start_slot
+            .as_usize()
+            .saturating_sub(oldest_block_slot.as_usize());
+        let remaining = oldest_block_slot.as_usize();
+
+        let elapsed = started.elapsed().as_secs_f64();
+        let slots_per_second = if elapsed > 0.0 {
+            completed as f64 / elapsed
+        } else {
+            0.0
+        };
+        let estimated_seconds_remaining = if slots_per_second > 0.0 {
+            Some((remaining as f64 / slots_per_second) as u64)
+        } else {
+            None
+        };
+
+        BackFillProgress {
+            completed,
+            remaining,
+            oldest_block_slot,
+            slots_per_second,
+            estimated_seconds_remaining,
+        }
+    }
+
+    /// Publishes the progress of the backfill to the global sync state and to event subscribers.
+    fn report_progress(&self) {
+        let progress = self.progress();
+
+        // The sync manager owns the transitions between sync states, only refresh the progress
+        // whilst it reports a backfill.
+        {
+            let mut sync_state = self.network_globals.sync_state.write();
+            if matches!(*sync_state, SyncState::BackFillSyncing { .. }) {
+                *sync_state = SyncState::BackFillSyncing {
+                    completed: progress.completed,
+                    remaining: progress.remaining,
+                    estimated_seconds_remaining: progress.estimated_seconds_remaining,
+                };
+            }
+        }
+
+        if let Some(event_handler) = self.beacon_chain.event_handler.as_ref() {
+            if event_handler.has_backfill_progress_subscribers() {
+                event_handler.register(EventKind::BackFillProgress(progress));
+            }
+        }
+    }
+
     /// Updates the global network state indicating the current state of a backfill sync.
     fn set_state(&self, state: BackFillState) {
         *self.network_globals.backfill_state.write() = state;
@@ -1172,3 +1412,219 @@ enum ResetEpochError {
     /// Backfill is not required.
     NotRequired,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beacon_processor::CHAIN_SEGMENT;
+    use crate::NetworkMessage;
+    use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use tokio::sync::mpsc;
+    use types::{BeaconBlock, MinimalEthSpec as E, Signature};
+
+    type T = EphemeralHarnessType<E>;
+
+    struct TestRig {
+        backfill: BackFillSync<T>,
+        cx: SyncNetworkContext<T>,
+        beacon_processor_rx: mpsc::Receiver<BeaconWorkEvent<T>>,
+        _network_rx: mpsc::UnboundedReceiver<NetworkMessage<E>>,
+        _harness: BeaconChainHarness<T>,
+    }
+
+    impl TestRig {
+        /// Creates a syncing backfill with a downloaded batch for each of `batch_ids`, newest
+        /// first, each served by a distinct peer.
+        fn new(batch_ids: &[u64]) -> (Self, Vec<PeerId>) {
+            let log = NullLoggerBuilder.build().unwrap();
+            let harness = BeaconChainHarness::builder(E)
+                .default_spec()
+                .deterministic_keypairs(8)
+                .fresh_ephemeral_store()
+                .build();
+            let globals = Arc::new(NetworkGlobals::new_test_globals(&log));
+            let (beacon_processor_tx, beacon_processor_rx) = mpsc::channel(100);
+            let (network_tx, network_rx) = mpsc::unbounded_channel();
+            let cx = SyncNetworkContext::new(
+                network_tx,
+                globals.clone(),
+                beacon_processor_tx,
+                false,
+                log.clone(),
+            );
+
+            let mut backfill = BackFillSync::new(harness.chain.clone(), globals, log);
+            let current_start = Epoch::new(batch_ids[0] + BACKFILL_EPOCHS_PER_BATCH);
+            backfill.current_start = current_start;
+            backfill.processing_target = Epoch::new(batch_ids[0]);
+            backfill.set_state(BackFillState::Syncing);
+
+            let mut peers = Vec::new();
+            for &id in batch_ids {
+                let peer = PeerId::random();
+                let batch_id = Epoch::new(id);
+                let mut batch = BatchInfo::new(&batch_id, BACKFILL_EPOCHS_PER_BATCH);
+                let mut block = BeaconBlock::empty(&harness.spec);
+                *block.slot_mut() = batch_id.start_slot(E::slots_per_epoch()) + 1;
+                assert!(batch.start_downloading_from_peer(peer, 1).is_ok());
+                assert!(batch
+                    .add_block(Arc::new(SignedBeaconBlock::from_block(
+                        block,
+                        Signature::empty(),
+                    )))
+                    .is_ok());
+                assert!(batch.download_completed().is_ok());
+                backfill.batches.insert(batch_id, batch);
+                peers.push(peer);
+            }
+
+            // The batch before the downloaded ones is still being downloaded.
+            let downloading_id = Epoch::new(batch_ids[batch_ids.len() - 1])
+                .saturating_sub(BACKFILL_EPOCHS_PER_BATCH);
+            let mut batch = BatchInfo::new(&downloading_id, BACKFILL_EPOCHS_PER_BATCH);
+            assert!(batch
+                .start_downloading_from_peer(PeerId::random(), 2)
+                .is_ok());
+            backfill.batches.insert(downloading_id, batch);
+            backfill.to_be_downloaded = downloading_id.saturating_sub(BACKFILL_EPOCHS_PER_BATCH);
+
+            let rig = TestRig {
+                backfill,
+                cx,
+                beacon_processor_rx,
+                _network_rx: network_rx,
+                _harness: harness,
+            };
+            (rig, peers)
+        }
+
+        /// Asserts that a single chain segment made of the batches `expected` was sent to the
+        /// processor.
+        #[track_caller]
+        fn expect_segment(&mut self, expected: &[u64]) {
+            let work = self
+                .beacon_processor_rx
+                .try_recv()
+                .expect("should send a chain segment");
+            assert_eq!(work.work_type(), CHAIN_SEGMENT);
+            assert!(self.beacon_processor_rx.try_recv().is_err());
+
+            let group = self
+                .backfill
+                .processing_group
+                .iter()
+                .map(|(id, _)| id.as_u64())
+                .collect::<Vec<_>>();
+            assert_eq!(group, expected);
+            assert_eq!(
+                self.backfill.current_processing_batch,
+                Some(Epoch::new(expected[0]))
+            );
+        }
+
+        fn process_result(
+            &mut self,
+            batch_id: u64,
+            result: BatchProcessResult,
+        ) -> Result<ProcessResult, BackFillError> {
+            self.backfill
+                .on_batch_process_result(&mut self.cx, Epoch::new(batch_id), &result)
+        }
+
+        fn batch(&self, batch_id: u64) -> &BatchInfo<E, BackFillBatchConfig> {
+            &self.backfill.batches[&Epoch::new(batch_id)]
+        }
+    }
+
+    #[test]
+    fn consecutive_batches_are_processed_together() {
+        let (mut rig, _) = TestRig::new(&[8, 6, 4]);
+
+        rig.backfill.process_completed_batches(&mut rig.cx).unwrap();
+        rig.expect_segment(&[4, 6, 8]);
+        for id in [4, 6, 8] {
+            assert!(matches!(rig.batch(id).state(), BatchState::Processing(_)));
+        }
+
+        // The segment is resolved as a whole, and the chain advances to its oldest batch.
+        assert!(matches!(
+            rig.process_result(
+                4,
+                BatchProcessResult::Success {
+                    was_non_empty: true
+                }
+            ),
+            Ok(ProcessResult::Successful)
+        ));
+        assert_eq!(rig.backfill.current_start, Epoch::new(4));
+        assert_eq!(rig.backfill.processing_target, Epoch::new(2));
+        assert!(matches!(
+            rig.batch(4).state(),
+            BatchState::AwaitingValidation(_)
+        ));
+        assert!(!rig.backfill.batches.contains_key(&Epoch::new(6)));
+        assert!(!rig.backfill.batches.contains_key(&Epoch::new(8)));
+    }
+
+    #[test]
+    fn failed_segment_is_attributed_to_the_faulty_batch() {
+        let (mut rig, peers) = TestRig::new(&[8, 6, 4]);
+
+        rig.backfill.process_completed_batches(&mut rig.cx).unwrap();
+        rig.expect_segment(&[4, 6, 8]);
+
+        // The segment fails, none of its peers are blamed and the newest batch is processed alone.
+        assert!(matches!(
+            rig.process_result(
+                4,
+                BatchProcessResult::FaultyFailure {
+                    imported_blocks: false,
+                    penalty: PeerAction::LowToleranceError,
+                }
+            ),
+            Ok(ProcessResult::Successful)
+        ));
+        rig.expect_segment(&[8]);
+        for id in [4, 6] {
+            assert!(matches!(
+                rig.batch(id).state(),
+                BatchState::AwaitingProcessing(..)
+            ));
+        }
+        for id in [4, 6, 8] {
+            assert!(rig.batch(id).failed_peers().is_empty());
+        }
+
+        // The newest batch is valid, so the next one is processed alone too.
+        assert!(matches!(
+            rig.process_result(
+                8,
+                BatchProcessResult::Success {
+                    was_non_empty: true
+                }
+            ),
+            Ok(ProcessResult::Successful)
+        ));
+        rig.expect_segment(&[6]);
+
+        // The failure is attributed to the peer which served the invalid batch only. There are no
+        // peers to download it from again, so the sync pauses.
+        assert!(matches!(
+            rig.process_result(
+                6,
+                BatchProcessResult::FaultyFailure {
+                    imported_blocks: false,
+                    penalty: PeerAction::LowToleranceError,
+                }
+            ),
+            Err(BackFillError::Paused)
+        ));
+        assert_eq!(rig.batch(6).failed_peers(), HashSet::from([peers[1]]));
+        assert!(rig.batch(4).failed_peers().is_empty());
+        assert!(matches!(
+            rig.batch(4).state(),
+            BatchState::AwaitingProcessing(..)
+        ));
+    }
+}
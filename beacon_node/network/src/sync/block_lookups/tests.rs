@@ -55,6 +55,7 @@ impl TestRig {
                 network_tx,
                 globals,
                 beacon_processor_tx,
+                false,
                 log.new(slog::o!("component" => "network_context")),
             )
         };
@@ -52,7 +52,7 @@ use std::boxed::Box;
 use std::ops::Sub;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use types::{EthSpec, Hash256, SignedBeaconBlock, Slot};
 
 /// The number of slots ahead of us that is allowed before requesting a long-range (batch)  Sync
@@ -77,6 +77,8 @@ pub enum RequestId {
     BackFillSync { id: Id },
     /// The request was from a chain in the range sync algorithm.
     RangeSync { id: Id },
+    /// The request was from a chain in the range sync algorithm, cross-checking a batch.
+    RangeCrossCheck { id: Id },
 }
 
 #[derive(Debug)]
@@ -96,9 +98,15 @@ pub enum SyncMessage<T: EthSpec> {
     /// A block with an unknown parent has been received.
     UnknownBlock(PeerId, Arc<SignedBeaconBlock<T>>, Hash256),
 
-    /// A peer has sent an object that references a block that is unknown. This triggers the
-    /// manager to attempt to find the block matching the unknown hash.
-    UnknownBlockHash(PeerId, Hash256),
+    /// Peers have sent objects that reference blocks that are unknown. This triggers the manager
+    /// to attempt to find the blocks matching the unknown hashes from the given peers.
+    ///
+    /// Whether the lookups were accepted is sent on `accepted_tx`. They are ignored while the
+    /// node is not synced or the execution engine is offline.
+    UnknownBlockHashes {
+        lookups: Vec<(Hash256, Vec<PeerId>)>,
+        accepted_tx: oneshot::Sender<bool>,
+    },
 
     /// A peer has disconnected.
     Disconnect(PeerId),
@@ -189,6 +197,7 @@ pub fn spawn<T: BeaconChainTypes>(
     network_globals: Arc<NetworkGlobals<T::EthSpec>>,
     network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
     beacon_processor_send: mpsc::Sender<BeaconWorkEvent<T>>,
+    range_sync_cross_check: bool,
     log: slog::Logger,
 ) -> mpsc::UnboundedSender<SyncMessage<T::EthSpec>> {
     assert!(
@@ -207,6 +216,7 @@ pub fn spawn<T: BeaconChainTypes>(
             network_send,
             network_globals.clone(),
             beacon_processor_send,
+            range_sync_cross_check,
             log.clone(),
         ),
         range_sync: RangeSync::new(beacon_chain.clone(), log.clone()),
@@ -291,10 +301,25 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     self.update_sync_state()
                 }
             }
+            RequestId::RangeCrossCheck { id } => {
+                if let Some((chain_id, batch_id)) =
+                    self.network.range_cross_check_response(id, true)
+                {
+                    self.range_sync.cross_check_error(
+                        &mut self.network,
+                        peer_id,
+                        batch_id,
+                        chain_id,
+                        id,
+                    );
+                    self.update_sync_state()
+                }
+            }
         }
     }
 
     fn peer_disconnect(&mut self, peer_id: &PeerId) {
+        self.network.remove_peer_throughput(peer_id);
         self.range_sync.peer_disconnect(&mut self.network, peer_id);
         self.block_lookups
             .peer_disconnected(peer_id, &mut self.network);
@@ -408,10 +433,12 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                             Ok(SyncStart::Syncing {
                                 completed,
                                 remaining,
+                                estimated_seconds_remaining,
                             }) => {
                                 sync_state = SyncState::BackFillSyncing {
                                     completed,
                                     remaining,
+                                    estimated_seconds_remaining,
                                 };
                             }
                             Ok(SyncStart::NotSyncing) => {} // Ignore updating the state if the backfill sync state didn't start.
@@ -526,14 +553,27 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                         .search_parent(block_root, block, peer_id, &mut self.network);
                 }
             }
-            SyncMessage::UnknownBlockHash(peer_id, block_hash) => {
-                // If we are not synced, ignore this block.
-                if self.network_globals.sync_state.read().is_synced()
-                    && self.network_globals.peers.read().is_connected(&peer_id)
-                    && self.network.is_execution_engine_online()
-                {
-                    self.block_lookups
-                        .search_block(block_hash, peer_id, &mut self.network);
+            SyncMessage::UnknownBlockHashes {
+                lookups,
+                accepted_tx,
+            } => {
+                // If we are not synced, ignore these blocks.
+                let accepted = self.network_globals.sync_state.read().is_synced()
+                    && self.network.is_execution_engine_online();
+                // The receiver may have been dropped, in which case there is no one to inform.
+                let _ = accepted_tx.send(accepted);
+                if accepted {
+                    for (block_hash, peers) in lookups {
+                        for peer_id in peers {
+                            if self.network_globals.peers.read().is_connected(&peer_id) {
+                                self.block_lookups.search_block(
+                                    block_hash,
+                                    peer_id,
+                                    &mut self.network,
+                                );
+                            }
+                        }
+                    }
                 }
             }
             SyncMessage::Disconnect(peer_id) => {
@@ -701,6 +741,22 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     self.update_sync_state();
                 }
             }
+            RequestId::RangeCrossCheck { id } => {
+                if let Some((chain_id, batch_id)) = self
+                    .network
+                    .range_cross_check_response(id, beacon_block.is_none())
+                {
+                    self.range_sync.cross_check_response(
+                        &mut self.network,
+                        peer_id,
+                        chain_id,
+                        batch_id,
+                        id,
+                        beacon_block,
+                    );
+                    self.update_sync_state();
+                }
+            }
         }
     }
 }
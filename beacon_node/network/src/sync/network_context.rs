@@ -12,7 +12,12 @@ use lighthouse_network::rpc::{BlocksByRangeRequest, BlocksByRootRequest, Goodbye
 use lighthouse_network::{Client, NetworkGlobals, PeerAction, PeerId, ReportSource, Request};
 use slog::{debug, trace, warn};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
+use types::Slot;
+
+/// The weight given to the latest observation when updating the download throughput of a peer.
+const THROUGHPUT_DECAY: f64 = 0.3;
 
 /// Wraps a Network channel to employ various RPC related network functionality for the Sync manager. This includes management of a global RPC request Id.
 
@@ -29,9 +34,18 @@ pub struct SyncNetworkContext<T: BeaconChainTypes> {
     /// BlocksByRange requests made by the range syncing algorithm.
     range_requests: FnvHashMap<Id, (ChainId, BatchId)>,
 
+    /// BlocksByRange requests made by range sync to cross-check a downloaded batch.
+    range_cross_check_requests: FnvHashMap<Id, (ChainId, BatchId)>,
+
     /// BlocksByRange requests made by backfill syncing.
     backfill_requests: FnvHashMap<Id, BatchId>,
 
+    /// Whether range sync should cross-check downloaded batches against a second peer.
+    range_sync_cross_check: bool,
+
+    /// Moving average of the blocks per second downloaded from each peer.
+    peer_throughput: FnvHashMap<PeerId, f64>,
+
     /// Whether the ee is online. If it's not, we don't allow access to the
     /// `beacon_processor_send`.
     execution_engine_state: EngineState,
@@ -48,6 +62,7 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
         network_globals: Arc<NetworkGlobals<T::EthSpec>>,
         beacon_processor_send: mpsc::Sender<WorkEvent<T>>,
+        range_sync_cross_check: bool,
         log: slog::Logger,
     ) -> Self {
         Self {
@@ -56,7 +71,10 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             network_globals,
             request_id: 1,
             range_requests: FnvHashMap::default(),
+            range_cross_check_requests: FnvHashMap::default(),
             backfill_requests: FnvHashMap::default(),
+            range_sync_cross_check,
+            peer_throughput: FnvHashMap::default(),
             beacon_processor_send,
             log,
         }
@@ -127,6 +145,38 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         Ok(id)
     }
 
+    /// A blocks by range request for the last block of a range sync batch, sent to a peer other
+    /// than the one that served the batch.
+    pub fn range_cross_check_request(
+        &mut self,
+        peer_id: PeerId,
+        slot: Slot,
+        chain_id: ChainId,
+        batch_id: BatchId,
+    ) -> Result<Id, &'static str> {
+        trace!(
+            self.log,
+            "Sending cross-check BlocksByRange Request";
+            "method" => "BlocksByRange",
+            "slot" => slot,
+            "peer" => %peer_id,
+        );
+        let request = Request::BlocksByRange(BlocksByRangeRequest {
+            start_slot: slot.as_u64(),
+            count: 1,
+        });
+        let id = self.next_id();
+        let request_id = RequestId::Sync(SyncRequestId::RangeCrossCheck { id });
+        self.send_network_msg(NetworkMessage::SendRequest {
+            peer_id,
+            request,
+            request_id,
+        })?;
+        self.range_cross_check_requests
+            .insert(id, (chain_id, batch_id));
+        Ok(id)
+    }
+
     /// A blocks by range request sent by the backfill sync algorithm
     pub fn backfill_blocks_by_range_request(
         &mut self,
@@ -166,6 +216,19 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         }
     }
 
+    /// Received a response to a range sync cross-check.
+    pub fn range_cross_check_response(
+        &mut self,
+        request_id: Id,
+        remove: bool,
+    ) -> Option<(ChainId, BatchId)> {
+        if remove {
+            self.range_cross_check_requests.remove(&request_id)
+        } else {
+            self.range_cross_check_requests.get(&request_id).cloned()
+        }
+    }
+
     /// Received a blocks by range response.
     pub fn backfill_sync_response(&mut self, request_id: Id, remove: bool) -> Option<BatchId> {
         if remove {
@@ -223,6 +286,36 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         Ok(id)
     }
 
+    /// Whether range sync batches should be cross-checked against a second peer.
+    pub fn range_sync_cross_check(&self) -> bool {
+        self.range_sync_cross_check
+    }
+
+    /// Updates the download throughput of a peer after it served `blocks` in `duration`.
+    pub fn record_download(&mut self, peer_id: PeerId, blocks: usize, duration: Duration) {
+        // Empty responses are cheap to serve and say little about the peer's bandwidth.
+        if blocks == 0 {
+            return;
+        }
+        let observed = blocks as f64 / duration.as_secs_f64().max(0.001);
+        self.peer_throughput
+            .entry(peer_id)
+            .and_modify(|throughput| {
+                *throughput = THROUGHPUT_DECAY * observed + (1.0 - THROUGHPUT_DECAY) * *throughput
+            })
+            .or_insert(observed);
+    }
+
+    /// Returns the observed download throughput of a peer in blocks per second, if known.
+    pub fn peer_throughput(&self, peer_id: &PeerId) -> Option<f64> {
+        self.peer_throughput.get(peer_id).copied()
+    }
+
+    /// Forgets the download throughput of a disconnected peer.
+    pub fn remove_peer_throughput(&mut self, peer_id: &PeerId) {
+        self.peer_throughput.remove(peer_id);
+    }
+
     pub fn is_execution_engine_online(&self) -> bool {
         self.execution_engine_state == EngineState::Online
     }
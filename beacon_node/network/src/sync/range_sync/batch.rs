@@ -5,7 +5,8 @@ use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 use std::ops::Sub;
 use std::sync::Arc;
-use types::{Epoch, EthSpec, SignedBeaconBlock, Slot};
+use std::time::{Duration, Instant};
+use types::{Epoch, EthSpec, Hash256, SignedBeaconBlock, Slot};
 
 /// The number of times to retry a batch before it is considered failed.
 const MAX_BATCH_DOWNLOAD_ATTEMPTS: u8 = 5;
@@ -14,6 +15,10 @@ const MAX_BATCH_DOWNLOAD_ATTEMPTS: u8 = 5;
 /// after `MAX_BATCH_PROCESSING_ATTEMPTS` times, it is considered faulty.
 const MAX_BATCH_PROCESSING_ATTEMPTS: u8 = 3;
 
+/// The number of peers that must disagree with a downloaded batch before it is downloaded again.
+/// A single disagreement is settled by cross-checking the batch with a third peer.
+const MAX_CROSS_CHECK_DISAGREEMENTS: usize = 2;
+
 /// Allows customisation of the above constants used in other sync methods such as BackFillSync.
 pub trait BatchConfig {
     /// The maximum batch download attempts.
@@ -96,6 +101,12 @@ pub struct BatchInfo<T: EthSpec, B: BatchConfig = RangeSyncBatchConfig> {
     failed_download_attempts: Vec<PeerId>,
     /// State of the batch.
     state: BatchState<T>,
+    /// When the current download of the batch was requested.
+    download_started: Option<Instant>,
+    /// Progress of the cross-check of the downloaded blocks against a second peer.
+    cross_check: CrossCheck,
+    /// Peers that disagreed with the current download when cross-checking it.
+    cross_check_disagreements: Vec<PeerId>,
     /// Pin the generic
     marker: std::marker::PhantomData<B>,
 }
@@ -123,6 +134,21 @@ pub enum BatchState<T: EthSpec> {
     Failed,
 }
 
+/// Progress of the cross-check of a downloaded batch.
+///
+/// Before a downloaded batch is processed, the root of its last block can be requested from a
+/// second peer. Since processing verifies that the blocks of a batch form a chain, agreement on
+/// the last root is enough to trust the whole batch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrossCheck {
+    /// The batch has not been cross-checked.
+    Unchecked,
+    /// The cross-check has been requested from the given peer.
+    Requested(PeerId, Id),
+    /// The batch was cross-checked, or the cross-check was skipped.
+    Done,
+}
+
 impl<T: EthSpec> BatchState<T> {
     /// Helper function for poisoning a state.
     pub fn poison(&mut self) -> BatchState<T> {
@@ -149,6 +175,9 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
             failed_download_attempts: Vec::new(),
             non_faulty_processing_attempts: 0,
             state: BatchState::AwaitingDownload,
+            download_started: None,
+            cross_check: CrossCheck::Unchecked,
+            cross_check_disagreements: Vec::new(),
             marker: std::marker::PhantomData,
         }
     }
@@ -230,6 +259,45 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
         &self.failed_processing_attempts
     }
 
+    /// Returns the time taken by the last completed download, if the batch is awaiting processing.
+    pub fn download_duration(&self) -> Option<Duration> {
+        match self.state {
+            BatchState::AwaitingProcessing(..) => {
+                self.download_started.map(|started| started.elapsed())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn cross_check(&self) -> CrossCheck {
+        self.cross_check
+    }
+
+    pub fn set_cross_check(&mut self, cross_check: CrossCheck) {
+        self.cross_check = cross_check;
+    }
+
+    /// Returns the slot and root of the last downloaded block, if the batch is awaiting
+    /// processing and non-empty.
+    pub fn last_block(&self) -> Option<(Slot, Hash256)> {
+        match &self.state {
+            BatchState::AwaitingProcessing(_, blocks) => blocks
+                .last()
+                .map(|block| (block.slot(), block.canonical_root())),
+            _ => None,
+        }
+    }
+
+    /// Returns the peers that disagreed with the current download when cross-checking it.
+    pub fn cross_check_disagreements(&self) -> &[PeerId] {
+        &self.cross_check_disagreements
+    }
+
+    /// Verifies if an incoming cross-check response belongs to this batch.
+    pub fn is_expecting_cross_check(&self, peer_id: &PeerId, request_id: &Id) -> bool {
+        self.cross_check == CrossCheck::Requested(*peer_id, *request_id)
+    }
+
     /// Adds a block to a downloading batch.
     pub fn add_block(&mut self, block: Arc<SignedBeaconBlock<T>>) -> Result<(), WrongState> {
         match self.state.poison() {
@@ -350,6 +418,9 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
         match self.state.poison() {
             BatchState::AwaitingDownload => {
                 self.state = BatchState::Downloading(peer, Vec::new(), request_id);
+                self.download_started = Some(Instant::now());
+                self.cross_check = CrossCheck::Unchecked;
+                self.cross_check_disagreements.clear();
                 Ok(())
             }
             BatchState::Poisoned => unreachable!("Poisoned batch"),
@@ -363,6 +434,49 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
         }
     }
 
+    /// Registers that `checker` disagreed with the last block of the downloaded batch.
+    ///
+    /// A single disagreement doesn't tell which of the two peers is at fault, so the batch keeps
+    /// its blocks and is cross-checked again by a third peer. Only once
+    /// `MAX_CROSS_CHECK_DISAGREEMENTS` peers have disagreed are the blocks dropped and the
+    /// attempt registered as a failed download of the peer that served them.
+    #[must_use = "Batch may have failed"]
+    pub fn cross_check_failed(
+        &mut self,
+        checker: PeerId,
+    ) -> Result<BatchOperationOutcome, WrongState> {
+        match self.state.poison() {
+            BatchState::AwaitingProcessing(peer, blocks) => {
+                self.cross_check = CrossCheck::Unchecked;
+                if !self.cross_check_disagreements.contains(&checker) {
+                    self.cross_check_disagreements.push(checker);
+                }
+                if self.cross_check_disagreements.len() < MAX_CROSS_CHECK_DISAGREEMENTS {
+                    self.state = BatchState::AwaitingProcessing(peer, blocks);
+                    return Ok(self.outcome());
+                }
+
+                self.failed_download_attempts.push(peer);
+                self.state = if self.failed_download_attempts.len()
+                    >= B::max_batch_download_attempts() as usize
+                {
+                    BatchState::Failed
+                } else {
+                    BatchState::AwaitingDownload
+                };
+                Ok(self.outcome())
+            }
+            BatchState::Poisoned => unreachable!("Poisoned batch"),
+            other => {
+                self.state = other;
+                Err(WrongState(format!(
+                    "Cross-check failed for batch in wrong state {:?}",
+                    self.state
+                )))
+            }
+        }
+    }
+
     pub fn start_processing(&mut self) -> Result<Vec<Arc<SignedBeaconBlock<T>>>, WrongState> {
         match self.state.poison() {
             BatchState::AwaitingProcessing(peer, blocks) => {
@@ -421,6 +535,31 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
         }
     }
 
+    /// Returns a batch being processed to the `AwaitingProcessing` state with its `blocks`,
+    /// without registering a processing attempt.
+    ///
+    /// This is used when the batch was processed as part of a larger segment which failed, so
+    /// that it can be processed again on its own.
+    pub fn processing_deferred(
+        &mut self,
+        blocks: Vec<Arc<SignedBeaconBlock<T>>>,
+    ) -> Result<(), WrongState> {
+        match self.state.poison() {
+            BatchState::Processing(attempt) => {
+                self.state = BatchState::AwaitingProcessing(attempt.peer_id, blocks);
+                Ok(())
+            }
+            BatchState::Poisoned => unreachable!("Poisoned batch"),
+            other => {
+                self.state = other;
+                Err(WrongState(format!(
+                    "Deferring processing for batch in wrong state: {:?}",
+                    self.state
+                )))
+            }
+        }
+    }
+
     #[must_use = "Batch may have failed"]
     pub fn validation_failed(&mut self) -> Result<BatchOperationOutcome, WrongState> {
         match self.state.poison() {
@@ -1,4 +1,4 @@
-use super::batch::{BatchInfo, BatchProcessingResult, BatchState};
+use super::batch::{BatchInfo, BatchProcessingResult, BatchState, CrossCheck};
 use crate::beacon_processor::{ChainSegmentProcessId, WorkEvent as BeaconWorkEvent};
 use crate::sync::{
     manager::Id, network_context::SyncNetworkContext, BatchOperationOutcome, BatchProcessResult,
@@ -8,6 +8,7 @@ use fnv::FnvHashMap;
 use lighthouse_network::{PeerAction, PeerId};
 use rand::seq::SliceRandom;
 use slog::{crit, debug, o, warn};
+use std::cmp::Ordering;
 use std::collections::{btree_map::Entry, BTreeMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
@@ -202,8 +203,20 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             }
         }
 
+        // batches being cross-checked by this peer are processed without the cross-check
+        let mut cross_checks_dropped = false;
+        for batch in self.batches.values_mut() {
+            if matches!(batch.cross_check(), CrossCheck::Requested(checker, _) if checker == *peer_id)
+            {
+                batch.set_cross_check(CrossCheck::Done);
+                cross_checks_dropped = true;
+            }
+        }
+
         if self.peers.is_empty() {
             Err(RemoveChain::EmptyPeerPool)
+        } else if cross_checks_dropped {
+            self.process_completed_batches(network)
         } else {
             Ok(KeepChain)
         }
@@ -259,11 +272,15 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
 
             match batch.download_completed() {
                 Ok(received) => {
+                    if let Some(duration) = batch.download_duration() {
+                        network.record_download(*peer_id, received, duration);
+                    }
                     let awaiting_batches = batch_id
                         .saturating_sub(self.optimistic_start.unwrap_or(self.processing_target))
                         / EPOCHS_PER_BATCH;
                     debug!(self.log, "Completed batch received"; "epoch" => batch_id, "blocks" => received, "awaiting_batches" => awaiting_batches);
 
+                    self.cross_check_batch(network, batch_id)?;
                     // pre-emptively request more blocks from peers whilst we process current blocks,
                     self.request_batches(network)?;
                     self.process_completed_batches(network)
@@ -286,6 +303,135 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
         }
     }
 
+    /// Requests the last block of a downloaded batch from a peer other than the one that served
+    /// it, if cross-checking is enabled. Batches that can't be cross-checked are marked as done so
+    /// that their processing is not delayed.
+    fn cross_check_batch(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+        batch_id: BatchId,
+    ) -> ProcessingResult {
+        let batch = match self.batches.get_mut(&batch_id) {
+            Some(batch) if batch.cross_check() == CrossCheck::Unchecked => batch,
+            _ => return Ok(KeepChain),
+        };
+
+        let (slot, downloader) = match (batch.last_block(), batch.current_peer()) {
+            (Some((slot, _root)), Some(peer)) if network.range_sync_cross_check() => (slot, *peer),
+            _ => {
+                // cross-checking is disabled or the batch is empty
+                batch.set_cross_check(CrossCheck::Done);
+                return Ok(KeepChain);
+            }
+        };
+
+        // never ask a peer that already disagreed with this download, so that a single peer
+        // can't keep rejecting it. Prefer peers that have not failed this batch, are less busy and
+        // are faster
+        let failed_peers = batch.failed_peers();
+        let disagreeing_peers = batch.cross_check_disagreements();
+        let checker = self
+            .peers
+            .iter()
+            .filter(|(peer, _)| **peer != downloader && !disagreeing_peers.contains(peer))
+            .map(|(peer, requests)| (failed_peers.contains(peer), requests.len(), *peer))
+            .min_by(|a, b| {
+                (a.0, a.1)
+                    .cmp(&(b.0, b.1))
+                    .then_with(|| compare_throughput(network, &a.2, &b.2))
+            })
+            .map(|(_, _, peer)| peer);
+
+        let cross_check = match checker {
+            Some(checker) => {
+                match network.range_cross_check_request(checker, slot, self.id, batch_id) {
+                    Ok(request_id) => {
+                        debug!(self.log, "Cross-checking batch"; "epoch" => batch_id,
+                            "peer" => %checker, "slot" => slot);
+                        CrossCheck::Requested(checker, request_id)
+                    }
+                    Err(e) => {
+                        warn!(self.log, "Could not send cross-check request";
+                            "batch_id" => batch_id, "error" => e);
+                        CrossCheck::Done
+                    }
+                }
+            }
+            None => {
+                debug!(self.log, "No peer to cross-check batch"; "epoch" => batch_id);
+                CrossCheck::Done
+            }
+        };
+        batch.set_cross_check(cross_check);
+        Ok(KeepChain)
+    }
+
+    /// A response to a cross-check has been received for a batch on this chain.
+    ///
+    /// If the second peer agrees with the last block of the batch it can be processed. Otherwise
+    /// the batch is cross-checked again by a third peer, and only downloaded again if that peer
+    /// disagrees as well.
+    pub fn on_cross_check_response(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+        batch_id: BatchId,
+        peer_id: &PeerId,
+        request_id: Id,
+        beacon_block: Option<Arc<SignedBeaconBlock<T::EthSpec>>>,
+    ) -> ProcessingResult {
+        let batch = match self.batches.get_mut(&batch_id) {
+            Some(batch) if batch.is_expecting_cross_check(peer_id, &request_id) => batch,
+            _ => return Ok(KeepChain),
+        };
+
+        let expected_root = batch.last_block().map(|(_slot, root)| root);
+        let received_root = beacon_block.map(|block| block.canonical_root());
+        if received_root.is_some() && received_root == expected_root {
+            debug!(self.log, "Batch cross-check succeeded"; "epoch" => batch_id, "peer" => %peer_id);
+            batch.set_cross_check(CrossCheck::Done);
+            return self.process_completed_batches(network);
+        }
+
+        // Either the stream ended without the expected block or a different block was sent. We
+        // can't know which peer is wrong, so no peer is penalised.
+        warn!(self.log, "Batch cross-check failed"; "epoch" => batch_id, "peer" => %peer_id,
+            "expected_root" => ?expected_root, "received_root" => ?received_root);
+        if let BatchOperationOutcome::Failed { blacklist } = batch.cross_check_failed(*peer_id)? {
+            return Err(RemoveChain::ChainFailed {
+                blacklist,
+                failing_batch: batch_id,
+            });
+        }
+
+        if let BatchState::AwaitingDownload = batch.state() {
+            // enough peers disagreed with the download, request it again
+            self.retry_batch_download(network, batch_id)
+        } else {
+            // settle the disagreement with another peer
+            self.cross_check_batch(network, batch_id)?;
+            self.process_completed_batches(network)
+        }
+    }
+
+    /// A cross-check request has failed. The batch is processed without it.
+    pub fn inject_cross_check_error(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+        batch_id: BatchId,
+        peer_id: &PeerId,
+        request_id: Id,
+    ) -> ProcessingResult {
+        match self.batches.get_mut(&batch_id) {
+            Some(batch) if batch.is_expecting_cross_check(peer_id, &request_id) => {
+                debug!(self.log, "Batch cross-check failed. RPC Error"; "epoch" => batch_id,
+                    "peer" => %peer_id);
+                batch.set_cross_check(CrossCheck::Done);
+                self.process_completed_batches(network)
+            }
+            _ => Ok(KeepChain),
+        }
+    }
+
     /// Processes the batch with the given id.
     /// The batch must exist and be ready for processing
     fn process_batch(
@@ -298,6 +444,14 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             return Ok(KeepChain);
         }
 
+        // wait for the batch to be cross-checked, if required
+        self.cross_check_batch(network, batch_id)?;
+        if let Some(CrossCheck::Requested(..)) =
+            self.batches.get(&batch_id).map(|b| b.cross_check())
+        {
+            return Ok(KeepChain);
+        }
+
         let beacon_processor_send = match network.processor_channel_if_enabled() {
             Some(channel) => channel,
             None => return Ok(KeepChain),
@@ -876,8 +1030,13 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                 .iter()
                 .map(|(peer, requests)| (failed_peers.contains(peer), requests.len(), *peer))
                 .collect::<Vec<_>>();
-            // Sort peers prioritizing unrelated peers with less active requests.
-            priorized_peers.sort_unstable();
+            // Sort peers prioritizing unrelated peers with less active requests, then faster
+            // peers.
+            priorized_peers.sort_unstable_by(|a, b| {
+                (a.0, a.1)
+                    .cmp(&(b.0, b.1))
+                    .then_with(|| compare_throughput(network, &a.2, &b.2))
+            });
             priorized_peers.get(0).map(|&(_, _, peer)| peer)
         };
 
@@ -996,6 +1155,9 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             })
             .collect::<Vec<_>>();
         idle_peers.shuffle(&mut rng);
+        // peers are popped from the back, so the fastest peers are requested first. Ties keep
+        // their random order.
+        idle_peers.sort_by(|a, b| compare_throughput(network, b, a));
 
         // check if we have the batch for our optimistic start. If not, request it first.
         // We wait for this batch before requesting any other batches.
@@ -1070,6 +1232,19 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
     }
 }
 
+/// Orders peers by their observed download throughput, fastest first. Peers that haven't served a
+/// batch yet are considered the fastest so that their throughput gets measured.
+pub(super) fn compare_throughput<T: BeaconChainTypes>(
+    network: &SyncNetworkContext<T>,
+    a: &PeerId,
+    b: &PeerId,
+) -> Ordering {
+    let throughput = |peer| network.peer_throughput(peer).unwrap_or(f64::INFINITY);
+    throughput(b)
+        .partial_cmp(&throughput(a))
+        .unwrap_or(Ordering::Equal)
+}
+
 impl<T: BeaconChainTypes> slog::KV for &mut SyncingChain<T> {
     fn serialize(
         &self,
@@ -225,6 +225,65 @@ where
         }
     }
 
+    /// A response to a batch cross-check has been received from the network.
+    pub fn cross_check_response(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+        peer_id: PeerId,
+        chain_id: ChainId,
+        batch_id: BatchId,
+        request_id: Id,
+        beacon_block: Option<Arc<SignedBeaconBlock<T::EthSpec>>>,
+    ) {
+        match self.chains.call_by_id(chain_id, |chain| {
+            chain.on_cross_check_response(network, batch_id, &peer_id, request_id, beacon_block)
+        }) {
+            Ok((removed_chain, sync_type)) => {
+                if let Some((removed_chain, remove_reason)) = removed_chain {
+                    self.on_chain_removed(
+                        removed_chain,
+                        sync_type,
+                        remove_reason,
+                        network,
+                        "cross-check response",
+                    );
+                }
+            }
+            Err(_) => {
+                trace!(self.log, "Cross-check response for removed chain"; "chain" => chain_id)
+            }
+        }
+    }
+
+    /// A batch cross-check request has failed. The batch is processed without it.
+    pub fn cross_check_error(
+        &mut self,
+        network: &mut SyncNetworkContext<T>,
+        peer_id: PeerId,
+        batch_id: BatchId,
+        chain_id: ChainId,
+        request_id: Id,
+    ) {
+        match self.chains.call_by_id(chain_id, |chain| {
+            chain.inject_cross_check_error(network, batch_id, &peer_id, request_id)
+        }) {
+            Ok((removed_chain, sync_type)) => {
+                if let Some((removed_chain, remove_reason)) = removed_chain {
+                    self.on_chain_removed(
+                        removed_chain,
+                        sync_type,
+                        remove_reason,
+                        network,
+                        "cross-check error",
+                    );
+                }
+            }
+            Err(_) => {
+                trace!(self.log, "Cross-check error for removed chain"; "chain" => chain_id)
+            }
+        }
+    }
+
     pub fn handle_block_process_result(
         &mut self,
         network: &mut SyncNetworkContext<T>,
@@ -374,6 +433,7 @@ mod tests {
     use crate::service::RequestId;
     use crate::NetworkMessage;
 
+    use super::super::chain::compare_throughput;
     use super::*;
     use crate::beacon_processor::WorkEvent as BeaconWorkEvent;
     use beacon_chain::builder::Witness;
@@ -389,8 +449,9 @@ mod tests {
     use slot_clock::SystemTimeSlotClock;
     use std::collections::HashSet;
     use std::sync::Arc;
+    use std::time::Duration;
     use store::MemoryStore;
-    use types::{Hash256, MinimalEthSpec as E};
+    use types::{BeaconBlock, Hash256, MinimalEthSpec as E, Signature};
 
     #[derive(Debug)]
     struct FakeStorage {
@@ -566,6 +627,27 @@ mod tests {
             (peer_id, local_info, remote_info)
         }
 
+        /// Reads a range sync request from the network receiver channel, checking the kind of
+        /// request and the peer it was sent to.
+        #[track_caller]
+        fn grab_range_request(&mut self, expected_peer: &PeerId, cross_check: bool) -> (Id, u64) {
+            match self.grab_request(expected_peer) {
+                (RequestId::Sync(crate::sync::manager::RequestId::RangeSync { id }), request)
+                    if !cross_check =>
+                {
+                    (id, request.start_slot)
+                }
+                (
+                    RequestId::Sync(crate::sync::manager::RequestId::RangeCrossCheck { id }),
+                    request,
+                ) if cross_check => {
+                    assert_eq!(request.count, 1);
+                    (id, request.start_slot)
+                }
+                other => panic!("unexpected request {:?}", other),
+            }
+        }
+
         #[track_caller]
         fn expect_empty_processor(&mut self) {
             match self.beacon_processor_rx.try_recv() {
@@ -591,6 +673,13 @@ mod tests {
     }
 
     fn range(log_enabled: bool) -> (TestRig, RangeSync<TestBeaconChainType, FakeStorage>) {
+        range_with_cross_check(log_enabled, false)
+    }
+
+    fn range_with_cross_check(
+        log_enabled: bool,
+        cross_check: bool,
+    ) -> (TestRig, RangeSync<TestBeaconChainType, FakeStorage>) {
         let chain = Arc::new(FakeStorage::default());
         let log = build_log(slog::Level::Trace, log_enabled);
         let (beacon_processor_tx, beacon_processor_rx) = mpsc::channel(10);
@@ -604,6 +693,7 @@ mod tests {
             network_tx,
             globals.clone(),
             beacon_processor_tx,
+            cross_check,
             log.new(o!("component" => "network_context")),
         );
         let test_rig = TestRig {
@@ -723,4 +813,156 @@ mod tests {
         rig.expect_chain_segment();
         rig.expect_chain_segment();
     }
+
+    fn block_at_slot(slot: u64, state_root: u8) -> Arc<SignedBeaconBlock<E>> {
+        let mut block = BeaconBlock::empty(&E::default_spec());
+        *block.slot_mut() = slot.into();
+        *block.state_root_mut() = Hash256::repeat_byte(state_root);
+        Arc::new(SignedBeaconBlock::from_block(block, Signature::empty()))
+    }
+
+    /// Downloads a single block batch from one peer of a head chain with two peers, returning
+    /// the id of the cross-check request sent to the other peer.
+    fn download_cross_checked_batch(
+        rig: &mut TestRig,
+        range: &mut RangeSync<TestBeaconChainType, FakeStorage>,
+    ) -> (PeerId, PeerId, Id, Arc<SignedBeaconBlock<E>>) {
+        let (peer1, local_info, head_info) = rig.head_peer();
+        range.add_peer(&mut rig.cx, local_info.clone(), peer1, head_info.clone());
+        let (id, start_slot) = rig.grab_range_request(&peer1, false);
+        let peer2 = PeerId::random();
+        range.add_peer(&mut rig.cx, local_info, peer2, head_info);
+
+        let (chain_id, batch_id) = rig.cx.range_sync_response(id, true).unwrap();
+        let block = block_at_slot(start_slot, 0);
+        range.blocks_by_range_response(
+            &mut rig.cx,
+            peer1,
+            chain_id,
+            batch_id,
+            id,
+            Some(block.clone()),
+        );
+        range.blocks_by_range_response(&mut rig.cx, peer1, chain_id, batch_id, id, None);
+
+        // the batch is not processed until the second peer has confirmed it
+        let (check_id, check_slot) = rig.grab_range_request(&peer2, true);
+        assert_eq!(check_slot, start_slot);
+        rig.expect_empty_processor();
+        (peer1, peer2, check_id, block)
+    }
+
+    #[test]
+    fn cross_checked_batch_is_processed() {
+        let (mut rig, mut range) = range_with_cross_check(false, true);
+        let (_peer1, peer2, check_id, block) = download_cross_checked_batch(&mut rig, &mut range);
+
+        let (chain_id, batch_id) = rig.cx.range_cross_check_response(check_id, false).unwrap();
+        range.cross_check_response(
+            &mut rig.cx,
+            peer2,
+            chain_id,
+            batch_id,
+            check_id,
+            Some(block),
+        );
+        rig.expect_chain_segment();
+    }
+
+    /// Sends a cross-check response from `peer` with `block`, as the last block of the batch.
+    fn respond_to_cross_check(
+        rig: &mut TestRig,
+        range: &mut RangeSync<TestBeaconChainType, FakeStorage>,
+        peer: PeerId,
+        check_id: Id,
+        block: Arc<SignedBeaconBlock<E>>,
+    ) {
+        let (chain_id, batch_id) = rig.cx.range_cross_check_response(check_id, false).unwrap();
+        range.cross_check_response(&mut rig.cx, peer, chain_id, batch_id, check_id, Some(block));
+    }
+
+    #[test]
+    fn cross_check_mismatch_is_checked_by_third_peer() {
+        let (mut rig, mut range) = range_with_cross_check(false, true);
+        let (_peer1, peer2, check_id, block) = download_cross_checked_batch(&mut rig, &mut range);
+        let (peer3, local_info, head_info) = rig.head_peer();
+        range.add_peer(&mut rig.cx, local_info, peer3, head_info);
+
+        // the second peer always disagrees with the batch
+        let other_block = block_at_slot(block.slot().as_u64(), 1);
+        respond_to_cross_check(&mut rig, &mut range, peer2, check_id, other_block);
+
+        // the batch is kept, and cross-checked by the third peer instead of the second one again
+        rig.expect_empty_processor();
+        let (check_id, check_slot) = rig.grab_range_request(&peer3, true);
+        assert_eq!(check_slot, block.slot().as_u64());
+
+        // the third peer agrees, so the lying peer didn't cost the batch a download attempt
+        respond_to_cross_check(&mut rig, &mut range, peer3, check_id, block);
+        rig.expect_chain_segment();
+    }
+
+    #[test]
+    fn cross_check_mismatch_without_third_peer_is_processed() {
+        let (mut rig, mut range) = range_with_cross_check(false, true);
+        let (_peer1, peer2, check_id, block) = download_cross_checked_batch(&mut rig, &mut range);
+
+        let other_block = block_at_slot(block.slot().as_u64(), 1);
+        respond_to_cross_check(&mut rig, &mut range, peer2, check_id, other_block);
+
+        // there is no other peer to settle the disagreement, so the batch is verified by
+        // processing it
+        rig.expect_chain_segment();
+        assert!(rig.network_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn cross_check_mismatches_redownload_batch() {
+        let (mut rig, mut range) = range_with_cross_check(false, true);
+        let (peer1, peer2, check_id, block) = download_cross_checked_batch(&mut rig, &mut range);
+        let (peer3, local_info, head_info) = rig.head_peer();
+        range.add_peer(&mut rig.cx, local_info, peer3, head_info);
+
+        let other_block = block_at_slot(block.slot().as_u64(), 1);
+        respond_to_cross_check(&mut rig, &mut range, peer2, check_id, other_block.clone());
+        let (check_id, _) = rig.grab_range_request(&peer3, true);
+        respond_to_cross_check(&mut rig, &mut range, peer3, check_id, other_block);
+
+        // both other peers disagree, so the batch is requested again from one of them
+        rig.expect_empty_processor();
+        match rig.network_rx.try_recv() {
+            Ok(NetworkMessage::SendRequest {
+                peer_id,
+                request: Request::BlocksByRange(_),
+                request_id: RequestId::Sync(crate::sync::manager::RequestId::RangeSync { .. }),
+            }) => assert_ne!(peer_id, peer1),
+            other => panic!("expected a batch request, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn peers_are_ordered_by_throughput() {
+        let (mut rig, _range) = range(false);
+        let slow = PeerId::random();
+        let fast = PeerId::random();
+        let unknown = PeerId::random();
+        rig.cx.record_download(slow, 10, Duration::from_secs(2));
+        rig.cx.record_download(fast, 10, Duration::from_secs(1));
+        // empty responses don't change the throughput
+        rig.cx.record_download(fast, 0, Duration::from_secs(100));
+
+        let sorted = |cx: &SyncNetworkContext<TestBeaconChainType>| {
+            let mut peers = vec![slow, fast, unknown];
+            peers.sort_by(|a, b| compare_throughput(cx, a, b));
+            peers
+        };
+        // peers without a measured throughput come first so that they get measured
+        assert_eq!(sorted(&rig.cx), vec![unknown, fast, slow]);
+
+        // a single fast download is enough to overtake a peer, but is smoothed out
+        rig.cx.record_download(slow, 100, Duration::from_secs(1));
+        let throughput = rig.cx.peer_throughput(&slow).unwrap();
+        assert!((throughput - (0.3 * 100.0 + 0.7 * 5.0)).abs() < 1e-9);
+        assert_eq!(sorted(&rig.cx), vec![unknown, slow, fast]);
+    }
 }
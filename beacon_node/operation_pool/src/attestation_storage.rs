@@ -1,18 +1,26 @@
+use crate::metrics;
 use crate::AttestationStats;
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{hash_map::Entry, HashMap};
 use types::{
     AggregateSignature, Attestation, AttestationData, BeaconState, BitList, Checkpoint, Epoch,
     EthSpec, Hash256, Slot,
 };
 
+/// The maximum number of unaggregated votes retained to top up aggregates.
+///
+/// Once it is reached, unaggregated attestations are only aggregated into the aggregates already
+/// in the pool, and are not retained until some votes are pruned.
+pub const MAX_UNAGGREGATED_VOTES: usize = 65_536;
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct CheckpointKey {
     pub source: Checkpoint,
     pub target_epoch: Epoch,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct CompactAttestationData {
     pub slot: Slot,
     pub index: u64,
@@ -20,13 +28,31 @@ pub struct CompactAttestationData {
     pub target_root: Hash256,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct CompactIndexedAttestation<T: EthSpec> {
     pub attesting_indices: Vec<u64>,
     pub aggregation_bits: BitList<T::MaxValidatorsPerCommittee>,
     pub signature: AggregateSignature,
 }
 
+/// A single validator's vote, stored without its own copy of the aggregation bitfield.
+#[derive(Debug, PartialEq)]
+pub struct UnaggregatedVote {
+    pub committee_position: usize,
+    pub validator_index: u64,
+    pub signature: AggregateSignature,
+}
+
+/// All of the unaggregated votes received for a single `AttestationData`.
+///
+/// Each validator may only cast one vote per `AttestationData`, so the votes are always
+/// mutually disjoint and `aggregation_bits` is simply the union of their committee positions.
+#[derive(Debug, PartialEq)]
+pub struct UnaggregatedVotes<T: EthSpec> {
+    pub aggregation_bits: BitList<T::MaxValidatorsPerCommittee>,
+    pub votes: Vec<UnaggregatedVote>,
+}
+
 #[derive(Debug)]
 pub struct SplitAttestation<T: EthSpec> {
     pub checkpoint: CheckpointKey,
@@ -41,14 +67,32 @@ pub struct AttestationRef<'a, T: EthSpec> {
     pub indexed: &'a CompactIndexedAttestation<T>,
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default)]
 pub struct AttestationMap<T: EthSpec> {
     checkpoint_map: HashMap<CheckpointKey, AttestationDataMap<T>>,
+    /// The number of unaggregated votes retained across all of `checkpoint_map`.
+    num_unaggregated_votes: usize,
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default)]
 pub struct AttestationDataMap<T: EthSpec> {
     attestations: HashMap<CompactAttestationData, Vec<CompactIndexedAttestation<T>>>,
+    unaggregated: HashMap<CompactAttestationData, UnaggregatedVotes<T>>,
+}
+
+/// Only the aggregates are compared, since the unaggregated votes retained to top them up are not
+/// persisted to disk.
+impl<T: EthSpec> PartialEq for AttestationMap<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.checkpoint_map == other.checkpoint_map
+    }
+}
+
+/// As for `AttestationMap`, only the aggregates are compared.
+impl<T: EthSpec> PartialEq for AttestationDataMap<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.attestations == other.attestations
+    }
 }
 
 impl<T: EthSpec> SplitAttestation<T> {
@@ -141,6 +185,69 @@ impl<T: EthSpec> CompactIndexedAttestation<T> {
         self.aggregation_bits = self.aggregation_bits.union(&other.aggregation_bits);
         self.signature.add_assign_aggregate(&other.signature);
     }
+
+    /// Return `true` if every signer of `self` is also a signer of `other`.
+    pub fn signers_subset_of(&self, other: &Self) -> bool {
+        self.aggregation_bits
+            .difference(&other.aggregation_bits)
+            .is_zero()
+    }
+
+    /// Add a single unaggregated vote to this aggregate.
+    ///
+    /// The caller must ensure that the vote's signer is not already included.
+    pub fn aggregate_vote(&mut self, vote: &UnaggregatedVote) {
+        if self
+            .aggregation_bits
+            .set(vote.committee_position, true)
+            .is_err()
+        {
+            return;
+        }
+        if let Err(i) = self.attesting_indices.binary_search(&vote.validator_index) {
+            self.attesting_indices.insert(i, vote.validator_index);
+        }
+        self.signature.add_assign_aggregate(&vote.signature);
+    }
+
+    /// Return the single vote contained in this attestation, if it is unaggregated.
+    fn as_unaggregated_vote(&self) -> Option<UnaggregatedVote> {
+        if self.attesting_indices.len() != 1 || self.aggregation_bits.num_set_bits() != 1 {
+            return None;
+        }
+        let committee_position = self.aggregation_bits.iter().position(|bit| bit)?;
+        Some(UnaggregatedVote {
+            committee_position,
+            validator_index: self.attesting_indices[0],
+            signature: self.signature.clone(),
+        })
+    }
+}
+
+impl<T: EthSpec> UnaggregatedVotes<T> {
+    fn new(committee_len: usize) -> Option<Self> {
+        Some(Self {
+            aggregation_bits: BitList::with_capacity(committee_len).ok()?,
+            votes: vec![],
+        })
+    }
+
+    /// Record `vote`, returning `false` if a vote from the same committee position is known.
+    fn insert(&mut self, vote: UnaggregatedVote) -> bool {
+        if self
+            .aggregation_bits
+            .get(vote.committee_position)
+            .unwrap_or(true)
+            || self
+                .aggregation_bits
+                .set(vote.committee_position, true)
+                .is_err()
+        {
+            return false;
+        }
+        self.votes.push(vote);
+        true
+    }
 }
 
 impl<T: EthSpec> AttestationMap<T> {
@@ -155,26 +262,18 @@ impl<T: EthSpec> AttestationMap<T> {
             .checkpoint_map
             .entry(checkpoint)
             .or_insert_with(AttestationDataMap::default);
-        let attestations = attestation_map
-            .attestations
-            .entry(data)
-            .or_insert_with(Vec::new);
 
-        // Greedily aggregate the attestation with all existing attestations.
-        // NOTE: this is sub-optimal and in future we will remove this in favour of max-clique
-        // aggregation.
-        let mut aggregated = false;
-        for existing_attestation in attestations.iter_mut() {
-            if existing_attestation.signers_disjoint_from(&indexed) {
-                existing_attestation.aggregate(&indexed);
-                aggregated = true;
-            } else if *existing_attestation == indexed {
-                aggregated = true;
+        match indexed.as_unaggregated_vote() {
+            Some(vote) if self.num_unaggregated_votes < MAX_UNAGGREGATED_VOTES => {
+                if attestation_map.insert_unaggregated(data, indexed, vote) {
+                    self.num_unaggregated_votes += 1;
+                }
             }
-        }
-
-        if !aggregated {
-            attestations.push(indexed);
+            Some(_) => {
+                metrics::inc_counter(&metrics::UNAGGREGATED_VOTES_DROPPED);
+                attestation_map.insert_aggregate(data, indexed);
+            }
+            None => attestation_map.insert_aggregate(data, indexed),
         }
     }
 
@@ -196,10 +295,32 @@ impl<T: EthSpec> AttestationMap<T> {
             .flat_map(|(checkpoint_key, attestation_map)| attestation_map.iter(checkpoint_key))
     }
 
+    /// Returns a copy of the attestations matching `checkpoint_keys`, in which compatible
+    /// aggregates of the same data have been aggregated, so that they are packed into a block as
+    /// a single attestation.
+    pub fn aggregate_compatible(&self, checkpoint_keys: &[CheckpointKey]) -> Self {
+        let checkpoint_map = checkpoint_keys
+            .iter()
+            .filter_map(|checkpoint_key| {
+                let attestation_map = self.checkpoint_map.get(checkpoint_key)?;
+                Some((*checkpoint_key, attestation_map.aggregate_compatible()))
+            })
+            .collect();
+        Self {
+            checkpoint_map,
+            num_unaggregated_votes: 0,
+        }
+    }
+
     /// Prune attestations that are from before the previous epoch.
     pub fn prune(&mut self, current_epoch: Epoch) {
         self.checkpoint_map
             .retain(|checkpoint_key, _| current_epoch <= checkpoint_key.target_epoch + 1);
+        self.num_unaggregated_votes = self
+            .checkpoint_map
+            .values()
+            .map(AttestationDataMap::num_unaggregated_votes)
+            .sum();
     }
 
     /// Statistics about all attestations stored in the map.
@@ -212,12 +333,138 @@ impl<T: EthSpec> AttestationMap<T> {
                 acc.num_attestation_data += new.num_attestation_data;
                 acc.max_aggregates_per_data =
                     std::cmp::max(acc.max_aggregates_per_data, new.max_aggregates_per_data);
+                acc.num_unaggregated_votes += new.num_unaggregated_votes;
                 acc
             })
     }
 }
 
 impl<T: EthSpec> AttestationDataMap<T> {
+    /// Insert an aggregate attestation.
+    ///
+    /// The aggregate is greedily aggregated with all existing aggregates it is disjoint from. If
+    /// it can't be aggregated with any of them it is stored separately, after first being
+    /// topped up with all of the unaggregated votes it is missing.
+    fn insert_aggregate(
+        &mut self,
+        data: CompactAttestationData,
+        mut indexed: CompactIndexedAttestation<T>,
+    ) {
+        let attestations = self.attestations.entry(data).or_insert_with(Vec::new);
+
+        // Greedily aggregate the attestation with all existing attestations.
+        // NOTE: this is sub-optimal and in future we will remove this in favour of max-clique
+        // aggregation.
+        let mut aggregated = false;
+        for existing_attestation in attestations.iter_mut() {
+            if existing_attestation.signers_disjoint_from(&indexed) {
+                existing_attestation.aggregate(&indexed);
+                aggregated = true;
+            } else if *existing_attestation == indexed {
+                aggregated = true;
+            }
+        }
+
+        if aggregated {
+            return;
+        }
+
+        if let Some(unaggregated) = self.unaggregated.get(&data) {
+            for vote in &unaggregated.votes {
+                if !indexed
+                    .aggregation_bits
+                    .get(vote.committee_position)
+                    .unwrap_or(true)
+                {
+                    indexed.aggregate_vote(vote);
+                }
+            }
+        }
+
+        // After topping up, the aggregate may add nothing to an existing one (e.g. when it was
+        // built by the naive aggregation pool from votes we have already seen).
+        if attestations
+            .iter()
+            .all(|existing| !indexed.signers_subset_of(existing))
+        {
+            attestations.push(indexed);
+        }
+    }
+
+    /// Insert an unaggregated attestation, adding its vote to every aggregate that lacks it.
+    ///
+    /// The vote is retained so that aggregates which arrive later can be topped up with it.
+    /// Returns `false` if the vote was already known, in which case nothing is inserted.
+    fn insert_unaggregated(
+        &mut self,
+        data: CompactAttestationData,
+        indexed: CompactIndexedAttestation<T>,
+        vote: UnaggregatedVote,
+    ) -> bool {
+        let committee_len = indexed.aggregation_bits.len();
+        let unaggregated = match self.unaggregated.entry(data) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => match UnaggregatedVotes::new(committee_len) {
+                Some(votes) => e.insert(votes),
+                None => return false,
+            },
+        };
+        if !unaggregated.insert(vote) {
+            return false;
+        }
+        let vote = unaggregated.votes.last().expect("vote was just inserted");
+
+        let attestations = self.attestations.entry(data).or_insert_with(Vec::new);
+        if attestations.is_empty() {
+            attestations.push(indexed);
+            return true;
+        }
+        for existing_attestation in attestations.iter_mut() {
+            if existing_attestation.signers_disjoint_from(&indexed) {
+                existing_attestation.aggregate_vote(vote);
+            }
+        }
+        true
+    }
+
+    fn num_unaggregated_votes(&self) -> usize {
+        self.unaggregated
+            .values()
+            .map(|unaggregated| unaggregated.votes.len())
+            .sum()
+    }
+
+    /// Returns a copy of the aggregates in which each aggregate has been greedily aggregated with
+    /// the other aggregates of the same data which it is disjoint from, largest first.
+    ///
+    /// Unaggregated votes are not copied, since they are already included in the aggregates.
+    fn aggregate_compatible(&self) -> Self {
+        let attestations = self
+            .attestations
+            .iter()
+            .map(|(data, aggregates)| {
+                let mut aggregates = aggregates.iter().collect::<Vec<_>>();
+                aggregates.sort_by_key(|indexed| Reverse(indexed.aggregation_bits.num_set_bits()));
+
+                let mut aggregated: Vec<CompactIndexedAttestation<T>> = vec![];
+                for indexed in aggregates {
+                    match aggregated
+                        .iter_mut()
+                        .find(|existing| existing.signers_disjoint_from(indexed))
+                    {
+                        Some(existing) => existing.aggregate(indexed),
+                        None => aggregated.push(indexed.clone()),
+                    }
+                }
+                (*data, aggregated)
+            })
+            .collect();
+        Self {
+            attestations,
+            unaggregated: HashMap::new(),
+        }
+    }
+
     pub fn iter<'a>(
         &'a self,
         checkpoint_key: &'a CheckpointKey,
@@ -240,6 +487,170 @@ impl<T: EthSpec> AttestationDataMap<T> {
             stats.max_aggregates_per_data =
                 std::cmp::max(stats.max_aggregates_per_data, aggregates.len());
         }
+        stats.num_unaggregated_votes = self.num_unaggregated_votes();
         stats
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    /// An unaggregated attestation from the validator at `committee_position` of a committee of
+    /// `committee_len` validators.
+    fn unaggregated(
+        slot: u64,
+        committee_len: usize,
+        committee_position: usize,
+    ) -> (Attestation<E>, Vec<u64>) {
+        let mut aggregation_bits = BitList::with_capacity(committee_len).unwrap();
+        aggregation_bits.set(committee_position, true).unwrap();
+        let attestation = Attestation {
+            aggregation_bits,
+            data: AttestationData {
+                slot: Slot::new(slot),
+                index: 0,
+                beacon_block_root: Hash256::repeat_byte(1),
+                source: Checkpoint::default(),
+                target: Checkpoint::default(),
+            },
+            signature: AggregateSignature::infinity(),
+        };
+        (attestation, vec![committee_position as u64])
+    }
+
+    fn aggregate(committee_positions: &[usize]) -> CompactIndexedAttestation<E> {
+        let mut aggregation_bits = BitList::with_capacity(8).unwrap();
+        for position in committee_positions {
+            aggregation_bits.set(*position, true).unwrap();
+        }
+        CompactIndexedAttestation {
+            attesting_indices: committee_positions.iter().map(|i| *i as u64).collect(),
+            aggregation_bits,
+            signature: AggregateSignature::infinity(),
+        }
+    }
+
+    #[test]
+    fn aggregate_compatible_merges_disjoint_aggregates() {
+        let data = CompactAttestationData {
+            slot: Slot::new(1),
+            index: 0,
+            beacon_block_root: Hash256::repeat_byte(1),
+            target_root: Hash256::repeat_byte(2),
+        };
+        let checkpoint_key = CheckpointKey {
+            source: Checkpoint::default(),
+            target_epoch: Epoch::new(0),
+        };
+        let mut attestation_map = AttestationMap::<E>::default();
+        attestation_map.checkpoint_map.insert(
+            checkpoint_key,
+            AttestationDataMap {
+                attestations: HashMap::from([(
+                    data,
+                    vec![
+                        aggregate(&[0, 1]),
+                        aggregate(&[1, 2, 3]),
+                        aggregate(&[4, 5]),
+                    ],
+                )]),
+                unaggregated: HashMap::new(),
+            },
+        );
+
+        let aggregated = attestation_map.aggregate_compatible(&[checkpoint_key]);
+        let mut aggregates = aggregated
+            .get_attestations(&checkpoint_key)
+            .map(|att| att.indexed.attesting_indices.clone())
+            .collect::<Vec<_>>();
+        aggregates.sort();
+        assert_eq!(aggregates, vec![vec![0, 1], vec![1, 2, 3, 4, 5]]);
+
+        // Attestations for other checkpoints are not copied.
+        let other_key = CheckpointKey {
+            source: Checkpoint::default(),
+            target_epoch: Epoch::new(1),
+        };
+        assert_eq!(
+            attestation_map
+                .aggregate_compatible(&[other_key])
+                .get_attestations(&other_key)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn unaggregated_votes_are_capped() {
+        let committee_len = 64;
+        let mut attestation_map = AttestationMap::<E>::default();
+        for i in 0..MAX_UNAGGREGATED_VOTES {
+            let (attestation, attesting_indices) =
+                unaggregated((i / committee_len) as u64, committee_len, i % committee_len);
+            attestation_map.insert(attestation, attesting_indices);
+        }
+        let stats = attestation_map.stats();
+        assert_eq!(stats.num_unaggregated_votes, MAX_UNAGGREGATED_VOTES);
+        assert_eq!(
+            stats.num_attestation_data,
+            MAX_UNAGGREGATED_VOTES / committee_len
+        );
+
+        // A further vote is aggregated into the existing aggregate, but isn't retained.
+        let slot = (MAX_UNAGGREGATED_VOTES / committee_len) as u64;
+        let (first, first_indices) = unaggregated(slot, committee_len, 0);
+        let (second, second_indices) = unaggregated(slot, committee_len, 1);
+        attestation_map.insert(first, first_indices);
+        attestation_map.insert(second, second_indices);
+        assert_eq!(
+            attestation_map.stats().num_unaggregated_votes,
+            MAX_UNAGGREGATED_VOTES
+        );
+        let data_key = CompactAttestationData {
+            slot: Slot::new(slot),
+            index: 0,
+            beacon_block_root: Hash256::repeat_byte(1),
+            target_root: Hash256::zero(),
+        };
+        let aggregates = attestation_map
+            .iter()
+            .filter(|att| *att.data == data_key)
+            .map(|att| att.indexed.attesting_indices.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(aggregates, vec![vec![0, 1]]);
+
+        // Pruning frees space for new votes.
+        attestation_map.prune(Epoch::new(2));
+        assert_eq!(attestation_map.stats().num_unaggregated_votes, 0);
+        let (attestation, attesting_indices) = unaggregated(0, committee_len, 0);
+        attestation_map.insert(attestation, attesting_indices);
+        assert_eq!(attestation_map.stats().num_unaggregated_votes, 1);
+    }
+
+    #[test]
+    fn equality_ignores_unaggregated_votes() {
+        let committee_len = 8;
+        let mut attestation_map = AttestationMap::<E>::default();
+        for committee_position in 0..3 {
+            let (attestation, attesting_indices) =
+                unaggregated(0, committee_len, committee_position);
+            attestation_map.insert(attestation, attesting_indices);
+        }
+        assert_eq!(attestation_map.stats().num_unaggregated_votes, 3);
+
+        // Only the aggregates are persisted, so the restored map retains no unaggregated votes.
+        let mut restored = AttestationMap::<E>::default();
+        for att in attestation_map.iter() {
+            restored.insert(
+                att.clone_as_attestation(),
+                att.indexed.attesting_indices.clone(),
+            );
+        }
+        assert_eq!(restored.stats().num_unaggregated_votes, 0);
+        assert_eq!(attestation_map, restored);
+    }
+}
@@ -19,22 +19,25 @@ pub use persistence::{
 };
 pub use reward_cache::RewardCache;
 
-use crate::attestation_storage::{AttestationMap, CheckpointKey};
+use crate::attestation_storage::{AttestationMap, CheckpointKey, MAX_UNAGGREGATED_VOTES};
 use crate::bls_to_execution_changes::BlsToExecutionChanges;
 use crate::sync_aggregate_id::SyncAggregateId;
 use attester_slashing::AttesterSlashingMaxCover;
-use max_cover::maximum_cover;
-use parking_lot::{RwLock, RwLockWriteGuard};
+use max_cover::{maximum_cover, maximum_cover_exact};
+use parking_lot::{Mutex, RwLock, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use serde_derive::{Deserialize, Serialize};
 use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
     get_slashable_indices_modular, verify_exit, VerifySignatures,
 };
 use state_processing::{SigVerifiedOp, VerifyOperation};
-use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use std::time::Duration;
+use strum::{Display, EnumString, EnumVariantNames};
 use types::{
     sync_aggregate::Error as SyncAggregateError, typenum::Unsigned, AbstractExecPayload,
     Attestation, AttestationData, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
@@ -48,6 +51,8 @@ type SyncContributions<T> = RwLock<HashMap<SyncAggregateId, Vec<SyncCommitteeCon
 pub struct OperationPool<T: EthSpec + Default> {
     /// Map from attestation ID (see below) to vectors of attestations.
     attestations: RwLock<AttestationMap<T>>,
+    /// Unaggregated attestations waiting to be inserted into `attestations` as a batch.
+    unaggregated_attestations: Mutex<Vec<(Attestation<T>, Vec<u64>)>>,
     /// Map from sync aggregate ID to the best `SyncCommitteeContribution`s seen for that ID.
     sync_contributions: SyncContributions<T>,
     /// Set of attester slashings, and the fork version they were verified against.
@@ -85,6 +90,32 @@ pub struct AttestationStats {
     pub num_attestation_data: usize,
     /// Maximum number of aggregates for a single `AttestationData`.
     pub max_aggregates_per_data: usize,
+    /// Number of unaggregated votes retained for topping up aggregates.
+    pub num_unaggregated_votes: usize,
+}
+
+/// The algorithm used to select attestations for inclusion in a block.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumVariantNames,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum AttestationPackingAlgorithm {
+    /// Greedy maximum coverage, which is fast but may be sub-optimal.
+    #[default]
+    Greedy,
+    /// Branch-and-bound search for the maximum coverage, starting from the greedy solution and
+    /// limited by a time budget.
+    BranchAndBound,
 }
 
 impl From<SyncAggregateError> for OpPoolError {
@@ -206,6 +237,43 @@ impl<T: EthSpec> OperationPool<T> {
         Ok(())
     }
 
+    /// Buffer an unaggregated attestation until the next call to
+    /// `Self::insert_buffered_unaggregated_attestations`.
+    ///
+    /// Unaggregated attestations arrive far more often than aggregates, so they are inserted in
+    /// batches rather than taking the write lock on `attestations` for each of them. Once
+    /// `MAX_UNAGGREGATED_VOTES` attestations are buffered, further attestations are dropped.
+    ///
+    /// ## Note
+    ///
+    /// This function assumes the given `attestation` is valid.
+    pub fn buffer_unaggregated_attestation(
+        &self,
+        attestation: Attestation<T>,
+        attesting_indices: Vec<u64>,
+    ) {
+        let mut buffered = self.unaggregated_attestations.lock();
+        if buffered.len() < MAX_UNAGGREGATED_VOTES {
+            buffered.push((attestation, attesting_indices));
+        } else {
+            metrics::inc_counter(&metrics::UNAGGREGATED_VOTES_DROPPED);
+        }
+    }
+
+    /// Insert all of the buffered unaggregated attestations into the pool, taking the write lock
+    /// on `attestations` once.
+    pub fn insert_buffered_unaggregated_attestations(&self) {
+        let buffered = std::mem::take(&mut *self.unaggregated_attestations.lock());
+        if buffered.is_empty() {
+            return;
+        }
+
+        let mut attestations = self.attestations.write();
+        for (attestation, attesting_indices) in buffered {
+            attestations.insert(attestation, attesting_indices);
+        }
+    }
+
     /// Total number of attestations in the pool, including attestations for the same data.
     pub fn num_attestations(&self) -> usize {
         self.attestation_stats().num_attestations
@@ -215,6 +283,61 @@ impl<T: EthSpec> OperationPool<T> {
         self.attestations.read().stats()
     }
 
+    /// Count the attestations in the pool by their age in slots, relative to `slot`.
+    ///
+    /// Attestations from later slots are counted as having an age of zero.
+    pub fn attestation_age_distribution(&self, slot: Slot) -> BTreeMap<u64, usize> {
+        let mut distribution = BTreeMap::new();
+        for att in self.attestations.read().iter() {
+            *distribution
+                .entry(slot.saturating_sub(att.data.slot).as_u64())
+                .or_insert(0) += 1;
+        }
+        distribution
+    }
+
+    /// Compute the proposer reward for including each attestation in the pool on its own in a
+    /// block on top of `state`.
+    ///
+    /// Attestations which couldn't be included in such a block are omitted.
+    pub fn attestation_rewards(
+        &self,
+        state: &BeaconState<T>,
+        spec: &ChainSpec,
+    ) -> Result<Vec<u64>, OpPoolError> {
+        let (prev_epoch_key, curr_epoch_key) = CheckpointKey::keys_for_state(state);
+        let all_attestations = self.attestations.read();
+        let total_active_balance = state
+            .get_total_active_balance()
+            .map_err(OpPoolError::GetAttestationsTotalBalanceError)?;
+
+        // Use a fresh reward cache so that the cache used for block production is left alone.
+        let mut reward_cache = RewardCache::default();
+        reward_cache.update(state)?;
+
+        let mut keys = vec![curr_epoch_key];
+        if prev_epoch_key != curr_epoch_key {
+            keys.push(prev_epoch_key);
+        }
+
+        let rewards = keys
+            .iter()
+            .flat_map(|key| {
+                self.get_valid_attestations_for_epoch(
+                    key,
+                    &all_attestations,
+                    state,
+                    &reward_cache,
+                    total_active_balance,
+                    |_| true,
+                    spec,
+                )
+            })
+            .map(|cover| cover.score() as u64)
+            .collect();
+        Ok(rewards)
+    }
+
     /// Return all valid attestations for the given epoch, for use in max cover.
     #[allow(clippy::too_many_arguments)]
     fn get_valid_attestations_for_epoch<'a>(
@@ -251,10 +374,45 @@ impl<T: EthSpec> OperationPool<T> {
         prev_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
         curr_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
         spec: &ChainSpec,
+    ) -> Result<Vec<Attestation<T>>, OpPoolError> {
+        self.get_attestations_with_packing(
+            state,
+            prev_epoch_validity_filter,
+            curr_epoch_validity_filter,
+            AttestationPackingAlgorithm::Greedy,
+            Duration::ZERO,
+            spec,
+        )
+    }
+
+    /// As `Self::get_attestations`, but packing the attestations of each epoch with `algorithm`.
+    ///
+    /// The `time_budget` applies to each epoch's attestations, which are packed in parallel, and
+    /// is ignored by the greedy algorithm.
+    pub fn get_attestations_with_packing(
+        &self,
+        state: &BeaconState<T>,
+        prev_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
+        curr_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
+        algorithm: AttestationPackingAlgorithm,
+        time_budget: Duration,
+        spec: &ChainSpec,
     ) -> Result<Vec<Attestation<T>>, OpPoolError> {
         // Attestations for the current fork, which may be from the current or previous epoch.
         let (prev_epoch_key, curr_epoch_key) = CheckpointKey::keys_for_state(state);
         let all_attestations = self.attestations.read();
+
+        // The exact search is only worthwhile once compatible aggregates have been aggregated,
+        // since it otherwise considers them as separate attestations.
+        let aggregated_attestations;
+        let all_attestations = match algorithm {
+            AttestationPackingAlgorithm::Greedy => &*all_attestations,
+            AttestationPackingAlgorithm::BranchAndBound => {
+                aggregated_attestations =
+                    all_attestations.aggregate_compatible(&[prev_epoch_key, curr_epoch_key]);
+                &aggregated_attestations
+            }
+        };
         let total_active_balance = state
             .get_total_active_balance()
             .map_err(OpPoolError::GetAttestationsTotalBalanceError)?;
@@ -274,7 +432,7 @@ impl<T: EthSpec> OperationPool<T> {
         let prev_epoch_att = self
             .get_valid_attestations_for_epoch(
                 &prev_epoch_key,
-                &*all_attestations,
+                all_attestations,
                 state,
                 &reward_cache,
                 total_active_balance,
@@ -285,7 +443,7 @@ impl<T: EthSpec> OperationPool<T> {
         let curr_epoch_att = self
             .get_valid_attestations_for_epoch(
                 &curr_epoch_key,
-                &*all_attestations,
+                all_attestations,
                 state,
                 &reward_cache,
                 total_active_balance,
@@ -311,15 +469,23 @@ impl<T: EthSpec> OperationPool<T> {
                 if prev_epoch_key == curr_epoch_key {
                     vec![]
                 } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
+                    pack_attestations(
+                        prev_epoch_att,
+                        prev_epoch_limit,
+                        "prev_epoch_attestations",
+                        algorithm,
+                        time_budget,
+                    )
                 }
             },
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(
+                pack_attestations(
                     curr_epoch_att,
                     T::MaxAttestations::to_usize(),
                     "curr_epoch_attestations",
+                    algorithm,
+                    time_budget,
                 )
             },
         );
@@ -706,6 +872,42 @@ impl<T: EthSpec> OperationPool<T> {
     }
 }
 
+/// Select up to `limit` of `attestations` using `algorithm`, recording the reward achieved by the
+/// exact solver relative to greedy packing.
+fn pack_attestations<'a, T: EthSpec>(
+    attestations: impl Iterator<Item = AttMaxCover<'a, T>>,
+    limit: usize,
+    label: &str,
+    algorithm: AttestationPackingAlgorithm,
+    time_budget: Duration,
+) -> Vec<AttMaxCover<'a, T>> {
+    match algorithm {
+        AttestationPackingAlgorithm::Greedy => maximum_cover(attestations, limit, label),
+        AttestationPackingAlgorithm::BranchAndBound => {
+            let cover = maximum_cover_exact(attestations, limit, label, time_budget);
+            metrics::set_int_gauge(
+                &metrics::ATTESTATION_PACKING_REWARD,
+                &[label, "greedy"],
+                cover.greedy_score as i64,
+            );
+            metrics::set_int_gauge(
+                &metrics::ATTESTATION_PACKING_REWARD,
+                &[label, "branch-and-bound"],
+                cover.score as i64,
+            );
+            metrics::set_int_gauge(
+                &metrics::ATTESTATION_PACKING_REWARD_IMPROVEMENT,
+                &[label],
+                cover.score.saturating_sub(cover.greedy_score) as i64,
+            );
+            if !cover.complete {
+                metrics::inc_counter_vec(&metrics::ATTESTATION_PACKING_TIMEOUTS, &[label]);
+            }
+            cover.solution
+        }
+    }
+}
+
 /// Filter up to a maximum number of operations out of an iterator.
 fn filter_limit_operations<'a, T: 'a, V: 'a, I, F, G>(
     operations: I,
@@ -1037,6 +1239,124 @@ mod release_tests {
         assert_eq!(op_pool.num_attestations(), committees.len());
     }
 
+    /// Unaggregated attestations should be added to existing aggregates and used to top up
+    /// aggregates that arrive later.
+    #[test]
+    fn attestation_unaggregated_top_up() {
+        let (harness, ref spec) = attestation_test_state::<MainnetEthSpec>(1);
+
+        let mut state = harness.get_current_state();
+
+        let op_pool = OperationPool::<MainnetEthSpec>::new();
+
+        let slot = state.slot();
+        let num_validators =
+            MainnetEthSpec::slots_per_epoch() as usize * spec.target_committee_size;
+        let attestations = harness.make_attestations(
+            (0..num_validators).collect::<Vec<_>>().as_slice(),
+            &state,
+            Hash256::zero(),
+            SignedBeaconBlockHash::from(Hash256::zero()),
+            slot,
+        );
+        let (atts, _) = attestations.into_iter().next().unwrap();
+        let committee_size = atts.len();
+        let half = committee_size / 2;
+
+        let aggregate = |atts: &[(Attestation<MainnetEthSpec>, SubnetId)]| {
+            let mut agg = atts[0].0.clone();
+            for (att, _) in &atts[1..] {
+                agg.aggregate(att);
+            }
+            agg
+        };
+        let insert = |att: Attestation<MainnetEthSpec>| {
+            let attesting_indices = get_attesting_indices_from_state(&state, &att).unwrap();
+            op_pool.insert_attestation(att, attesting_indices).unwrap();
+        };
+
+        // Gossip votes from the second half of the committee, one of them twice.
+        for (att, _) in &atts[half..] {
+            insert(att.clone());
+        }
+        insert(atts[half].0.clone());
+
+        let stats = op_pool.attestation_stats();
+        assert_eq!(stats.num_unaggregated_votes, committee_size - half);
+        assert_eq!(stats.num_attestations, 1);
+
+        // An aggregate overlapping the votes can't be aggregated with the existing aggregate,
+        // but it should be topped up with the votes it lacks.
+        insert(aggregate(&atts[..=half]));
+        assert_eq!(op_pool.num_attestations(), 2);
+
+        // An aggregate that overlaps both existing aggregates and adds nothing once topped up
+        // shouldn't be stored.
+        insert(aggregate(&[atts[0].clone(), atts[half].clone()]));
+        assert_eq!(op_pool.num_attestations(), 2);
+
+        *state.slot_mut() += spec.min_attestation_inclusion_delay;
+
+        let block_attestations = op_pool
+            .get_attestations(&state, |_| true, |_| true, spec)
+            .expect("should have block attestations");
+        assert_eq!(block_attestations.len(), 1);
+        assert_eq!(
+            block_attestations[0].aggregation_bits.num_set_bits(),
+            committee_size
+        );
+    }
+
+    /// Attestations should be counted by age and have a reward once they can be included.
+    #[test]
+    fn attestation_age_distribution_and_rewards() {
+        let (harness, ref spec) = attestation_test_state::<MainnetEthSpec>(1);
+
+        let mut state = harness.get_current_state();
+
+        let op_pool = OperationPool::<MainnetEthSpec>::new();
+
+        let slot = state.slot();
+        let num_validators =
+            MainnetEthSpec::slots_per_epoch() as usize * spec.target_committee_size;
+        let attestations = harness.make_attestations(
+            (0..num_validators).collect::<Vec<_>>().as_slice(),
+            &state,
+            Hash256::zero(),
+            SignedBeaconBlockHash::from(Hash256::zero()),
+            slot,
+        );
+
+        let mut num_attestations = 0;
+        for (_, aggregate) in attestations {
+            let att = aggregate.unwrap().message.aggregate;
+            let attesting_indices = get_attesting_indices_from_state(&state, &att).unwrap();
+            op_pool.insert_attestation(att, attesting_indices).unwrap();
+            num_attestations += 1;
+        }
+
+        assert_eq!(
+            op_pool.attestation_age_distribution(slot),
+            BTreeMap::from([(0, num_attestations)])
+        );
+        assert_eq!(
+            op_pool.attestation_age_distribution(slot + 2),
+            BTreeMap::from([(2, num_attestations)])
+        );
+
+        // Attestations can't be included before the min attestation inclusion delay.
+        assert!(op_pool
+            .attestation_rewards(&state, spec)
+            .unwrap()
+            .is_empty());
+
+        *state.slot_mut() += spec.min_attestation_inclusion_delay;
+
+        let rewards = op_pool.attestation_rewards(&state, spec).unwrap();
+        assert_eq!(rewards.len(), num_attestations);
+        assert!(rewards.iter().all(|reward| *reward > 0));
+    }
+
     /// Adding lots of attestations that only intersect pairwise should lead to two aggregate
     /// attestations.
     #[test]
@@ -1221,6 +1541,28 @@ mod release_tests {
         for att in &best_attestations {
             assert!(att.aggregation_bits.num_set_bits() >= big_step_size);
         }
+
+        // All validators have the same balance, so the exact solver must cover at least as many
+        // validators as the greedy algorithm.
+        let exact_attestations = op_pool
+            .get_attestations_with_packing(
+                &state,
+                |_| true,
+                |_| true,
+                AttestationPackingAlgorithm::BranchAndBound,
+                Duration::from_millis(100),
+                spec,
+            )
+            .expect("should have exact attestations");
+        assert!(exact_attestations.len() <= max_attestations);
+        let num_covered = |attestations: &[Attestation<MainnetEthSpec>]| {
+            attestations
+                .iter()
+                .flat_map(|att| get_attesting_indices_from_state(&state, att).unwrap())
+                .collect::<HashSet<_>>()
+                .len()
+        };
+        assert!(num_covered(&exact_attestations) >= num_covered(&best_attestations));
     }
 
     #[test]
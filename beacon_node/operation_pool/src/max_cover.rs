@@ -1,5 +1,7 @@
 use crate::metrics;
 use itertools::Itertools;
+use std::cmp::Reverse;
+use std::time::{Duration, Instant};
 
 /// Trait for types that we can compute a maximum cover for.
 ///
@@ -100,6 +102,173 @@ where
     result
 }
 
+/// The result of an exact maximum cover search.
+pub struct ExactCover<T> {
+    /// The best solution found, in descending order of score.
+    pub solution: Vec<T>,
+    /// The total score of `solution`.
+    pub score: usize,
+    /// The total score of the greedy solution, which the search starts from.
+    pub greedy_score: usize,
+    /// `false` if the time budget expired before the search space was exhausted, in which case
+    /// `solution` may not be optimal.
+    pub complete: bool,
+}
+
+/// Compute a maximum cover using a branch-and-bound search, starting from the greedy solution.
+///
+/// The search stops after `time_budget`, returning the best solution found so far. The result is
+/// never worse than the greedy solution.
+///
+/// The search relies on an item's score never increasing when its covering set is updated, so
+/// that the sum of the highest scores of the remaining items bounds what they can add to a
+/// partial solution.
+pub fn maximum_cover_exact<I, T>(
+    items_iter: I,
+    limit: usize,
+    label: &str,
+    time_budget: Duration,
+) -> ExactCover<T>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover,
+{
+    let deadline = Instant::now() + time_budget;
+    let items: Vec<T> = items_iter
+        .into_iter()
+        .filter(|item| item.score() != 0)
+        .collect();
+
+    let greedy = maximum_cover(items.clone(), limit, label);
+    let greedy_score = total_score(&greedy);
+
+    let mut search = BranchAndBound {
+        deadline,
+        best: greedy,
+        best_score: greedy_score,
+        complete: true,
+    };
+    search.search(Node {
+        candidates: items,
+        chosen: vec![],
+        score: 0,
+        remaining: limit,
+    });
+
+    let mut solution = search.best;
+    solution.sort_by_key(|item| Reverse(item.score()));
+    ExactCover {
+        solution,
+        score: search.best_score,
+        greedy_score,
+        complete: search.complete,
+    }
+}
+
+/// Returns the sum of the scores of the items in `solution`.
+pub fn total_score<T: MaxCover>(solution: &[T]) -> usize {
+    solution.iter().map(MaxCover::score).sum()
+}
+
+struct BranchAndBound<T> {
+    deadline: Instant,
+    best: Vec<T>,
+    best_score: usize,
+    complete: bool,
+}
+
+/// A node of the search tree, representing the solutions which extend `chosen` (with total score
+/// `score`) by at most `remaining` of `candidates`, all of which have non-zero scores.
+struct Node<T> {
+    candidates: Vec<T>,
+    chosen: Vec<T>,
+    score: usize,
+    remaining: usize,
+}
+
+impl<T: MaxCover> BranchAndBound<T> {
+    /// Search the tree below `root` depth-first.
+    ///
+    /// The search uses an explicit stack rather than recursion, since the depth of the tree is
+    /// only bounded by the number of candidates.
+    fn search(&mut self, root: Node<T>) {
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if node.score > self.best_score {
+                self.best = node.chosen.clone();
+                self.best_score = node.score;
+            }
+            if node.remaining == 0 || node.candidates.is_empty() {
+                continue;
+            }
+            if self.expired() {
+                return;
+            }
+
+            // Prune if even the highest-scoring candidates can't improve on the best solution.
+            let mut scores = node
+                .candidates
+                .iter()
+                .map(MaxCover::score)
+                .collect::<Vec<_>>();
+            scores.sort_unstable_by_key(|score| Reverse(*score));
+            let bound = node.score + scores.iter().take(node.remaining).sum::<usize>();
+            if bound <= self.best_score {
+                continue;
+            }
+
+            // Branch on the highest-scoring candidate, first including and then excluding it.
+            let mut rest = node.candidates;
+            let best_index = rest
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, item)| item.score())
+                .map(|(i, _)| i)
+                .unwrap_or_default();
+            let item = rest.swap_remove(best_index);
+
+            // Updating the covering sets is the most expensive part of the search, so the
+            // deadline is checked for each candidate.
+            let mut included = Vec::with_capacity(rest.len());
+            for other in &rest {
+                if self.expired() {
+                    return;
+                }
+                let mut other = other.clone();
+                other.update_covering_set(item.intermediate(), item.covering_set());
+                if other.score() != 0 {
+                    included.push(other);
+                }
+            }
+
+            // The last node pushed is searched first.
+            stack.push(Node {
+                candidates: rest,
+                chosen: node.chosen.clone(),
+                score: node.score,
+                remaining: node.remaining,
+            });
+            let item_score = item.score();
+            let mut chosen = node.chosen;
+            chosen.push(item);
+            stack.push(Node {
+                candidates: included,
+                chosen,
+                score: node.score + item_score,
+                remaining: node.remaining - 1,
+            });
+        }
+    }
+
+    /// Returns `true` if the time budget has expired, marking the search as incomplete.
+    fn expired(&mut self) -> bool {
+        if Instant::now() >= self.deadline {
+            self.complete = false;
+        }
+        !self.complete
+    }
+}
+
 /// Perform a greedy merge of two max cover solutions, preferring higher-score values.
 pub fn merge_solutions<I1, I2, T>(cover1: I1, cover2: I2, limit: usize) -> Vec<T::Object>
 where
@@ -208,6 +377,46 @@ mod test {
         assert_eq!(quality(&cover), 11);
     }
 
+    #[test]
+    fn exact_finds_optimal() {
+        let sets = vec![
+            HashSet::from_iter(vec![0, 1, 8, 11, 14]),
+            HashSet::from_iter(vec![2, 3, 7, 9, 10]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13]),
+            HashSet::from_iter(vec![9, 10]),
+            HashSet::from_iter(vec![5, 6, 7, 8]),
+            HashSet::from_iter(vec![0, 1, 2, 3, 4]),
+        ];
+        let cover = maximum_cover_exact(sets.clone(), 3, "test", Duration::from_secs(10));
+        assert!(cover.complete);
+        assert_eq!(cover.greedy_score, 11);
+        assert_eq!(cover.score, 15);
+        assert_eq!(quality(&cover.solution), 15);
+        assert_eq!(cover.solution.len(), 3);
+        assert!(cover.solution.iter().all(|set| sets[0..3].contains(set)));
+    }
+
+    #[test]
+    fn exact_stops_at_deadline_on_large_input() {
+        // Overlapping sets, so that the search is deep and must update many covering sets.
+        let sets = (0..2_000)
+            .map(|i| HashSet::from_iter(i..i + 15))
+            .collect::<Vec<HashSet<usize>>>();
+        let start = Instant::now();
+        let cover = maximum_cover_exact(sets, 300, "test", Duration::from_millis(50));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(!cover.complete);
+        assert!(cover.score >= cover.greedy_score);
+        assert_eq!(quality(&cover.solution), cover.score);
+    }
+
+    #[test]
+    fn exact_without_budget_returns_greedy() {
+        let cover = maximum_cover_exact(example_system(), 2, "test", Duration::from_secs(0));
+        assert_eq!(cover.score, cover.greedy_score);
+        assert_eq!(quality(&cover.solution), 5);
+    }
+
     #[test]
     fn intersecting_ok() {
         let sets = vec![
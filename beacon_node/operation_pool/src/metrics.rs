@@ -28,4 +28,23 @@ lazy_static! {
         "Number of non-trivial items considered in a max coverage optimisation",
         &["label"]
     );
+    pub static ref ATTESTATION_PACKING_REWARD: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "op_pool_attestation_packing_reward_gwei",
+        "Proposer reward of the attestations packed by the exact solver and by the greedy algorithm",
+        &["label", "algorithm"]
+    );
+    pub static ref ATTESTATION_PACKING_REWARD_IMPROVEMENT: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "op_pool_attestation_packing_reward_improvement_gwei",
+        "Additional proposer reward of the attestations packed by the exact solver over greedy packing",
+        &["label"]
+    );
+    pub static ref ATTESTATION_PACKING_TIMEOUTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "op_pool_attestation_packing_timeouts_total",
+        "Number of times the exact solver ran out of time before proving its solution optimal",
+        &["label"]
+    );
+    pub static ref UNAGGREGATED_VOTES_DROPPED: Result<IntCounter> = try_create_int_counter(
+        "op_pool_unaggregated_votes_dropped_total",
+        "Number of unaggregated votes not retained because the op pool's limit was reached"
+    );
 }
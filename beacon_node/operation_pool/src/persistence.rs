@@ -188,6 +188,7 @@ impl<T: EthSpec> PersistedOperationPool<T> {
         }
         let op_pool = OperationPool {
             attestations,
+            unaggregated_attestations: Default::default(),
             sync_contributions,
             attester_slashings,
             proposer_slashings,
@@ -233,6 +233,31 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Disables the discv5 discovery protocol. The node will not search for new peers or participate in the discovery protocol.")
                 .takes_value(false),
         )
+        .arg(
+            Arg::with_name("enable-peer-diversity")
+                .long("enable-peer-diversity")
+                .help("When pruning excess peers, favour retaining a diverse mix of client \
+                       implementations and IP prefixes, and disconnect peers that advertise a \
+                       different next fork ahead of a scheduled fork. This reduces the risk of \
+                       eclipse attacks and of being partitioned with a faulty client.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("discovery-subnet-query-parallelism")
+                .long("discovery-subnet-query-parallelism")
+                .value_name("INTEGER")
+                .help("The maximum number of subnet discovery queries to run concurrently.")
+                .hidden(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("discovery-subnets-per-query")
+                .long("discovery-subnets-per-query")
+                .value_name("INTEGER")
+                .help("The maximum number of subnets to search for in a single discovery query.")
+                .hidden(true)
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("trusted-peers")
                 .long("trusted-peers")
@@ -261,6 +286,35 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             .min_values(0)
             .hidden(true)
         )
+        .arg(
+            Arg::with_name("record-network-traffic")
+                .long("record-network-traffic")
+                .value_name("FILE")
+                .help("Record all inbound gossip messages and RPC responses, along with their \
+                       timestamps and senders, to the given file. Messages which arrive faster \
+                       than they can be written are dropped and marked as missing in the \
+                       recording. Recordings can be replayed offline to reproduce block and \
+                       attestation processing.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("record-network-traffic-max-size")
+                .long("record-network-traffic-max-size")
+                .value_name("GIGABYTES")
+                .help("Maximum size of the network traffic recording. Recording stops once the \
+                       recording reaches this size. [default: 16]")
+                .requires("record-network-traffic")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("gossip-validation-log")
+                .long("gossip-validation-log")
+                .help("Keep a log of the most recent gossip messages which were ignored or \
+                       rejected, along with the reason, and serve it on the \
+                       /lighthouse/gossip/validation_log HTTP API endpoint. This adds a small \
+                       overhead to the processing of every gossip message.")
+                .takes_value(false)
+        )
         /* REST API related arguments */
         .arg(
             Arg::with_name("http")
@@ -505,7 +559,9 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .alias("execution-endpoints")
                 .help("Server endpoint for an execution layer JWT-authenticated HTTP \
                        JSON-RPC connection. Uses the same endpoint to populate the \
-                       deposit cache.")
+                       deposit cache. A comma-separated list of endpoints may be supplied, in \
+                       which case payloads are verified by all of them, payloads are produced by \
+                       whichever responds first and the first is used for the deposit cache.")
                 .takes_value(true)
         )
         .arg(
@@ -514,7 +570,23 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("EXECUTION-JWT")
                 .alias("jwt-secrets")
                 .help("File path which contains the hex-encoded JWT secret for the \
-                       execution endpoint provided in the --execution-endpoint flag.")
+                       execution endpoint provided in the --execution-endpoint flag. When \
+                       multiple endpoints are supplied this may be a comma-separated list with \
+                       one file per endpoint, or a single file used for all endpoints.")
+                .requires("execution-endpoint")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("execution-quorum")
+                .long("execution-quorum")
+                .value_name("COUNT")
+                .help("The number of execution endpoints which must consider a payload valid \
+                       before it is treated as valid. A payload is invalid if any endpoint \
+                       considers it invalid, and otherwise it is imported optimistically. \
+                       Defaults to a majority of the endpoints supplied to --execution-endpoint \
+                       which are not offline, so that one endpoint going offline does not stop \
+                       the node from verifying payloads. A fixed quorum is stricter, but blocks \
+                       are only imported optimistically whilst fewer endpoints are online.")
                 .requires("execution-endpoint")
                 .takes_value(true)
         )
@@ -566,7 +638,10 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .long("builder")
                 .alias("payload-builder")
                 .alias("payload-builders")
-                .help("The URL of a service compatible with the MEV-boost API.")
+                .help("The URL of a service compatible with the MEV-boost API. Multiple relays \
+                       may be supplied as a comma-separated list, in which case bids are \
+                       requested from all of them and the most valuable valid bid is used. \
+                       Validators are registered with every relay.")
                 .requires("execution-endpoint")
                 .takes_value(true)
         )
@@ -578,6 +653,19 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("1")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("execution-recording-dir")
+                .long("execution-recording-dir")
+                .value_name("DIR")
+                .help("Record every JSON-RPC request to, and response from, each execution \
+                       endpoint to a file in this directory. JWT tokens are not recorded. \
+                       Recordings can be replayed by the mock execution engine to reproduce \
+                       interop issues. This is intended for debugging only; recordings contain \
+                       full execution payloads and grow quickly. Recording stops once the \
+                       recording reaches 4 GB.")
+                .requires("execution-endpoint")
+                .takes_value(true)
+        )
         /*
          * Database purging and compaction.
          */
@@ -781,6 +869,39 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .conflicts_with("checkpoint-state")
         )
+        .arg(
+            Arg::with_name("checkpoint-sync-providers")
+                .long("checkpoint-sync-providers")
+                .help("Comma-separated list of additional beacon node HTTP endpoints to query \
+                       during checkpoint sync. The finalized checkpoint is only trusted once a \
+                       quorum of these and --checkpoint-sync-url agree on it.")
+                .value_name("BEACON_NODES")
+                .takes_value(true)
+                .requires("checkpoint-sync-url")
+        )
+        .arg(
+            Arg::with_name("checkpoint-sync-quorum")
+                .long("checkpoint-sync-quorum")
+                .help("The number of checkpoint sync providers which must agree on the finalized \
+                       checkpoint. Defaults to a majority of all providers.")
+                .value_name("COUNT")
+                .takes_value(true)
+                .requires("checkpoint-sync-url")
+        )
+        .arg(
+            Arg::with_name("checkpoint-sync-from-peers")
+                .long("checkpoint-sync-from-peers")
+                .help("Download the state of the checkpoint given by --wss-checkpoint directly \
+                       from peers on the p2p network, rather than from an HTTP endpoint. Peers \
+                       must serve finalized states (see --serve-finalized-states). If the first \
+                       slot of the checkpoint epoch was skipped, the state of the latest earlier \
+                       block at the start of an epoch is downloaded instead. Only peers which \
+                       have finalized this checkpoint or a later one are downloaded from.")
+                .takes_value(false)
+                .requires("wss-checkpoint")
+                .conflicts_with("checkpoint-state")
+                .conflicts_with("checkpoint-sync-url")
+        )
         .arg(
             Arg::with_name("checkpoint-sync-url-timeout")
                 .long("checkpoint-sync-url-timeout")
@@ -831,6 +952,26 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("INTEGER")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("validator-monitor-history-epochs")
+                .long("validator-monitor-history-epochs")
+                .help("The number of epochs of per-validator performance records to keep in the \
+                    database for monitored validators. Records are served by the \
+                    /lighthouse/validator_monitor/{index}/history HTTP API. Set to 0 to disable \
+                    storing records. Defaults to 8192 (~5 weeks on mainnet).")
+                .value_name("EPOCHS")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("validator-monitor-alert-webhook")
+                .long("validator-monitor-alert-webhook")
+                .help("A URL to which alerts for monitored validators are sent as HTTP POST \
+                    requests. Alerts are raised for missed attestations, missed block proposals, \
+                    slashings and balance decreases, and are also available on the \
+                    /eth/v1/events HTTP API.")
+                .value_name("URL")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("disable-lock-timeouts")
                 .long("disable-lock-timeouts")
@@ -880,6 +1021,28 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        --prepare-payload-lookahead flag.")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("attestation-packing")
+                .long("attestation-packing")
+                .value_name("ALGORITHM")
+                .help("The algorithm used to select attestations for inclusion in produced blocks. \
+                       \"greedy\" is fast but may miss out on rewards. \"branch-and-bound\" \
+                       searches for the most profitable attestations, starting from the greedy \
+                       solution, for up to --attestation-packing-time-budget.")
+                .possible_values(beacon_chain::chain_config::AttestationPackingAlgorithm::VARIANTS)
+                .default_value("greedy")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("attestation-packing-time-budget")
+                .long("attestation-packing-time-budget")
+                .value_name("MILLISECONDS")
+                .help("The maximum number of milliseconds spent packing each epoch's attestations \
+                       when --attestation-packing is not \"greedy\". The attestations of the \
+                       current and previous epochs are packed in parallel.")
+                .default_value("50")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("fork-choice-before-proposal-timeout")
                 .long("fork-choice-before-proposal-timeout")
@@ -954,6 +1117,56 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("0")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("builder-boost-factor")
+                .long("builder-boost-factor")
+                .value_name("PERCENTAGE")
+                .help("The percentage to multiply the value of a builder bid by before comparing \
+                    it to the value of the local EE's payload. Values above 100 favour builder \
+                    payloads and values below 100 favour local payloads. Example: use 90 to only \
+                    choose a builder payload if it is worth at least ~11% more than the local \
+                    payload. Defaults to 100.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("builder-circuit-breaker-error-rate")
+                .long("builder-circuit-breaker-error-rate")
+                .value_name("PERCENTAGE")
+                .help("Stop requesting bids from a relay when at least this percentage of the \
+                    last 20 requests to it failed. The relay is used again after the cool-down. \
+                    Must be between 1 and 100.")
+                .default_value("50")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("builder-circuit-breaker-late-bid-ms")
+                .long("builder-circuit-breaker-late-bid-ms")
+                .value_name("MILLISECONDS")
+                .help("Bids which arrive after this many milliseconds are considered late. Stop \
+                    requesting bids from a relay when at least half of the last 20 requests to \
+                    it were late.")
+                .default_value("800")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("builder-circuit-breaker-failed-reveals")
+                .long("builder-circuit-breaker-failed-reveals")
+                .value_name("COUNT")
+                .help("Stop requesting bids from a relay after it fails to reveal the payload of \
+                    this many consecutive blinded blocks.")
+                .default_value("2")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("builder-circuit-breaker-cooldown-slots")
+                .long("builder-circuit-breaker-cooldown-slots")
+                .value_name("SLOTS")
+                .help("The number of slots to stop requesting bids from a relay for, once it has \
+                    tripped the builder circuit breaker. The breaker can also be tripped and \
+                    reset via the /lighthouse/builder/circuit_breaker HTTP API.")
+                .default_value("64")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("count-unrealized")
                 .long("count-unrealized")
@@ -994,6 +1207,18 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        client during finalized sync. By default block hashes will be checked in \
                        Lighthouse and only passed to the EL if initial verification fails.")
         )
+        .arg(
+            Arg::with_name("unsafe-historical-archive-sync")
+                .long("unsafe-historical-archive-sync")
+                .help("UNSAFE: Sync the chain without an execution node by importing every \
+                       block optimistically, verifying only the block hash of its execution \
+                       payload. Intended only for building archive nodes \
+                       (e.g. alongside --reconstruct-historic-states) for historical research. \
+                       A node using this flag cannot verify the chain and must not be used for \
+                       validating or as a source of truth.")
+                .takes_value(false)
+                .conflicts_with("execution-endpoint")
+        )
         .arg(
             Arg::with_name("light-client-server")
                 .long("light-client-server")
@@ -1001,6 +1226,22 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        [experimental]")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("serve-finalized-states")
+                .long("serve-finalized-states")
+                .help("Serve the states of finalized checkpoints which are still held in the \
+                       database to peers on the p2p network so that they may checkpoint sync \
+                       using --checkpoint-sync-from-peers.")
+                .takes_value(false)
+        )
+        .arg(
+            Arg::with_name("range-sync-cross-check")
+                .long("range-sync-cross-check")
+                .help("Confirm the last block of each batch downloaded during range sync with a \
+                       second peer before processing it. Disagreements are settled by a third peer, \
+                       and batches rejected by both are downloaded again.")
+                .takes_value(false)
+        )
         .arg(
             Arg::with_name("gui")
                 .long("gui")
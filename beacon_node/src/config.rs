@@ -16,7 +16,6 @@ use sensitive_url::SensitiveUrl;
 use slog::{info, warn, Logger};
 use std::cmp;
 use std::cmp::max;
-use std::fmt::Debug;
 use std::fs;
 use std::net::Ipv6Addr;
 use std::net::{IpAddr, Ipv4Addr, ToSocketAddrs};
@@ -287,25 +286,43 @@ pub fn get_config<E: EthSpec>(
         // stakers. The merge is already complicated enough.
         client_config.sync_eth1_chain = true;
 
-        // Parse a single execution endpoint, logging warnings if multiple endpoints are supplied.
-        let execution_endpoint =
-            parse_only_one_value(endpoints, SensitiveUrl::parse, "--execution-endpoint", log)?;
+        // Parse one or more execution endpoints, all of which are used for redundancy.
+        let execution_endpoints = endpoints
+            .split(',')
+            .map(SensitiveUrl::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("--execution-endpoint contains an invalid value {:?}", e))?;
+        let execution_endpoint = execution_endpoints
+            .first()
+            .cloned()
+            .ok_or("Must provide at least one value to --execution-endpoint")?;
 
         // JWTs are required if `--execution-endpoint` is supplied. They can be either passed via
         // file_path or directly as string.
 
-        let secret_file: PathBuf;
-        // Parse a single JWT secret from a given file_path, logging warnings if multiple are supplied.
-        if let Some(secret_files) = cli_args.value_of("execution-jwt") {
-            secret_file =
-                parse_only_one_value(secret_files, PathBuf::from_str, "--execution-jwt", log)?;
+        let secret_files: Vec<PathBuf>;
+        // Parse a JWT secret file for each execution endpoint.
+        if let Some(paths) = cli_args.value_of("execution-jwt") {
+            secret_files = paths
+                .split(',')
+                .map(PathBuf::from_str)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("--execution-jwt contains an invalid value {:?}", e))?;
+            if secret_files.len() != 1 && secret_files.len() != execution_endpoints.len() {
+                return Err(format!(
+                    "--execution-jwt must contain either one secret or one secret per \
+                    execution endpoint, got {} secrets for {} endpoints",
+                    secret_files.len(),
+                    execution_endpoints.len()
+                ));
+            }
 
         // Check if the JWT secret key is passed directly via cli flag and persist it to the default
         // file location.
         } else if let Some(jwt_secret_key) = cli_args.value_of("execution-jwt-secret-key") {
             use std::fs::File;
             use std::io::Write;
-            secret_file = client_config.data_dir().join(DEFAULT_JWT_FILE);
+            let secret_file = client_config.data_dir().join(DEFAULT_JWT_FILE);
             let mut jwt_secret_key_file = File::create(secret_file.clone())
                 .map_err(|e| format!("Error while creating jwt_secret_key file: {:?}", e))?;
             jwt_secret_key_file
@@ -316,20 +333,27 @@ pub fn get_config<E: EthSpec>(
                         e
                     )
                 })?;
+            secret_files = vec![secret_file];
         } else {
             return Err("Error! Please set either --execution-jwt file_path or --execution-jwt-secret-key directly via cli when using --execution-endpoint".to_string());
         }
 
-        // Parse and set the payload builder, if any.
-        if let Some(endpoint) = cli_args.value_of("builder") {
-            let payload_builder =
-                parse_only_one_value(endpoint, SensitiveUrl::parse, "--builder", log)?;
-            el_config.builder_url = Some(payload_builder);
+        // Parse and set the payload builders, if any. Bids are requested from all of them.
+        if let Some(endpoints) = cli_args.value_of("builder") {
+            el_config.builder_urls = endpoints
+                .split(',')
+                .map(SensitiveUrl::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("--builder contains an invalid value {:?}", e))?;
         }
 
+        // The deposit cache is populated from the first execution endpoint.
+        let secret_file = secret_files[0].clone();
+
         // Set config values from parse values.
-        el_config.secret_files = vec![secret_file.clone()];
-        el_config.execution_endpoints = vec![execution_endpoint.clone()];
+        el_config.secret_files = secret_files;
+        el_config.execution_endpoints = execution_endpoints;
+        el_config.execution_quorum = clap_utils::parse_optional(cli_args, "execution-quorum")?;
         el_config.suggested_fee_recipient =
             clap_utils::parse_optional(cli_args, "suggested-fee-recipient")?;
         el_config.jwt_id = clap_utils::parse_optional(cli_args, "execution-jwt-id")?;
@@ -337,9 +361,29 @@ pub fn get_config<E: EthSpec>(
         el_config.default_datadir = client_config.data_dir().clone();
         el_config.builder_profit_threshold =
             clap_utils::parse_required(cli_args, "builder-profit-threshold")?;
+        el_config.builder_boost_factor =
+            clap_utils::parse_optional(cli_args, "builder-boost-factor")?;
+        let max_error_rate =
+            clap_utils::parse_required(cli_args, "builder-circuit-breaker-error-rate")?;
+        // A rate of zero would trip every relay as soon as it has enough requests.
+        if !(1..=100).contains(&max_error_rate) {
+            return Err(
+                "--builder-circuit-breaker-error-rate must be a percentage between 1 and 100"
+                    .to_string(),
+            );
+        }
+        el_config.builder_circuit_breaker.max_error_rate = max_error_rate;
+        el_config.builder_circuit_breaker.late_bid_threshold_ms =
+            clap_utils::parse_required(cli_args, "builder-circuit-breaker-late-bid-ms")?;
+        el_config.builder_circuit_breaker.max_failed_reveals =
+            clap_utils::parse_required(cli_args, "builder-circuit-breaker-failed-reveals")?;
+        el_config.builder_circuit_breaker.cooldown_slots =
+            clap_utils::parse_required(cli_args, "builder-circuit-breaker-cooldown-slots")?;
         let execution_timeout_multiplier =
             clap_utils::parse_required(cli_args, "execution-timeout-multiplier")?;
         el_config.execution_timeout_multiplier = Some(execution_timeout_multiplier);
+        el_config.engine_recording_dir =
+            clap_utils::parse_optional(cli_args, "execution-recording-dir")?;
 
         // If `--execution-endpoint` is provided, we should ignore any `--eth1-endpoints` values and
         // use `--execution-endpoint` instead. Also, log a deprecation warning.
@@ -470,10 +514,41 @@ pub fn get_config<E: EthSpec>(
         } else if let Some(remote_bn_url) = cli_args.value_of("checkpoint-sync-url") {
             let url = SensitiveUrl::parse(remote_bn_url)
                 .map_err(|e| format!("Invalid checkpoint sync URL: {:?}", e))?;
+            let additional_urls = cli_args
+                .value_of("checkpoint-sync-providers")
+                .map(|urls| {
+                    urls.split(',')
+                        .map(|url| {
+                            SensitiveUrl::parse(url.trim()).map_err(|e| {
+                                format!("Invalid checkpoint sync provider URL: {:?}", e)
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?
+                .unwrap_or_default();
+            let quorum = clap_utils::parse_optional::<usize>(cli_args, "checkpoint-sync-quorum")?;
+
+            if let Some(quorum) = quorum {
+                let provider_count = additional_urls.len() + 1;
+                if quorum == 0 || quorum > provider_count {
+                    return Err(format!(
+                        "--checkpoint-sync-quorum must be between 1 and the number of providers \
+                         ({})",
+                        provider_count
+                    ));
+                }
+            }
 
             ClientGenesis::CheckpointSyncUrl {
                 genesis_state_bytes,
                 url,
+                additional_urls,
+                quorum,
+            }
+        } else if cli_args.is_present("checkpoint-sync-from-peers") {
+            ClientGenesis::CheckpointSyncPeers {
+                genesis_state_bytes,
             }
         } else {
             // Note: re-serializing the genesis state is not so efficient, however it avoids adding
@@ -484,7 +559,10 @@ pub fn get_config<E: EthSpec>(
             }
         }
     } else {
-        if cli_args.is_present("checkpoint-state") || cli_args.is_present("checkpoint-sync-url") {
+        if cli_args.is_present("checkpoint-state")
+            || cli_args.is_present("checkpoint-sync-url")
+            || cli_args.is_present("checkpoint-sync-from-peers")
+        {
             return Err(
                 "Checkpoint sync is not available for this network as no genesis state is known"
                     .to_string(),
@@ -667,6 +745,18 @@ pub fn get_config<E: EthSpec>(
         client_config.validator_monitor_individual_tracking_threshold = count;
     }
 
+    if let Some(epochs) = clap_utils::parse_optional(cli_args, "validator-monitor-history-epochs")?
+    {
+        client_config.chain.validator_monitor_history_epochs = epochs;
+    }
+
+    if let Some(url) = cli_args.value_of("validator-monitor-alert-webhook") {
+        client_config.chain.validator_monitor_alert_webhook = Some(
+            SensitiveUrl::parse(url)
+                .map_err(|e| format!("Invalid validator monitor alert webhook URL: {:?}", e))?,
+        );
+    }
+
     if cli_args.is_present("disable-lock-timeouts") {
         client_config.chain.enable_lock_timeouts = false;
     }
@@ -699,6 +789,12 @@ pub fn get_config<E: EthSpec>(
 
     client_config.chain.always_prepare_payload = cli_args.is_present("always-prepare-payload");
 
+    client_config.chain.attestation_packing_algorithm =
+        clap_utils::parse_required(cli_args, "attestation-packing")?;
+    client_config.chain.attestation_packing_time_budget = Duration::from_millis(
+        clap_utils::parse_required(cli_args, "attestation-packing-time-budget")?,
+    );
+
     if let Some(timeout) =
         clap_utils::parse_optional(cli_args, "fork-choice-before-proposal-timeout")?
     {
@@ -739,6 +835,20 @@ pub fn get_config<E: EthSpec>(
     client_config.chain.optimistic_finalized_sync =
         !cli_args.is_present("disable-optimistic-finalized-sync");
 
+    if cli_args.is_present("unsafe-historical-archive-sync") {
+        warn!(
+            log,
+            "Historical archive sync enabled";
+            "info" => "execution payloads will not be verified, do not use this node for \
+                validating or as a source of truth"
+        );
+        client_config.chain.historical_archive_sync = true;
+        // There is no execution node to follow the deposit contract from.
+        client_config.sync_eth1_chain = false;
+        // There is no execution node to reconstruct pruned payloads from.
+        client_config.store.prune_payloads = false;
+    }
+
     // Payload selection configs
     if cli_args.is_present("always-prefer-builder-payload") {
         client_config.always_prefer_builder_payload = true;
@@ -1149,6 +1259,20 @@ pub fn set_network_config(
         warn!(log, "Discovery is disabled. New peers will not be found");
     }
 
+    if cli_args.is_present("enable-peer-diversity") {
+        config.peer_diversity = true;
+    }
+
+    if let Some(parallelism) =
+        clap_utils::parse_optional(cli_args, "discovery-subnet-query-parallelism")?
+    {
+        config.discovery_max_concurrent_subnet_queries = parallelism;
+    }
+
+    if let Some(subnets) = clap_utils::parse_optional(cli_args, "discovery-subnets-per-query")? {
+        config.discovery_max_subnets_in_query = subnets;
+    }
+
     if cli_args.is_present("disable-upnp") {
         config.upnp_enabled = false;
     }
@@ -1167,6 +1291,16 @@ pub fn set_network_config(
 
     // Light client server config.
     config.enable_light_client_server = cli_args.is_present("light-client-server");
+    config.enable_finalized_state_server = cli_args.is_present("serve-finalized-states");
+    config.range_sync_cross_check = cli_args.is_present("range-sync-cross-check");
+
+    config.record_network_traffic = clap_utils::parse_optional(cli_args, "record-network-traffic")?;
+    if let Some(max_size_gbs) =
+        clap_utils::parse_optional::<usize>(cli_args, "record-network-traffic-max-size")?
+    {
+        config.record_network_traffic_max_size_mbs = max_size_gbs * 1024;
+    }
+    config.gossip_validation_log = cli_args.is_present("gossip-validation-log");
 
     // This flag can be used both with or without a value. Try to parse it first with a value, if
     // no value is defined but the flag is present, use the default params.
@@ -1216,38 +1350,3 @@ pub fn get_slots_per_restore_point<E: EthSpec>(
         Ok((default, false))
     }
 }
-
-/// Parses the `cli_value` as a comma-separated string of values to be parsed with `parser`.
-///
-/// If there is more than one value, log a warning. If there are no values, return an error.
-pub fn parse_only_one_value<F, T, E>(
-    cli_value: &str,
-    parser: F,
-    flag_name: &str,
-    log: &Logger,
-) -> Result<T, String>
-where
-    F: Fn(&str) -> Result<T, E>,
-    E: Debug,
-{
-    let values = cli_value
-        .split(',')
-        .map(parser)
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("{} contains an invalid value {:?}", flag_name, e))?;
-
-    if values.len() > 1 {
-        warn!(
-            log,
-            "Multiple values provided";
-            "info" => "multiple values are deprecated, only the first value will be used",
-            "count" => values.len(),
-            "flag" => flag_name
-        );
-    }
-
-    values
-        .into_iter()
-        .next()
-        .ok_or(format!("Must provide at least one value to {}", flag_name))
-}
@@ -214,6 +214,13 @@ pub enum DBColumn {
     OptimisticTransitionBlock,
     #[strum(serialize = "bhs")]
     BeaconHistoricalSummaries,
+    /// For audits of payloads revealed by builders, keyed by block root.
+    #[strum(serialize = "bau")]
+    BuilderAudit,
+    /// For per-epoch records of validators monitored by the validator monitor, keyed by epoch and
+    /// validator index.
+    #[strum(serialize = "vmh")]
+    ValidatorMonitorHistory,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -80,4 +80,47 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("The directory which contains the enr and it's assoicated private key")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("crawl")
+                .long("crawl")
+                .help("Instead of serving as a boot-node, walk the discv5 DHT and write a report \
+                of every node found, including its fork digest, subnet subscriptions and client. \
+                The process exits once the crawl is complete.")
+                .requires("crawl-output")
+        )
+        .arg(
+            Arg::with_name("crawl-output")
+                .long("crawl-output")
+                .value_name("FILE")
+                .help("The file to write the crawl report to.")
+                .requires("crawl")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("crawl-format")
+                .long("crawl-format")
+                .value_name("FORMAT")
+                .help("The format of the crawl report. Defaults to csv if the output file has a \
+                .csv extension, otherwise json.")
+                .possible_values(&["json", "csv"])
+                .requires("crawl")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("crawl-duration")
+                .long("crawl-duration")
+                .value_name("SECONDS")
+                .help("The maximum duration of the crawl. [default: 600]")
+                .requires("crawl")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("crawl-parallelism")
+                .long("crawl-parallelism")
+                .value_name("INTEGER")
+                .help("The maximum number of concurrent FINDNODE requests made during the crawl. \
+                [default: 16]")
+                .requires("crawl")
+                .takes_value(true)
+        )
 }
@@ -4,6 +4,7 @@ use eth2_network_config::Eth2NetworkConfig;
 use lighthouse_network::discovery::create_enr_builder_from_config;
 use lighthouse_network::discv5::IpMode;
 use lighthouse_network::discv5::{enr::CombinedKey, Discv5Config, Enr};
+use lighthouse_network::libp2p::identity::Keypair;
 use lighthouse_network::{
     discovery::{load_enr_from_disk, use_or_load_enr},
     load_private_key, CombinedKeyExt, NetworkConfig,
@@ -11,8 +12,79 @@ use lighthouse_network::{
 use serde_derive::{Deserialize, Serialize};
 use ssz::Encode;
 use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
 use std::{marker::PhantomData, path::PathBuf};
-use types::EthSpec;
+use types::{ChainSpec, EthSpec};
+
+/// The default maximum duration of a DHT crawl.
+const DEFAULT_CRAWL_DURATION_SECS: u64 = 600;
+/// The default number of concurrent `FINDNODE` requests made during a crawl.
+const DEFAULT_CRAWL_PARALLELISM: usize = 16;
+
+/// The file format of a crawl report.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CrawlReportFormat {
+    Json,
+    Csv,
+}
+
+impl FromStr for CrawlReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(CrawlReportFormat::Json),
+            "csv" => Ok(CrawlReportFormat::Csv),
+            other => Err(format!("Unknown crawl report format: {}", other)),
+        }
+    }
+}
+
+/// Parameters for crawling the DHT instead of serving as a bootnode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrawlConfig {
+    /// The file the report is written to.
+    pub output: PathBuf,
+    pub format: CrawlReportFormat,
+    /// The crawl is stopped after this duration, even if there are nodes left to query.
+    pub duration: Duration,
+    /// The maximum number of concurrent `FINDNODE` requests.
+    pub parallelism: usize,
+}
+
+impl CrawlConfig {
+    fn from_cli(matches: &ArgMatches<'_>) -> Result<Option<Self>, String> {
+        if !matches.is_present("crawl") {
+            return Ok(None);
+        }
+
+        let output: PathBuf = clap_utils::parse_required(matches, "crawl-output")?;
+        let format = match clap_utils::parse_optional(matches, "crawl-format")? {
+            Some(format) => format,
+            // Infer the format from the file extension when not specified.
+            None if output.extension().map_or(false, |ext| ext == "csv") => CrawlReportFormat::Csv,
+            None => CrawlReportFormat::Json,
+        };
+        let duration = Duration::from_secs(
+            clap_utils::parse_optional(matches, "crawl-duration")?
+                .unwrap_or(DEFAULT_CRAWL_DURATION_SECS),
+        );
+        let parallelism = clap_utils::parse_optional(matches, "crawl-parallelism")?
+            .unwrap_or(DEFAULT_CRAWL_PARALLELISM);
+        if parallelism == 0 {
+            return Err("--crawl-parallelism must be greater than zero".into());
+        }
+
+        Ok(Some(CrawlConfig {
+            output,
+            format,
+            duration,
+            parallelism,
+        }))
+    }
+}
 
 /// A set of configuration parameters for the bootnode, established from CLI arguments.
 pub struct BootNodeConfig<T: EthSpec> {
@@ -21,7 +93,15 @@ pub struct BootNodeConfig<T: EthSpec> {
     pub boot_nodes: Vec<Enr>,
     pub local_enr: Enr,
     pub local_key: CombinedKey,
+    /// The libp2p form of `local_key`, required to run `Discovery` when crawling.
+    pub local_keypair: Keypair,
     pub discv5_config: Discv5Config,
+    /// The network configuration that the listening socket and `discv5_config` were taken from.
+    pub network_config: NetworkConfig,
+    /// If set, the DHT is crawled instead of running the bootnode server.
+    pub crawl: Option<CrawlConfig>,
+    /// The chain spec of the network, required to run `Discovery` when crawling.
+    pub spec: ChainSpec,
     phantom: PhantomData<T>,
 }
 
@@ -31,6 +111,7 @@ impl<T: EthSpec> BootNodeConfig<T> {
         eth2_network_config: &Eth2NetworkConfig,
     ) -> Result<Self, String> {
         let data_dir = get_data_dir(matches);
+        let spec = eth2_network_config.chain_spec::<T>()?;
 
         // Try and obtain bootnodes
 
@@ -115,8 +196,6 @@ impl<T: EthSpec> BootNodeConfig<T> {
         } else {
             // build the enr_fork_id and add it to the local_enr if it exists
             let enr_fork = {
-                let spec = eth2_network_config.chain_spec::<T>()?;
-
                 if eth2_network_config.beacon_state_is_known() {
                     let genesis_state = eth2_network_config.beacon_state::<T>()?;
 
@@ -159,7 +238,11 @@ impl<T: EthSpec> BootNodeConfig<T> {
             boot_nodes,
             local_enr,
             local_key,
-            discv5_config: network_config.discv5_config,
+            local_keypair: private_key,
+            discv5_config: network_config.discv5_config.clone(),
+            network_config,
+            crawl: CrawlConfig::from_cli(matches)?,
+            spec,
             phantom: PhantomData,
         })
     }
@@ -176,6 +259,8 @@ pub struct BootNodeConfigSerialization {
     pub local_enr: Enr,
     pub disable_packet_filter: bool,
     pub enable_enr_auto_update: bool,
+    #[serde(default)]
+    pub crawl: Option<CrawlConfig>,
 }
 
 impl BootNodeConfigSerialization {
@@ -187,7 +272,11 @@ impl BootNodeConfigSerialization {
             boot_nodes,
             local_enr,
             local_key: _,
+            local_keypair: _,
             discv5_config,
+            network_config: _,
+            crawl,
+            spec: _,
             phantom: _,
         } = config;
 
@@ -197,6 +286,7 @@ impl BootNodeConfigSerialization {
             local_enr: local_enr.clone(),
             disable_packet_filter: !discv5_config.enable_packet_filter,
             enable_enr_auto_update: discv5_config.enr_update,
+            crawl: crawl.clone(),
         }
     }
 }
@@ -0,0 +1,594 @@
+//! Walks the discv5 DHT and records every ENR that is found.
+//!
+//! The crawler starts the beacon node's `Discovery` service with the bootnode's key, ENR and
+//! listening address, then repeatedly sends `FINDNODE` requests to every node it learns about until
+//! there are no new nodes left to query (or the crawl duration elapses). The resulting report
+//! describes the fork digest, subnet subscriptions and, where advertised, the client of each node,
+//! along with a summary of subnet coverage for the local fork digest.
+
+use crate::config::{BootNodeConfig, CrawlConfig, CrawlReportFormat};
+use futures::stream::{FuturesUnordered, StreamExt};
+use lighthouse_network::{
+    discovery::Discovery,
+    discv5::enr::NodeId,
+    rpc::methods::{MetaData, MetaDataV2},
+    Enr, EnrExt, Eth2Enr, NetworkGlobals,
+};
+use rlp::Rlp;
+use serde_derive::Serialize;
+use slog::{debug, info, warn};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use types::{Epoch, EthSpec, Unsigned};
+
+/// The ENR field in which some clients advertise their name and version.
+const CLIENT_ENR_KEY: &str = "client";
+/// The log distances requested from each node.
+///
+/// Node IDs are uniformly random, so half of all nodes are at log distance 256 from any given node,
+/// a quarter at 255, and so on. These four buckets therefore hold 15/16 of the network as seen by
+/// each node. A node in one of the closer buckets of the queried node is almost always in one of
+/// the furthest buckets of some other node, so it is still found as the crawl visits the network.
+const CRAWL_DISTANCES: [u64; 4] = [256, 255, 254, 253];
+/// The interval at which crawl progress is logged.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Information about a single node found during a crawl.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrawledNode {
+    pub node_id: String,
+    pub peer_id: String,
+    pub seq: u64,
+    pub ip4: Option<Ipv4Addr>,
+    pub ip6: Option<Ipv6Addr>,
+    pub udp_port: Option<u16>,
+    pub tcp_port: Option<u16>,
+    /// The hex-encoded fork digest from the `eth2` field.
+    pub fork_digest: Option<String>,
+    pub next_fork_version: Option<String>,
+    pub next_fork_epoch: Option<Epoch>,
+    /// The attestation subnets advertised in the `attnets` field.
+    pub attnets: Vec<u64>,
+    /// The sync committee subnets advertised in the `syncnets` field.
+    pub syncnets: Vec<u64>,
+    /// The client identification advertised in the ENR, if any.
+    pub client: Option<String>,
+    /// True if the node responded to one of our `FINDNODE` requests.
+    pub responsive: bool,
+    pub enr: String,
+}
+
+impl CrawledNode {
+    pub fn from_enr<T: EthSpec>(enr: &Enr) -> Self {
+        let eth2 = enr.eth2().ok();
+        let attnets = enr
+            .attestation_bitfield::<T>()
+            .map(|bitfield| set_bits(bitfield.iter()))
+            .unwrap_or_default();
+        let syncnets = enr
+            .sync_committee_bitfield::<T>()
+            .map(|bitfield| set_bits(bitfield.iter()))
+            .unwrap_or_default();
+
+        CrawledNode {
+            node_id: hex::encode(enr.node_id().raw()),
+            peer_id: enr.peer_id().to_string(),
+            seq: enr.seq(),
+            ip4: enr.ip4(),
+            ip6: enr.ip6(),
+            udp_port: enr.udp4().or_else(|| enr.udp6()),
+            tcp_port: enr.tcp4().or_else(|| enr.tcp6()),
+            fork_digest: eth2
+                .as_ref()
+                .map(|fork_id| hex::encode(fork_id.fork_digest)),
+            next_fork_version: eth2
+                .as_ref()
+                .map(|fork_id| hex::encode(fork_id.next_fork_version)),
+            next_fork_epoch: eth2.map(|fork_id| fork_id.next_fork_epoch),
+            attnets,
+            syncnets,
+            client: client_from_enr(enr),
+            responsive: false,
+            enr: enr.to_base64(),
+        }
+    }
+}
+
+/// Aggregate statistics over all crawled nodes.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct CrawlSummary {
+    pub total_nodes: usize,
+    pub responsive_nodes: usize,
+    /// The number of nodes found for each fork digest.
+    pub fork_digests: BTreeMap<String, usize>,
+    /// The number of nodes advertising each client, for nodes on the local fork digest.
+    pub clients: BTreeMap<String, usize>,
+    /// The number of nodes on the local fork digest subscribed to each attestation subnet.
+    pub attestation_subnet_peers: Vec<usize>,
+    /// The number of nodes on the local fork digest subscribed to each sync committee subnet.
+    pub sync_committee_subnet_peers: Vec<usize>,
+}
+
+impl CrawlSummary {
+    pub fn new<T: EthSpec>(nodes: &[CrawledNode], local_fork_digest: Option<&str>) -> Self {
+        let mut summary = CrawlSummary {
+            total_nodes: nodes.len(),
+            attestation_subnet_peers: vec![0; T::SubnetBitfieldLength::to_usize()],
+            sync_committee_subnet_peers: vec![0; T::SyncCommitteeSubnetCount::to_usize()],
+            ..CrawlSummary::default()
+        };
+
+        for node in nodes {
+            if node.responsive {
+                summary.responsive_nodes += 1;
+            }
+
+            let fork_digest = node.fork_digest.clone().unwrap_or_default();
+            *summary.fork_digests.entry(fork_digest).or_default() += 1;
+
+            if local_fork_digest.is_some() && node.fork_digest.as_deref() != local_fork_digest {
+                continue;
+            }
+
+            let client = node.client.clone().unwrap_or_else(|| "unknown".into());
+            *summary.clients.entry(client).or_default() += 1;
+
+            for subnet in &node.attnets {
+                if let Some(count) = summary.attestation_subnet_peers.get_mut(*subnet as usize) {
+                    *count += 1;
+                }
+            }
+            for subnet in &node.syncnets {
+                if let Some(count) = summary
+                    .sync_committee_subnet_peers
+                    .get_mut(*subnet as usize)
+                {
+                    *count += 1;
+                }
+            }
+        }
+
+        summary
+    }
+}
+
+/// The output of a crawl.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrawlReport {
+    pub local_fork_digest: Option<String>,
+    pub duration_secs: u64,
+    pub summary: CrawlSummary,
+    pub nodes: Vec<CrawledNode>,
+}
+
+impl CrawlReport {
+    /// Writes the report to `config.output` in the configured format.
+    pub fn write(&self, config: &CrawlConfig) -> Result<(), String> {
+        let file = File::create(&config.output)
+            .map_err(|e| format!("Unable to create {:?}: {}", config.output, e))?;
+        let mut writer = BufWriter::new(file);
+
+        match config.format {
+            CrawlReportFormat::Json => serde_json::to_writer_pretty(&mut writer, self)
+                .map_err(|e| format!("Unable to write crawl report: {}", e))?,
+            CrawlReportFormat::Csv => self
+                .write_csv(&mut writer)
+                .map_err(|e| format!("Unable to write crawl report: {}", e))?,
+        }
+
+        writer
+            .flush()
+            .map_err(|e| format!("Unable to write crawl report: {}", e))
+    }
+
+    /// Writes one row per node. The summary is omitted since it can be derived from the rows.
+    fn write_csv<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writeln!(
+            writer,
+            "node_id,peer_id,seq,ip4,ip6,udp_port,tcp_port,fork_digest,next_fork_version,\
+            next_fork_epoch,attnets,syncnets,client,responsive,enr"
+        )?;
+        for node in &self.nodes {
+            let fields = [
+                node.node_id.clone(),
+                node.peer_id.clone(),
+                node.seq.to_string(),
+                display_or_empty(node.ip4),
+                display_or_empty(node.ip6),
+                display_or_empty(node.udp_port),
+                display_or_empty(node.tcp_port),
+                node.fork_digest.clone().unwrap_or_default(),
+                node.next_fork_version.clone().unwrap_or_default(),
+                display_or_empty(node.next_fork_epoch),
+                join_subnets(&node.attnets),
+                join_subnets(&node.syncnets),
+                node.client.clone().unwrap_or_default(),
+                node.responsive.to_string(),
+                node.enr.clone(),
+            ];
+            let row = fields
+                .iter()
+                .map(|field| csv_escape(field))
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(writer, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Crawls the DHT, writing a report to the configured output once complete.
+pub async fn run<T: EthSpec>(
+    config: BootNodeConfig<T>,
+    crawl_config: CrawlConfig,
+    log: slog::Logger,
+) {
+    let BootNodeConfig {
+        listen_socket,
+        boot_nodes,
+        local_enr,
+        local_keypair,
+        mut network_config,
+        spec,
+        ..
+    } = config;
+
+    let local_fork_digest = local_enr
+        .eth2()
+        .ok()
+        .map(|fork_id| hex::encode(fork_id.fork_digest));
+
+    info!(
+        log, "Starting DHT crawl";
+        "listening_address" => %listen_socket,
+        "fork_digest" => local_fork_digest.as_deref().unwrap_or("none"),
+        "max_duration_secs" => crawl_config.duration.as_secs(),
+        "parallelism" => crawl_config.parallelism,
+    );
+
+    // `Discovery` adds the bootnodes to its routing table and starts listening.
+    network_config.boot_nodes_enr = boot_nodes
+        .iter()
+        .filter(|enr| **enr != local_enr)
+        .cloned()
+        .collect();
+    let network_globals = NetworkGlobals::<T>::new(
+        local_enr.clone(),
+        None,
+        None,
+        MetaData::V2(MetaDataV2 {
+            seq_number: 0,
+            attnets: Default::default(),
+            syncnets: Default::default(),
+        }),
+        vec![],
+        &log,
+    );
+    let discovery = match Discovery::<T>::new(
+        &local_keypair,
+        &network_config,
+        Arc::new(network_globals),
+        &spec,
+        &log,
+    )
+    .await
+    {
+        Ok(discovery) => discovery,
+        Err(e) => {
+            slog::crit!(log, "Could not start discovery"; "error" => ?e);
+            return;
+        }
+    };
+
+    let start = Instant::now();
+    let mut nodes: HashMap<NodeId, CrawledNode> = HashMap::new();
+    let mut queried: HashSet<NodeId> = HashSet::new();
+    let mut queue: VecDeque<Enr> = VecDeque::new();
+    let mut active_requests = FuturesUnordered::new();
+
+    // Seed the crawl with the bootnodes and the rest of the routing table.
+    for enr in boot_nodes.into_iter().chain(discovery.table_entries_enr()) {
+        record_enr::<T>(enr, &local_enr, &mut nodes, &queried, &mut queue);
+    }
+
+    let deadline = tokio::time::sleep(crawl_config.duration);
+    tokio::pin!(deadline);
+    let mut progress_interval = tokio::time::interval(PROGRESS_INTERVAL);
+
+    loop {
+        while active_requests.len() < crawl_config.parallelism {
+            let enr = match queue.pop_front() {
+                Some(enr) => enr,
+                None => break,
+            };
+            if !queried.insert(enr.node_id()) {
+                continue;
+            }
+            let node_id = enr.node_id();
+            let request = discovery.find_node_designated_peer(enr, CRAWL_DISTANCES.to_vec());
+            active_requests.push(async move { (node_id, request.await) });
+        }
+
+        if active_requests.is_empty() {
+            break;
+        }
+
+        tokio::select! {
+            Some((node_id, result)) = active_requests.next() => match result {
+                Ok(found) => {
+                    if let Some(node) = nodes.get_mut(&node_id) {
+                        node.responsive = true;
+                    }
+                    for enr in found {
+                        record_enr::<T>(enr, &local_enr, &mut nodes, &queried, &mut queue);
+                    }
+                }
+                Err(e) => {
+                    debug!(log, "FINDNODE request failed"; "node_id" => %node_id, "error" => e);
+                }
+            },
+            _ = progress_interval.tick() => {
+                info!(
+                    log, "Crawl progress";
+                    "discovered" => nodes.len(),
+                    "queried" => queried.len(),
+                    "queued" => queue.len(),
+                    "active_requests" => active_requests.len(),
+                );
+            }
+            _ = &mut deadline => {
+                warn!(log, "Crawl duration elapsed, stopping"; "unqueried" => queue.len());
+                break;
+            }
+        }
+    }
+
+    let mut nodes = nodes.into_values().collect::<Vec<_>>();
+    nodes.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+    let summary = CrawlSummary::new::<T>(&nodes, local_fork_digest.as_deref());
+
+    let uncovered_attnets = summary
+        .attestation_subnet_peers
+        .iter()
+        .filter(|count| **count == 0)
+        .count();
+    info!(
+        log, "Crawl complete";
+        "nodes" => summary.total_nodes,
+        "responsive" => summary.responsive_nodes,
+        "fork_digests" => summary.fork_digests.len(),
+        "uncovered_attestation_subnets" => uncovered_attnets,
+        "duration_secs" => start.elapsed().as_secs(),
+    );
+
+    let report = CrawlReport {
+        local_fork_digest,
+        duration_secs: start.elapsed().as_secs(),
+        summary,
+        nodes,
+    };
+    match report.write(&crawl_config) {
+        Ok(()) => info!(log, "Crawl report written"; "path" => ?crawl_config.output),
+        Err(e) => slog::crit!(log, "Failed to write crawl report"; "error" => e),
+    }
+}
+
+/// Adds `enr` to the set of crawled nodes, queueing it to be queried if it is new.
+fn record_enr<T: EthSpec>(
+    enr: Enr,
+    local_enr: &Enr,
+    nodes: &mut HashMap<NodeId, CrawledNode>,
+    queried: &HashSet<NodeId>,
+    queue: &mut VecDeque<Enr>,
+) {
+    let node_id = enr.node_id();
+    if node_id == local_enr.node_id() {
+        return;
+    }
+
+    match nodes.get_mut(&node_id) {
+        // Keep the most recent version of each ENR.
+        Some(node) if node.seq < enr.seq() => {
+            let responsive = node.responsive;
+            *node = CrawledNode::from_enr::<T>(&enr);
+            node.responsive = responsive;
+        }
+        Some(_) => {}
+        None => {
+            nodes.insert(node_id, CrawledNode::from_enr::<T>(&enr));
+            if !queried.contains(&node_id) {
+                queue.push_back(enr);
+            }
+        }
+    }
+}
+
+/// Returns the client advertised in the `client` ENR field, if present.
+///
+/// The field is an RLP list of strings: the client name, its version and optionally a build
+/// identifier. These are joined with a `/`.
+fn client_from_enr(enr: &Enr) -> Option<String> {
+    let segments = Rlp::new(enr.get(CLIENT_ENR_KEY)?)
+        .as_list::<Vec<u8>>()
+        .ok()?
+        .into_iter()
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| String::from_utf8(segment).ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments.join("/"))
+    }
+}
+
+fn set_bits(bits: impl Iterator<Item = bool>) -> Vec<u64> {
+    bits.enumerate()
+        .filter_map(|(index, set)| set.then_some(index as u64))
+        .collect()
+}
+
+fn join_subnets(subnets: &[u64]) -> String {
+    subnets
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn display_or_empty<D: std::fmt::Display>(value: Option<D>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains(|c| c == ',' || c == '"' || c == '\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lighthouse_network::discv5::enr::{CombinedKey, EnrBuilder};
+    use rlp::RlpStream;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    fn enr_with_client(segments: &[&str]) -> Enr {
+        let mut stream = RlpStream::new_list(segments.len());
+        for segment in segments {
+            stream.append(&segment.to_string());
+        }
+        EnrBuilder::new("v4")
+            .add_value(CLIENT_ENR_KEY, &stream.out())
+            .build(&CombinedKey::generate_secp256k1())
+            .unwrap()
+    }
+
+    fn node(
+        fork_digest: Option<&str>,
+        attnets: Vec<u64>,
+        syncnets: Vec<u64>,
+        client: Option<&str>,
+        responsive: bool,
+    ) -> CrawledNode {
+        CrawledNode {
+            node_id: String::new(),
+            peer_id: String::new(),
+            seq: 1,
+            ip4: None,
+            ip6: None,
+            udp_port: None,
+            tcp_port: None,
+            fork_digest: fork_digest.map(Into::into),
+            next_fork_version: None,
+            next_fork_epoch: None,
+            attnets,
+            syncnets,
+            client: client.map(Into::into),
+            responsive,
+            enr: String::new(),
+        }
+    }
+
+    #[test]
+    fn client_from_enr_decodes_rlp_list() {
+        let enr = enr_with_client(&["Lighthouse", "v4.0.0", "abc123"]);
+        assert_eq!(
+            client_from_enr(&enr),
+            Some("Lighthouse/v4.0.0/abc123".to_string())
+        );
+
+        let enr = enr_with_client(&["Lighthouse", "v4.0.0"]);
+        assert_eq!(client_from_enr(&enr), Some("Lighthouse/v4.0.0".to_string()));
+    }
+
+    #[test]
+    fn client_from_enr_missing_or_invalid() {
+        let enr = EnrBuilder::new("v4")
+            .build(&CombinedKey::generate_secp256k1())
+            .unwrap();
+        assert_eq!(client_from_enr(&enr), None);
+
+        assert_eq!(client_from_enr(&enr_with_client(&[])), None);
+
+        // A string rather than a list.
+        let enr = EnrBuilder::new("v4")
+            .add_value(CLIENT_ENR_KEY, &rlp::encode(&"Lighthouse".to_string()))
+            .build(&CombinedKey::generate_secp256k1())
+            .unwrap();
+        assert_eq!(client_from_enr(&enr), None);
+    }
+
+    #[test]
+    fn set_bits_returns_indices() {
+        assert_eq!(set_bits(vec![].into_iter()), Vec::<u64>::new());
+        assert_eq!(
+            set_bits(vec![true, false, false, true, true].into_iter()),
+            vec![0, 3, 4]
+        );
+    }
+
+    #[test]
+    fn csv_escape_quotes_when_required() {
+        assert_eq!(csv_escape("Lighthouse/v4.0.0"), "Lighthouse/v4.0.0");
+        assert_eq!(csv_escape("1,2"), "\"1,2\"");
+        assert_eq!(csv_escape("a \"b\""), "\"a \"\"b\"\"\"");
+        assert_eq!(csv_escape("a\nb"), "\"a\nb\"");
+    }
+
+    #[test]
+    fn crawl_summary() {
+        let nodes = vec![
+            node(Some("aa"), vec![0, 1], vec![0], Some("Lighthouse"), true),
+            node(Some("aa"), vec![1], vec![], None, false),
+            // Only counted towards the fork digests and responsive nodes.
+            node(Some("bb"), vec![2], vec![1], Some("Prysm"), true),
+            node(None, vec![], vec![], None, false),
+        ];
+        let summary = CrawlSummary::new::<E>(&nodes, Some("aa"));
+
+        assert_eq!(summary.total_nodes, 4);
+        assert_eq!(summary.responsive_nodes, 2);
+        assert_eq!(
+            summary.fork_digests,
+            BTreeMap::from([
+                (String::new(), 1),
+                ("aa".to_string(), 2),
+                ("bb".to_string(), 1)
+            ])
+        );
+        assert_eq!(
+            summary.clients,
+            BTreeMap::from([("Lighthouse".to_string(), 1), ("unknown".to_string(), 1)])
+        );
+        assert_eq!(summary.attestation_subnet_peers.len(), 64);
+        assert_eq!(&summary.attestation_subnet_peers[..3], &[1, 2, 0]);
+        assert_eq!(summary.sync_committee_subnet_peers, vec![1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn crawl_summary_without_local_fork_digest() {
+        let nodes = vec![
+            node(Some("aa"), vec![0], vec![], None, true),
+            node(Some("bb"), vec![0, 70], vec![], None, true),
+        ];
+        let summary = CrawlSummary::new::<E>(&nodes, None);
+
+        // All nodes are counted and out of range subnets are ignored.
+        assert_eq!(
+            summary.clients,
+            BTreeMap::from([("unknown".to_string(), 2)])
+        );
+        assert_eq!(summary.attestation_subnet_peers[0], 2);
+        assert_eq!(summary.attestation_subnet_peers.iter().sum::<usize>(), 2);
+    }
+}
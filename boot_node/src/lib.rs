@@ -5,6 +5,7 @@ use slog::{o, Drain, Level, Logger};
 use eth2_network_config::Eth2NetworkConfig;
 mod cli;
 pub mod config;
+pub mod crawler;
 mod server;
 pub use cli::cli_app;
 use config::{BootNodeConfig, BootNodeConfigSerialization};
@@ -92,9 +93,12 @@ fn main<T: EthSpec>(
         &eth2_network_config.chain_spec::<T>()?,
     )?;
 
-    // Run the boot node
+    // Run the boot node, or crawl the DHT if requested
     if !lh_matches.is_present("immediate-shutdown") {
-        runtime.block_on(server::run(config, log));
+        match config.crawl.clone() {
+            Some(crawl_config) => runtime.block_on(crawler::run(config, crawl_config, log)),
+            None => runtime.block_on(server::run(config, log)),
+        }
     }
     Ok(())
 }
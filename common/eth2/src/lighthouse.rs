@@ -4,14 +4,20 @@ mod attestation_performance;
 pub mod attestation_rewards;
 mod block_packing_efficiency;
 mod block_rewards;
+mod builder_audits;
+mod builder_circuit_breaker;
+mod engine_health;
+mod op_pool;
+mod optimistic_blocks;
 mod standard_block_rewards;
 mod sync_committee_rewards;
+mod validator_monitor_history;
 
 use crate::{
     ok_or_error,
     types::{
         BeaconState, ChainSpec, DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock,
-        GenericResponse, ValidatorId,
+        GenericResponse, Slot, ValidatorId,
     },
     BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, StateId, StatusCode,
 };
@@ -29,10 +35,31 @@ pub use attestation_rewards::StandardAttestationRewards;
 pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
-pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
-pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use block_rewards::{
+    AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery, PayloadSource,
+    PayloadValues,
+};
+pub use builder_audits::{BuilderAudit, BuilderAuditOutcome, BuilderBidRecord};
+pub use builder_circuit_breaker::{
+    BuilderCircuitBreakerStatus, CircuitBreakerRequest, CircuitBreakerTrigger, RelayCircuitStatus,
+};
+pub use engine_health::{
+    ExecutionEngineHealth, ExecutionEngineState, ExecutionLayerHealth, MethodLatency,
+};
+pub use lighthouse_network::{
+    types::{BackFillProgress, SyncState},
+    GossipValidationEntry, GossipValidationResult, PeerInfo,
+};
+pub use op_pool::{
+    AttestationPoolSummary, OpPoolDryRun, OpPoolSummary, OperationPoolSummary, PackedOperation,
+};
+pub use optimistic_blocks::{
+    OptimisticBlock, OptimisticBlockRevalidation, OptimisticHeadStatus, OptimisticHealth,
+    RevalidationStatus,
+};
 pub use standard_block_rewards::StandardBlockReward;
 pub use sync_committee_rewards::SyncCommitteeReward;
+pub use validator_monitor_history::{ValidatorMonitorEpochRecord, ValidatorMonitorHistoryQuery};
 
 // Define "legacy" implementations of `Option<T>` which use four bytes for encoding the union
 // selector.
@@ -50,6 +77,18 @@ pub struct Peer<T: EthSpec> {
     pub peer_info: PeerInfo<T>,
 }
 
+/// Query parameters for the `/lighthouse/gossip/validation_log` endpoint.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GossipValidationLogQuery {
+    /// Only return entries with this result.
+    pub result: Option<GossipValidationResult>,
+    /// Only return entries whose topic contains this string, e.g. `beacon_block`.
+    pub topic: Option<String>,
+    /// Return at most this many of the most recent entries.
+    pub limit: Option<usize>,
+}
+
 /// The results of validators voting during an epoch.
 ///
 /// Provides information about the current and previous epochs.
@@ -407,6 +446,247 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/optimistic_blocks`
+    pub async fn get_lighthouse_optimistic_blocks(
+        &self,
+    ) -> Result<GenericResponse<Vec<OptimisticBlock>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("optimistic_blocks");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/optimistic_blocks/health`
+    pub async fn get_lighthouse_optimistic_blocks_health(
+        &self,
+    ) -> Result<GenericResponse<OptimisticHealth>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("optimistic_blocks")
+            .push("health");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/optimistic_blocks/revalidate`
+    ///
+    /// Re-validates the given optimistic blocks, or all of them if `block_roots` is empty.
+    pub async fn post_lighthouse_optimistic_blocks_revalidate(
+        &self,
+        block_roots: &[Hash256],
+    ) -> Result<GenericResponse<Vec<OptimisticBlockRevalidation>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("optimistic_blocks")
+            .push("revalidate");
+
+        self.post_with_response(path, &block_roots).await
+    }
+
+    /// `GET lighthouse/eth1/engine`
+    pub async fn get_lighthouse_eth1_engine(
+        &self,
+    ) -> Result<GenericResponse<ExecutionLayerHealth>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("eth1")
+            .push("engine");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/builder/audits`
+    pub async fn get_lighthouse_builder_audits(
+        &self,
+    ) -> Result<GenericResponse<Vec<BuilderAudit>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("builder")
+            .push("audits");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/builder/circuit_breaker`
+    pub async fn get_lighthouse_builder_circuit_breaker(
+        &self,
+    ) -> Result<GenericResponse<BuilderCircuitBreakerStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("builder")
+            .push("circuit_breaker");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/builder/circuit_breaker/trip`
+    pub async fn post_lighthouse_builder_circuit_breaker_trip(
+        &self,
+        request: &CircuitBreakerRequest,
+    ) -> Result<GenericResponse<BuilderCircuitBreakerStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("builder")
+            .push("circuit_breaker")
+            .push("trip");
+
+        self.post_with_response(path, request).await
+    }
+
+    /// `POST lighthouse/builder/circuit_breaker/reset`
+    pub async fn post_lighthouse_builder_circuit_breaker_reset(
+        &self,
+        request: &CircuitBreakerRequest,
+    ) -> Result<GenericResponse<BuilderCircuitBreakerStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("builder")
+            .push("circuit_breaker")
+            .push("reset");
+
+        self.post_with_response(path, request).await
+    }
+
+    /// `GET lighthouse/validator_monitor/{index}/history`
+    pub async fn get_lighthouse_validator_monitor_history(
+        &self,
+        validator_index: u64,
+        query: &ValidatorMonitorHistoryQuery,
+    ) -> Result<GenericResponse<Vec<ValidatorMonitorEpochRecord>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_monitor")
+            .push(&validator_index.to_string())
+            .push("history");
+
+        if let Some(from_epoch) = query.from_epoch {
+            path.query_pairs_mut()
+                .append_pair("from_epoch", &from_epoch.to_string());
+        }
+        if let Some(to_epoch) = query.to_epoch {
+            path.query_pairs_mut()
+                .append_pair("to_epoch", &to_epoch.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/analysis/block_rewards`
+    pub async fn get_lighthouse_analysis_block_rewards(
+        &self,
+        query: &BlockRewardsQuery,
+    ) -> Result<Vec<BlockReward>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("block_rewards");
+
+        path.query_pairs_mut()
+            .append_pair("start_slot", &query.start_slot.to_string())
+            .append_pair("end_slot", &query.end_slot.to_string())
+            .append_pair(
+                "include_attestations",
+                &query.include_attestations.to_string(),
+            );
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/op_pool/summary`
+    pub async fn get_lighthouse_op_pool_summary(
+        &self,
+    ) -> Result<GenericResponse<OpPoolSummary>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("op_pool")
+            .push("summary");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/op_pool/dry_run/{slot}`
+    pub async fn get_lighthouse_op_pool_dry_run<E: EthSpec>(
+        &self,
+        slot: Slot,
+    ) -> Result<GenericResponse<OpPoolDryRun<E>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("op_pool")
+            .push("dry_run")
+            .push(&slot.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/gossip/validation_log`
+    pub async fn get_lighthouse_gossip_validation_log(
+        &self,
+        query: &GossipValidationLogQuery,
+    ) -> Result<GenericResponse<Vec<GossipValidationEntry>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("gossip")
+            .push("validation_log");
+
+        if let Some(result) = query.result {
+            let result = match result {
+                GossipValidationResult::Accept => "accept",
+                GossipValidationResult::Ignore => "ignore",
+                GossipValidationResult::Reject => "reject",
+            };
+            path.query_pairs_mut().append_pair("result", result);
+        }
+        if let Some(topic) = &query.topic {
+            path.query_pairs_mut().append_pair("topic", topic);
+        }
+        if let Some(limit) = query.limit {
+            path.query_pairs_mut()
+                .append_pair("limit", &limit.to_string());
+        }
+
+        self.get(path).await
+    }
+
     /*
      * Note:
      *
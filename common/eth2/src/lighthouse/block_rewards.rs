@@ -20,6 +20,35 @@ pub struct BlockReward {
     pub attestation_rewards: AttestationRewards,
     /// Sum of rewards due to sync committee signatures.
     pub sync_committee_rewards: u64,
+    /// The values of the execution payloads considered whilst producing the block.
+    ///
+    /// Only present for blocks recently produced by this beacon node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_values: Option<PayloadValues>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadSource {
+    Local,
+    Builder,
+}
+
+/// The values of the local and builder payloads which competed for inclusion in a block.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PayloadValues {
+    /// The source of the payload which was chosen.
+    pub source: PayloadSource,
+    /// The value of the local execution engine's payload.
+    ///
+    /// `None` if the engine failed to produce a payload. Engines which do not support
+    /// `engine_getPayloadV2` do not report a value, so it is reported as zero.
+    pub local_value: Option<u64>,
+    /// The value of the best builder bid, if any.
+    pub builder_value: Option<u64>,
+    /// The percentage that the builder value was multiplied by before comparison with the local
+    /// value.
+    pub builder_boost_factor: u64,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
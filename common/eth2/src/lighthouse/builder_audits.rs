@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use types::{Address, ExecutionBlockHash, Hash256, Slot, Uint256};
+
+/// A bid received from a relay whilst producing a blinded block.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BuilderBidRecord {
+    /// The URL of the relay, with any credentials redacted.
+    pub relay: String,
+    #[serde(with = "eth2_serde_utils::quoted_u256")]
+    pub value: Uint256,
+    pub block_hash: ExecutionBlockHash,
+    pub parent_hash: ExecutionBlockHash,
+    pub fee_recipient: Address,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub block_number: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub gas_limit: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub gas_used: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub timestamp: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuilderAuditOutcome {
+    /// The proposer's fee recipient received at least the bid value and the execution block
+    /// contains the revealed transactions.
+    Delivered,
+    /// The proposer's fee recipient received less than the bid value.
+    UnderDelivered,
+    /// The execution block's transaction count differs from the revealed payload.
+    TransactionCountMismatch,
+    /// The execution block or the fee recipient's balance could not be retrieved from the
+    /// execution layer.
+    Unavailable,
+    /// The local payload was chosen over every bid, so no relay was relied upon.
+    LocalPayload,
+}
+
+impl BuilderAuditOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuilderAuditOutcome::Delivered => "delivered",
+            BuilderAuditOutcome::UnderDelivered => "under_delivered",
+            BuilderAuditOutcome::TransactionCountMismatch => "transaction_count_mismatch",
+            BuilderAuditOutcome::Unavailable => "unavailable",
+            BuilderAuditOutcome::LocalPayload => "local_payload",
+        }
+    }
+}
+
+/// The result of checking a payload revealed by a relay against the bid it was chosen for.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BuilderAudit {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    /// The fee recipient requested by the proposer, which should receive the bid value.
+    pub proposer_fee_recipient: Address,
+    /// The bid which was used for the proposal, or `None` if the local payload was used.
+    pub bid: Option<BuilderBidRecord>,
+    /// Every bid received for the proposal, including the chosen one.
+    pub bids: Vec<BuilderBidRecord>,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub revealed_transactions: u64,
+    /// The number of transactions in the execution block.
+    ///
+    /// Zero if the outcome is `Unavailable` or `LocalPayload`.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub execution_transactions: u64,
+    /// The increase in the proposer's fee recipient balance caused by the execution block.
+    ///
+    /// Zero if the outcome is `Unavailable` or `LocalPayload`.
+    #[serde(with = "eth2_serde_utils::quoted_u256")]
+    pub delivered_value: Uint256,
+    pub outcome: BuilderAuditOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
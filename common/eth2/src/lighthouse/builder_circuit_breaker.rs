@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use types::Slot;
+
+/// The condition which caused the builder circuit breaker to trip for a relay.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerTrigger {
+    /// Too many recent header requests to the relay failed.
+    ErrorRate,
+    /// Too many recent bids from the relay arrived close to the request deadline.
+    LateBids,
+    /// The relay repeatedly failed to reveal payloads for blinded blocks signed by our proposers.
+    FailedReveals,
+    /// The circuit breaker was tripped via the HTTP API.
+    Manual,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct RelayCircuitStatus {
+    /// The URL of the relay, with any credentials redacted.
+    pub relay: String,
+    /// `true` if the relay is not currently being asked for bids.
+    pub tripped: bool,
+    pub trigger: Option<CircuitBreakerTrigger>,
+    /// The first slot at which the relay will be used again. `None` if the relay was tripped
+    /// manually, in which case it remains tripped until it is reset.
+    pub cooldown_until: Option<Slot>,
+    /// The number of header requests in the window used to compute error and late bid rates.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub requests: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub errors: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub late_bids: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub consecutive_failed_reveals: u64,
+}
+
+/// The response of the `/lighthouse/builder/circuit_breaker` endpoints.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BuilderCircuitBreakerStatus {
+    /// `true` if the circuit breaker has been tripped manually for all relays, in which case local
+    /// payloads are used until it is reset.
+    pub tripped: bool,
+    pub relays: Vec<RelayCircuitStatus>,
+}
+
+/// The request body of the `/lighthouse/builder/circuit_breaker/{trip,reset}` endpoints.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerRequest {
+    /// The URL of a single relay to trip or reset, as reported by the status endpoint. If omitted
+    /// the request applies to every relay.
+    #[serde(default)]
+    pub relay: Option<String>,
+}
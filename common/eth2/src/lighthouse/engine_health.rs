@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use types::ExecutionBlockHash;
+
+/// The state of an execution engine, as determined by its most recent upcheck.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionEngineState {
+    Synced,
+    Syncing,
+    Offline,
+    AuthFailed,
+}
+
+/// Response latency percentiles for a single JSON-RPC method, over the most recent requests.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct MethodLatency {
+    pub method: String,
+    /// The number of requests the percentiles were computed from, including failed requests.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub samples: u64,
+    /// The number of those requests that failed, e.g. by timing out or returning an error.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub errors: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub p50_ms: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub p90_ms: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub p99_ms: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub max_ms: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ExecutionEngineHealth {
+    /// The URL of the engine, with any credentials redacted.
+    pub endpoint: String,
+    pub state: ExecutionEngineState,
+    /// The engine's `latest` block, as of its most recent upcheck.
+    pub latest_block_hash: Option<ExecutionBlockHash>,
+    pub latest_block_number: Option<u64>,
+    /// The number of blocks by which the engine's `latest` block trails the execution block of
+    /// the beacon chain head.
+    pub head_lag: Option<u64>,
+    /// `true` if the engine is not synced, or its head trails the beacon chain head by more than
+    /// the tolerance.
+    pub degraded: bool,
+    pub latencies: Vec<MethodLatency>,
+}
+
+/// The response of the `/lighthouse/eth1/engine` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ExecutionLayerHealth {
+    /// The execution block number of the beacon chain head, if it is post-merge.
+    pub head_block_number: Option<u64>,
+    /// `true` if fewer than the execution quorum of engines are healthy.
+    pub degraded: bool,
+    pub engines: Vec<ExecutionEngineHealth>,
+}
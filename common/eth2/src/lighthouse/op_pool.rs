@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use types::{
+    Attestation, AttesterSlashing, EthSpec, ProposerSlashing, SignedBlsToExecutionChange,
+    SignedVoluntaryExit, Slot,
+};
+
+/// A summary of the contents of the operation pool.
+///
+/// Rewards are the proposer rewards in gwei for including each operation on its own in a block
+/// at `slot`, ignoring any overlap between operations.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OpPoolSummary {
+    /// The slot of the block that rewards are computed for.
+    pub slot: Slot,
+    pub attestations: AttestationPoolSummary,
+    pub proposer_slashings: OperationPoolSummary,
+    pub attester_slashings: OperationPoolSummary,
+    pub voluntary_exits: OperationPoolSummary,
+    pub bls_to_execution_changes: OperationPoolSummary,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AttestationPoolSummary {
+    /// Total number of attestations, including attestations for the same data.
+    pub count: usize,
+    /// Number of unique `AttestationData` attested to.
+    pub num_attestation_data: usize,
+    /// Number of unaggregated votes retained for aggregation.
+    pub num_unaggregated_votes: usize,
+    /// Number of attestations keyed by their age in slots, relative to `slot`.
+    pub age_distribution: BTreeMap<u64, usize>,
+    /// Number of attestations which could be included in a block at `slot`.
+    pub num_includable: usize,
+    pub total_reward: u64,
+    pub max_reward: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OperationPoolSummary {
+    pub count: usize,
+    pub total_reward: u64,
+    pub max_reward: u64,
+}
+
+/// The operations which would be packed into a block at `slot` and the rewards they contribute.
+///
+/// Rewards are in gwei and account for overlap with the operations packed before them.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(bound = "T: EthSpec")]
+pub struct OpPoolDryRun<T: EthSpec> {
+    pub slot: Slot,
+    pub proposer_index: u64,
+    /// Sum of the rewards of all packed operations.
+    pub total_reward: u64,
+    pub attestations: Vec<PackedOperation<Attestation<T>>>,
+    pub proposer_slashings: Vec<PackedOperation<ProposerSlashing>>,
+    pub attester_slashings: Vec<PackedOperation<AttesterSlashing<T>>>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PackedOperation<O> {
+    pub operation: O,
+    pub reward: u64,
+}
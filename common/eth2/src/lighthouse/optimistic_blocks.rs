@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use types::{ExecutionBlockHash, Hash256, Slot};
+
+/// A block which was imported without its execution payload being verified by the execution
+/// layer.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OptimisticBlock {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    pub execution_block_hash: ExecutionBlockHash,
+    /// The number of slots since the block's slot.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub age: u64,
+    /// Whether the block is an ancestor of (or is) the current head.
+    pub canonical: bool,
+}
+
+/// Explains why the head is, or is not, optimistic.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptimisticHeadStatus {
+    /// The head's execution payload has been verified.
+    Verified,
+    /// The node has no execution layer configured.
+    NoExecutionEngine,
+    /// The execution layer can't be reached or refused our credentials.
+    ExecutionEngineOffline,
+    /// The execution layer is reachable but still syncing.
+    ExecutionEngineSyncing,
+    /// The execution layer is synced but has not yet verified the head's payload. It will be
+    /// verified by the next fork choice update, or by re-validating the optimistic blocks.
+    AwaitingValidation,
+}
+
+/// The response of the `/lighthouse/optimistic_blocks/health` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OptimisticHealth {
+    pub head_root: Hash256,
+    pub head_slot: Slot,
+    pub is_optimistic: bool,
+    pub status: OptimisticHeadStatus,
+    /// The number of optimistic blocks known to fork choice.
+    pub optimistic_blocks: usize,
+    pub oldest_optimistic_slot: Option<Slot>,
+}
+
+/// The result of re-submitting an optimistic block's payload to the execution layer.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevalidationStatus {
+    Valid,
+    Invalid,
+    /// The execution layer could not yet verify the payload.
+    Optimistic,
+    Error,
+}
+
+/// The response of the `POST /lighthouse/optimistic_blocks/revalidate` endpoint for a block.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OptimisticBlockRevalidation {
+    pub block_root: Hash256,
+    pub status: RevalidationStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
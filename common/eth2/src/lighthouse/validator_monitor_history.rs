@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use types::Epoch;
+
+/// The performance of a monitored validator during a single epoch, as persisted by the validator
+/// monitor.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorMonitorEpochRecord {
+    pub epoch: Epoch,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// `true` if any attestation by the validator was included on-chain.
+    ///
+    /// Post-Altair, only attestations which were timely for at least one flag are counted.
+    pub attestation_hit: bool,
+    pub attestation_head_hit: bool,
+    pub attestation_target_hit: bool,
+    /// The minimum inclusion distance of the validator's attestations, if known.
+    pub attestation_inclusion_distance: Option<u64>,
+    /// The number of the validator's sync committee signatures which were included in blocks.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub sync_signature_inclusions: u64,
+    /// The number of blocks proposed by the validator which were observed by this node.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub blocks_proposed: u64,
+    /// The validator's balance in gwei, as of the last head state observed during the epoch.
+    pub balance: Option<u64>,
+    /// The change in the validator's balance since the previous epoch, in gwei.
+    pub balance_delta: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorMonitorHistoryQuery {
+    pub from_epoch: Option<Epoch>,
+    pub to_epoch: Option<Epoch>,
+}
@@ -13,7 +13,7 @@ use std::time::Duration;
 pub use types::*;
 
 #[cfg(feature = "lighthouse")]
-use crate::lighthouse::BlockReward;
+use crate::lighthouse::{BackFillProgress, BlockReward, GossipValidationEntry};
 
 /// An API error serializable to JSON.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -897,6 +897,38 @@ pub struct SseLateHead {
     pub execution_optimistic: bool,
 }
 
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseValidatorMissedAttestation {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub epoch: Epoch,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseValidatorMissedProposal {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub slot: Slot,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseValidatorSlashed {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub epoch: Epoch,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseValidatorBalanceDecrease {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub epoch: Epoch,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub balance: u64,
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub balance_delta: i64,
+}
+
 #[superstruct(
     variants(V1, V2),
     variant_attributes(derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize))
@@ -984,6 +1016,18 @@ pub enum EventKind<T: EthSpec> {
     #[cfg(feature = "lighthouse")]
     BlockReward(BlockReward),
     PayloadAttributes(VersionedSsePayloadAttributes),
+    #[cfg(feature = "lighthouse")]
+    GossipValidation(GossipValidationEntry),
+    #[cfg(feature = "lighthouse")]
+    BackFillProgress(BackFillProgress),
+    #[cfg(feature = "lighthouse")]
+    ValidatorMissedAttestation(SseValidatorMissedAttestation),
+    #[cfg(feature = "lighthouse")]
+    ValidatorMissedProposal(SseValidatorMissedProposal),
+    #[cfg(feature = "lighthouse")]
+    ValidatorSlashed(SseValidatorSlashed),
+    #[cfg(feature = "lighthouse")]
+    ValidatorBalanceDecrease(SseValidatorBalanceDecrease),
 }
 
 impl<T: EthSpec> EventKind<T> {
@@ -1000,6 +1044,18 @@ impl<T: EthSpec> EventKind<T> {
             EventKind::LateHead(_) => "late_head",
             #[cfg(feature = "lighthouse")]
             EventKind::BlockReward(_) => "block_reward",
+            #[cfg(feature = "lighthouse")]
+            EventKind::GossipValidation(_) => "gossip_validation",
+            #[cfg(feature = "lighthouse")]
+            EventKind::BackFillProgress(_) => "backfill_progress",
+            #[cfg(feature = "lighthouse")]
+            EventKind::ValidatorMissedAttestation(_) => "validator_missed_attestation",
+            #[cfg(feature = "lighthouse")]
+            EventKind::ValidatorMissedProposal(_) => "validator_missed_proposal",
+            #[cfg(feature = "lighthouse")]
+            EventKind::ValidatorSlashed(_) => "validator_slashed",
+            #[cfg(feature = "lighthouse")]
+            EventKind::ValidatorBalanceDecrease(_) => "validator_balance_decrease",
         }
     }
 
@@ -1061,6 +1117,51 @@ impl<T: EthSpec> EventKind<T> {
             "block_reward" => Ok(EventKind::BlockReward(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Block Reward: {:?}", e)),
             )?)),
+            #[cfg(feature = "lighthouse")]
+            "gossip_validation" => Ok(EventKind::GossipValidation(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Gossip Validation: {:?}", e))
+                })?,
+            )),
+            #[cfg(feature = "lighthouse")]
+            "backfill_progress" => Ok(EventKind::BackFillProgress(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Backfill Progress: {:?}", e))
+                })?,
+            )),
+            #[cfg(feature = "lighthouse")]
+            "validator_missed_attestation" => Ok(EventKind::ValidatorMissedAttestation(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!(
+                        "Validator Missed Attestation: {:?}",
+                        e
+                    ))
+                })?,
+            )),
+            #[cfg(feature = "lighthouse")]
+            "validator_missed_proposal" => Ok(EventKind::ValidatorMissedProposal(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!(
+                        "Validator Missed Proposal: {:?}",
+                        e
+                    ))
+                })?,
+            )),
+            #[cfg(feature = "lighthouse")]
+            "validator_slashed" => Ok(EventKind::ValidatorSlashed(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Validator Slashed: {:?}", e))
+                })?,
+            )),
+            #[cfg(feature = "lighthouse")]
+            "validator_balance_decrease" => Ok(EventKind::ValidatorBalanceDecrease(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!(
+                        "Validator Balance Decrease: {:?}",
+                        e
+                    ))
+                })?,
+            )),
             _ => Err(ServerError::InvalidServerSentEvent(
                 "Could not parse event tag".to_string(),
             )),
@@ -1089,6 +1190,18 @@ pub enum EventTopic {
     PayloadAttributes,
     #[cfg(feature = "lighthouse")]
     BlockReward,
+    #[cfg(feature = "lighthouse")]
+    GossipValidation,
+    #[cfg(feature = "lighthouse")]
+    BackFillProgress,
+    #[cfg(feature = "lighthouse")]
+    ValidatorMissedAttestation,
+    #[cfg(feature = "lighthouse")]
+    ValidatorMissedProposal,
+    #[cfg(feature = "lighthouse")]
+    ValidatorSlashed,
+    #[cfg(feature = "lighthouse")]
+    ValidatorBalanceDecrease,
 }
 
 impl FromStr for EventTopic {
@@ -1107,6 +1220,18 @@ impl FromStr for EventTopic {
             "late_head" => Ok(EventTopic::LateHead),
             #[cfg(feature = "lighthouse")]
             "block_reward" => Ok(EventTopic::BlockReward),
+            #[cfg(feature = "lighthouse")]
+            "gossip_validation" => Ok(EventTopic::GossipValidation),
+            #[cfg(feature = "lighthouse")]
+            "backfill_progress" => Ok(EventTopic::BackFillProgress),
+            #[cfg(feature = "lighthouse")]
+            "validator_missed_attestation" => Ok(EventTopic::ValidatorMissedAttestation),
+            #[cfg(feature = "lighthouse")]
+            "validator_missed_proposal" => Ok(EventTopic::ValidatorMissedProposal),
+            #[cfg(feature = "lighthouse")]
+            "validator_slashed" => Ok(EventTopic::ValidatorSlashed),
+            #[cfg(feature = "lighthouse")]
+            "validator_balance_decrease" => Ok(EventTopic::ValidatorBalanceDecrease),
             _ => Err("event topic cannot be parsed.".to_string()),
         }
     }
@@ -1126,6 +1251,18 @@ impl fmt::Display for EventTopic {
             EventTopic::LateHead => write!(f, "late_head"),
             #[cfg(feature = "lighthouse")]
             EventTopic::BlockReward => write!(f, "block_reward"),
+            #[cfg(feature = "lighthouse")]
+            EventTopic::GossipValidation => write!(f, "gossip_validation"),
+            #[cfg(feature = "lighthouse")]
+            EventTopic::BackFillProgress => write!(f, "backfill_progress"),
+            #[cfg(feature = "lighthouse")]
+            EventTopic::ValidatorMissedAttestation => write!(f, "validator_missed_attestation"),
+            #[cfg(feature = "lighthouse")]
+            EventTopic::ValidatorMissedProposal => write!(f, "validator_missed_proposal"),
+            #[cfg(feature = "lighthouse")]
+            EventTopic::ValidatorSlashed => write!(f, "validator_slashed"),
+            #[cfg(feature = "lighthouse")]
+            EventTopic::ValidatorBalanceDecrease => write!(f, "validator_balance_decrease"),
         }
     }
 }
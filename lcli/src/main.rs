@@ -10,9 +10,11 @@ mod generate_bootnode_enr;
 mod indexed_attestations;
 mod insecure_validators;
 mod interop_genesis;
+mod mock_el;
 mod new_testnet;
 mod parse_ssz;
 mod replace_state_pubkeys;
+mod replay_network;
 mod skip_slots;
 mod transition_blocks;
 
@@ -767,6 +769,149 @@ fn main() {
                         .help("Number of repeat runs, useful for benchmarking."),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("mock-el")
+                .about("Serves a mock execution engine over the engine API, for local testnets.")
+                .arg(
+                    Arg::with_name("jwt-output-path")
+                        .long("jwt-output-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a hex-encoded JWT secret. A new secret is generated and \
+                            written to this path if it does not exist."),
+                )
+                .arg(
+                    Arg::with_name("listen-address")
+                        .long("listen-address")
+                        .value_name("IP_ADDRESS")
+                        .takes_value(true)
+                        .default_value("127.0.0.1")
+                        .help("The IPv4 address to listen on."),
+                )
+                .arg(
+                    Arg::with_name("listen-port")
+                        .long("listen-port")
+                        .value_name("PORT")
+                        .takes_value(true)
+                        .default_value("8551")
+                        .help("The port to listen on."),
+                )
+                .arg(
+                    Arg::with_name("terminal-total-difficulty")
+                        .long("terminal-total-difficulty")
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("The terminal total difficulty of the proof-of-work chain."),
+                )
+                .arg(
+                    Arg::with_name("terminal-block")
+                        .long("terminal-block")
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("The block number of the terminal proof-of-work block."),
+                )
+                .arg(
+                    Arg::with_name("terminal-block-hash")
+                        .long("terminal-block-hash")
+                        .value_name("HASH")
+                        .takes_value(true)
+                        .help("The hash of the terminal proof-of-work block, if overridden."),
+                )
+                .arg(
+                    Arg::with_name("shanghai-time")
+                        .long("shanghai-time")
+                        .value_name("UNIX_TIMESTAMP")
+                        .takes_value(true)
+                        .help("The timestamp at which Shanghai activates. Shanghai is never \
+                            activated if this is not set."),
+                )
+                .arg(
+                    Arg::with_name("fake-transactions")
+                        .long("fake-transactions")
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("The number of opaque transactions to include in each payload."),
+                )
+                .arg(
+                    Arg::with_name("replay")
+                        .long("replay")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Respond with the exchanges from an engine recording made with \
+                            `lighthouse bn --execution-recording-dir`, rather than building \
+                            payloads."),
+                )
+                .arg(
+                    Arg::with_name("replay-engine")
+                        .long("replay-engine")
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .default_value("0")
+                        .requires("replay")
+                        .help("The index of the execution endpoint whose exchanges are replayed, \
+                            when the recording node used multiple execution endpoints."),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("replay-network")
+                .about("Replays a network recording against a fresh beacon chain started from a \
+                    checkpoint.")
+                .arg(
+                    Arg::with_name("recording")
+                        .long("recording")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a recording made with `lighthouse bn \
+                            --record-network-traffic`."),
+                )
+                .arg(
+                    Arg::with_name("checkpoint-state")
+                        .long("checkpoint-state")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to the SSZ encoded state to start the chain from. This \
+                            should be the state the recording node started from."),
+                )
+                .arg(
+                    Arg::with_name("checkpoint-block")
+                        .long("checkpoint-block")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to the SSZ encoded block of the checkpoint state."),
+                )
+                .arg(
+                    Arg::with_name("genesis-state")
+                        .long("genesis-state")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Path to the SSZ encoded genesis state. Defaults to the genesis \
+                            state of the testnet directory."),
+                )
+                .arg(
+                    Arg::with_name("execution-endpoint")
+                        .long("execution-endpoint")
+                        .value_name("URL")
+                        .takes_value(true)
+                        .requires("execution-jwt")
+                        .help("The engine API endpoint of an execution engine, required to \
+                            import post-merge blocks."),
+                )
+                .arg(
+                    Arg::with_name("execution-jwt")
+                        .long("execution-jwt")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .requires("execution-endpoint")
+                        .help("Path to the JWT secret of the execution engine."),
+                )
+        )
         .get_matches();
 
     let result = matches
@@ -857,6 +1002,10 @@ fn run<T: EthSpec>(
             .map_err(|e| format!("Failed to run indexed-attestations command: {}", e)),
         ("block-root", Some(matches)) => block_root::run::<T>(env, matches)
             .map_err(|e| format!("Failed to run block-root command: {}", e)),
+        ("mock-el", Some(matches)) => mock_el::run::<T>(env, matches)
+            .map_err(|e| format!("Failed to run mock-el command: {}", e)),
+        ("replay-network", Some(matches)) => replay_network::run::<T>(env, testnet_dir, matches)
+            .map_err(|e| format!("Failed to run replay-network command: {}", e)),
         (other, _) => Err(format!("Unknown subcommand {}. See --help.", other)),
     }
 }
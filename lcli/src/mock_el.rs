@@ -0,0 +1,105 @@
+//! # Mock-EL
+//!
+//! Serves the mock execution engine from `execution_layer::test_utils` over the engine API, so
+//! that local testnets can be run without an execution client.
+//!
+//! Payloads built by the mock engine are valid from the point of view of the consensus layer, but
+//! contain no real state transition. Their transactions are opaque bytes.
+//!
+//! ## Example
+//!
+//! Serve a post-merge engine at genesis which includes 16 transactions in each payload and
+//! activates Capella at the given timestamp:
+//!
+//! ```ignore
+//! lcli mock-el \
+//!     --jwt-output-path /tmp/jwt.hex \
+//!     --listen-port 8551 \
+//!     --terminal-total-difficulty 0 \
+//!     --terminal-block 0 \
+//!     --fake-transactions 16 \
+//!     --shanghai-time 1680000000
+//! ```
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use environment::Environment;
+use execution_layer::{
+    auth::JwtKey,
+    test_utils::{Config, MockExecutionConfig, MockServer, ReplaySession},
+};
+use std::fs;
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use types::{EthSpec, ExecutionBlockHash, Uint256};
+
+pub fn run<T: EthSpec>(mut env: Environment<T>, matches: &ArgMatches) -> Result<(), String> {
+    let jwt_path: PathBuf = parse_required(matches, "jwt-output-path")?;
+    let listen_addr: Ipv4Addr = parse_required(matches, "listen-address")?;
+    let listen_port: u16 = parse_required(matches, "listen-port")?;
+    let terminal_difficulty: u64 = parse_required(matches, "terminal-total-difficulty")?;
+    let terminal_block: u64 = parse_required(matches, "terminal-block")?;
+    let terminal_block_hash: Option<ExecutionBlockHash> =
+        parse_optional(matches, "terminal-block-hash")?;
+    let shanghai_time: Option<u64> = parse_optional(matches, "shanghai-time")?;
+    let fake_transactions: usize = parse_required(matches, "fake-transactions")?;
+    let replay_path: Option<PathBuf> = parse_optional(matches, "replay")?;
+    let replay_engine: usize = parse_required(matches, "replay-engine")?;
+
+    let jwt_key = load_or_create_jwt_key(&jwt_path)?;
+
+    let config = MockExecutionConfig {
+        server_config: Config {
+            listen_addr,
+            listen_port,
+        },
+        jwt_key,
+        terminal_difficulty: Uint256::from(terminal_difficulty),
+        terminal_block,
+        terminal_block_hash: terminal_block_hash.unwrap_or_else(ExecutionBlockHash::zero),
+        shanghai_time,
+    };
+
+    let server: MockServer<T> = MockServer::new_with_config(env.runtime().handle(), config);
+    server.execution_block_generator().fake_transactions = fake_transactions;
+
+    if let Some(replay_path) = replay_path {
+        let session = ReplaySession::from_file(&replay_path, replay_engine)?;
+        server.start_replay(session);
+        info!(
+            "Replaying engine {} of recording {:?}",
+            replay_engine, replay_path
+        );
+    }
+
+    info!("Mock execution engine listening on {}", server.url());
+    info!("JWT secret: {:?}", jwt_path);
+
+    env.block_until_shutdown_requested()?;
+
+    if let Some(session) = server.stop_replay() {
+        info!(
+            "Replay finished with {} unused exchanges and {} divergences",
+            session.remaining(),
+            session.divergences().len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Load a hex-encoded JWT secret from `path`, generating and writing a new one if it does not
+/// exist. The file format matches the one accepted by `lighthouse bn --execution-jwt`.
+fn load_or_create_jwt_key(path: &Path) -> Result<JwtKey, String> {
+    if path.exists() {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read JWT secret {:?}: {:?}", path, e))?;
+        let bytes = hex::decode(contents.trim().trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid JWT secret in {:?}: {:?}", path, e))?;
+        JwtKey::from_slice(&bytes)
+    } else {
+        let key = JwtKey::random();
+        fs::write(path, key.hex_string())
+            .map_err(|e| format!("Unable to write JWT secret {:?}: {:?}", path, e))?;
+        Ok(key)
+    }
+}
@@ -0,0 +1,129 @@
+//! # Replay Network
+//!
+//! Replays a recording made with `lighthouse bn --record-network-traffic` against a fresh,
+//! in-memory beacon chain started from a checkpoint. Gossip messages are passed through the same
+//! router and beacon processor as a running node, so fork choice and block processing issues seen
+//! by the recording node can be reproduced offline.
+//!
+//! The checkpoint should be the finalized state and block that the recording node started from.
+//! Post-merge blocks can only be imported if an execution engine is provided, for example an
+//! `lcli mock-el` replaying an engine recording made by the same node.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! lcli replay-network \
+//!     --recording /tmp/network.rec \
+//!     --checkpoint-state /tmp/checkpoint-state.ssz \
+//!     --checkpoint-block /tmp/checkpoint-block.ssz \
+//!     --execution-endpoint http://localhost:8551 \
+//!     --execution-jwt /tmp/jwt.hex
+//! ```
+use crate::transition_blocks::load_from_ssz_with;
+use beacon_chain::{builder::BeaconChainBuilder, test_utils::EphemeralHarnessType};
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use environment::Environment;
+use eth2_network_config::Eth2NetworkConfig;
+use execution_layer::ExecutionLayer;
+use lighthouse_network::recorder::RecordingReader;
+use network::replay::replay_recording;
+use sensitive_url::SensitiveUrl;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use store::{HotColdDB, StoreConfig};
+use types::{BeaconState, EthSpec, SignedBeaconBlock};
+
+pub fn run<T: EthSpec>(
+    env: Environment<T>,
+    testnet_dir: PathBuf,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let recording_path: PathBuf = parse_required(matches, "recording")?;
+    let checkpoint_state_path: PathBuf = parse_required(matches, "checkpoint-state")?;
+    let checkpoint_block_path: PathBuf = parse_required(matches, "checkpoint-block")?;
+    let genesis_state_path: Option<PathBuf> = parse_optional(matches, "genesis-state")?;
+    let execution_endpoint: Option<SensitiveUrl> = parse_optional(matches, "execution-endpoint")?;
+    let execution_jwt: Option<PathBuf> = parse_optional(matches, "execution-jwt")?;
+
+    let context = env.core_context();
+    let executor = context.executor.clone();
+    let log = context.log().clone();
+
+    let eth2_network_config = Eth2NetworkConfig::load(testnet_dir)?;
+    let spec = eth2_network_config.chain_spec::<T>()?;
+
+    let checkpoint_state =
+        load_from_ssz_with(&checkpoint_state_path, &spec, BeaconState::from_ssz_bytes)?;
+    let checkpoint_block = load_from_ssz_with(
+        &checkpoint_block_path,
+        &spec,
+        SignedBeaconBlock::from_ssz_bytes,
+    )?;
+    let genesis_state = match genesis_state_path {
+        Some(path) => load_from_ssz_with(&path, &spec, BeaconState::from_ssz_bytes)?,
+        None => eth2_network_config.beacon_state::<T>()?,
+    };
+
+    let execution_layer = match execution_endpoint {
+        Some(url) => {
+            let config = execution_layer::Config {
+                execution_endpoints: vec![url],
+                secret_files: execution_jwt.into_iter().collect(),
+                ..Default::default()
+            };
+            let execution_layer =
+                ExecutionLayer::from_config(config, executor.clone(), log.clone())
+                    .map_err(|e| format!("Unable to start execution layer: {:?}", e))?;
+            Some(execution_layer)
+        }
+        None => None,
+    };
+
+    let store = HotColdDB::open_ephemeral(StoreConfig::default(), spec.clone(), log.clone())
+        .map_err(|e| format!("Failed to create ephemeral store: {:?}", e))?;
+
+    let chain = BeaconChainBuilder::<EphemeralHarnessType<T>>::new(T::default())
+        .logger(log.clone())
+        .custom_spec(spec.clone())
+        .store(Arc::new(store))
+        .task_executor(executor.clone())
+        .execution_layer(execution_layer)
+        .weak_subjectivity_state(checkpoint_state, checkpoint_block, genesis_state)?
+        .dummy_eth1_backend()?
+        .testing_slot_clock(Duration::from_secs(spec.seconds_per_slot))?
+        .shutdown_sender(executor.shutdown_sender())
+        .build()?;
+    let chain = Arc::new(chain);
+
+    let recording = RecordingReader::open(&recording_path)?;
+    info!("Replaying network recording {:?}", recording_path);
+
+    let clock_chain = chain.clone();
+    let summary = executor
+        .handle()
+        .ok_or("shutdown in progress")?
+        .block_on(replay_recording(
+            chain.clone(),
+            recording,
+            move |time| clock_chain.slot_clock.set_current_time(time),
+            executor.clone(),
+            log,
+        ))?;
+
+    info!("Replay finished: {:?}", summary);
+    if summary.dropped_messages > 0 {
+        warn!(
+            "The recording is missing {} messages, the replayed chain may differ from the \
+             recording node",
+            summary.dropped_messages
+        );
+    }
+    info!(
+        "Head block root: {:?}",
+        chain.canonical_head.cached_head().head_block_root()
+    );
+
+    Ok(())
+}
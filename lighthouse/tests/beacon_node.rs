@@ -2,6 +2,7 @@ use beacon_node::{beacon_chain::CountUnrealizedFull, ClientConfig as Config};
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
+    AttestationPackingAlgorithm, DEFAULT_ATTESTATION_PACKING_TIME_BUDGET,
     DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_THRESHOLD,
 };
 use eth1::Eth1Endpoint;
@@ -157,6 +158,24 @@ fn checkpoint_sync_url_timeout_default() {
         });
 }
 
+#[test]
+#[should_panic]
+fn checkpoint_sync_quorum_exceeds_providers() {
+    CommandLineTest::new()
+        .flag("checkpoint-sync-url", Some("http://localhost:5052"))
+        .flag("checkpoint-sync-providers", Some("http://localhost:5053"))
+        .flag("checkpoint-sync-quorum", Some("3"))
+        .run_with_zero_port();
+}
+
+#[test]
+#[should_panic]
+fn checkpoint_sync_from_peers_requires_wss_checkpoint() {
+    CommandLineTest::new()
+        .flag("checkpoint-sync-from-peers", None)
+        .run_with_zero_port();
+}
+
 #[test]
 fn prepare_payload_lookahead_default() {
     CommandLineTest::new()
@@ -197,6 +216,40 @@ fn always_prepare_payload_override() {
         .with_config(|config| assert!(config.chain.always_prepare_payload));
 }
 
+#[test]
+fn attestation_packing_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing_algorithm,
+                AttestationPackingAlgorithm::Greedy
+            );
+            assert_eq!(
+                config.chain.attestation_packing_time_budget,
+                DEFAULT_ATTESTATION_PACKING_TIME_BUDGET
+            );
+        });
+}
+
+#[test]
+fn attestation_packing_branch_and_bound() {
+    CommandLineTest::new()
+        .flag("attestation-packing", Some("branch-and-bound"))
+        .flag("attestation-packing-time-budget", Some("200"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing_algorithm,
+                AttestationPackingAlgorithm::BranchAndBound
+            );
+            assert_eq!(
+                config.chain.attestation_packing_time_budget,
+                Duration::from_millis(200)
+            );
+        });
+}
+
 #[test]
 fn paranoid_block_proposal_default() {
     CommandLineTest::new()
@@ -425,8 +478,6 @@ fn eth1_cache_follow_distance_manual() {
 fn run_merge_execution_endpoints_flag_test(flag: &str) {
     use sensitive_url::SensitiveUrl;
     let urls = vec!["http://sigp.io/no-way:1337", "http://infura.not_real:4242"];
-    // we don't support redundancy for execution-endpoints
-    // only the first provided endpoint is parsed.
 
     let mut endpoint_arg = urls[0].to_string();
     for url in urls.iter().skip(1) {
@@ -456,13 +507,35 @@ fn run_merge_execution_endpoints_flag_test(flag: &str) {
         .run_with_zero_port()
         .with_config(|config| {
             let config = config.execution_layer.as_ref().unwrap();
-            assert_eq!(config.execution_endpoints.len(), 1);
             assert_eq!(
-                config.execution_endpoints[0],
-                SensitiveUrl::parse(&urls[0]).unwrap()
+                config.execution_endpoints,
+                urls.iter()
+                    .map(|url| SensitiveUrl::parse(url).unwrap())
+                    .collect::<Vec<_>>()
             );
-            // Only the first secret file should be used.
-            assert_eq!(config.secret_files, vec![jwts[0].clone()]);
+            assert_eq!(config.secret_files, jwts);
+            assert_eq!(config.execution_quorum, None);
+        });
+}
+#[test]
+fn execution_quorum_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "execution-endpoint",
+            Some("http://localhost:8551/,http://localhost:8552/"),
+        )
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .flag("execution-quorum", Some("1"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(config.execution_endpoints.len(), 2);
+            assert_eq!(config.secret_files, vec![dir.path().join("jwt-file")]);
+            assert_eq!(config.execution_quorum, Some(1));
         });
 }
 #[test]
@@ -503,6 +576,28 @@ fn execution_timeout_multiplier_flag() {
         });
 }
 #[test]
+fn execution_recording_dir_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("http://meow.cats"))
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .flag(
+            "execution-recording-dir",
+            dir.path().join("recordings").as_os_str().to_str(),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(
+                config.engine_recording_dir,
+                Some(dir.path().join("recordings"))
+            );
+        });
+}
+#[test]
 fn merge_execution_endpoints_flag() {
     run_merge_execution_endpoints_flag_test("execution-endpoints")
 }
@@ -606,9 +701,7 @@ fn run_payload_builder_flag_test(flag: &str, builders: &str) {
         .collect();
     run_payload_builder_flag_test_with_config(flag, builders, None, None, |config| {
         let config = config.execution_layer.as_ref().unwrap();
-        // Only first provided endpoint is parsed as we don't support
-        // redundancy.
-        assert_eq!(config.builder_url, all_builders.get(0).cloned());
+        assert_eq!(config.builder_urls, all_builders);
     })
 }
 fn run_payload_builder_flag_test_with_config<F: Fn(&Config)>(
@@ -709,6 +802,139 @@ fn builder_fallback_flags() {
             );
         },
     );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-boost-factor"),
+        Some("90"),
+        |config| {
+            assert_eq!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .builder_boost_factor,
+                Some(90)
+            );
+        },
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        None,
+        None,
+        |config| {
+            assert_eq!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .builder_boost_factor,
+                None
+            );
+        },
+    );
+}
+
+#[test]
+fn builder_circuit_breaker_flags() {
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        None,
+        None,
+        |config| {
+            let breaker = &config
+                .execution_layer
+                .as_ref()
+                .unwrap()
+                .builder_circuit_breaker;
+            assert_eq!(breaker.max_error_rate, 50);
+            assert_eq!(breaker.late_bid_threshold_ms, 800);
+            assert_eq!(breaker.max_failed_reveals, 2);
+            assert_eq!(breaker.cooldown_slots, 64);
+        },
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-circuit-breaker-error-rate"),
+        Some("25"),
+        |config| {
+            let breaker = &config
+                .execution_layer
+                .as_ref()
+                .unwrap()
+                .builder_circuit_breaker;
+            assert_eq!(breaker.max_error_rate, 25);
+        },
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-circuit-breaker-late-bid-ms"),
+        Some("500"),
+        |config| {
+            let breaker = &config
+                .execution_layer
+                .as_ref()
+                .unwrap()
+                .builder_circuit_breaker;
+            assert_eq!(breaker.late_bid_threshold_ms, 500);
+        },
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-circuit-breaker-failed-reveals"),
+        Some("1"),
+        |config| {
+            let breaker = &config
+                .execution_layer
+                .as_ref()
+                .unwrap()
+                .builder_circuit_breaker;
+            assert_eq!(breaker.max_failed_reveals, 1);
+        },
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-circuit-breaker-cooldown-slots"),
+        Some("32"),
+        |config| {
+            let breaker = &config
+                .execution_layer
+                .as_ref()
+                .unwrap()
+                .builder_circuit_breaker;
+            assert_eq!(breaker.cooldown_slots, 32);
+        },
+    );
+}
+
+#[test]
+#[should_panic]
+fn builder_circuit_breaker_error_rate_zero() {
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-circuit-breaker-error-rate"),
+        Some("0"),
+        |_| {},
+    );
+}
+
+#[test]
+#[should_panic]
+fn builder_circuit_breaker_error_rate_above_100() {
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-circuit-breaker-error-rate"),
+        Some("101"),
+        |_| {},
+    );
 }
 
 fn run_jwt_optional_flags_test(jwt_flag: &str, jwt_id_flag: &str, jwt_version_flag: &str) {
@@ -1041,6 +1267,39 @@ fn disable_discovery_flag() {
         .with_config(|config| assert!(config.network.disable_discovery));
 }
 #[test]
+fn peer_diversity_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.peer_diversity));
+}
+#[test]
+fn peer_diversity_flag() {
+    CommandLineTest::new()
+        .flag("enable-peer-diversity", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.peer_diversity));
+}
+#[test]
+fn discovery_subnet_query_tuning_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.discovery_max_concurrent_subnet_queries, 2);
+            assert_eq!(config.network.discovery_max_subnets_in_query, 3);
+        });
+}
+#[test]
+fn discovery_subnet_query_tuning_flags() {
+    CommandLineTest::new()
+        .flag("discovery-subnet-query-parallelism", Some("4"))
+        .flag("discovery-subnets-per-query", Some("8"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.discovery_max_concurrent_subnet_queries, 4);
+            assert_eq!(config.network.discovery_max_subnets_in_query, 8);
+        });
+}
+#[test]
 fn disable_upnp_flag() {
     CommandLineTest::new()
         .flag("disable-upnp", None)
@@ -1559,6 +1818,49 @@ fn validator_monitor_metrics_threshold_custom() {
             assert_eq!(config.validator_monitor_individual_tracking_threshold, 42)
         });
 }
+#[test]
+fn validator_monitor_history_epochs_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.validator_monitor_history_epochs,
+                beacon_node::beacon_chain::chain_config::DEFAULT_VALIDATOR_MONITOR_HISTORY_EPOCHS
+            )
+        });
+}
+#[test]
+fn validator_monitor_history_epochs_custom() {
+    CommandLineTest::new()
+        .flag("validator-monitor-history-epochs", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.validator_monitor_history_epochs, 0));
+}
+#[test]
+fn validator_monitor_alert_webhook_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.chain.validator_monitor_alert_webhook.is_none()));
+}
+#[test]
+fn validator_monitor_alert_webhook_flag() {
+    CommandLineTest::new()
+        .flag(
+            "validator-monitor-alert-webhook",
+            Some("http://localhost:8080/alerts"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config
+                    .chain
+                    .validator_monitor_alert_webhook
+                    .as_ref()
+                    .map(|url| url.full.as_str()),
+                Some("http://localhost:8080/alerts")
+            )
+        });
+}
 
 // Tests for Store flags.
 #[test]
@@ -2028,6 +2330,80 @@ fn light_client_server_enabled() {
         .with_config(|config| assert_eq!(config.network.enable_light_client_server, true));
 }
 
+#[test]
+fn serve_finalized_states_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.enable_finalized_state_server, false));
+}
+
+#[test]
+fn serve_finalized_states_enabled() {
+    CommandLineTest::new()
+        .flag("serve-finalized-states", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.enable_finalized_state_server, true));
+}
+
+#[test]
+fn range_sync_cross_check_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.range_sync_cross_check, false));
+}
+
+#[test]
+fn range_sync_cross_check_enabled() {
+    CommandLineTest::new()
+        .flag("range-sync-cross-check", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.range_sync_cross_check, true));
+}
+
+#[test]
+fn record_network_traffic_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.record_network_traffic, None));
+}
+
+#[test]
+fn record_network_traffic_flag() {
+    CommandLineTest::new()
+        .flag("record-network-traffic", Some("/tmp/network.rec"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.record_network_traffic,
+                Some(PathBuf::from("/tmp/network.rec"))
+            )
+        });
+}
+
+#[test]
+fn record_network_traffic_max_size_flag() {
+    CommandLineTest::new()
+        .flag("record-network-traffic", Some("/tmp/network.rec"))
+        .flag("record-network-traffic-max-size", Some("2"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.record_network_traffic_max_size_mbs, 2048));
+}
+
+#[test]
+fn gossip_validation_log_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.gossip_validation_log));
+}
+
+#[test]
+fn gossip_validation_log_flag() {
+    CommandLineTest::new()
+        .flag("gossip-validation-log", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.gossip_validation_log));
+}
+
 #[test]
 fn gui_flag() {
     CommandLineTest::new()
@@ -2057,3 +2433,22 @@ fn disable_optimistic_finalized_sync() {
             assert!(!config.chain.optimistic_finalized_sync);
         });
 }
+
+#[test]
+fn historical_archive_sync_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.chain.historical_archive_sync));
+}
+
+#[test]
+fn unsafe_historical_archive_sync_flag() {
+    CommandLineTest::new()
+        .flag("unsafe-historical-archive-sync", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.chain.historical_archive_sync);
+            assert!(!config.sync_eth1_chain);
+            assert!(!config.store.prune_payloads);
+        });
+}
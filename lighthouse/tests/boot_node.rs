@@ -1,4 +1,4 @@
-use boot_node::config::BootNodeConfigSerialization;
+use boot_node::config::{BootNodeConfigSerialization, CrawlReportFormat};
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use clap::ArgMatches;
@@ -11,6 +11,7 @@ use std::net::Ipv4Addr;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
+use std::time::Duration;
 use tempfile::TempDir;
 use unused_port::unused_udp4_port;
 
@@ -164,6 +165,31 @@ fn network_dir_flag() {
         .with_config(|config| assert_eq!(config.local_enr, enr))
 }
 
+#[test]
+fn crawl_flags() {
+    let tmp_dir = TempDir::new().unwrap();
+    let output = tmp_dir.path().join("crawl.csv");
+    CommandLineTest::new()
+        .flag("crawl", None)
+        .flag("crawl-output", output.to_str())
+        .flag("crawl-duration", Some("60"))
+        .run_with_ip()
+        .with_config(|config| {
+            let crawl = config.crawl.as_ref().expect("crawl should be enabled");
+            assert_eq!(crawl.output, output);
+            assert_eq!(crawl.format, CrawlReportFormat::Csv);
+            assert_eq!(crawl.duration, Duration::from_secs(60));
+            assert_eq!(crawl.parallelism, 16);
+        })
+}
+
+#[test]
+fn crawl_disabled_by_default() {
+    CommandLineTest::new()
+        .run_with_ip()
+        .with_config(|config| assert!(config.crawl.is_none()))
+}
+
 fn save_enr_to_disk(dir: &Path, enr: &Enr) -> Result<(), String> {
     let mut file = File::create(dir.join(Path::new(ENR_FILENAME)))
         .map_err(|e| format!("Could not create ENR file: {:?}", e))?;
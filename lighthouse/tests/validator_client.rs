@@ -477,6 +477,23 @@ fn disable_run_on_all() {
         });
 }
 
+#[test]
+fn avoid_degraded_el_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert!(!config.avoid_degraded_el);
+    });
+}
+
+#[test]
+fn avoid_degraded_el_flag() {
+    CommandLineTest::new()
+        .flag("avoid-degraded-el", None)
+        .run()
+        .with_config(|config| {
+            assert!(config.avoid_degraded_el);
+        });
+}
+
 #[test]
 fn latency_measurement_service() {
     CommandLineTest::new().run().with_config(|config| {
@@ -176,10 +176,14 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 
     /// Perform some queries against the node to determine if it is a good candidate, updating
     /// `self.status` and returning that result.
+    ///
+    /// If `avoid_degraded_el` is `true`, a node which reports that its execution layer is
+    /// degraded is considered to be `NotSynced`.
     pub async fn refresh_status<T: SlotClock>(
         &self,
         slot_clock: Option<&T>,
         spec: &ChainSpec,
+        avoid_degraded_el: bool,
         log: &Logger,
     ) -> Result<(), CandidateError> {
         let new_status = if let Err(e) = self.is_online(log).await {
@@ -188,6 +192,8 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
             Err(e)
         } else if let Err(e) = self.is_synced(slot_clock, log).await {
             Err(e)
+        } else if avoid_degraded_el {
+            self.is_execution_layer_healthy(log).await
         } else {
             Ok(())
         };
@@ -309,6 +315,35 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
             Ok(())
         }
     }
+
+    /// Checks if the beacon node reports that its execution layer is healthy.
+    ///
+    /// This uses a Lighthouse-specific endpoint, so any error is ignored in case the node is not
+    /// running Lighthouse.
+    async fn is_execution_layer_healthy(&self, log: &Logger) -> Result<(), CandidateError> {
+        match self.beacon_node.get_lighthouse_eth1_engine().await {
+            Ok(health) if health.data.degraded => {
+                warn!(
+                    log,
+                    "Beacon node execution layer is degraded";
+                    "msg" => "other beacon nodes will be preferred",
+                    "head_block_number" => ?health.data.head_block_number,
+                    "endpoint" => %self.beacon_node,
+                );
+                Err(CandidateError::NotSynced)
+            }
+            Ok(_) => Ok(()),
+            Err(e) => {
+                debug!(
+                    log,
+                    "Unable to check beacon node execution layer";
+                    "error" => %e,
+                    "endpoint" => %self.beacon_node,
+                );
+                Ok(())
+            }
+        }
+    }
 }
 
 /// A collection of `CandidateBeaconNode` that can be used to perform requests with "fallback"
@@ -318,6 +353,7 @@ pub struct BeaconNodeFallback<T, E> {
     candidates: Vec<CandidateBeaconNode<E>>,
     slot_clock: Option<T>,
     disable_run_on_all: bool,
+    avoid_degraded_el: bool,
     spec: ChainSpec,
     log: Logger,
 }
@@ -326,6 +362,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     pub fn new(
         candidates: Vec<CandidateBeaconNode<E>>,
         disable_run_on_all: bool,
+        avoid_degraded_el: bool,
         spec: ChainSpec,
         log: Logger,
     ) -> Self {
@@ -333,6 +370,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             candidates,
             slot_clock: None,
             disable_run_on_all,
+            avoid_degraded_el,
             spec,
             log,
         }
@@ -389,7 +427,8 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     /// poll them to see if their status has changed.
     ///
     /// We do not poll nodes that are synced to avoid sending additional requests when everything is
-    /// going smoothly.
+    /// going smoothly, unless `avoid_degraded_el` is set, in which case every node is polled so that
+    /// an execution layer which becomes degraded is detected.
     pub async fn update_unready_candidates(&self) {
         let mut futures = Vec::new();
         for candidate in &self.candidates {
@@ -399,13 +438,14 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             //
             // Note: `RequireSynced` is always set to false here. This forces us to recheck the sync
             // status of nodes that were previously not-synced.
-            if candidate.status(RequireSynced::Yes).await.is_err() {
+            if self.avoid_degraded_el || candidate.status(RequireSynced::Yes).await.is_err() {
                 // There exists a race-condition that could result in `refresh_status` being called
                 // when the status does not require refreshing anymore. This is deemed an
                 // acceptable inefficiency.
                 futures.push(candidate.refresh_status(
                     self.slot_clock.as_ref(),
                     &self.spec,
+                    self.avoid_degraded_el,
                     &self.log,
                 ));
             }
@@ -550,7 +590,12 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 Ok(()) => Ok(()),
                 Err(_) => {
                     candidate
-                        .refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
+                        .refresh_status(
+                            self.slot_clock.as_ref(),
+                            &self.spec,
+                            self.avoid_degraded_el,
+                            &self.log,
+                        )
                         .await
                 }
             };
@@ -659,7 +704,12 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 Ok(()) => Ok(()),
                 Err(_) => {
                     candidate
-                        .refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
+                        .refresh_status(
+                            self.slot_clock.as_ref(),
+                            &self.spec,
+                            self.avoid_degraded_el,
+                            &self.log,
+                        )
                         .await
                 }
             };
@@ -36,6 +36,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        api calls only go out to the first available and synced beacon node")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("avoid-degraded-el")
+                .long("avoid-degraded-el")
+                .help("If present, a beacon node which reports that its execution layer is \
+                       offline, syncing or stuck on an old head is treated as unsynced, so that \
+                       other beacon nodes are preferred. Intended for use with multiple \
+                       `--beacon-nodes`. Only Lighthouse beacon nodes report the health of \
+                       their execution layer.")
+                .takes_value(false)
+        )
         // This argument is deprecated, use `--beacon-nodes` instead.
         .arg(
             Arg::with_name("server")
@@ -73,6 +73,8 @@ pub struct Config {
     pub block_delay: Option<Duration>,
     /// Disables publishing http api requests to all beacon nodes for select api calls.
     pub disable_run_on_all: bool,
+    /// Prefer beacon nodes which do not report a degraded execution layer.
+    pub avoid_degraded_el: bool,
     /// Enables a service which attempts to measure latency between the VC and BNs.
     pub enable_latency_measurement_service: bool,
 }
@@ -113,6 +115,7 @@ impl Default for Config {
             builder_registration_timestamp_override: None,
             gas_limit: None,
             disable_run_on_all: false,
+            avoid_degraded_el: false,
             enable_latency_measurement_service: true,
         }
     }
@@ -196,6 +199,7 @@ impl Config {
 
         config.allow_unsynced_beacon_node = cli_args.is_present("allow-unsynced");
         config.disable_run_on_all = cli_args.is_present("disable-run-on-all");
+        config.avoid_degraded_el = cli_args.is_present("avoid-degraded-el");
         config.disable_auto_discover = cli_args.is_present("disable-auto-discover");
         config.init_slashing_protection = cli_args.is_present("init-slashing-protection");
         config.use_long_timeouts = cli_args.is_present("use-long-timeouts");
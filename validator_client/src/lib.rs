@@ -344,6 +344,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         let mut beacon_nodes: BeaconNodeFallback<_, T> = BeaconNodeFallback::new(
             candidates,
             config.disable_run_on_all,
+            config.avoid_degraded_el,
             context.eth2_config.spec.clone(),
             log.clone(),
         );